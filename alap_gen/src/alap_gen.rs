@@ -1,6 +1,6 @@
 // generated by alap_gen.
 
-use std::{fs::File, io::Read, str::FromStr};
+use std::str::FromStr;
 
 use crate::{
     attributes::*, grammar::Specification, production::ProductionTail, symbol::tag::TagOrToken,
@@ -27,56 +27,132 @@ pub enum AATerminal {
     AAEnd,
     ActionCode,
     Attr,
+    AttrType,
+    Begin,
     Colon,
+    Coverage,
+    DefaultConflict,
+    Derivation,
+    Derive,
     Dot,
     Error,
+    Expect,
+    Functional,
     Ident,
     Inject,
+    Intern,
+    Keywords,
     Left,
+    LeftParen,
+    Lifetime,
+    ListSeparator,
     Literal,
+    Mode,
+    Module,
     NewSection,
+    NoSkip,
     NonAssoc,
     NumberExpr,
+    Observer,
+    Optional,
+    Plus,
     Precedence,
+    PrecedenceFor,
     PredicateExpr,
+    Prefix,
+    Recover,
     ReduceReduce,
     RegEx,
+    Reject,
+    Repeat,
     Right,
+    RightParen,
     RustCode,
+    RustType,
     ShiftReduce,
     Skip,
+    Spanned,
+    Star,
+    Start,
     Target,
+    Test,
     Token,
+    TokenLiteral,
+    TokenName,
+    Transform,
+    Type,
+    TypeParameter,
+    TypedName,
     VerticalBar,
+    Visitor,
+    Yields,
 }
 
-impl std::fmt::Display for AATerminal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for AATerminal {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             AATerminal::AAEnd => write!(f, r###"AAEnd"###),
             AATerminal::ActionCode => write!(f, r###"ActionCode"###),
             AATerminal::Attr => write!(f, r###""%attr""###),
+            AATerminal::AttrType => write!(f, r###"AttrType"###),
+            AATerminal::Begin => write!(f, r###""%begin""###),
             AATerminal::Colon => write!(f, r###"":""###),
+            AATerminal::Coverage => write!(f, r###""%coverage""###),
+            AATerminal::DefaultConflict => write!(f, r###""%default-conflict""###),
+            AATerminal::Derivation => write!(f, r###""%derivation""###),
+            AATerminal::Derive => write!(f, r###""%derive""###),
             AATerminal::Dot => write!(f, r###"".""###),
             AATerminal::Error => write!(f, r###""%error""###),
+            AATerminal::Expect => write!(f, r###""%expect""###),
+            AATerminal::Functional => write!(f, r###""%functional""###),
             AATerminal::Ident => write!(f, r###"Ident"###),
             AATerminal::Inject => write!(f, r###""%inject""###),
+            AATerminal::Intern => write!(f, r###""%intern""###),
+            AATerminal::Keywords => write!(f, r###""%keywords""###),
             AATerminal::Left => write!(f, r###""%left""###),
+            AATerminal::LeftParen => write!(f, r###""(""###),
+            AATerminal::Lifetime => write!(f, r###"Lifetime"###),
+            AATerminal::ListSeparator => write!(f, r###""%""###),
             AATerminal::Literal => write!(f, r###"Literal"###),
+            AATerminal::Mode => write!(f, r###""%mode""###),
+            AATerminal::Module => write!(f, r###""%module""###),
             AATerminal::NewSection => write!(f, r###""%%""###),
+            AATerminal::NoSkip => write!(f, r###""%noskip""###),
             AATerminal::NonAssoc => write!(f, r###""%nonassoc""###),
             AATerminal::NumberExpr => write!(f, r###"NumberExpr"###),
+            AATerminal::Observer => write!(f, r###""%observer""###),
+            AATerminal::Optional => write!(f, r###""?""###),
+            AATerminal::Plus => write!(f, r###""+""###),
             AATerminal::Precedence => write!(f, r###""%prec""###),
+            AATerminal::PrecedenceFor => write!(f, r###""%prec-for""###),
             AATerminal::PredicateExpr => write!(f, r###"PredicateExpr"###),
+            AATerminal::Prefix => write!(f, r###""%prefix""###),
+            AATerminal::Recover => write!(f, r###""%recover""###),
             AATerminal::ReduceReduce => write!(f, r###""%reduce_reduce""###),
             AATerminal::RegEx => write!(f, r###"RegEx"###),
+            AATerminal::Reject => write!(f, r###""%reject""###),
+            AATerminal::Repeat => write!(f, r###""%repeat""###),
             AATerminal::Right => write!(f, r###""%right""###),
+            AATerminal::RightParen => write!(f, r###"")""###),
             AATerminal::RustCode => write!(f, r###"RustCode"###),
+            AATerminal::RustType => write!(f, r###"RustType"###),
             AATerminal::ShiftReduce => write!(f, r###""%shift_reduce""###),
             AATerminal::Skip => write!(f, r###""%skip""###),
+            AATerminal::Spanned => write!(f, r###""%spanned""###),
+            AATerminal::Star => write!(f, r###""*""###),
+            AATerminal::Start => write!(f, r###""%start""###),
             AATerminal::Target => write!(f, r###""%target""###),
+            AATerminal::Test => write!(f, r###""%test""###),
             AATerminal::Token => write!(f, r###""%token""###),
+            AATerminal::TokenLiteral => write!(f, r###"TokenLiteral"###),
+            AATerminal::TokenName => write!(f, r###"TokenName"###),
+            AATerminal::Transform => write!(f, r###""%transform""###),
+            AATerminal::Type => write!(f, r###""%type""###),
+            AATerminal::TypeParameter => write!(f, r###"TypeParameter"###),
+            AATerminal::TypedName => write!(f, r###"TypedName"###),
             AATerminal::VerticalBar => write!(f, r###""|""###),
+            AATerminal::Visitor => write!(f, r###""%visitor""###),
+            AATerminal::Yields => write!(f, r###""=>""###),
         }
     }
 }
@@ -86,31 +162,63 @@ lazy_static::lazy_static! {
         use AATerminal::*;
         lexan::LexicalAnalyzer::new(
             &[
+                (ListSeparator, r###"%"###),
                 (NewSection, r###"%%"###),
                 (Attr, r###"%attr"###),
+                (Begin, r###"%begin"###),
+                (Coverage, r###"%coverage"###),
+                (DefaultConflict, r###"%default-conflict"###),
+                (Derivation, r###"%derivation"###),
+                (Derive, r###"%derive"###),
                 (Error, r###"%error"###),
+                (Expect, r###"%expect"###),
+                (Functional, r###"%functional"###),
                 (Inject, r###"%inject"###),
+                (Intern, r###"%intern"###),
+                (Keywords, r###"%keywords"###),
                 (Left, r###"%left"###),
+                (Mode, r###"%mode"###),
+                (Module, r###"%module"###),
                 (NonAssoc, r###"%nonassoc"###),
+                (NoSkip, r###"%noskip"###),
+                (Observer, r###"%observer"###),
                 (Precedence, r###"%prec"###),
+                (PrecedenceFor, r###"%prec-for"###),
+                (Prefix, r###"%prefix"###),
+                (Recover, r###"%recover"###),
                 (ReduceReduce, r###"%reduce_reduce"###),
+                (Reject, r###"%reject"###),
+                (Repeat, r###"%repeat"###),
                 (Right, r###"%right"###),
                 (ShiftReduce, r###"%shift_reduce"###),
                 (Skip, r###"%skip"###),
+                (Spanned, r###"%spanned"###),
+                (Start, r###"%start"###),
                 (Target, r###"%target"###),
+                (Test, r###"%test"###),
                 (Token, r###"%token"###),
+                (Transform, r###"%transform"###),
+                (Type, r###"%type"###),
+                (Visitor, r###"%visitor"###),
+                (LeftParen, r###"("###),
+                (RightParen, r###")"###),
+                (Star, r###"*"###),
+                (Plus, r###"+"###),
                 (Dot, r###"."###),
                 (Colon, r###":"###),
+                (Yields, r###"=>"###),
+                (Optional, r###"?"###),
                 (VerticalBar, r###"|"###),
             ],
             &[
-                (ActionCode, r###"(!\{(.|[\n\r])*?!\})"###),
+                (ActionCode, r###"(!\{("(\\(.|[\n\r])|[^"\\])*"|'(\\.|[^'\\])'|/\*(.|[\n\r])*?\*/|//([^\n\r!]|!+[^\n\r!}])*|[^"'/!]|['/!])*?!\})"###),
                 (Literal, r###"("(\\"|[^"\t\r\n\v\f])*")"###),
                 (RustCode, r###"(%\{(.|[\n\r])*?%\})"###),
+                (Lifetime, r###"(<'[a-zA-Z_][a-zA-Z0-9_]*>)"###),
+                (TypeParameter, r###"(<[a-zA-Z_][a-zA-Z0-9_]*\s*:[^<>]+>)"###),
                 (NumberExpr, r###"([0-9]+)"###),
                 (Ident, r###"([a-zA-Z]+[a-zA-Z0-9_]*)"###),
-                (RegEx, r###"(\(.+\))"###),
-                (PredicateExpr, r###"(\?\((.|[\n\r])*?\?\))"###),
+                (PredicateExpr, r###"(\?\(("(\\(.|[\n\r])|[^"\\])*"|'(\\.|[^'\\])'|/\*(.|[\n\r])*?\*/|//([^\n\r?]|\?+[^\n\r?)])*|[^"'/?]|['/?])*?\?\))"###),
             ],
             &[
                 r###"(/\*(.|[\n\r])*?\*/)"###,
@@ -119,6 +227,80 @@ lazy_static::lazy_static! {
             ],
             AAEnd,
         )
+        // %mode TOKEN_NAME
+        .with_mode(
+            &[
+            ],
+            &[
+                (TokenName, r###"([a-zA-Z]+[a-zA-Z0-9_]*)"###),
+            ],
+            &[
+                r###"(/\*(.|[\n\r])*?\*/)"###,
+                r###"(//[^\n\r]*)"###,
+                r###"(\s+)"###,
+            ],
+        )
+        // %mode TOKEN_TEXT
+        .with_mode(
+            &[
+            ],
+            &[
+                (TokenLiteral, r###"("(\\"|[^"\t\r\n\v\f])*")"###),
+                (RegEx, r###"(\(.+\))"###),
+            ],
+            &[
+                r###"(/\*(.|[\n\r])*?\*/)"###,
+                r###"(//[^\n\r]*)"###,
+                r###"(\s+)"###,
+            ],
+        )
+        // %mode TYPED_NAME
+        .with_mode(
+            &[
+            ],
+            &[
+                (TypedName, r###"([a-zA-Z]+[a-zA-Z0-9_]*)"###),
+            ],
+            &[
+                r###"(/\*(.|[\n\r])*?\*/)"###,
+                r###"(//[^\n\r]*)"###,
+                r###"(\s+)"###,
+            ],
+        )
+        // %mode RUST_TYPE
+        .with_mode(
+            &[
+            ],
+            &[
+                (RustType, r###"([^\s][^\n\r]*)"###),
+            ],
+            &[
+                r###"([ \t]+)"###,
+            ],
+        )
+        // %mode ATTR_TYPE
+        .with_mode(
+            &[
+            ],
+            &[
+                (AttrType, r###"([^\s%/]([^\n\r%/]*[^\s%/])?)"###),
+            ],
+            &[
+                r###"(/\*(.|[\n\r])*?\*/)"###,
+                r###"(//[^\n\r]*)"###,
+                r###"(\s+)"###,
+            ],
+        )
+        .with_mode_switch(Attr, 5)
+        .with_mode_switch(AttrType, 0)
+        .with_mode_switch(RegEx, 0)
+        .with_mode_switch(RustType, 0)
+        .with_mode_switch(Skip, 2)
+        .with_mode_switch(Token, 1)
+        .with_mode_switch(TokenLiteral, 0)
+        .with_mode_switch(TokenName, 2)
+        .with_mode_switch(Type, 3)
+        .with_mode_switch(TypedName, 4)
     };
 }
 
@@ -127,15 +309,32 @@ pub enum AANonTerminal {
     AAStart,
     Action,
     AttributeType,
+    AttributeTypeName,
     Configuration,
+    CoverageRecording,
+    DefaultConflictResolution,
     Definitions,
+    DerivationRecording,
+    Derives,
+    Element,
     ExpectedConflicts,
     ExpectedRRConflicts,
     ExpectedSRConflicts,
+    FunctionalInterface,
     Injection,
     InjectionHead,
+    Interning,
+    KeywordDefinition,
+    ModeDefinition,
+    ModeDefinitions,
+    ModeHead,
+    ModeSwitch,
+    ModuleName,
     NewTokenName,
+    NoSkipDefinition,
     Number,
+    Observing,
+    OptionalDisplayName,
     OptionalInjection,
     Preamble,
     PrecedenceDefinition,
@@ -146,35 +345,65 @@ pub enum AANonTerminal {
     ProductionRules,
     ProductionTail,
     ProductionTailList,
+    RecoveryDefinition,
     RegularExpression,
+    Rejection,
+    RepetitionRecursion,
+    SeparatedList,
     SkipDefinition,
     SkipDefinitions,
     Specification,
+    SpecificationTest,
+    SpecificationTests,
+    StartSymbol,
+    StartSymbols,
     Symbol,
     SymbolList,
+    SymbolPrefix,
     Tag,
     TagList,
     TaggedPrecedence,
     TargetType,
     TokenDefinition,
+    TokenDefinitionHead,
     TokenDefinitions,
+    TypeDefinition,
+    TypeDefinitions,
+    VisitorGeneration,
 }
 
-impl std::fmt::Display for AANonTerminal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for AANonTerminal {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             AANonTerminal::AAStart => write!(f, r"AAStart"),
             AANonTerminal::Action => write!(f, r"Action"),
             AANonTerminal::AttributeType => write!(f, r"AttributeType"),
+            AANonTerminal::AttributeTypeName => write!(f, r"AttributeTypeName"),
             AANonTerminal::Configuration => write!(f, r"Configuration"),
+            AANonTerminal::CoverageRecording => write!(f, r"CoverageRecording"),
+            AANonTerminal::DefaultConflictResolution => write!(f, r"DefaultConflictResolution"),
             AANonTerminal::Definitions => write!(f, r"Definitions"),
+            AANonTerminal::DerivationRecording => write!(f, r"DerivationRecording"),
+            AANonTerminal::Derives => write!(f, r"Derives"),
+            AANonTerminal::Element => write!(f, r"Element"),
             AANonTerminal::ExpectedConflicts => write!(f, r"ExpectedConflicts"),
             AANonTerminal::ExpectedRRConflicts => write!(f, r"ExpectedRRConflicts"),
             AANonTerminal::ExpectedSRConflicts => write!(f, r"ExpectedSRConflicts"),
+            AANonTerminal::FunctionalInterface => write!(f, r"FunctionalInterface"),
             AANonTerminal::Injection => write!(f, r"Injection"),
             AANonTerminal::InjectionHead => write!(f, r"InjectionHead"),
+            AANonTerminal::Interning => write!(f, r"Interning"),
+            AANonTerminal::KeywordDefinition => write!(f, r"KeywordDefinition"),
+            AANonTerminal::ModeDefinition => write!(f, r"ModeDefinition"),
+            AANonTerminal::ModeDefinitions => write!(f, r"ModeDefinitions"),
+            AANonTerminal::ModeHead => write!(f, r"ModeHead"),
+            AANonTerminal::ModeSwitch => write!(f, r"ModeSwitch"),
+            AANonTerminal::ModuleName => write!(f, r"ModuleName"),
             AANonTerminal::NewTokenName => write!(f, r"NewTokenName"),
+            AANonTerminal::NoSkipDefinition => write!(f, r"NoSkipDefinition"),
             AANonTerminal::Number => write!(f, r"Number"),
+            AANonTerminal::Observing => write!(f, r"Observing"),
+            AANonTerminal::OptionalDisplayName => write!(f, r"OptionalDisplayName"),
             AANonTerminal::OptionalInjection => write!(f, r"OptionalInjection"),
             AANonTerminal::Preamble => write!(f, r"Preamble"),
             AANonTerminal::PrecedenceDefinition => write!(f, r"PrecedenceDefinition"),
@@ -185,18 +414,31 @@ impl std::fmt::Display for AANonTerminal {
             AANonTerminal::ProductionRules => write!(f, r"ProductionRules"),
             AANonTerminal::ProductionTail => write!(f, r"ProductionTail"),
             AANonTerminal::ProductionTailList => write!(f, r"ProductionTailList"),
+            AANonTerminal::RecoveryDefinition => write!(f, r"RecoveryDefinition"),
             AANonTerminal::RegularExpression => write!(f, r"RegularExpression"),
+            AANonTerminal::Rejection => write!(f, r"Rejection"),
+            AANonTerminal::RepetitionRecursion => write!(f, r"RepetitionRecursion"),
+            AANonTerminal::SeparatedList => write!(f, r"SeparatedList"),
             AANonTerminal::SkipDefinition => write!(f, r"SkipDefinition"),
             AANonTerminal::SkipDefinitions => write!(f, r"SkipDefinitions"),
             AANonTerminal::Specification => write!(f, r"Specification"),
+            AANonTerminal::SpecificationTest => write!(f, r"SpecificationTest"),
+            AANonTerminal::SpecificationTests => write!(f, r"SpecificationTests"),
+            AANonTerminal::StartSymbol => write!(f, r"StartSymbol"),
+            AANonTerminal::StartSymbols => write!(f, r"StartSymbols"),
             AANonTerminal::Symbol => write!(f, r"Symbol"),
             AANonTerminal::SymbolList => write!(f, r"SymbolList"),
+            AANonTerminal::SymbolPrefix => write!(f, r"SymbolPrefix"),
             AANonTerminal::Tag => write!(f, r"Tag"),
             AANonTerminal::TagList => write!(f, r"TagList"),
             AANonTerminal::TaggedPrecedence => write!(f, r"TaggedPrecedence"),
             AANonTerminal::TargetType => write!(f, r"TargetType"),
             AANonTerminal::TokenDefinition => write!(f, r"TokenDefinition"),
+            AANonTerminal::TokenDefinitionHead => write!(f, r"TokenDefinitionHead"),
             AANonTerminal::TokenDefinitions => write!(f, r"TokenDefinitions"),
+            AANonTerminal::TypeDefinition => write!(f, r"TypeDefinition"),
+            AANonTerminal::TypeDefinitions => write!(f, r"TypeDefinitions"),
+            AANonTerminal::VisitorGeneration => write!(f, r"VisitorGeneration"),
         }
     }
 }
@@ -206,6 +448,18 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
         &AALEXAN
     }
 
+    fn lexical_mode(label: &str) -> Option<usize> {
+        match label {
+            "%mode INITIAL" => Some(0),
+            "%mode TOKEN_NAME" => Some(1),
+            "%mode TOKEN_TEXT" => Some(2),
+            "%mode TYPED_NAME" => Some(3),
+            "%mode RUST_TYPE" => Some(4),
+            "%mode ATTR_TYPE" => Some(5),
+            _ => None,
+        }
+    }
+
     fn viable_error_recovery_states(_token: &AATerminal) -> BTreeSet<u32> {
         btree_set![]
     }
@@ -213,24 +467,44 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
     fn look_ahead_set(state: u32) -> BTreeSet<AATerminal> {
         use AATerminal::*;
         return match state {
-            0 => btree_set![Attr, Inject, Target, RustCode],
+            0 => btree_set![Attr, Inject, RustCode, Target],
             1 => btree_set![AAEnd],
             2 => btree_set![Attr, Target],
             3 => btree_set![
+                AAEnd,
                 Attr,
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Ident,
                 Inject,
+                Intern,
+                Keywords,
                 Left,
+                Mode,
+                Module,
                 NewSection,
+                NoSkip,
                 NonAssoc,
+                Observer,
+                PrecedenceFor,
+                Prefix,
+                Recover,
                 ReduceReduce,
+                Repeat,
                 Right,
+                RustCode,
                 ShiftReduce,
                 Skip,
+                Start,
                 Target,
+                Test,
                 Token,
-                Ident,
-                RustCode,
-                AAEnd
+                Type,
+                Visitor
             ],
             4 => btree_set![Literal],
             5 => btree_set![Dot],
@@ -238,198 +512,1717 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
             7 => btree_set![NewSection],
             8 => btree_set![Inject, Target],
             9 => btree_set![Attr, Inject],
-            10 => btree_set![Ident],
+            10 => btree_set![AttrType],
             11 => btree_set![Ident],
             12 => btree_set![Dot],
             13 => btree_set![
+                AAEnd,
                 Attr,
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Ident,
                 Inject,
+                Intern,
+                Keywords,
                 Left,
+                Mode,
+                Module,
                 NewSection,
+                NoSkip,
                 NonAssoc,
+                Observer,
+                PrecedenceFor,
+                Prefix,
+                Recover,
                 ReduceReduce,
+                Repeat,
                 Right,
+                RustCode,
                 ShiftReduce,
                 Skip,
+                Start,
                 Target,
+                Test,
                 Token,
-                Ident,
-                RustCode,
-                AAEnd
+                Type,
+                Visitor
             ],
             14 => btree_set![Attr, Inject, Target],
-            15 => btree_set![Inject, Token],
+            15 => btree_set![Inject, Keywords, Token],
             16 => btree_set![Target],
             17 => btree_set![Attr],
-            18 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce, Target],
-            19 => btree_set![Attr, Inject, NewSection, ReduceReduce, ShiftReduce],
-            20 => btree_set![Attr, Target],
-            21 => btree_set![NewSection],
-            22 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            23 => btree_set![Token],
-            24 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce],
-            25 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce],
-            26 => btree_set![Inject, Ident],
-            27 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip],
-            28 => btree_set![Token],
-            29 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            30 => btree_set![Ident],
-            31 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce],
-            32 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce],
-            33 => btree_set![Ident, AAEnd],
-            34 => btree_set![Ident],
-            35 => btree_set![Inject, Left, NewSection, NonAssoc, Right],
-            36 => btree_set![Skip],
-            37 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            38 => btree_set![Literal, RegEx],
-            39 => btree_set![Literal, RegEx],
-            40 => btree_set![Inject, NewSection],
-            41 => btree_set![Inject, NewSection, ShiftReduce],
-            42 => btree_set![Inject, NewSection, ReduceReduce],
-            43 => btree_set![NumberExpr],
-            44 => btree_set![NumberExpr],
-            45 => btree_set![Inject, NewSection],
-            46 => btree_set![Inject, Ident, AAEnd],
-            47 => btree_set![
-                Dot,
-                Error,
-                VerticalBar,
-                ActionCode,
-                Ident,
-                Literal,
-                PredicateExpr
-            ],
-            48 => btree_set![Colon],
-            49 => btree_set![Inject, Ident, AAEnd],
-            50 => btree_set![Left, NonAssoc, Right],
-            51 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip],
-            52 => btree_set![RegEx],
-            53 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            54 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            55 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            56 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip, Token],
-            57 => btree_set![NewSection],
-            58 => btree_set![ShiftReduce],
-            59 => btree_set![ReduceReduce],
-            60 => btree_set![Inject, NewSection, ShiftReduce],
-            61 => btree_set![Inject, NewSection, ReduceReduce, ShiftReduce],
-            62 => btree_set![Inject, NewSection, ReduceReduce],
-            63 => btree_set![NewSection],
-            64 => btree_set![Ident, AAEnd],
-            65 => btree_set![Dot, VerticalBar],
-            66 => btree_set![Dot, VerticalBar],
-            67 => btree_set![Dot, VerticalBar],
-            68 => btree_set![Dot, VerticalBar, ActionCode],
-            69 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
-                ActionCode,
-                Ident,
-                Literal,
-                PredicateExpr
+            18 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Spanned,
+                Start,
+                Target,
+                Visitor
             ],
-            70 => btree_set![Dot, VerticalBar],
-            71 => btree_set![Dot, Precedence, VerticalBar, ActionCode],
-            72 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
+            19 => btree_set![Inject, Spanned, Target],
+            20 => btree_set![
+                Attr,
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Lifetime,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                TypeParameter,
+                Visitor
+            ],
+            21 => btree_set![Attr, Target],
+            22 => btree_set![NewSection],
+            23 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            24 => btree_set![Keywords, Token],
+            25 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            26 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            27 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Target,
+                Visitor
+            ],
+            28 => btree_set![
+                Attr,
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            29 => btree_set![
+                Attr,
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            30 => btree_set![Ident, Inject],
+            31 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Type
+            ],
+            32 => btree_set![Keywords, Token],
+            33 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            34 => btree_set![
                 ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            35 => btree_set![
                 Ident,
-                Literal,
-                PredicateExpr
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
             ],
-            73 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
+            36 => btree_set![Ident],
+            37 => btree_set![TokenName],
+            38 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            39 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Intern,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            40 => btree_set![AAEnd, Ident, Test],
+            41 => btree_set![Ident],
+            42 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            43 => btree_set![Skip],
+            44 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            45 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            46 => btree_set![
                 ActionCode,
-                Ident,
-                Literal,
-                PredicateExpr
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
             ],
-            74 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
+            47 => btree_set![Ident],
+            48 => btree_set![
                 ActionCode,
+                Begin,
+                Dot,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type,
+                VerticalBar
+            ],
+            49 => btree_set![
                 Ident,
-                Literal,
-                PredicateExpr
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
             ],
-            75 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
-                ActionCode,
+            50 => btree_set![
                 Ident,
-                Literal,
-                PredicateExpr
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
             ],
-            76 => btree_set![
-                Dot,
-                Error,
-                VerticalBar,
+            51 => btree_set![RegEx, TokenLiteral],
+            52 => btree_set![RegEx, TokenLiteral],
+            53 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            54 => btree_set![Ident],
+            55 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            56 => btree_set![AAEnd, Test],
+            57 => btree_set![AAEnd, Ident, Inject, Test],
+            58 => btree_set![
                 ActionCode,
-                Ident,
-                Literal,
-                PredicateExpr
-            ],
-            77 => btree_set![Ident, AAEnd],
-            78 => btree_set![Inject, Left, NewSection, NonAssoc, Right],
-            79 => btree_set![Ident, Literal],
-            80 => btree_set![Ident, Literal],
-            81 => btree_set![Ident, Literal],
-            82 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip],
-            83 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Skip],
-            84 => btree_set![Inject, NewSection],
-            85 => btree_set![Inject, NewSection],
-            86 => btree_set![Inject, Ident, AAEnd],
-            87 => btree_set![
                 Dot,
                 Error,
-                VerticalBar,
-                ActionCode,
                 Ident,
+                LeftParen,
                 Literal,
-                PredicateExpr
+                PredicateExpr,
+                VerticalBar
             ],
-            88 => btree_set![Dot, VerticalBar],
-            89 => btree_set![Dot, Precedence, VerticalBar, ActionCode],
-            90 => btree_set![Dot, VerticalBar, ActionCode],
-            91 => btree_set![Dot, VerticalBar],
-            92 => btree_set![Ident, Literal],
-            93 => btree_set![
-                Dot,
-                Error,
-                Precedence,
-                VerticalBar,
-                ActionCode,
-                Ident,
-                Literal,
-                PredicateExpr
-            ],
-            94 => btree_set![Inject, Left, NewSection, NonAssoc, Right],
-            95 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            96 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            97 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            98 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            99 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            100 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            101 => btree_set![Dot, VerticalBar],
-            102 => btree_set![Dot, VerticalBar, ActionCode],
-            103 => btree_set![Dot, VerticalBar],
-            104 => btree_set![Dot, VerticalBar],
-            105 => btree_set![Dot, VerticalBar, ActionCode],
-            106 => btree_set![Dot, VerticalBar, ActionCode],
-            107 => btree_set![Inject, Left, NewSection, NonAssoc, Right, Ident, Literal],
-            108 => btree_set![Dot, VerticalBar],
+            59 => btree_set![Colon],
+            60 => btree_set![AAEnd, Ident, Inject, Test],
+            61 => btree_set![
+                Inject,
+                Left,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            62 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            63 => btree_set![Inject, Keywords, Token],
+            64 => btree_set![Ident],
+            65 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Type
+            ],
+            66 => btree_set![RegEx],
+            67 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            68 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            69 => btree_set![
+                ActionCode,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            70 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Literal,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            71 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Literal,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Transform,
+                Type
+            ],
+            72 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Literal,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Transform,
+                Type
+            ],
+            73 => btree_set![
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            74 => btree_set![Ident],
+            75 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            76 => btree_set![
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            77 => btree_set![AAEnd, Test],
+            78 => btree_set![Literal],
+            79 => btree_set![AAEnd, Ident, Test],
+            80 => btree_set![Dot, VerticalBar],
+            81 => btree_set![Dot, VerticalBar],
+            82 => btree_set![Dot, VerticalBar],
+            83 => btree_set![ActionCode, Dot, VerticalBar],
+            84 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                VerticalBar
+            ],
+            85 => btree_set![ActionCode, Dot, Precedence, Reject, VerticalBar],
+            86 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            87 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            88 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            89 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            90 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            91 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            92 => btree_set![Error, Ident, LeftParen, Literal],
+            93 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                PredicateExpr,
+                VerticalBar
+            ],
+            94 => btree_set![AAEnd, Ident, Test],
+            95 => btree_set![NewSection, NoSkip, Recover, Type],
+            96 => btree_set![Left, NonAssoc, PrecedenceFor, Right],
+            97 => btree_set![
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            98 => btree_set![Inject, Keywords, Token],
+            99 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Type
+            ],
+            100 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Type
+            ],
+            101 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            102 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Transform,
+                Type
+            ],
+            103 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Transform,
+                Type
+            ],
+            104 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            105 => btree_set![Ident],
+            106 => btree_set![
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            107 => btree_set![
+                Coverage,
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            108 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            109 => btree_set![Yields],
+            110 => btree_set![AAEnd, Ident, Inject, Test],
+            111 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                PredicateExpr,
+                VerticalBar
+            ],
+            112 => btree_set![Dot, VerticalBar],
+            113 => btree_set![ActionCode, Dot, Precedence, Reject, VerticalBar],
+            114 => btree_set![ActionCode, Dot, VerticalBar],
+            115 => btree_set![Dot, VerticalBar],
+            116 => btree_set![Ident, Literal],
+            117 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            118 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            119 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            120 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            121 => btree_set![Error, Ident, LeftParen, Literal],
+            122 => btree_set![Error, Ident, LeftParen, Literal],
+            123 => btree_set![Error, Ident, LeftParen, Literal, Precedence, RightParen],
+            124 => btree_set![Inject, NewSection, NoSkip, Recover, Type],
+            125 => btree_set![Ident, Inject, NewSection, NoSkip, Recover, Type],
+            126 => btree_set![Inject, NewSection, NoSkip, Recover, Type],
+            127 => btree_set![TypedName],
+            128 => btree_set![Ident],
+            129 => btree_set![Ident, Literal],
+            130 => btree_set![
+                Inject,
+                Left,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            131 => btree_set![Ident, Literal, NumberExpr],
+            132 => btree_set![Ident, Literal, NumberExpr],
+            133 => btree_set![Ident, Literal, NumberExpr],
+            134 => btree_set![Ident],
+            135 => btree_set![
+                Inject,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Type
+            ],
+            136 => btree_set![ActionCode],
+            137 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            138 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            139 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            140 => btree_set![
+                DefaultConflict,
+                Derivation,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            141 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            142 => btree_set![Ident],
+            143 => btree_set![Dot, VerticalBar],
+            144 => btree_set![ActionCode, Dot, VerticalBar],
+            145 => btree_set![Dot, VerticalBar],
+            146 => btree_set![Dot, VerticalBar],
+            147 => btree_set![Literal],
+            148 => btree_set![Dot, VerticalBar],
+            149 => btree_set![ActionCode, Dot, RightParen, VerticalBar],
+            150 => btree_set![ActionCode, Dot, RightParen, VerticalBar],
+            151 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            152 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                Literal,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                VerticalBar
+            ],
+            153 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            154 => btree_set![RightParen],
+            155 => btree_set![NewSection, NoSkip, Recover, Type],
+            156 => btree_set![NewSection, NoSkip, Recover, Type],
+            157 => btree_set![Ident, Inject, NewSection, NoSkip, Recover, Type],
+            158 => btree_set![NewSection, NoSkip, Recover, Type],
+            159 => btree_set![RustType],
+            160 => btree_set![Ident, Inject, NewSection, NoSkip, Recover, Type],
+            161 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            162 => btree_set![Ident, Inject, Literal, NewSection, NoSkip, Recover, Type],
+            163 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            164 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            165 => btree_set![
+                Inject,
+                Left,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            166 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Ident,
+                Inject,
+                Literal,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce
+            ],
+            167 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            168 => btree_set![Ident, Literal],
+            169 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            170 => btree_set![Ident, Literal],
+            171 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            172 => btree_set![Ident, Literal],
+            173 => btree_set![Ident, Literal],
+            174 => btree_set![
+                ActionCode,
+                Begin,
+                Inject,
+                Keywords,
+                Left,
+                Mode,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Skip,
+                Token,
+                Type
+            ],
+            175 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            176 => btree_set![Ident],
+            177 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            178 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Observer,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start,
+                Visitor
+            ],
+            179 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            180 => btree_set![AAEnd, Test],
+            181 => btree_set![Dot, VerticalBar],
+            182 => btree_set![Dot, VerticalBar],
+            183 => btree_set![
+                ActionCode,
+                Dot,
+                Error,
+                Ident,
+                LeftParen,
+                ListSeparator,
+                Literal,
+                NewSection,
+                Optional,
+                Plus,
+                Precedence,
+                PredicateExpr,
+                RightParen,
+                Star,
+                VerticalBar
+            ],
+            184 => btree_set![Inject, NewSection, NoSkip, Recover, Type],
+            185 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            186 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            187 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            188 => btree_set![
+                Ident,
+                Inject,
+                Left,
+                Literal,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            189 => btree_set![
+                Inject,
+                Left,
+                NewSection,
+                NoSkip,
+                NonAssoc,
+                PrecedenceFor,
+                Recover,
+                Right,
+                Type
+            ],
+            190 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce
+            ],
+            191 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            192 => btree_set![Ident],
+            193 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            194 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce
+            ],
+            195 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat
+            ],
+            196 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat,
+                ShiftReduce
+            ],
+            197 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat
+            ],
+            198 => btree_set![NumberExpr],
+            199 => btree_set![NumberExpr],
+            200 => btree_set![NumberExpr],
+            201 => btree_set![Ident],
+            202 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            203 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            204 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat
+            ],
+            205 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix, Repeat],
+            206 => btree_set![Ident],
+            207 => btree_set![ShiftReduce],
+            208 => btree_set![ReduceReduce],
+            209 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat
+            ],
+            210 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat,
+                ShiftReduce
+            ],
+            211 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat
+            ],
+            212 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            213 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            214 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix, Repeat],
+            215 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix],
+            216 => btree_set![Ident],
+            217 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix, Repeat],
+            218 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat
+            ],
+            219 => btree_set![
+                DefaultConflict,
+                Derive,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                Repeat
+            ],
+            220 => btree_set![
+                DefaultConflict,
+                Derive,
+                Expect,
+                Functional,
+                Inject,
+                Module,
+                NewSection,
+                Prefix,
+                ReduceReduce,
+                Repeat,
+                ShiftReduce,
+                Start
+            ],
+            221 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix],
+            222 => btree_set![Functional, Inject, Module, NewSection, Prefix],
+            223 => btree_set![Literal],
+            224 => btree_set![Derive, Functional, Inject, Module, NewSection, Prefix],
+            225 => btree_set![Functional, Inject, Module, NewSection, Prefix],
+            226 => btree_set![Inject, Module, NewSection, Prefix],
+            227 => btree_set![Inject, Module, NewSection, Prefix],
+            228 => btree_set![Functional, Inject, Module, NewSection, Prefix],
+            229 => btree_set![Inject, Module, NewSection, Prefix],
+            230 => btree_set![Inject, NewSection, Prefix],
+            231 => btree_set![Ident],
+            232 => btree_set![Inject, NewSection, Prefix],
+            233 => btree_set![Inject, NewSection],
+            234 => btree_set![Ident],
+            235 => btree_set![Inject, NewSection, Prefix],
+            236 => btree_set![Inject, NewSection],
+            237 => btree_set![NewSection],
+            238 => btree_set![Inject, NewSection],
+            239 => btree_set![NewSection],
             _ => panic!("illegal state: {state}"),
         };
     }
@@ -444,697 +2237,3493 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
         use AATerminal::*;
         let aa_tag = *aa_token.tag();
         return match aa_state {
+            // State<0>:
+            //   AAStart: . Specification #(NonAssoc, 0)
             0 => match aa_tag {
                 Inject => Action::Shift(4),
                 // OptionalInjection: <empty> #(NonAssoc, 0)
-                RustCode => Action::Reduce(2),
-                // Preamble: <empty> #(NonAssoc, 0)
-                Attr | Target => Action::Reduce(6),
+                RustCode => Action::Reduce(5),
+                // Preamble: <empty> #(NonAssoc, 0)
+                Attr | Target => Action::Reduce(9),
+                _ => Action::SyntaxError,
+            },
+            // State<1>:
+            //   AAStart: Specification .  #(NonAssoc, 0)
+            1 => match aa_tag {
+                // AAStart: Specification #(NonAssoc, 0)
+                AAEnd => Action::Accept,
+                _ => Action::SyntaxError,
+            },
+            // State<2>:
+            //   Specification: Preamble . Configuration "%%" Definitions "%%" ProductionRules SpecificationTests #(NonAssoc, 0)
+            2 => match aa_tag {
+                Attr => Action::Shift(10),
+                Target => Action::Shift(11),
+                _ => Action::SyntaxError,
+            },
+            // State<3>:
+            //   OptionalInjection: Injection .  #(NonAssoc, 0)
+            3 => match aa_tag {
+                // OptionalInjection: Injection #(NonAssoc, 0)
+                AAEnd | Attr | Coverage | DefaultConflict | Derivation | Derive | Expect
+                | Functional | Ident | Inject | Intern | Keywords | Left | Mode | Module
+                | NewSection | NoSkip | NonAssoc | Observer | PrecedenceFor | Prefix | Recover
+                | ReduceReduce | Repeat | Right | RustCode | ShiftReduce | Skip | Start
+                | Target | Test | Token | Type | Visitor => Action::Reduce(6),
+                _ => Action::SyntaxError,
+            },
+            // State<4>:
+            //   InjectionHead: "%inject" . Literal #(Right, 1)
+            4 => match aa_tag {
+                Literal => Action::Shift(12),
+                _ => Action::SyntaxError,
+            },
+            // State<5>:
+            //   Injection: InjectionHead . "." #(NonAssoc, 0)
+            5 => match aa_tag {
+                Dot => Action::Shift(13),
+                _ => Action::SyntaxError,
+            },
+            // State<6>:
+            //   Preamble: OptionalInjection . RustCode OptionalInjection #(NonAssoc, 0)
+            6 => match aa_tag {
+                RustCode => Action::Shift(14),
+                _ => Action::SyntaxError,
+            },
+            // State<7>:
+            //   Specification: Preamble Configuration . "%%" Definitions "%%" ProductionRules SpecificationTests #(NonAssoc, 0)
+            7 => match aa_tag {
+                NewSection => Action::Shift(15),
+                _ => Action::SyntaxError,
+            },
+            // State<8>:
+            //   Configuration: AttributeType . OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            8 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Target => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<9>:
+            //   Configuration: TargetType . OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            9 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Attr => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<10>:
+            //   AttributeType: "%attr" . AttributeTypeName #(NonAssoc, 0)
+            //   AttributeType: "%attr" . AttributeTypeName "%spanned" #(NonAssoc, 0)
+            10 => match aa_tag {
+                AttrType => Action::Shift(19),
+                _ => Action::SyntaxError,
+            },
+            // State<11>:
+            //   TargetType: "%target" . Ident #(NonAssoc, 0)
+            //   TargetType: "%target" . Ident Lifetime #(NonAssoc, 0)
+            //   TargetType: "%target" . Ident TypeParameter #(NonAssoc, 0)
+            11 => match aa_tag {
+                Ident => Action::Shift(20),
+                _ => Action::SyntaxError,
+            },
+            // State<12>:
+            //   InjectionHead: "%inject" Literal .  #(Right, 1)
+            12 => match aa_tag {
+                // InjectionHead: "%inject" Literal #(Right, 1)
+                Dot => Action::Reduce(7),
+                _ => Action::SyntaxError,
+            },
+            // State<13>:
+            //   Injection: InjectionHead "." .  #(NonAssoc, 0)
+            13 => match aa_tag {
+                // Injection: InjectionHead "." #(NonAssoc, 0)
+                AAEnd | Attr | Coverage | DefaultConflict | Derivation | Derive | Expect
+                | Functional | Ident | Inject | Intern | Keywords | Left | Mode | Module
+                | NewSection | NoSkip | NonAssoc | Observer | PrecedenceFor | Prefix | Recover
+                | ReduceReduce | Repeat | Right | RustCode | ShiftReduce | Skip | Start
+                | Target | Test | Token | Type | Visitor => Action::Reduce(8),
+                _ => Action::SyntaxError,
+            },
+            // State<14>:
+            //   Preamble: OptionalInjection RustCode . OptionalInjection #(NonAssoc, 0)
+            14 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Attr | Target => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<15>:
+            //   Specification: Preamble Configuration "%%" . Definitions "%%" ProductionRules SpecificationTests #(NonAssoc, 0)
+            15 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Keywords | Token => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<16>:
+            //   Configuration: AttributeType OptionalInjection . TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            16 => match aa_tag {
+                Target => Action::Shift(11),
+                _ => Action::SyntaxError,
+            },
+            // State<17>:
+            //   Configuration: TargetType OptionalInjection . AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            17 => match aa_tag {
+                Attr => Action::Shift(10),
+                _ => Action::SyntaxError,
+            },
+            // State<18>:
+            //   AttributeType: "%attr" AttributeTypeName .  #(NonAssoc, 0)
+            //   AttributeType: "%attr" AttributeTypeName . "%spanned" #(NonAssoc, 0)
+            18 => match aa_tag {
+                Spanned => Action::Shift(27),
+                // AttributeType: "%attr" AttributeTypeName #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Intern | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat
+                | ShiftReduce | Start | Target | Visitor => Action::Reduce(13),
+                _ => Action::SyntaxError,
+            },
+            // State<19>:
+            //   AttributeTypeName: AttrType .  #(NonAssoc, 0)
+            19 => match aa_tag {
+                // AttributeTypeName: AttrType #(NonAssoc, 0)
+                Inject | Spanned | Target => Action::Reduce(15),
+                _ => Action::SyntaxError,
+            },
+            // State<20>:
+            //   TargetType: "%target" Ident .  #(NonAssoc, 0)
+            //   TargetType: "%target" Ident . Lifetime #(NonAssoc, 0)
+            //   TargetType: "%target" Ident . TypeParameter #(NonAssoc, 0)
+            20 => match aa_tag {
+                Lifetime => Action::Shift(28),
+                TypeParameter => Action::Shift(29),
+                // TargetType: "%target" Ident #(NonAssoc, 0)
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => Action::Reduce(16),
+                _ => Action::SyntaxError,
+            },
+            // State<21>:
+            //   Preamble: OptionalInjection RustCode OptionalInjection .  #(NonAssoc, 0)
+            21 => match aa_tag {
+                // Preamble: OptionalInjection RustCode OptionalInjection #(NonAssoc, 0)
+                Attr | Target => Action::Reduce(10),
+                _ => Action::SyntaxError,
+            },
+            // State<22>:
+            //   Specification: Preamble Configuration "%%" Definitions . "%%" ProductionRules SpecificationTests #(NonAssoc, 0)
+            22 => match aa_tag {
+                NewSection => Action::Shift(30),
+                _ => Action::SyntaxError,
+            },
+            // State<23>:
+            //   Definitions: TokenDefinitions . SkipDefinitions ModeDefinitions PrecedenceDefinitions TypeDefinitions #(NonAssoc, 0)
+            //   TokenDefinitions: TokenDefinitions . OptionalInjection TokenDefinition OptionalInjection #(NonAssoc, 0)
+            23 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Keywords | Token => Action::Reduce(5),
+                // SkipDefinitions: <empty> #(NonAssoc, 0)
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => Action::Reduce(73),
+                _ => Action::SyntaxError,
+            },
+            // State<24>:
+            //   TokenDefinitions: OptionalInjection . TokenDefinition #(NonAssoc, 0)
+            24 => match aa_tag {
+                Keywords => Action::Shift(36),
+                Token => Action::Shift(37),
+                _ => Action::SyntaxError,
+            },
+            // State<25>:
+            //   Configuration: AttributeType OptionalInjection TargetType . OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            25 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Intern
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<26>:
+            //   Configuration: TargetType OptionalInjection AttributeType . OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            26 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Intern
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<27>:
+            //   AttributeType: "%attr" AttributeTypeName "%spanned" .  #(NonAssoc, 0)
+            27 => match aa_tag {
+                // AttributeType: "%attr" AttributeTypeName "%spanned" #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Intern | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat
+                | ShiftReduce | Start | Target | Visitor => Action::Reduce(14),
+                _ => Action::SyntaxError,
+            },
+            // State<28>:
+            //   TargetType: "%target" Ident Lifetime .  #(NonAssoc, 0)
+            28 => match aa_tag {
+                // TargetType: "%target" Ident Lifetime #(NonAssoc, 0)
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => Action::Reduce(17),
+                _ => Action::SyntaxError,
+            },
+            // State<29>:
+            //   TargetType: "%target" Ident TypeParameter .  #(NonAssoc, 0)
+            29 => match aa_tag {
+                // TargetType: "%target" Ident TypeParameter #(NonAssoc, 0)
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => Action::Reduce(18),
+                _ => Action::SyntaxError,
+            },
+            // State<30>:
+            //   Specification: Preamble Configuration "%%" Definitions "%%" . ProductionRules SpecificationTests #(NonAssoc, 0)
+            30 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Ident => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<31>:
+            //   Definitions: TokenDefinitions SkipDefinitions . ModeDefinitions PrecedenceDefinitions TypeDefinitions #(NonAssoc, 0)
+            //   SkipDefinitions: SkipDefinitions . OptionalInjection SkipDefinition OptionalInjection #(NonAssoc, 0)
+            31 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Skip => Action::Reduce(5),
+                // ModeDefinitions: <empty> #(NonAssoc, 0)
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Type => Action::Reduce(76),
+                _ => Action::SyntaxError,
+            },
+            // State<32>:
+            //   TokenDefinitions: TokenDefinitions OptionalInjection . TokenDefinition OptionalInjection #(NonAssoc, 0)
+            32 => match aa_tag {
+                Keywords => Action::Shift(36),
+                Token => Action::Shift(37),
+                _ => Action::SyntaxError,
+            },
+            // State<33>:
+            //   TokenDefinitions: OptionalInjection TokenDefinition .  #(NonAssoc, 0)
+            33 => match aa_tag {
+                // TokenDefinitions: OptionalInjection TokenDefinition #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(55),
+                _ => Action::SyntaxError,
+            },
+            // State<34>:
+            //   TokenDefinition: TokenDefinitionHead .  #(NonAssoc, 0)
+            //   TokenDefinition: TokenDefinitionHead . Action #(NonAssoc, 0)
+            //   TokenDefinition: TokenDefinitionHead . ModeSwitch #(NonAssoc, 0)
+            //   TokenDefinition: TokenDefinitionHead . ModeSwitch Action #(NonAssoc, 0)
+            34 => match aa_tag {
+                Begin => Action::Shift(47),
+                ActionCode => Action::Shift(48),
+                // TokenDefinition: TokenDefinitionHead #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(57),
+                _ => Action::SyntaxError,
+            },
+            // State<35>:
+            //   TokenDefinition: KeywordDefinition .  #(NonAssoc, 0)
+            //   KeywordDefinition: KeywordDefinition . Ident #(NonAssoc, 0)
+            35 => match aa_tag {
+                Ident => Action::Shift(49),
+                // TokenDefinition: KeywordDefinition #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(61),
+                _ => Action::SyntaxError,
+            },
+            // State<36>:
+            //   KeywordDefinition: "%keywords" . Ident #(NonAssoc, 0)
+            36 => match aa_tag {
+                Ident => Action::Shift(50),
+                _ => Action::SyntaxError,
+            },
+            // State<37>:
+            //   TokenDefinitionHead: "%token" . NewTokenName TokenLiteral OptionalDisplayName #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" . NewTokenName RegularExpression OptionalDisplayName #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" . NewTokenName RegularExpression OptionalDisplayName "%transform" Action #(NonAssoc, 0)
+            37 => match aa_tag {
+                TokenName => Action::Shift(52),
+                _ => Action::SyntaxError,
+            },
+            // State<38>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection . Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            38 => match aa_tag {
+                Intern => Action::Shift(54),
+                // Interning: <empty> #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => Action::Reduce(19),
+                _ => Action::SyntaxError,
+            },
+            // State<39>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection . Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            39 => match aa_tag {
+                Intern => Action::Shift(54),
+                // Interning: <empty> #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => Action::Reduce(19),
+                _ => Action::SyntaxError,
+            },
+            // State<40>:
+            //   Specification: Preamble Configuration "%%" Definitions "%%" ProductionRules . SpecificationTests #(NonAssoc, 0)
+            //   ProductionRules: ProductionRules . ProductionGroup OptionalInjection #(NonAssoc, 0)
+            40 => match aa_tag {
+                Ident => Action::Shift(59),
+                // SpecificationTests: <empty> #(NonAssoc, 0)
+                AAEnd | Test => Action::Reduce(2),
+                _ => Action::SyntaxError,
+            },
+            // State<41>:
+            //   ProductionRules: OptionalInjection . ProductionGroup OptionalInjection #(NonAssoc, 0)
+            41 => match aa_tag {
+                Ident => Action::Shift(59),
+                _ => Action::SyntaxError,
+            },
+            // State<42>:
+            //   Definitions: TokenDefinitions SkipDefinitions ModeDefinitions . PrecedenceDefinitions TypeDefinitions #(NonAssoc, 0)
+            //   ModeDefinitions: ModeDefinitions . ModeDefinition #(NonAssoc, 0)
+            42 => match aa_tag {
+                Mode => Action::Shift(64),
+                // PrecedenceDefinitions: <empty> #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(80),
+                _ => Action::SyntaxError,
+            },
+            // State<43>:
+            //   SkipDefinitions: SkipDefinitions OptionalInjection . SkipDefinition OptionalInjection #(NonAssoc, 0)
+            43 => match aa_tag {
+                Skip => Action::Shift(66),
+                _ => Action::SyntaxError,
+            },
+            // State<44>:
+            //   TokenDefinitions: TokenDefinitions OptionalInjection TokenDefinition . OptionalInjection #(NonAssoc, 0)
+            44 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Keywords | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor
+                | Recover | Right | Skip | Token | Type => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<45>:
+            //   TokenDefinition: TokenDefinitionHead Action .  #(NonAssoc, 0)
+            45 => match aa_tag {
+                // TokenDefinition: TokenDefinitionHead Action #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(58),
+                _ => Action::SyntaxError,
+            },
+            // State<46>:
+            //   TokenDefinition: TokenDefinitionHead ModeSwitch .  #(NonAssoc, 0)
+            //   TokenDefinition: TokenDefinitionHead ModeSwitch . Action #(NonAssoc, 0)
+            46 => match aa_tag {
+                ActionCode => Action::Shift(48),
+                // TokenDefinition: TokenDefinitionHead ModeSwitch #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(59),
+                _ => Action::SyntaxError,
+            },
+            // State<47>:
+            //   ModeSwitch: "%begin" . Ident #(NonAssoc, 0)
+            47 => match aa_tag {
+                Ident => Action::Shift(69),
+                _ => Action::SyntaxError,
+            },
+            // State<48>:
+            //   Action: ActionCode .  #(NonAssoc, 0)
+            48 => match aa_tag {
+                // Action: ActionCode #(NonAssoc, 0)
+                ActionCode | Begin | Dot | Inject | Keywords | Left | Mode | NewSection
+                | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type
+                | VerticalBar => Action::Reduce(120),
+                _ => Action::SyntaxError,
+            },
+            // State<49>:
+            //   KeywordDefinition: KeywordDefinition Ident .  #(NonAssoc, 0)
+            49 => match aa_tag {
+                // KeywordDefinition: KeywordDefinition Ident #(NonAssoc, 0)
+                Ident | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(63),
+                _ => Action::SyntaxError,
+            },
+            // State<50>:
+            //   KeywordDefinition: "%keywords" Ident .  #(NonAssoc, 0)
+            50 => match aa_tag {
+                // KeywordDefinition: "%keywords" Ident #(NonAssoc, 0)
+                Ident | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(62),
+                _ => Action::SyntaxError,
+            },
+            // State<51>:
+            //   TokenDefinitionHead: "%token" NewTokenName . TokenLiteral OptionalDisplayName #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" NewTokenName . RegularExpression OptionalDisplayName #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" NewTokenName . RegularExpression OptionalDisplayName "%transform" Action #(NonAssoc, 0)
+            51 => match aa_tag {
+                RegEx => Action::Shift(72),
+                TokenLiteral => Action::Shift(70),
+                _ => Action::SyntaxError,
+            },
+            // State<52>:
+            //   NewTokenName: TokenName .  #(NonAssoc, 0) ?( !self.is_allowable_name($1.matched_text()) ?)
+            //   NewTokenName: TokenName .  #(NonAssoc, 0)
+            52 => match aa_tag {
+                RegEx | TokenLiteral => {
+                    if !self.is_allowable_name(aa_attributes.at_len_minus_n(1).matched_text()) {
+                        // NewTokenName: TokenName #(NonAssoc, 0) ?( !self.is_allowable_name($1.matched_text()) ?)
+                        Action::Reduce(71)
+                    } else {
+                        // NewTokenName: TokenName #(NonAssoc, 0)
+                        Action::Reduce(72)
+                    }
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<53>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning . CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            53 => match aa_tag {
+                Coverage => Action::Shift(74),
+                // CoverageRecording: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => Action::Reduce(21),
+                _ => Action::SyntaxError,
+            },
+            // State<54>:
+            //   Interning: "%intern" . Ident OptionalInjection #(NonAssoc, 0)
+            54 => match aa_tag {
+                Ident => Action::Shift(75),
+                _ => Action::SyntaxError,
+            },
+            // State<55>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning . CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            55 => match aa_tag {
+                Coverage => Action::Shift(74),
+                // CoverageRecording: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => Action::Reduce(21),
+                _ => Action::SyntaxError,
+            },
+            // State<56>:
+            //   Specification: Preamble Configuration "%%" Definitions "%%" ProductionRules SpecificationTests .  #(NonAssoc, 0)
+            //   SpecificationTests: SpecificationTests . SpecificationTest #(NonAssoc, 0)
+            56 => match aa_tag {
+                Test => Action::Shift(78),
+                // Specification: Preamble Configuration "%%" Definitions "%%" ProductionRules SpecificationTests #(NonAssoc, 0)
+                AAEnd => Action::Reduce(1),
+                _ => Action::SyntaxError,
+            },
+            // State<57>:
+            //   ProductionRules: ProductionRules ProductionGroup . OptionalInjection #(NonAssoc, 0)
+            57 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                AAEnd | Ident | Test => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<58>:
+            //   ProductionGroup: ProductionGroupHead . ProductionTailList "." #(NonAssoc, 0)
+            58 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                ActionCode => Action::Shift(48),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                PredicateExpr => Action::Shift(85),
+                // ProductionTail: <empty> #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(107),
+                _ => Action::SyntaxError,
+            },
+            // State<59>:
+            //   ProductionGroupHead: Ident . ":" #(NonAssoc, 0)
+            59 => match aa_tag {
+                Colon => Action::Shift(93),
+                _ => Action::SyntaxError,
+            },
+            // State<60>:
+            //   ProductionRules: OptionalInjection ProductionGroup . OptionalInjection #(NonAssoc, 0)
+            60 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                AAEnd | Ident | Test => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<61>:
+            //   Definitions: TokenDefinitions SkipDefinitions ModeDefinitions PrecedenceDefinitions . TypeDefinitions #(NonAssoc, 0)
+            //   PrecedenceDefinitions: PrecedenceDefinitions . OptionalInjection PrecedenceDefinition OptionalInjection #(NonAssoc, 0)
+            61 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Left | NonAssoc | PrecedenceFor | Right => Action::Reduce(5),
+                // TypeDefinitions: <empty> #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(93),
+                _ => Action::SyntaxError,
+            },
+            // State<62>:
+            //   ModeDefinitions: ModeDefinitions ModeDefinition .  #(NonAssoc, 0)
+            62 => match aa_tag {
+                // ModeDefinitions: ModeDefinitions ModeDefinition #(NonAssoc, 0)
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(77),
+                _ => Action::SyntaxError,
+            },
+            // State<63>:
+            //   ModeDefinition: ModeHead . TokenDefinitions SkipDefinitions #(NonAssoc, 0)
+            63 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Keywords | Token => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<64>:
+            //   ModeHead: "%mode" . Ident #(NonAssoc, 0)
+            64 => match aa_tag {
+                Ident => Action::Shift(98),
+                _ => Action::SyntaxError,
+            },
+            // State<65>:
+            //   SkipDefinitions: SkipDefinitions OptionalInjection SkipDefinition . OptionalInjection #(NonAssoc, 0)
+            65 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<66>:
+            //   SkipDefinition: "%skip" . RegularExpression #(NonAssoc, 0)
+            66 => match aa_tag {
+                RegEx => Action::Shift(72),
+                _ => Action::SyntaxError,
+            },
+            // State<67>:
+            //   TokenDefinitions: TokenDefinitions OptionalInjection TokenDefinition OptionalInjection .  #(NonAssoc, 0)
+            67 => match aa_tag {
+                // TokenDefinitions: TokenDefinitions OptionalInjection TokenDefinition OptionalInjection #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(56),
+                _ => Action::SyntaxError,
+            },
+            // State<68>:
+            //   TokenDefinition: TokenDefinitionHead ModeSwitch Action .  #(NonAssoc, 0)
+            68 => match aa_tag {
+                // TokenDefinition: TokenDefinitionHead ModeSwitch Action #(NonAssoc, 0)
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(60),
+                _ => Action::SyntaxError,
+            },
+            // State<69>:
+            //   ModeSwitch: "%begin" Ident .  #(NonAssoc, 0)
+            69 => match aa_tag {
+                // ModeSwitch: "%begin" Ident #(NonAssoc, 0)
+                ActionCode | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => Action::Reduce(69),
+                _ => Action::SyntaxError,
+            },
+            // State<70>:
+            //   TokenDefinitionHead: "%token" NewTokenName TokenLiteral . OptionalDisplayName #(NonAssoc, 0)
+            70 => match aa_tag {
+                Literal => Action::Shift(102),
+                // OptionalDisplayName: <empty> #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    Action::Reduce(67)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<71>:
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression . OptionalDisplayName #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression . OptionalDisplayName "%transform" Action #(NonAssoc, 0)
+            71 => match aa_tag {
+                Literal => Action::Shift(102),
+                // OptionalDisplayName: <empty> #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Transform | Type => {
+                    Action::Reduce(67)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<72>:
+            //   RegularExpression: RegEx .  #(NonAssoc, 0)
+            72 => match aa_tag {
+                // RegularExpression: RegEx #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Literal | Mode | NewSection
+                | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token
+                | Transform | Type => Action::Reduce(70),
+                _ => Action::SyntaxError,
+            },
+            // State<73>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording . DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            73 => match aa_tag {
+                Derivation => Action::Shift(105),
+                // DerivationRecording: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    Action::Reduce(23)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<74>:
+            //   CoverageRecording: "%coverage" . Ident OptionalInjection #(NonAssoc, 0)
+            74 => match aa_tag {
+                Ident => Action::Shift(106),
+                _ => Action::SyntaxError,
+            },
+            // State<75>:
+            //   Interning: "%intern" Ident . OptionalInjection #(NonAssoc, 0)
+            75 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<76>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording . DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            76 => match aa_tag {
+                Derivation => Action::Shift(105),
+                // DerivationRecording: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    Action::Reduce(23)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<77>:
+            //   SpecificationTests: SpecificationTests SpecificationTest .  #(NonAssoc, 0)
+            77 => match aa_tag {
+                // SpecificationTests: SpecificationTests SpecificationTest #(NonAssoc, 0)
+                AAEnd | Test => Action::Reduce(3),
+                _ => Action::SyntaxError,
+            },
+            // State<78>:
+            //   SpecificationTest: "%test" . Literal "=>" Ident #(NonAssoc, 0)
+            78 => match aa_tag {
+                Literal => Action::Shift(109),
+                _ => Action::SyntaxError,
+            },
+            // State<79>:
+            //   ProductionRules: ProductionRules ProductionGroup OptionalInjection .  #(NonAssoc, 0)
+            79 => match aa_tag {
+                // ProductionRules: ProductionRules ProductionGroup OptionalInjection #(NonAssoc, 0)
+                AAEnd | Ident | Test => Action::Reduce(102),
+                _ => Action::SyntaxError,
+            },
+            // State<80>:
+            //   ProductionGroup: ProductionGroupHead ProductionTailList . "." #(NonAssoc, 0)
+            //   ProductionTailList: ProductionTailList . "|" ProductionTail #(NonAssoc, 0)
+            80 => match aa_tag {
+                Dot => Action::Shift(110),
+                VerticalBar => Action::Shift(111),
+                _ => Action::SyntaxError,
+            },
+            // State<81>:
+            //   ProductionTailList: ProductionTail .  #(NonAssoc, 0)
+            81 => match aa_tag {
+                // ProductionTailList: ProductionTail #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(105),
+                _ => Action::SyntaxError,
+            },
+            // State<82>:
+            //   ProductionTail: Action .  #(NonAssoc, 0)
+            82 => match aa_tag {
+                // ProductionTail: Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(108),
+                _ => Action::SyntaxError,
+            },
+            // State<83>:
+            //   ProductionTail: Predicate . Action #(NonAssoc, 0)
+            //   ProductionTail: Predicate .  #(NonAssoc, 0)
+            83 => match aa_tag {
+                ActionCode => Action::Shift(48),
+                // ProductionTail: Predicate #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(110),
+                _ => Action::SyntaxError,
+            },
+            // State<84>:
+            //   ProductionTail: SymbolList . Predicate TaggedPrecedence Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . Predicate TaggedPrecedence #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . Predicate Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . Predicate Rejection #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . Predicate #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . TaggedPrecedence Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . TaggedPrecedence #(NonAssoc, 0)
+            //   ProductionTail: SymbolList . Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList .  #(NonAssoc, 0)
+            //   SymbolList: SymbolList . Element #(NonAssoc, 0)
+            84 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                Precedence => Action::Shift(116),
+                ActionCode => Action::Shift(48),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                PredicateExpr => Action::Shift(85),
+                // ProductionTail: SymbolList #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(119),
+                _ => Action::SyntaxError,
+            },
+            // State<85>:
+            //   Predicate: PredicateExpr .  #(NonAssoc, 0)
+            85 => match aa_tag {
+                // Predicate: PredicateExpr #(NonAssoc, 0)
+                ActionCode | Dot | Precedence | Reject | VerticalBar => Action::Reduce(121),
+                _ => Action::SyntaxError,
+            },
+            // State<86>:
+            //   SymbolList: Element .  #(NonAssoc, 0)
+            86 => match aa_tag {
+                // SymbolList: Element #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(125),
+                _ => Action::SyntaxError,
+            },
+            // State<87>:
+            //   Element: Symbol .  #(NonAssoc, 0)
+            //   Element: Symbol . "?" #(NonAssoc, 0)
+            //   Element: Symbol . "*" #(NonAssoc, 0)
+            //   Element: Symbol . "+" #(NonAssoc, 0)
+            //   SeparatedList: Symbol . "%" Symbol #(NonAssoc, 0)
+            //   SeparatedList: Symbol . "%%" Symbol #(NonAssoc, 0)
+            87 => match aa_tag {
+                ListSeparator => Action::Shift(121),
+                NewSection => Action::Shift(122),
+                Optional => Action::Shift(118),
+                Plus => Action::Shift(120),
+                Star => Action::Shift(119),
+                // Element: Symbol #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(127),
+                _ => Action::SyntaxError,
+            },
+            // State<88>:
+            //   Element: SeparatedList .  #(NonAssoc, 0)
+            88 => match aa_tag {
+                // Element: SeparatedList #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(128),
+                _ => Action::SyntaxError,
+            },
+            // State<89>:
+            //   Symbol: Ident .  #(NonAssoc, 0)
+            89 => match aa_tag {
+                // Symbol: Ident #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => Action::Reduce(134),
+                _ => Action::SyntaxError,
+            },
+            // State<90>:
+            //   Symbol: Literal .  #(NonAssoc, 0)
+            90 => match aa_tag {
+                // Symbol: Literal #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => Action::Reduce(135),
+                _ => Action::SyntaxError,
+            },
+            // State<91>:
+            //   Symbol: "%error" .  #(NonAssoc, 0)
+            91 => match aa_tag {
+                // Symbol: "%error" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => Action::Reduce(136),
+                _ => Action::SyntaxError,
+            },
+            // State<92>:
+            //   Symbol: "(" . SymbolList ")" #(NonAssoc, 0)
+            //   Symbol: "(" . SymbolList TaggedPrecedence ")" #(NonAssoc, 0)
+            92 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                _ => Action::SyntaxError,
+            },
+            // State<93>:
+            //   ProductionGroupHead: Ident ":" .  #(NonAssoc, 0)
+            93 => match aa_tag {
+                // ProductionGroupHead: Ident ":" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | PredicateExpr
+                | VerticalBar => Action::Reduce(104),
+                _ => Action::SyntaxError,
+            },
+            // State<94>:
+            //   ProductionRules: OptionalInjection ProductionGroup OptionalInjection .  #(NonAssoc, 0)
+            94 => match aa_tag {
+                // ProductionRules: OptionalInjection ProductionGroup OptionalInjection #(NonAssoc, 0)
+                AAEnd | Ident | Test => Action::Reduce(101),
+                _ => Action::SyntaxError,
+            },
+            // State<95>:
+            //   Definitions: TokenDefinitions SkipDefinitions ModeDefinitions PrecedenceDefinitions TypeDefinitions .  #(NonAssoc, 0)
+            //   TypeDefinitions: TypeDefinitions . TypeDefinition OptionalInjection #(NonAssoc, 0)
+            //   TypeDefinitions: TypeDefinitions . NoSkipDefinition OptionalInjection #(NonAssoc, 0)
+            //   TypeDefinitions: TypeDefinitions . RecoveryDefinition OptionalInjection #(NonAssoc, 0)
+            95 => match aa_tag {
+                NoSkip => Action::Shift(128),
+                Recover => Action::Shift(129),
+                Type => Action::Shift(127),
+                // Definitions: TokenDefinitions SkipDefinitions ModeDefinitions PrecedenceDefinitions TypeDefinitions #(NonAssoc, 0)
+                NewSection => Action::Reduce(54),
+                _ => Action::SyntaxError,
+            },
+            // State<96>:
+            //   PrecedenceDefinitions: PrecedenceDefinitions OptionalInjection . PrecedenceDefinition OptionalInjection #(NonAssoc, 0)
+            96 => match aa_tag {
+                Left => Action::Shift(131),
+                NonAssoc => Action::Shift(133),
+                PrecedenceFor => Action::Shift(134),
+                Right => Action::Shift(132),
+                _ => Action::SyntaxError,
+            },
+            // State<97>:
+            //   TokenDefinitions: TokenDefinitions . OptionalInjection TokenDefinition OptionalInjection #(NonAssoc, 0)
+            //   ModeDefinition: ModeHead TokenDefinitions . SkipDefinitions #(NonAssoc, 0)
+            97 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Keywords | Token => Action::Reduce(5),
+                // SkipDefinitions: <empty> #(NonAssoc, 0)
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => Action::Reduce(73),
+                _ => Action::SyntaxError,
+            },
+            // State<98>:
+            //   ModeHead: "%mode" Ident .  #(NonAssoc, 0)
+            98 => match aa_tag {
+                // ModeHead: "%mode" Ident #(NonAssoc, 0)
+                Inject | Keywords | Token => Action::Reduce(79),
+                _ => Action::SyntaxError,
+            },
+            // State<99>:
+            //   SkipDefinitions: SkipDefinitions OptionalInjection SkipDefinition OptionalInjection .  #(NonAssoc, 0)
+            99 => match aa_tag {
+                // SkipDefinitions: SkipDefinitions OptionalInjection SkipDefinition OptionalInjection #(NonAssoc, 0)
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Skip | Type => Action::Reduce(74),
+                _ => Action::SyntaxError,
+            },
+            // State<100>:
+            //   SkipDefinition: "%skip" RegularExpression .  #(NonAssoc, 0)
+            100 => match aa_tag {
+                // SkipDefinition: "%skip" RegularExpression #(NonAssoc, 0)
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Skip | Type => Action::Reduce(75),
+                _ => Action::SyntaxError,
+            },
+            // State<101>:
+            //   TokenDefinitionHead: "%token" NewTokenName TokenLiteral OptionalDisplayName .  #(NonAssoc, 0)
+            101 => match aa_tag {
+                // TokenDefinitionHead: "%token" NewTokenName TokenLiteral OptionalDisplayName #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    Action::Reduce(64)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<102>:
+            //   OptionalDisplayName: Literal .  #(NonAssoc, 0)
+            102 => match aa_tag {
+                // OptionalDisplayName: Literal #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Transform | Type => {
+                    Action::Reduce(68)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<103>:
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName .  #(NonAssoc, 0)
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName . "%transform" Action #(NonAssoc, 0)
+            103 => match aa_tag {
+                Transform => Action::Shift(136),
+                // TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    Action::Reduce(65)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<104>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording . VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            104 => match aa_tag {
+                Visitor => Action::Shift(138),
+                // VisitorGeneration: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    Action::Reduce(25)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<105>:
+            //   DerivationRecording: "%derivation" . Ident OptionalInjection #(NonAssoc, 0)
+            105 => match aa_tag {
+                Ident => Action::Shift(139),
+                _ => Action::SyntaxError,
+            },
+            // State<106>:
+            //   CoverageRecording: "%coverage" Ident . OptionalInjection #(NonAssoc, 0)
+            106 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derivation | Derive | Expect | Functional | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<107>:
+            //   Interning: "%intern" Ident OptionalInjection .  #(NonAssoc, 0)
+            107 => match aa_tag {
+                // Interning: "%intern" Ident OptionalInjection #(NonAssoc, 0)
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => Action::Reduce(20),
+                _ => Action::SyntaxError,
+            },
+            // State<108>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording . VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            108 => match aa_tag {
+                Visitor => Action::Shift(138),
+                // VisitorGeneration: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    Action::Reduce(25)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<109>:
+            //   SpecificationTest: "%test" Literal . "=>" Ident #(NonAssoc, 0)
+            109 => match aa_tag {
+                Yields => Action::Shift(142),
+                _ => Action::SyntaxError,
+            },
+            // State<110>:
+            //   ProductionGroup: ProductionGroupHead ProductionTailList "." .  #(NonAssoc, 0)
+            110 => match aa_tag {
+                // ProductionGroup: ProductionGroupHead ProductionTailList "." #(NonAssoc, 0)
+                AAEnd | Ident | Inject | Test => Action::Reduce(103),
+                _ => Action::SyntaxError,
+            },
+            // State<111>:
+            //   ProductionTailList: ProductionTailList "|" . ProductionTail #(NonAssoc, 0)
+            111 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                ActionCode => Action::Shift(48),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                PredicateExpr => Action::Shift(85),
+                // ProductionTail: <empty> #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(107),
+                _ => Action::SyntaxError,
+            },
+            // State<112>:
+            //   ProductionTail: Predicate Action .  #(NonAssoc, 0)
+            112 => match aa_tag {
+                // ProductionTail: Predicate Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(109),
+                _ => Action::SyntaxError,
+            },
+            // State<113>:
+            //   ProductionTail: SymbolList Predicate . TaggedPrecedence Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList Predicate . TaggedPrecedence #(NonAssoc, 0)
+            //   ProductionTail: SymbolList Predicate . Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList Predicate . Rejection #(NonAssoc, 0)
+            //   ProductionTail: SymbolList Predicate .  #(NonAssoc, 0)
+            113 => match aa_tag {
+                Precedence => Action::Shift(116),
+                Reject => Action::Shift(147),
+                ActionCode => Action::Shift(48),
+                // ProductionTail: SymbolList Predicate #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(115),
+                _ => Action::SyntaxError,
+            },
+            // State<114>:
+            //   ProductionTail: SymbolList TaggedPrecedence . Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList TaggedPrecedence .  #(NonAssoc, 0)
+            114 => match aa_tag {
+                ActionCode => Action::Shift(48),
+                // ProductionTail: SymbolList TaggedPrecedence #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(117),
+                _ => Action::SyntaxError,
+            },
+            // State<115>:
+            //   ProductionTail: SymbolList Action .  #(NonAssoc, 0)
+            115 => match aa_tag {
+                // ProductionTail: SymbolList Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(118),
+                _ => Action::SyntaxError,
+            },
+            // State<116>:
+            //   TaggedPrecedence: "%prec" . Ident #(NonAssoc, 0)
+            //   TaggedPrecedence: "%prec" . Literal #(NonAssoc, 0)
+            116 => match aa_tag {
+                Ident => Action::Shift(149),
+                Literal => Action::Shift(150),
+                _ => Action::SyntaxError,
+            },
+            // State<117>:
+            //   SymbolList: SymbolList Element .  #(NonAssoc, 0)
+            117 => match aa_tag {
+                // SymbolList: SymbolList Element #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(126),
+                _ => Action::SyntaxError,
+            },
+            // State<118>:
+            //   Element: Symbol "?" .  #(NonAssoc, 0)
+            118 => match aa_tag {
+                // Element: Symbol "?" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(129),
+                _ => Action::SyntaxError,
+            },
+            // State<119>:
+            //   Element: Symbol "*" .  #(NonAssoc, 0)
+            119 => match aa_tag {
+                // Element: Symbol "*" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(130),
+                _ => Action::SyntaxError,
+            },
+            // State<120>:
+            //   Element: Symbol "+" .  #(NonAssoc, 0)
+            120 => match aa_tag {
+                // Element: Symbol "+" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(131),
+                _ => Action::SyntaxError,
+            },
+            // State<121>:
+            //   SeparatedList: Symbol "%" . Symbol #(NonAssoc, 0)
+            121 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                _ => Action::SyntaxError,
+            },
+            // State<122>:
+            //   SeparatedList: Symbol "%%" . Symbol #(NonAssoc, 0)
+            122 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                _ => Action::SyntaxError,
+            },
+            // State<123>:
+            //   SymbolList: SymbolList . Element #(NonAssoc, 0)
+            //   Symbol: "(" SymbolList . ")" #(NonAssoc, 0)
+            //   Symbol: "(" SymbolList . TaggedPrecedence ")" #(NonAssoc, 0)
+            123 => match aa_tag {
+                Error => Action::Shift(91),
+                LeftParen => Action::Shift(92),
+                Precedence => Action::Shift(116),
+                RightParen => Action::Shift(153),
+                Ident => Action::Shift(89),
+                Literal => Action::Shift(90),
+                _ => Action::SyntaxError,
+            },
+            // State<124>:
+            //   TypeDefinitions: TypeDefinitions TypeDefinition . OptionalInjection #(NonAssoc, 0)
+            124 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<125>:
+            //   TypeDefinitions: TypeDefinitions NoSkipDefinition . OptionalInjection #(NonAssoc, 0)
+            //   NoSkipDefinition: NoSkipDefinition . Ident #(NonAssoc, 0)
+            125 => match aa_tag {
+                Inject => Action::Shift(4),
+                Ident => Action::Shift(157),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<126>:
+            //   TypeDefinitions: TypeDefinitions RecoveryDefinition . OptionalInjection #(NonAssoc, 0)
+            126 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<127>:
+            //   TypeDefinition: "%type" . TypedName RustType #(NonAssoc, 0)
+            127 => match aa_tag {
+                TypedName => Action::Shift(159),
+                _ => Action::SyntaxError,
+            },
+            // State<128>:
+            //   NoSkipDefinition: "%noskip" . Ident #(NonAssoc, 0)
+            128 => match aa_tag {
+                Ident => Action::Shift(160),
+                _ => Action::SyntaxError,
+            },
+            // State<129>:
+            //   RecoveryDefinition: "%recover" . TagList #(NonAssoc, 0)
+            129 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                _ => Action::SyntaxError,
+            },
+            // State<130>:
+            //   PrecedenceDefinitions: PrecedenceDefinitions OptionalInjection PrecedenceDefinition . OptionalInjection #(NonAssoc, 0)
+            130 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Type => {
+                    Action::Reduce(5)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<131>:
+            //   PrecedenceDefinition: "%left" . TagList #(NonAssoc, 0)
+            //   PrecedenceDefinition: "%left" . Number TagList #(NonAssoc, 0)
+            131 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<132>:
+            //   PrecedenceDefinition: "%right" . TagList #(NonAssoc, 0)
+            //   PrecedenceDefinition: "%right" . Number TagList #(NonAssoc, 0)
+            132 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<133>:
+            //   PrecedenceDefinition: "%nonassoc" . TagList #(NonAssoc, 0)
+            //   PrecedenceDefinition: "%nonassoc" . Number TagList #(NonAssoc, 0)
+            133 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<134>:
+            //   PrecedenceDefinition: "%prec-for" . Ident Tag #(NonAssoc, 0)
+            134 => match aa_tag {
+                Ident => Action::Shift(173),
+                _ => Action::SyntaxError,
+            },
+            // State<135>:
+            //   SkipDefinitions: SkipDefinitions . OptionalInjection SkipDefinition OptionalInjection #(NonAssoc, 0)
+            //   ModeDefinition: ModeHead TokenDefinitions SkipDefinitions .  #(NonAssoc, 0)
+            135 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                Skip => Action::Reduce(5),
+                // ModeDefinition: ModeHead TokenDefinitions SkipDefinitions #(NonAssoc, 0)
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Type => Action::Reduce(78),
+                _ => Action::SyntaxError,
+            },
+            // State<136>:
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName "%transform" . Action #(NonAssoc, 0)
+            136 => match aa_tag {
+                ActionCode => Action::Shift(48),
+                _ => Action::SyntaxError,
+            },
+            // State<137>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration . Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            137 => match aa_tag {
+                Observer => Action::Shift(176),
+                // Observing: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(27),
+                _ => Action::SyntaxError,
+            },
+            // State<138>:
+            //   VisitorGeneration: "%visitor" . OptionalInjection #(NonAssoc, 0)
+            138 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Observer
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<139>:
+            //   DerivationRecording: "%derivation" Ident . OptionalInjection #(NonAssoc, 0)
+            139 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Observer
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    Action::Reduce(5)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<140>:
+            //   CoverageRecording: "%coverage" Ident OptionalInjection .  #(NonAssoc, 0)
+            140 => match aa_tag {
+                // CoverageRecording: "%coverage" Ident OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => Action::Reduce(22),
+                _ => Action::SyntaxError,
+            },
+            // State<141>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration . Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            141 => match aa_tag {
+                Observer => Action::Shift(176),
+                // Observing: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(27),
+                _ => Action::SyntaxError,
+            },
+            // State<142>:
+            //   SpecificationTest: "%test" Literal "=>" . Ident #(NonAssoc, 0)
+            142 => match aa_tag {
+                Ident => Action::Shift(180),
+                _ => Action::SyntaxError,
+            },
+            // State<143>:
+            //   ProductionTailList: ProductionTailList "|" ProductionTail .  #(NonAssoc, 0)
+            143 => match aa_tag {
+                // ProductionTailList: ProductionTailList "|" ProductionTail #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(106),
+                _ => Action::SyntaxError,
+            },
+            // State<144>:
+            //   ProductionTail: SymbolList Predicate TaggedPrecedence . Action #(NonAssoc, 0)
+            //   ProductionTail: SymbolList Predicate TaggedPrecedence .  #(NonAssoc, 0)
+            144 => match aa_tag {
+                ActionCode => Action::Shift(48),
+                // ProductionTail: SymbolList Predicate TaggedPrecedence #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(112),
+                _ => Action::SyntaxError,
+            },
+            // State<145>:
+            //   ProductionTail: SymbolList Predicate Action .  #(NonAssoc, 0)
+            145 => match aa_tag {
+                // ProductionTail: SymbolList Predicate Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(113),
+                _ => Action::SyntaxError,
+            },
+            // State<146>:
+            //   ProductionTail: SymbolList Predicate Rejection .  #(NonAssoc, 0)
+            146 => match aa_tag {
+                // ProductionTail: SymbolList Predicate Rejection #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(114),
+                _ => Action::SyntaxError,
+            },
+            // State<147>:
+            //   Rejection: "%reject" . Literal #(NonAssoc, 0)
+            147 => match aa_tag {
+                Literal => Action::Shift(182),
+                _ => Action::SyntaxError,
+            },
+            // State<148>:
+            //   ProductionTail: SymbolList TaggedPrecedence Action .  #(NonAssoc, 0)
+            148 => match aa_tag {
+                // ProductionTail: SymbolList TaggedPrecedence Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(116),
+                _ => Action::SyntaxError,
+            },
+            // State<149>:
+            //   TaggedPrecedence: "%prec" Ident .  #(NonAssoc, 0)
+            149 => match aa_tag {
+                // TaggedPrecedence: "%prec" Ident #(NonAssoc, 0)
+                ActionCode | Dot | RightParen | VerticalBar => Action::Reduce(123),
+                _ => Action::SyntaxError,
+            },
+            // State<150>:
+            //   TaggedPrecedence: "%prec" Literal .  #(NonAssoc, 0)
+            150 => match aa_tag {
+                // TaggedPrecedence: "%prec" Literal #(NonAssoc, 0)
+                ActionCode | Dot | RightParen | VerticalBar => Action::Reduce(124),
+                _ => Action::SyntaxError,
+            },
+            // State<151>:
+            //   SeparatedList: Symbol "%" Symbol .  #(NonAssoc, 0)
+            151 => match aa_tag {
+                // SeparatedList: Symbol "%" Symbol #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(132),
+                _ => Action::SyntaxError,
+            },
+            // State<152>:
+            //   SeparatedList: Symbol "%%" Symbol .  #(NonAssoc, 0)
+            152 => match aa_tag {
+                // SeparatedList: Symbol "%%" Symbol #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => Action::Reduce(133),
+                _ => Action::SyntaxError,
+            },
+            // State<153>:
+            //   Symbol: "(" SymbolList ")" .  #(NonAssoc, 0)
+            153 => match aa_tag {
+                // Symbol: "(" SymbolList ")" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => Action::Reduce(137),
+                _ => Action::SyntaxError,
+            },
+            // State<154>:
+            //   Symbol: "(" SymbolList TaggedPrecedence . ")" #(NonAssoc, 0)
+            154 => match aa_tag {
+                RightParen => Action::Shift(183),
+                _ => Action::SyntaxError,
+            },
+            // State<155>:
+            //   TypeDefinitions: TypeDefinitions TypeDefinition OptionalInjection .  #(NonAssoc, 0)
+            155 => match aa_tag {
+                // TypeDefinitions: TypeDefinitions TypeDefinition OptionalInjection #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(94),
+                _ => Action::SyntaxError,
+            },
+            // State<156>:
+            //   TypeDefinitions: TypeDefinitions NoSkipDefinition OptionalInjection .  #(NonAssoc, 0)
+            156 => match aa_tag {
+                // TypeDefinitions: TypeDefinitions NoSkipDefinition OptionalInjection #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(95),
+                _ => Action::SyntaxError,
+            },
+            // State<157>:
+            //   NoSkipDefinition: NoSkipDefinition Ident .  #(NonAssoc, 0)
+            157 => match aa_tag {
+                // NoSkipDefinition: NoSkipDefinition Ident #(NonAssoc, 0)
+                Ident | Inject | NewSection | NoSkip | Recover | Type => Action::Reduce(99),
+                _ => Action::SyntaxError,
+            },
+            // State<158>:
+            //   TypeDefinitions: TypeDefinitions RecoveryDefinition OptionalInjection .  #(NonAssoc, 0)
+            158 => match aa_tag {
+                // TypeDefinitions: TypeDefinitions RecoveryDefinition OptionalInjection #(NonAssoc, 0)
+                NewSection | NoSkip | Recover | Type => Action::Reduce(96),
+                _ => Action::SyntaxError,
+            },
+            // State<159>:
+            //   TypeDefinition: "%type" TypedName . RustType #(NonAssoc, 0)
+            159 => match aa_tag {
+                RustType => Action::Shift(184),
+                _ => Action::SyntaxError,
+            },
+            // State<160>:
+            //   NoSkipDefinition: "%noskip" Ident .  #(NonAssoc, 0)
+            160 => match aa_tag {
+                // NoSkipDefinition: "%noskip" Ident #(NonAssoc, 0)
+                Ident | Inject | NewSection | NoSkip | Recover | Type => Action::Reduce(98),
+                _ => Action::SyntaxError,
+            },
+            // State<161>:
+            //   TagList: Tag .  #(NonAssoc, 0)
+            161 => match aa_tag {
+                // TagList: Tag #(NonAssoc, 0)
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => Action::Reduce(89),
+                _ => Action::SyntaxError,
+            },
+            // State<162>:
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            //   RecoveryDefinition: "%recover" TagList .  #(NonAssoc, 0)
+            162 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // RecoveryDefinition: "%recover" TagList #(NonAssoc, 0)
+                Inject | NewSection | NoSkip | Recover | Type => Action::Reduce(100),
+                _ => Action::SyntaxError,
+            },
+            // State<163>:
+            //   Tag: Literal .  #(NonAssoc, 0)
+            163 => match aa_tag {
+                // Tag: Literal #(NonAssoc, 0)
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => Action::Reduce(91),
+                _ => Action::SyntaxError,
+            },
+            // State<164>:
+            //   Tag: Ident .  #(NonAssoc, 0)
+            164 => match aa_tag {
+                // Tag: Ident #(NonAssoc, 0)
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => Action::Reduce(92),
+                _ => Action::SyntaxError,
+            },
+            // State<165>:
+            //   PrecedenceDefinitions: PrecedenceDefinitions OptionalInjection PrecedenceDefinition OptionalInjection .  #(NonAssoc, 0)
+            165 => match aa_tag {
+                // PrecedenceDefinitions: PrecedenceDefinitions OptionalInjection PrecedenceDefinition OptionalInjection #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(81),
+                _ => Action::SyntaxError,
+            },
+            // State<166>:
+            //   Number: NumberExpr .  #(NonAssoc, 0)
+            166 => match aa_tag {
+                // Number: NumberExpr #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Ident | Inject | Literal | Module
+                | NewSection | Prefix | ReduceReduce | Repeat | ShiftReduce => Action::Reduce(53),
+                _ => Action::SyntaxError,
+            },
+            // State<167>:
+            //   PrecedenceDefinition: "%left" TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            167 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%left" TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(82),
+                _ => Action::SyntaxError,
+            },
+            // State<168>:
+            //   PrecedenceDefinition: "%left" Number . TagList #(NonAssoc, 0)
+            168 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                _ => Action::SyntaxError,
+            },
+            // State<169>:
+            //   PrecedenceDefinition: "%right" TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            169 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%right" TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(84),
+                _ => Action::SyntaxError,
+            },
+            // State<170>:
+            //   PrecedenceDefinition: "%right" Number . TagList #(NonAssoc, 0)
+            170 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                _ => Action::SyntaxError,
+            },
+            // State<171>:
+            //   PrecedenceDefinition: "%nonassoc" TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            171 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%nonassoc" TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(86),
+                _ => Action::SyntaxError,
+            },
+            // State<172>:
+            //   PrecedenceDefinition: "%nonassoc" Number . TagList #(NonAssoc, 0)
+            172 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                _ => Action::SyntaxError,
+            },
+            // State<173>:
+            //   PrecedenceDefinition: "%prec-for" Ident . Tag #(NonAssoc, 0)
+            173 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                _ => Action::SyntaxError,
+            },
+            // State<174>:
+            //   TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName "%transform" Action .  #(NonAssoc, 0)
+            174 => match aa_tag {
+                // TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName "%transform" Action #(NonAssoc, 0)
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    Action::Reduce(66)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<175>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing . StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            175 => match aa_tag {
+                Start => Action::Shift(192),
+                // StartSymbol: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => Action::Reduce(29),
+                _ => Action::SyntaxError,
+            },
+            // State<176>:
+            //   Observing: "%observer" . Ident OptionalInjection #(NonAssoc, 0)
+            176 => match aa_tag {
+                Ident => Action::Shift(193),
+                _ => Action::SyntaxError,
+            },
+            // State<177>:
+            //   VisitorGeneration: "%visitor" OptionalInjection .  #(NonAssoc, 0)
+            177 => match aa_tag {
+                // VisitorGeneration: "%visitor" OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    Action::Reduce(26)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<178>:
+            //   DerivationRecording: "%derivation" Ident OptionalInjection .  #(NonAssoc, 0)
+            178 => match aa_tag {
+                // DerivationRecording: "%derivation" Ident OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    Action::Reduce(24)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<179>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing . StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            179 => match aa_tag {
+                Start => Action::Shift(192),
+                // StartSymbol: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => Action::Reduce(29),
+                _ => Action::SyntaxError,
+            },
+            // State<180>:
+            //   SpecificationTest: "%test" Literal "=>" Ident .  #(NonAssoc, 0)
+            180 => match aa_tag {
+                // SpecificationTest: "%test" Literal "=>" Ident #(NonAssoc, 0)
+                AAEnd | Test => Action::Reduce(4),
+                _ => Action::SyntaxError,
+            },
+            // State<181>:
+            //   ProductionTail: SymbolList Predicate TaggedPrecedence Action .  #(NonAssoc, 0)
+            181 => match aa_tag {
+                // ProductionTail: SymbolList Predicate TaggedPrecedence Action #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(111),
+                _ => Action::SyntaxError,
+            },
+            // State<182>:
+            //   Rejection: "%reject" Literal .  #(NonAssoc, 0)
+            182 => match aa_tag {
+                // Rejection: "%reject" Literal #(NonAssoc, 0)
+                Dot | VerticalBar => Action::Reduce(122),
+                _ => Action::SyntaxError,
+            },
+            // State<183>:
+            //   Symbol: "(" SymbolList TaggedPrecedence ")" .  #(NonAssoc, 0)
+            183 => match aa_tag {
+                // Symbol: "(" SymbolList TaggedPrecedence ")" #(NonAssoc, 0)
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => Action::Reduce(138),
+                _ => Action::SyntaxError,
+            },
+            // State<184>:
+            //   TypeDefinition: "%type" TypedName RustType .  #(NonAssoc, 0)
+            184 => match aa_tag {
+                // TypeDefinition: "%type" TypedName RustType #(NonAssoc, 0)
+                Inject | NewSection | NoSkip | Recover | Type => Action::Reduce(97),
+                _ => Action::SyntaxError,
+            },
+            // State<185>:
+            //   TagList: TagList Tag .  #(NonAssoc, 0)
+            185 => match aa_tag {
+                // TagList: TagList Tag #(NonAssoc, 0)
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => Action::Reduce(90),
+                _ => Action::SyntaxError,
+            },
+            // State<186>:
+            //   PrecedenceDefinition: "%left" Number TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            186 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%left" Number TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(83),
+                _ => Action::SyntaxError,
+            },
+            // State<187>:
+            //   PrecedenceDefinition: "%right" Number TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            187 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%right" Number TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(85),
+                _ => Action::SyntaxError,
+            },
+            // State<188>:
+            //   PrecedenceDefinition: "%nonassoc" Number TagList .  #(NonAssoc, 0)
+            //   TagList: TagList . Tag #(NonAssoc, 0)
+            188 => match aa_tag {
+                Ident => Action::Shift(164),
+                Literal => Action::Shift(163),
+                // PrecedenceDefinition: "%nonassoc" Number TagList #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(87),
+                _ => Action::SyntaxError,
+            },
+            // State<189>:
+            //   PrecedenceDefinition: "%prec-for" Ident Tag .  #(NonAssoc, 0)
+            189 => match aa_tag {
+                // PrecedenceDefinition: "%prec-for" Ident Tag #(NonAssoc, 0)
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => Action::Reduce(88),
+                _ => Action::SyntaxError,
+            },
+            // State<190>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol . ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            190 => match aa_tag {
+                Expect => Action::Shift(198),
+                ReduceReduce => Action::Shift(199),
+                ShiftReduce => Action::Shift(200),
+                // ExpectedConflicts: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => Action::Reduce(33),
+                _ => Action::SyntaxError,
+            },
+            // State<191>:
+            //   StartSymbol: StartSymbols .  #(NonAssoc, 0)
+            //   StartSymbols: StartSymbols . "%start" Ident OptionalInjection #(NonAssoc, 0)
+            191 => match aa_tag {
+                Start => Action::Shift(201),
+                // StartSymbol: StartSymbols #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => Action::Reduce(30),
+                _ => Action::SyntaxError,
+            },
+            // State<192>:
+            //   StartSymbols: "%start" . Ident OptionalInjection #(NonAssoc, 0)
+            192 => match aa_tag {
+                Ident => Action::Shift(202),
+                _ => Action::SyntaxError,
+            },
+            // State<193>:
+            //   Observing: "%observer" Ident . OptionalInjection #(NonAssoc, 0)
+            193 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(5),
+                _ => Action::SyntaxError,
+            },
+            // State<194>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol . ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            194 => match aa_tag {
+                Expect => Action::Shift(198),
+                ReduceReduce => Action::Shift(199),
+                ShiftReduce => Action::Shift(200),
+                // ExpectedConflicts: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => Action::Reduce(33),
+                _ => Action::SyntaxError,
+            },
+            // State<195>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts . DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            195 => match aa_tag {
+                DefaultConflict => Action::Shift(206),
+                // DefaultConflictResolution: <empty> #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    Action::Reduce(41)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<196>:
+            //   ExpectedConflicts: ExpectedRRConflicts . OptionalInjection ExpectedSRConflicts #(NonAssoc, 0)
+            //   ExpectedConflicts: ExpectedRRConflicts .  #(NonAssoc, 0)
+            196 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                ShiftReduce => Action::Reduce(5),
+                // ExpectedConflicts: ExpectedRRConflicts #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Module | NewSection | Prefix | Repeat => {
+                    Action::Reduce(36)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<197>:
+            //   ExpectedConflicts: ExpectedSRConflicts . OptionalInjection ExpectedRRConflicts #(NonAssoc, 0)
+            //   ExpectedConflicts: ExpectedSRConflicts .  #(NonAssoc, 0)
+            197 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                ReduceReduce => Action::Reduce(5),
+                // ExpectedConflicts: ExpectedSRConflicts #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Module | NewSection | Prefix | Repeat => {
+                    Action::Reduce(37)
+                }
+                _ => Action::SyntaxError,
+            },
+            // State<198>:
+            //   ExpectedConflicts: "%expect" . Number #(NonAssoc, 0)
+            198 => match aa_tag {
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<199>:
+            //   ExpectedRRConflicts: "%reduce_reduce" . Number #(NonAssoc, 0)
+            199 => match aa_tag {
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<200>:
+            //   ExpectedSRConflicts: "%shift_reduce" . Number #(NonAssoc, 0)
+            200 => match aa_tag {
+                NumberExpr => Action::Shift(166),
+                _ => Action::SyntaxError,
+            },
+            // State<201>:
+            //   StartSymbols: StartSymbols "%start" . Ident OptionalInjection #(NonAssoc, 0)
+            201 => match aa_tag {
+                Ident => Action::Shift(212),
+                _ => Action::SyntaxError,
+            },
+            // State<202>:
+            //   StartSymbols: "%start" Ident . OptionalInjection #(NonAssoc, 0)
+            202 => match aa_tag {
+                Inject => Action::Shift(4),
+                // OptionalInjection: <empty> #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(5),
                 _ => Action::SyntaxError,
             },
-            1 => match aa_tag {
-                // AAStart: Specification #(NonAssoc, 0)
-                AAEnd => Action::Accept,
+            // State<203>:
+            //   Observing: "%observer" Ident OptionalInjection .  #(NonAssoc, 0)
+            203 => match aa_tag {
+                // Observing: "%observer" Ident OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(28),
                 _ => Action::SyntaxError,
             },
-            2 => match aa_tag {
-                Attr => Action::Shift(10),
-                Target => Action::Shift(11),
+            // State<204>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts . DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            204 => match aa_tag {
+                DefaultConflict => Action::Shift(206),
+                // DefaultConflictResolution: <empty> #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    Action::Reduce(41)
+                }
                 _ => Action::SyntaxError,
             },
-            3 => match aa_tag {
-                // OptionalInjection: Injection #(NonAssoc, 0)
-                Attr | Inject | Left | NewSection | NonAssoc | ReduceReduce | Right
-                | ShiftReduce | Skip | Target | Token | Ident | RustCode | AAEnd => {
-                    Action::Reduce(3)
-                }
+            // State<205>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution . RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            205 => match aa_tag {
+                Repeat => Action::Shift(216),
+                // RepetitionRecursion: <empty> #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix => Action::Reduce(43),
                 _ => Action::SyntaxError,
             },
-            4 => match aa_tag {
-                Literal => Action::Shift(12),
+            // State<206>:
+            //   DefaultConflictResolution: "%default-conflict" . Ident #(NonAssoc, 0)
+            206 => match aa_tag {
+                Ident => Action::Shift(217),
                 _ => Action::SyntaxError,
             },
-            5 => match aa_tag {
-                Dot => Action::Shift(13),
+            // State<207>:
+            //   ExpectedConflicts: ExpectedRRConflicts OptionalInjection . ExpectedSRConflicts #(NonAssoc, 0)
+            207 => match aa_tag {
+                ShiftReduce => Action::Shift(200),
                 _ => Action::SyntaxError,
             },
-            6 => match aa_tag {
-                RustCode => Action::Shift(14),
+            // State<208>:
+            //   ExpectedConflicts: ExpectedSRConflicts OptionalInjection . ExpectedRRConflicts #(NonAssoc, 0)
+            208 => match aa_tag {
+                ReduceReduce => Action::Shift(199),
                 _ => Action::SyntaxError,
             },
-            7 => match aa_tag {
-                NewSection => Action::Shift(15),
+            // State<209>:
+            //   ExpectedConflicts: "%expect" Number .  #(NonAssoc, 0)
+            209 => match aa_tag {
+                // ExpectedConflicts: "%expect" Number #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => Action::Reduce(38),
                 _ => Action::SyntaxError,
             },
-            8 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Target => Action::Reduce(2),
+            // State<210>:
+            //   ExpectedRRConflicts: "%reduce_reduce" Number .  #(NonAssoc, 0)
+            210 => match aa_tag {
+                // ExpectedRRConflicts: "%reduce_reduce" Number #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat | ShiftReduce => Action::Reduce(39),
                 _ => Action::SyntaxError,
             },
-            9 => match aa_tag {
+            // State<211>:
+            //   ExpectedSRConflicts: "%shift_reduce" Number .  #(NonAssoc, 0)
+            211 => match aa_tag {
+                // ExpectedSRConflicts: "%shift_reduce" Number #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | ReduceReduce | Repeat => Action::Reduce(40),
+                _ => Action::SyntaxError,
+            },
+            // State<212>:
+            //   StartSymbols: StartSymbols "%start" Ident . OptionalInjection #(NonAssoc, 0)
+            212 => match aa_tag {
                 Inject => Action::Shift(4),
                 // OptionalInjection: <empty> #(NonAssoc, 0)
-                Attr => Action::Reduce(2),
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(5),
                 _ => Action::SyntaxError,
             },
-            10 => match aa_tag {
-                Ident => Action::Shift(18),
+            // State<213>:
+            //   StartSymbols: "%start" Ident OptionalInjection .  #(NonAssoc, 0)
+            213 => match aa_tag {
+                // StartSymbols: "%start" Ident OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(31),
                 _ => Action::SyntaxError,
             },
-            11 => match aa_tag {
-                Ident => Action::Shift(19),
+            // State<214>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution . RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            214 => match aa_tag {
+                Repeat => Action::Shift(216),
+                // RepetitionRecursion: <empty> #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix => Action::Reduce(43),
                 _ => Action::SyntaxError,
             },
-            12 => match aa_tag {
-                // InjectionHead: "%inject" Literal #(Right, 1)
-                Dot => Action::Reduce(4),
+            // State<215>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion . Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            215 => match aa_tag {
+                Derive => Action::Shift(223),
+                // Derives: <empty> #(NonAssoc, 0)
+                Functional | Inject | Module | NewSection | Prefix => Action::Reduce(45),
                 _ => Action::SyntaxError,
             },
-            13 => match aa_tag {
-                // Injection: InjectionHead "." #(NonAssoc, 0)
-                Attr | Inject | Left | NewSection | NonAssoc | ReduceReduce | Right
-                | ShiftReduce | Skip | Target | Token | Ident | RustCode | AAEnd => {
-                    Action::Reduce(5)
+            // State<216>:
+            //   RepetitionRecursion: "%repeat" . Ident #(NonAssoc, 0)
+            216 => match aa_tag {
+                Ident => Action::Shift(224),
+                _ => Action::SyntaxError,
+            },
+            // State<217>:
+            //   DefaultConflictResolution: "%default-conflict" Ident .  #(NonAssoc, 0)
+            217 => match aa_tag {
+                // DefaultConflictResolution: "%default-conflict" Ident #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    Action::Reduce(42)
                 }
                 _ => Action::SyntaxError,
             },
-            14 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Attr | Target => Action::Reduce(2),
+            // State<218>:
+            //   ExpectedConflicts: ExpectedRRConflicts OptionalInjection ExpectedSRConflicts .  #(NonAssoc, 0)
+            218 => match aa_tag {
+                // ExpectedConflicts: ExpectedRRConflicts OptionalInjection ExpectedSRConflicts #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => Action::Reduce(34),
                 _ => Action::SyntaxError,
             },
-            15 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Token => Action::Reduce(2),
+            // State<219>:
+            //   ExpectedConflicts: ExpectedSRConflicts OptionalInjection ExpectedRRConflicts .  #(NonAssoc, 0)
+            219 => match aa_tag {
+                // ExpectedConflicts: ExpectedSRConflicts OptionalInjection ExpectedRRConflicts #(NonAssoc, 0)
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => Action::Reduce(35),
                 _ => Action::SyntaxError,
             },
-            16 => match aa_tag {
-                Target => Action::Shift(11),
+            // State<220>:
+            //   StartSymbols: StartSymbols "%start" Ident OptionalInjection .  #(NonAssoc, 0)
+            220 => match aa_tag {
+                // StartSymbols: StartSymbols "%start" Ident OptionalInjection #(NonAssoc, 0)
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => Action::Reduce(32),
                 _ => Action::SyntaxError,
             },
-            17 => match aa_tag {
-                Attr => Action::Shift(10),
+            // State<221>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion . Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            221 => match aa_tag {
+                Derive => Action::Shift(223),
+                // Derives: <empty> #(NonAssoc, 0)
+                Functional | Inject | Module | NewSection | Prefix => Action::Reduce(45),
                 _ => Action::SyntaxError,
             },
-            18 => match aa_tag {
-                // AttributeType: "%attr" Ident #(NonAssoc, 0)
-                Inject | NewSection | ReduceReduce | ShiftReduce | Target => Action::Reduce(10),
+            // State<222>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives . FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            222 => match aa_tag {
+                Functional => Action::Shift(227),
+                // FunctionalInterface: <empty> #(NonAssoc, 0)
+                Inject | Module | NewSection | Prefix => Action::Reduce(47),
                 _ => Action::SyntaxError,
             },
-            19 => match aa_tag {
-                // TargetType: "%target" Ident #(NonAssoc, 0)
-                Attr | Inject | NewSection | ReduceReduce | ShiftReduce => Action::Reduce(11),
+            // State<223>:
+            //   Derives: "%derive" . Literal #(NonAssoc, 0)
+            223 => match aa_tag {
+                Literal => Action::Shift(228),
                 _ => Action::SyntaxError,
             },
-            20 => match aa_tag {
-                // Preamble: OptionalInjection RustCode OptionalInjection #(NonAssoc, 0)
-                Attr | Target => Action::Reduce(7),
+            // State<224>:
+            //   RepetitionRecursion: "%repeat" Ident .  #(NonAssoc, 0)
+            224 => match aa_tag {
+                // RepetitionRecursion: "%repeat" Ident #(NonAssoc, 0)
+                Derive | Functional | Inject | Module | NewSection | Prefix => Action::Reduce(44),
                 _ => Action::SyntaxError,
             },
-            21 => match aa_tag {
-                NewSection => Action::Shift(26),
+            // State<225>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives . FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            225 => match aa_tag {
+                Functional => Action::Shift(227),
+                // FunctionalInterface: <empty> #(NonAssoc, 0)
+                Inject | Module | NewSection | Prefix => Action::Reduce(47),
                 _ => Action::SyntaxError,
             },
-            22 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Token => Action::Reduce(2),
-                // SkipDefinitions: <empty> #(NonAssoc, 0)
-                Left | NewSection | NonAssoc | Right | Skip => Action::Reduce(28),
+            // State<226>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface . ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            226 => match aa_tag {
+                Module => Action::Shift(231),
+                // ModuleName: <empty> #(NonAssoc, 0)
+                Inject | NewSection | Prefix => Action::Reduce(49),
                 _ => Action::SyntaxError,
             },
-            23 => match aa_tag {
-                Token => Action::Shift(30),
+            // State<227>:
+            //   FunctionalInterface: "%functional" .  #(NonAssoc, 0)
+            227 => match aa_tag {
+                // FunctionalInterface: "%functional" #(NonAssoc, 0)
+                Inject | Module | NewSection | Prefix => Action::Reduce(48),
                 _ => Action::SyntaxError,
             },
-            24 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                NewSection | ReduceReduce | ShiftReduce => Action::Reduce(2),
+            // State<228>:
+            //   Derives: "%derive" Literal .  #(NonAssoc, 0)
+            228 => match aa_tag {
+                // Derives: "%derive" Literal #(NonAssoc, 0)
+                Functional | Inject | Module | NewSection | Prefix => Action::Reduce(46),
                 _ => Action::SyntaxError,
             },
-            25 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                NewSection | ReduceReduce | ShiftReduce => Action::Reduce(2),
+            // State<229>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface . ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            229 => match aa_tag {
+                Module => Action::Shift(231),
+                // ModuleName: <empty> #(NonAssoc, 0)
+                Inject | NewSection | Prefix => Action::Reduce(49),
                 _ => Action::SyntaxError,
             },
-            26 => match aa_tag {
+            // State<230>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName . SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            230 => match aa_tag {
+                Prefix => Action::Shift(234),
+                // SymbolPrefix: <empty> #(NonAssoc, 0)
+                Inject | NewSection => Action::Reduce(51),
+                _ => Action::SyntaxError,
+            },
+            // State<231>:
+            //   ModuleName: "%module" . Ident #(NonAssoc, 0)
+            231 => match aa_tag {
+                Ident => Action::Shift(235),
+                _ => Action::SyntaxError,
+            },
+            // State<232>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName . SymbolPrefix OptionalInjection #(NonAssoc, 0)
+            232 => match aa_tag {
+                Prefix => Action::Shift(234),
+                // SymbolPrefix: <empty> #(NonAssoc, 0)
+                Inject | NewSection => Action::Reduce(51),
+                _ => Action::SyntaxError,
+            },
+            // State<233>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix . OptionalInjection #(NonAssoc, 0)
+            233 => match aa_tag {
                 Inject => Action::Shift(4),
                 // OptionalInjection: <empty> #(NonAssoc, 0)
-                Ident => Action::Reduce(2),
+                NewSection => Action::Reduce(5),
                 _ => Action::SyntaxError,
             },
-            27 => match aa_tag {
+            // State<234>:
+            //   SymbolPrefix: "%prefix" . Ident #(NonAssoc, 0)
+            234 => match aa_tag {
+                Ident => Action::Shift(238),
+                _ => Action::SyntaxError,
+            },
+            // State<235>:
+            //   ModuleName: "%module" Ident .  #(NonAssoc, 0)
+            235 => match aa_tag {
+                // ModuleName: "%module" Ident #(NonAssoc, 0)
+                Inject | NewSection | Prefix => Action::Reduce(50),
+                _ => Action::SyntaxError,
+            },
+            // State<236>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix . OptionalInjection #(NonAssoc, 0)
+            236 => match aa_tag {
                 Inject => Action::Shift(4),
                 // OptionalInjection: <empty> #(NonAssoc, 0)
-                Skip => Action::Reduce(2),
-                // PrecedenceDefinitions: <empty> #(NonAssoc, 0)
-                Left | NewSection | NonAssoc | Right => Action::Reduce(31),
+                NewSection => Action::Reduce(5),
                 _ => Action::SyntaxError,
             },
-            28 => match aa_tag {
-                Token => Action::Shift(30),
+            // State<237>:
+            //   Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection .  #(NonAssoc, 0)
+            237 => match aa_tag {
+                // Configuration: AttributeType OptionalInjection TargetType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+                NewSection => Action::Reduce(11),
                 _ => Action::SyntaxError,
             },
-            29 => match aa_tag {
-                // TokenDefinitions: OptionalInjection TokenDefinition #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(21),
+            // State<238>:
+            //   SymbolPrefix: "%prefix" Ident .  #(NonAssoc, 0)
+            238 => match aa_tag {
+                // SymbolPrefix: "%prefix" Ident #(NonAssoc, 0)
+                Inject | NewSection => Action::Reduce(52),
                 _ => Action::SyntaxError,
             },
-            30 => match aa_tag {
-                Ident => Action::Shift(39),
+            // State<239>:
+            //   Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection .  #(NonAssoc, 0)
+            239 => match aa_tag {
+                // Configuration: TargetType OptionalInjection AttributeType OptionalInjection Interning CoverageRecording DerivationRecording VisitorGeneration Observing StartSymbol ExpectedConflicts DefaultConflictResolution RepetitionRecursion Derives FunctionalInterface ModuleName SymbolPrefix OptionalInjection #(NonAssoc, 0)
+                NewSection => Action::Reduce(12),
                 _ => Action::SyntaxError,
             },
+            _ => Action::IllegalState,
+        };
+    }
+
+    fn possible_actions(aa_state: u32, aa_tag: &AATerminal) -> Vec<lalr1_plus::Action> {
+        use lalr1_plus::Action;
+        use AATerminal::*;
+        match aa_state {
+            0 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                RustCode => vec![Action::Reduce(5)],
+                Attr | Target => vec![Action::Reduce(9)],
+                _ => vec![],
+            },
+            1 => match aa_tag {
+                AAEnd => vec![Action::Accept],
+                _ => vec![],
+            },
+            2 => match aa_tag {
+                Attr => vec![Action::Shift(10)],
+                Target => vec![Action::Shift(11)],
+                _ => vec![],
+            },
+            3 => match aa_tag {
+                AAEnd | Attr | Coverage | DefaultConflict | Derivation | Derive | Expect
+                | Functional | Ident | Inject | Intern | Keywords | Left | Mode | Module
+                | NewSection | NoSkip | NonAssoc | Observer | PrecedenceFor | Prefix | Recover
+                | ReduceReduce | Repeat | Right | RustCode | ShiftReduce | Skip | Start
+                | Target | Test | Token | Type | Visitor => vec![Action::Reduce(6)],
+                _ => vec![],
+            },
+            4 => match aa_tag {
+                Literal => vec![Action::Shift(12)],
+                _ => vec![],
+            },
+            5 => match aa_tag {
+                Dot => vec![Action::Shift(13)],
+                _ => vec![],
+            },
+            6 => match aa_tag {
+                RustCode => vec![Action::Shift(14)],
+                _ => vec![],
+            },
+            7 => match aa_tag {
+                NewSection => vec![Action::Shift(15)],
+                _ => vec![],
+            },
+            8 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Target => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            9 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Attr => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            10 => match aa_tag {
+                AttrType => vec![Action::Shift(19)],
+                _ => vec![],
+            },
+            11 => match aa_tag {
+                Ident => vec![Action::Shift(20)],
+                _ => vec![],
+            },
+            12 => match aa_tag {
+                Dot => vec![Action::Reduce(7)],
+                _ => vec![],
+            },
+            13 => match aa_tag {
+                AAEnd | Attr | Coverage | DefaultConflict | Derivation | Derive | Expect
+                | Functional | Ident | Inject | Intern | Keywords | Left | Mode | Module
+                | NewSection | NoSkip | NonAssoc | Observer | PrecedenceFor | Prefix | Recover
+                | ReduceReduce | Repeat | Right | RustCode | ShiftReduce | Skip | Start
+                | Target | Test | Token | Type | Visitor => vec![Action::Reduce(8)],
+                _ => vec![],
+            },
+            14 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Attr | Target => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            15 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Keywords | Token => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            16 => match aa_tag {
+                Target => vec![Action::Shift(11)],
+                _ => vec![],
+            },
+            17 => match aa_tag {
+                Attr => vec![Action::Shift(10)],
+                _ => vec![],
+            },
+            18 => match aa_tag {
+                Spanned => vec![Action::Shift(27)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Intern | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat
+                | ShiftReduce | Start | Target | Visitor => vec![Action::Reduce(13)],
+                _ => vec![],
+            },
+            19 => match aa_tag {
+                Inject | Spanned | Target => vec![Action::Reduce(15)],
+                _ => vec![],
+            },
+            20 => match aa_tag {
+                Lifetime => vec![Action::Shift(28)],
+                TypeParameter => vec![Action::Shift(29)],
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => vec![Action::Reduce(16)],
+                _ => vec![],
+            },
+            21 => match aa_tag {
+                Attr | Target => vec![Action::Reduce(10)],
+                _ => vec![],
+            },
+            22 => match aa_tag {
+                NewSection => vec![Action::Shift(30)],
+                _ => vec![],
+            },
+            23 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Keywords | Token => vec![Action::Reduce(5)],
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => vec![Action::Reduce(73)],
+                _ => vec![],
+            },
+            24 => match aa_tag {
+                Keywords => vec![Action::Shift(36)],
+                Token => vec![Action::Shift(37)],
+                _ => vec![],
+            },
+            25 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Intern
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            26 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Intern
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            27 => match aa_tag {
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Intern | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat
+                | ShiftReduce | Start | Target | Visitor => vec![Action::Reduce(14)],
+                _ => vec![],
+            },
+            28 => match aa_tag {
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => vec![Action::Reduce(17)],
+                _ => vec![],
+            },
+            29 => match aa_tag {
+                Attr | Coverage | DefaultConflict | Derivation | Derive | Expect | Functional
+                | Inject | Intern | Module | NewSection | Observer | Prefix | ReduceReduce
+                | Repeat | ShiftReduce | Start | Visitor => vec![Action::Reduce(18)],
+                _ => vec![],
+            },
+            30 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Ident => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
             31 => match aa_tag {
-                ReduceReduce => Action::Shift(43),
-                ShiftReduce => Action::Shift(44),
-                // ExpectedConflicts: <empty> #(NonAssoc, 0)
-                Inject | NewSection => Action::Reduce(12),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Skip => vec![Action::Reduce(5)],
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Type => vec![Action::Reduce(76)],
+                _ => vec![],
             },
             32 => match aa_tag {
-                ReduceReduce => Action::Shift(43),
-                ShiftReduce => Action::Shift(44),
-                // ExpectedConflicts: <empty> #(NonAssoc, 0)
-                Inject | NewSection => Action::Reduce(12),
-                _ => Action::SyntaxError,
+                Keywords => vec![Action::Shift(36)],
+                Token => vec![Action::Shift(37)],
+                _ => vec![],
             },
             33 => match aa_tag {
-                Ident => Action::Shift(48),
-                // Specification: Preamble Configuration "%%" Definitions "%%" ProductionRules #(NonAssoc, 0)
-                AAEnd => Action::Reduce(1),
-                _ => Action::SyntaxError,
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(55)],
+                _ => vec![],
             },
             34 => match aa_tag {
-                Ident => Action::Shift(48),
-                _ => Action::SyntaxError,
+                Begin => vec![Action::Shift(47)],
+                ActionCode => vec![Action::Shift(48)],
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(57)],
+                _ => vec![],
             },
             35 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Left | NonAssoc | Right => Action::Reduce(2),
-                // Definitions: TokenDefinitions SkipDefinitions PrecedenceDefinitions #(NonAssoc, 0)
-                NewSection => Action::Reduce(20),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(49)],
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(61)],
+                _ => vec![],
             },
             36 => match aa_tag {
-                Skip => Action::Shift(52),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(50)],
+                _ => vec![],
             },
             37 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                TokenName => vec![Action::Shift(52)],
+                _ => vec![],
             },
             38 => match aa_tag {
-                Literal => Action::Shift(54),
-                RegEx => Action::Shift(56),
-                _ => Action::SyntaxError,
+                Intern => vec![Action::Shift(54)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => vec![Action::Reduce(19)],
+                _ => vec![],
             },
             39 => match aa_tag {
-                Literal | RegEx => {
-                    if !Self::is_allowable_name(aa_attributes.at_len_minus_n(1).matched_text()) {
-                        // NewTokenName: Ident #(NonAssoc, 0) ?( !Self::is_allowable_name($1.matched_text()) ?)
-                        Action::Reduce(26)
-                    } else {
-                        // NewTokenName: Ident #(NonAssoc, 0)
-                        Action::Reduce(27)
-                    }
-                }
-                _ => Action::SyntaxError,
+                Intern => vec![Action::Shift(54)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => vec![Action::Reduce(19)],
+                _ => vec![],
             },
             40 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                NewSection => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(59)],
+                AAEnd | Test => vec![Action::Reduce(2)],
+                _ => vec![],
             },
             41 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                ShiftReduce => Action::Reduce(2),
-                // ExpectedConflicts: ExpectedRRConflicts #(NonAssoc, 0)
-                NewSection => Action::Reduce(15),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(59)],
+                _ => vec![],
             },
             42 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                ReduceReduce => Action::Reduce(2),
-                // ExpectedConflicts: ExpectedSRConflicts #(NonAssoc, 0)
-                NewSection => Action::Reduce(16),
-                _ => Action::SyntaxError,
+                Mode => vec![Action::Shift(64)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(80)],
+                _ => vec![],
             },
             43 => match aa_tag {
-                NumberExpr => Action::Shift(61),
-                _ => Action::SyntaxError,
+                Skip => vec![Action::Shift(66)],
+                _ => vec![],
             },
             44 => match aa_tag {
-                NumberExpr => Action::Shift(61),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Keywords | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor
+                | Recover | Right | Skip | Token | Type => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             45 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                NewSection => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(58)],
+                _ => vec![],
             },
             46 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Ident | AAEnd => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                ActionCode => vec![Action::Shift(48)],
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(59)],
+                _ => vec![],
             },
             47 => match aa_tag {
-                Error => Action::Shift(75),
-                ActionCode => Action::Shift(70),
-                Ident => Action::Shift(73),
-                Literal => Action::Shift(74),
-                PredicateExpr => Action::Shift(71),
-                // ProductionTail: <empty> #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(46),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(69)],
+                _ => vec![],
             },
             48 => match aa_tag {
-                Colon => Action::Shift(76),
-                _ => Action::SyntaxError,
+                ActionCode | Begin | Dot | Inject | Keywords | Left | Mode | NewSection
+                | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type
+                | VerticalBar => vec![Action::Reduce(120)],
+                _ => vec![],
             },
             49 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Ident | AAEnd => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                Ident | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(63)],
+                _ => vec![],
             },
             50 => match aa_tag {
-                Left => Action::Shift(79),
-                NonAssoc => Action::Shift(81),
-                Right => Action::Shift(80),
-                _ => Action::SyntaxError,
+                Ident | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(62)],
+                _ => vec![],
             },
             51 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Left | NewSection | NonAssoc | Right | Skip => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                RegEx => vec![Action::Shift(72)],
+                TokenLiteral => vec![Action::Shift(70)],
+                _ => vec![],
             },
             52 => match aa_tag {
-                RegEx => Action::Shift(56),
-                _ => Action::SyntaxError,
+                RegEx | TokenLiteral => vec![Action::Reduce(71), Action::Reduce(72)],
+                _ => vec![],
             },
             53 => match aa_tag {
-                // TokenDefinitions: TokenDefinitions OptionalInjection TokenDefinition OptionalInjection #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(22),
-                _ => Action::SyntaxError,
+                Coverage => vec![Action::Shift(74)],
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => vec![Action::Reduce(21)],
+                _ => vec![],
             },
             54 => match aa_tag {
-                // TokenDefinition: "%token" NewTokenName Literal #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(23),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(75)],
+                _ => vec![],
             },
             55 => match aa_tag {
-                // TokenDefinition: "%token" NewTokenName RegularExpression #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(24),
-                _ => Action::SyntaxError,
+                Coverage => vec![Action::Shift(74)],
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => vec![Action::Reduce(21)],
+                _ => vec![],
             },
             56 => match aa_tag {
-                // RegularExpression: RegEx #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip | Token => Action::Reduce(25),
-                _ => Action::SyntaxError,
+                Test => vec![Action::Shift(78)],
+                AAEnd => vec![Action::Reduce(1)],
+                _ => vec![],
             },
             57 => match aa_tag {
-                // Configuration: AttributeType OptionalInjection TargetType OptionalInjection ExpectedConflicts OptionalInjection #(NonAssoc, 0)
-                NewSection => Action::Reduce(8),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                AAEnd | Ident | Test => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             58 => match aa_tag {
-                ShiftReduce => Action::Shift(44),
-                _ => Action::SyntaxError,
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                ActionCode => vec![Action::Shift(48)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                PredicateExpr => vec![Action::Shift(85)],
+                Dot | VerticalBar => vec![Action::Reduce(107)],
+                _ => vec![],
             },
             59 => match aa_tag {
-                ReduceReduce => Action::Shift(43),
-                _ => Action::SyntaxError,
+                Colon => vec![Action::Shift(93)],
+                _ => vec![],
             },
             60 => match aa_tag {
-                // ExpectedRRConflicts: "%reduce_reduce" Number #(NonAssoc, 0)
-                Inject | NewSection | ShiftReduce => Action::Reduce(17),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                AAEnd | Ident | Test => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             61 => match aa_tag {
-                // Number: NumberExpr #(NonAssoc, 0)
-                Inject | NewSection | ReduceReduce | ShiftReduce => Action::Reduce(19),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Left | NonAssoc | PrecedenceFor | Right => vec![Action::Reduce(5)],
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(93)],
+                _ => vec![],
             },
             62 => match aa_tag {
-                // ExpectedSRConflicts: "%shift_reduce" Number #(NonAssoc, 0)
-                Inject | NewSection | ReduceReduce => Action::Reduce(18),
-                _ => Action::SyntaxError,
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(77)],
+                _ => vec![],
             },
             63 => match aa_tag {
-                // Configuration: TargetType OptionalInjection AttributeType OptionalInjection ExpectedConflicts OptionalInjection #(NonAssoc, 0)
-                NewSection => Action::Reduce(9),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Keywords | Token => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             64 => match aa_tag {
-                // ProductionRules: ProductionRules ProductionGroup OptionalInjection #(NonAssoc, 0)
-                Ident | AAEnd => Action::Reduce(41),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(98)],
+                _ => vec![],
             },
             65 => match aa_tag {
-                Dot => Action::Shift(86),
-                VerticalBar => Action::Shift(87),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             66 => match aa_tag {
-                // ProductionTailList: ProductionTail #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(44),
-                _ => Action::SyntaxError,
+                RegEx => vec![Action::Shift(72)],
+                _ => vec![],
             },
             67 => match aa_tag {
-                // ProductionTail: Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(47),
-                _ => Action::SyntaxError,
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(56)],
+                _ => vec![],
             },
             68 => match aa_tag {
-                ActionCode => Action::Shift(70),
-                // ProductionTail: Predicate #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(49),
-                _ => Action::SyntaxError,
+                Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(60)],
+                _ => vec![],
             },
             69 => match aa_tag {
-                Error => Action::Shift(75),
-                Precedence => Action::Shift(92),
-                ActionCode => Action::Shift(70),
-                Ident => Action::Shift(73),
-                Literal => Action::Shift(74),
-                PredicateExpr => Action::Shift(71),
-                // ProductionTail: SymbolList #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(57),
-                _ => Action::SyntaxError,
+                ActionCode | Inject | Keywords | Left | Mode | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Skip | Token | Type => vec![Action::Reduce(69)],
+                _ => vec![],
             },
             70 => match aa_tag {
-                // Action: ActionCode #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(58),
-                _ => Action::SyntaxError,
+                Literal => vec![Action::Shift(102)],
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    vec![Action::Reduce(67)]
+                }
+                _ => vec![],
             },
             71 => match aa_tag {
-                // Predicate: PredicateExpr #(NonAssoc, 0)
-                Dot | Precedence | VerticalBar | ActionCode => Action::Reduce(59),
-                _ => Action::SyntaxError,
+                Literal => vec![Action::Shift(102)],
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Transform | Type => {
+                    vec![Action::Reduce(67)]
+                }
+                _ => vec![],
             },
             72 => match aa_tag {
-                // SymbolList: Symbol #(NonAssoc, 0)
-                Dot | Error | Precedence | VerticalBar | ActionCode | Ident | Literal
-                | PredicateExpr => Action::Reduce(62),
-                _ => Action::SyntaxError,
+                ActionCode | Begin | Inject | Keywords | Left | Literal | Mode | NewSection
+                | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token
+                | Transform | Type => vec![Action::Reduce(70)],
+                _ => vec![],
             },
             73 => match aa_tag {
-                // Symbol: Ident #(NonAssoc, 0)
-                Dot | Error | Precedence | VerticalBar | ActionCode | Ident | Literal
-                | PredicateExpr => Action::Reduce(64),
-                _ => Action::SyntaxError,
+                Derivation => vec![Action::Shift(105)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    vec![Action::Reduce(23)]
+                }
+                _ => vec![],
             },
             74 => match aa_tag {
-                // Symbol: Literal #(NonAssoc, 0)
-                Dot | Error | Precedence | VerticalBar | ActionCode | Ident | Literal
-                | PredicateExpr => Action::Reduce(65),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(106)],
+                _ => vec![],
             },
             75 => match aa_tag {
-                // Symbol: "%error" #(NonAssoc, 0)
-                Dot | Error | Precedence | VerticalBar | ActionCode | Ident | Literal
-                | PredicateExpr => Action::Reduce(66),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             76 => match aa_tag {
-                // ProductionGroupHead: Ident ":" #(NonAssoc, 0)
-                Dot | Error | VerticalBar | ActionCode | Ident | Literal | PredicateExpr => {
-                    Action::Reduce(43)
+                Derivation => vec![Action::Shift(105)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    vec![Action::Reduce(23)]
                 }
-                _ => Action::SyntaxError,
+                _ => vec![],
             },
             77 => match aa_tag {
-                // ProductionRules: OptionalInjection ProductionGroup OptionalInjection #(NonAssoc, 0)
-                Ident | AAEnd => Action::Reduce(40),
-                _ => Action::SyntaxError,
+                AAEnd | Test => vec![Action::Reduce(3)],
+                _ => vec![],
             },
             78 => match aa_tag {
-                Inject => Action::Shift(4),
-                // OptionalInjection: <empty> #(NonAssoc, 0)
-                Left | NewSection | NonAssoc | Right => Action::Reduce(2),
-                _ => Action::SyntaxError,
+                Literal => vec![Action::Shift(109)],
+                _ => vec![],
             },
             79 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                _ => Action::SyntaxError,
+                AAEnd | Ident | Test => vec![Action::Reduce(102)],
+                _ => vec![],
             },
             80 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                _ => Action::SyntaxError,
+                Dot => vec![Action::Shift(110)],
+                VerticalBar => vec![Action::Shift(111)],
+                _ => vec![],
             },
             81 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                _ => Action::SyntaxError,
+                Dot | VerticalBar => vec![Action::Reduce(105)],
+                _ => vec![],
             },
             82 => match aa_tag {
-                // SkipDefinitions: SkipDefinitions OptionalInjection SkipDefinition OptionalInjection #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip => Action::Reduce(29),
-                _ => Action::SyntaxError,
+                Dot | VerticalBar => vec![Action::Reduce(108)],
+                _ => vec![],
             },
             83 => match aa_tag {
-                // SkipDefinition: "%skip" RegularExpression #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Skip => Action::Reduce(30),
-                _ => Action::SyntaxError,
+                ActionCode => vec![Action::Shift(48)],
+                Dot | VerticalBar => vec![Action::Reduce(110)],
+                _ => vec![],
             },
             84 => match aa_tag {
-                // ExpectedConflicts: ExpectedRRConflicts OptionalInjection ExpectedSRConflicts #(NonAssoc, 0)
-                Inject | NewSection => Action::Reduce(13),
-                _ => Action::SyntaxError,
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                Precedence => vec![Action::Shift(116)],
+                ActionCode => vec![Action::Shift(48)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                PredicateExpr => vec![Action::Shift(85)],
+                Dot | VerticalBar => vec![Action::Reduce(119)],
+                _ => vec![],
             },
             85 => match aa_tag {
-                // ExpectedConflicts: ExpectedSRConflicts OptionalInjection ExpectedRRConflicts #(NonAssoc, 0)
-                Inject | NewSection => Action::Reduce(14),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Precedence | Reject | VerticalBar => vec![Action::Reduce(121)],
+                _ => vec![],
             },
             86 => match aa_tag {
-                // ProductionGroup: ProductionGroupHead ProductionTailList "." #(NonAssoc, 0)
-                Inject | Ident | AAEnd => Action::Reduce(42),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(125)],
+                _ => vec![],
             },
             87 => match aa_tag {
-                Error => Action::Shift(75),
-                ActionCode => Action::Shift(70),
-                Ident => Action::Shift(73),
-                Literal => Action::Shift(74),
-                PredicateExpr => Action::Shift(71),
-                // ProductionTail: <empty> #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(46),
-                _ => Action::SyntaxError,
+                ListSeparator => vec![Action::Shift(121)],
+                NewSection => vec![Action::Shift(122)],
+                Optional => vec![Action::Shift(118)],
+                Plus => vec![Action::Shift(120)],
+                Star => vec![Action::Shift(119)],
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(127)],
+                _ => vec![],
             },
             88 => match aa_tag {
-                // ProductionTail: Predicate Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(48),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(128)],
+                _ => vec![],
             },
             89 => match aa_tag {
-                Precedence => Action::Shift(92),
-                ActionCode => Action::Shift(70),
-                // ProductionTail: SymbolList Predicate #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(53),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => vec![Action::Reduce(134)],
+                _ => vec![],
             },
             90 => match aa_tag {
-                ActionCode => Action::Shift(70),
-                // ProductionTail: SymbolList TaggedPrecedence #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(55),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => vec![Action::Reduce(135)],
+                _ => vec![],
             },
             91 => match aa_tag {
-                // ProductionTail: SymbolList Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(56),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => vec![Action::Reduce(136)],
+                _ => vec![],
             },
             92 => match aa_tag {
-                Ident => Action::Shift(105),
-                Literal => Action::Shift(106),
-                _ => Action::SyntaxError,
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                _ => vec![],
             },
             93 => match aa_tag {
-                // SymbolList: SymbolList Symbol #(NonAssoc, 0)
-                Dot | Error | Precedence | VerticalBar | ActionCode | Ident | Literal
-                | PredicateExpr => Action::Reduce(63),
-                _ => Action::SyntaxError,
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | PredicateExpr
+                | VerticalBar => vec![Action::Reduce(104)],
+                _ => vec![],
             },
             94 => match aa_tag {
-                // PrecedenceDefinitions: PrecedenceDefinitions OptionalInjection PrecedenceDefinition OptionalInjection #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right => Action::Reduce(32),
-                _ => Action::SyntaxError,
+                AAEnd | Ident | Test => vec![Action::Reduce(101)],
+                _ => vec![],
             },
             95 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                // PrecedenceDefinition: "%left" TagList #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right => Action::Reduce(33),
-                _ => Action::SyntaxError,
+                NoSkip => vec![Action::Shift(128)],
+                Recover => vec![Action::Shift(129)],
+                Type => vec![Action::Shift(127)],
+                NewSection => vec![Action::Reduce(54)],
+                _ => vec![],
             },
             96 => match aa_tag {
-                // TagList: Tag #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Ident | Literal => {
-                    Action::Reduce(36)
-                }
-                _ => Action::SyntaxError,
+                Left => vec![Action::Shift(131)],
+                NonAssoc => vec![Action::Shift(133)],
+                PrecedenceFor => vec![Action::Shift(134)],
+                Right => vec![Action::Shift(132)],
+                _ => vec![],
             },
             97 => match aa_tag {
-                // Tag: Literal #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Ident | Literal => {
-                    Action::Reduce(38)
-                }
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                Keywords | Token => vec![Action::Reduce(5)],
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Skip | Type => vec![Action::Reduce(73)],
+                _ => vec![],
             },
             98 => match aa_tag {
-                // Tag: Ident #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Ident | Literal => {
-                    Action::Reduce(39)
-                }
-                _ => Action::SyntaxError,
+                Inject | Keywords | Token => vec![Action::Reduce(79)],
+                _ => vec![],
             },
             99 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                // PrecedenceDefinition: "%right" TagList #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right => Action::Reduce(34),
-                _ => Action::SyntaxError,
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Skip | Type => vec![Action::Reduce(74)],
+                _ => vec![],
             },
             100 => match aa_tag {
-                Ident => Action::Shift(98),
-                Literal => Action::Shift(97),
-                // PrecedenceDefinition: "%nonassoc" TagList #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right => Action::Reduce(35),
-                _ => Action::SyntaxError,
+                Inject | Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Skip | Type => vec![Action::Reduce(75)],
+                _ => vec![],
             },
             101 => match aa_tag {
-                // ProductionTailList: ProductionTailList "|" ProductionTail #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(45),
-                _ => Action::SyntaxError,
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    vec![Action::Reduce(64)]
+                }
+                _ => vec![],
             },
             102 => match aa_tag {
-                ActionCode => Action::Shift(70),
-                // ProductionTail: SymbolList Predicate TaggedPrecedence #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(51),
-                _ => Action::SyntaxError,
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Transform | Type => {
+                    vec![Action::Reduce(68)]
+                }
+                _ => vec![],
             },
             103 => match aa_tag {
-                // ProductionTail: SymbolList Predicate Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(52),
-                _ => Action::SyntaxError,
+                Transform => vec![Action::Shift(136)],
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    vec![Action::Reduce(65)]
+                }
+                _ => vec![],
             },
             104 => match aa_tag {
-                // ProductionTail: SymbolList TaggedPrecedence Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(54),
-                _ => Action::SyntaxError,
+                Visitor => vec![Action::Shift(138)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    vec![Action::Reduce(25)]
+                }
+                _ => vec![],
             },
             105 => match aa_tag {
-                // TaggedPrecedence: "%prec" Ident #(NonAssoc, 0)
-                Dot | VerticalBar | ActionCode => Action::Reduce(60),
-                _ => Action::SyntaxError,
+                Ident => vec![Action::Shift(139)],
+                _ => vec![],
             },
             106 => match aa_tag {
-                // TaggedPrecedence: "%prec" Literal #(NonAssoc, 0)
-                Dot | VerticalBar | ActionCode => Action::Reduce(61),
-                _ => Action::SyntaxError,
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derivation | Derive | Expect | Functional | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => vec![Action::Reduce(5)],
+                _ => vec![],
             },
             107 => match aa_tag {
-                // TagList: TagList Tag #(NonAssoc, 0)
-                Inject | Left | NewSection | NonAssoc | Right | Ident | Literal => {
-                    Action::Reduce(37)
-                }
-                _ => Action::SyntaxError,
+                Coverage | DefaultConflict | Derivation | Derive | Expect | Functional | Inject
+                | Module | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce
+                | Start | Visitor => vec![Action::Reduce(20)],
+                _ => vec![],
             },
             108 => match aa_tag {
-                // ProductionTail: SymbolList Predicate TaggedPrecedence Action #(NonAssoc, 0)
-                Dot | VerticalBar => Action::Reduce(50),
-                _ => Action::SyntaxError,
+                Visitor => vec![Action::Shift(138)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    vec![Action::Reduce(25)]
+                }
+                _ => vec![],
+            },
+            109 => match aa_tag {
+                Yields => vec![Action::Shift(142)],
+                _ => vec![],
+            },
+            110 => match aa_tag {
+                AAEnd | Ident | Inject | Test => vec![Action::Reduce(103)],
+                _ => vec![],
+            },
+            111 => match aa_tag {
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                ActionCode => vec![Action::Shift(48)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                PredicateExpr => vec![Action::Shift(85)],
+                Dot | VerticalBar => vec![Action::Reduce(107)],
+                _ => vec![],
+            },
+            112 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(109)],
+                _ => vec![],
+            },
+            113 => match aa_tag {
+                Precedence => vec![Action::Shift(116)],
+                Reject => vec![Action::Shift(147)],
+                ActionCode => vec![Action::Shift(48)],
+                Dot | VerticalBar => vec![Action::Reduce(115)],
+                _ => vec![],
+            },
+            114 => match aa_tag {
+                ActionCode => vec![Action::Shift(48)],
+                Dot | VerticalBar => vec![Action::Reduce(117)],
+                _ => vec![],
+            },
+            115 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(118)],
+                _ => vec![],
+            },
+            116 => match aa_tag {
+                Ident => vec![Action::Shift(149)],
+                Literal => vec![Action::Shift(150)],
+                _ => vec![],
+            },
+            117 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(126)],
+                _ => vec![],
+            },
+            118 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(129)],
+                _ => vec![],
+            },
+            119 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(130)],
+                _ => vec![],
+            },
+            120 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(131)],
+                _ => vec![],
+            },
+            121 => match aa_tag {
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                _ => vec![],
+            },
+            122 => match aa_tag {
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                _ => vec![],
+            },
+            123 => match aa_tag {
+                Error => vec![Action::Shift(91)],
+                LeftParen => vec![Action::Shift(92)],
+                Precedence => vec![Action::Shift(116)],
+                RightParen => vec![Action::Shift(153)],
+                Ident => vec![Action::Shift(89)],
+                Literal => vec![Action::Shift(90)],
+                _ => vec![],
+            },
+            124 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            125 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Ident => vec![Action::Shift(157)],
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            126 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            127 => match aa_tag {
+                TypedName => vec![Action::Shift(159)],
+                _ => vec![],
+            },
+            128 => match aa_tag {
+                Ident => vec![Action::Shift(160)],
+                _ => vec![],
+            },
+            129 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                _ => vec![],
+            },
+            130 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right | Type => {
+                    vec![Action::Reduce(5)]
+                }
+                _ => vec![],
+            },
+            131 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            132 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            133 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            134 => match aa_tag {
+                Ident => vec![Action::Shift(173)],
+                _ => vec![],
+            },
+            135 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                Skip => vec![Action::Reduce(5)],
+                Left | Mode | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover | Right
+                | Type => vec![Action::Reduce(78)],
+                _ => vec![],
+            },
+            136 => match aa_tag {
+                ActionCode => vec![Action::Shift(48)],
+                _ => vec![],
+            },
+            137 => match aa_tag {
+                Observer => vec![Action::Shift(176)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(27)],
+                _ => vec![],
+            },
+            138 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Observer
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            139 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Observer
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    vec![Action::Reduce(5)]
+                }
+                _ => vec![],
+            },
+            140 => match aa_tag {
+                DefaultConflict | Derivation | Derive | Expect | Functional | Inject | Module
+                | NewSection | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start
+                | Visitor => vec![Action::Reduce(22)],
+                _ => vec![],
+            },
+            141 => match aa_tag {
+                Observer => vec![Action::Shift(176)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(27)],
+                _ => vec![],
+            },
+            142 => match aa_tag {
+                Ident => vec![Action::Shift(180)],
+                _ => vec![],
+            },
+            143 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(106)],
+                _ => vec![],
+            },
+            144 => match aa_tag {
+                ActionCode => vec![Action::Shift(48)],
+                Dot | VerticalBar => vec![Action::Reduce(112)],
+                _ => vec![],
+            },
+            145 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(113)],
+                _ => vec![],
+            },
+            146 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(114)],
+                _ => vec![],
+            },
+            147 => match aa_tag {
+                Literal => vec![Action::Shift(182)],
+                _ => vec![],
+            },
+            148 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(116)],
+                _ => vec![],
+            },
+            149 => match aa_tag {
+                ActionCode | Dot | RightParen | VerticalBar => vec![Action::Reduce(123)],
+                _ => vec![],
+            },
+            150 => match aa_tag {
+                ActionCode | Dot | RightParen | VerticalBar => vec![Action::Reduce(124)],
+                _ => vec![],
+            },
+            151 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(132)],
+                _ => vec![],
+            },
+            152 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | Literal | Precedence
+                | PredicateExpr | RightParen | VerticalBar => vec![Action::Reduce(133)],
+                _ => vec![],
+            },
+            153 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => vec![Action::Reduce(137)],
+                _ => vec![],
+            },
+            154 => match aa_tag {
+                RightParen => vec![Action::Shift(183)],
+                _ => vec![],
+            },
+            155 => match aa_tag {
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(94)],
+                _ => vec![],
+            },
+            156 => match aa_tag {
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(95)],
+                _ => vec![],
+            },
+            157 => match aa_tag {
+                Ident | Inject | NewSection | NoSkip | Recover | Type => vec![Action::Reduce(99)],
+                _ => vec![],
+            },
+            158 => match aa_tag {
+                NewSection | NoSkip | Recover | Type => vec![Action::Reduce(96)],
+                _ => vec![],
+            },
+            159 => match aa_tag {
+                RustType => vec![Action::Shift(184)],
+                _ => vec![],
+            },
+            160 => match aa_tag {
+                Ident | Inject | NewSection | NoSkip | Recover | Type => vec![Action::Reduce(98)],
+                _ => vec![],
+            },
+            161 => match aa_tag {
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => vec![Action::Reduce(89)],
+                _ => vec![],
+            },
+            162 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | NewSection | NoSkip | Recover | Type => vec![Action::Reduce(100)],
+                _ => vec![],
+            },
+            163 => match aa_tag {
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => vec![Action::Reduce(91)],
+                _ => vec![],
+            },
+            164 => match aa_tag {
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => vec![Action::Reduce(92)],
+                _ => vec![],
+            },
+            165 => match aa_tag {
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(81)],
+                _ => vec![],
+            },
+            166 => match aa_tag {
+                DefaultConflict | Derive | Functional | Ident | Inject | Literal | Module
+                | NewSection | Prefix | ReduceReduce | Repeat | ShiftReduce => {
+                    vec![Action::Reduce(53)]
+                }
+                _ => vec![],
+            },
+            167 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(82)],
+                _ => vec![],
+            },
+            168 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                _ => vec![],
+            },
+            169 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(84)],
+                _ => vec![],
+            },
+            170 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                _ => vec![],
+            },
+            171 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(86)],
+                _ => vec![],
+            },
+            172 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                _ => vec![],
+            },
+            173 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                _ => vec![],
+            },
+            174 => match aa_tag {
+                ActionCode | Begin | Inject | Keywords | Left | Mode | NewSection | NoSkip
+                | NonAssoc | PrecedenceFor | Recover | Right | Skip | Token | Type => {
+                    vec![Action::Reduce(66)]
+                }
+                _ => vec![],
+            },
+            175 => match aa_tag {
+                Start => vec![Action::Shift(192)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => vec![Action::Reduce(29)],
+                _ => vec![],
+            },
+            176 => match aa_tag {
+                Ident => vec![Action::Shift(193)],
+                _ => vec![],
+            },
+            177 => match aa_tag {
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => {
+                    vec![Action::Reduce(26)]
+                }
+                _ => vec![],
+            },
+            178 => match aa_tag {
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Observer | Prefix | ReduceReduce | Repeat | ShiftReduce | Start | Visitor => {
+                    vec![Action::Reduce(24)]
+                }
+                _ => vec![],
+            },
+            179 => match aa_tag {
+                Start => vec![Action::Shift(192)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => vec![Action::Reduce(29)],
+                _ => vec![],
+            },
+            180 => match aa_tag {
+                AAEnd | Test => vec![Action::Reduce(4)],
+                _ => vec![],
+            },
+            181 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(111)],
+                _ => vec![],
+            },
+            182 => match aa_tag {
+                Dot | VerticalBar => vec![Action::Reduce(122)],
+                _ => vec![],
+            },
+            183 => match aa_tag {
+                ActionCode | Dot | Error | Ident | LeftParen | ListSeparator | Literal
+                | NewSection | Optional | Plus | Precedence | PredicateExpr | RightParen | Star
+                | VerticalBar => vec![Action::Reduce(138)],
+                _ => vec![],
+            },
+            184 => match aa_tag {
+                Inject | NewSection | NoSkip | Recover | Type => vec![Action::Reduce(97)],
+                _ => vec![],
+            },
+            185 => match aa_tag {
+                Ident | Inject | Left | Literal | NewSection | NoSkip | NonAssoc
+                | PrecedenceFor | Recover | Right | Type => vec![Action::Reduce(90)],
+                _ => vec![],
+            },
+            186 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(83)],
+                _ => vec![],
+            },
+            187 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(85)],
+                _ => vec![],
+            },
+            188 => match aa_tag {
+                Ident => vec![Action::Shift(164)],
+                Literal => vec![Action::Shift(163)],
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(87)],
+                _ => vec![],
+            },
+            189 => match aa_tag {
+                Inject | Left | NewSection | NoSkip | NonAssoc | PrecedenceFor | Recover
+                | Right | Type => vec![Action::Reduce(88)],
+                _ => vec![],
+            },
+            190 => match aa_tag {
+                Expect => vec![Action::Shift(198)],
+                ReduceReduce => vec![Action::Shift(199)],
+                ShiftReduce => vec![Action::Shift(200)],
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => vec![Action::Reduce(33)],
+                _ => vec![],
+            },
+            191 => match aa_tag {
+                Start => vec![Action::Shift(201)],
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce => vec![Action::Reduce(30)],
+                _ => vec![],
+            },
+            192 => match aa_tag {
+                Ident => vec![Action::Shift(202)],
+                _ => vec![],
+            },
+            193 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            194 => match aa_tag {
+                Expect => vec![Action::Shift(198)],
+                ReduceReduce => vec![Action::Shift(199)],
+                ShiftReduce => vec![Action::Shift(200)],
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => vec![Action::Reduce(33)],
+                _ => vec![],
+            },
+            195 => match aa_tag {
+                DefaultConflict => vec![Action::Shift(206)],
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    vec![Action::Reduce(41)]
+                }
+                _ => vec![],
+            },
+            196 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                ShiftReduce => vec![Action::Reduce(5)],
+                DefaultConflict | Derive | Functional | Module | NewSection | Prefix | Repeat => {
+                    vec![Action::Reduce(36)]
+                }
+                _ => vec![],
+            },
+            197 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                ReduceReduce => vec![Action::Reduce(5)],
+                DefaultConflict | Derive | Functional | Module | NewSection | Prefix | Repeat => {
+                    vec![Action::Reduce(37)]
+                }
+                _ => vec![],
+            },
+            198 => match aa_tag {
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            199 => match aa_tag {
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            200 => match aa_tag {
+                NumberExpr => vec![Action::Shift(166)],
+                _ => vec![],
+            },
+            201 => match aa_tag {
+                Ident => vec![Action::Shift(212)],
+                _ => vec![],
+            },
+            202 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            203 => match aa_tag {
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(28)],
+                _ => vec![],
+            },
+            204 => match aa_tag {
+                DefaultConflict => vec![Action::Shift(206)],
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    vec![Action::Reduce(41)]
+                }
+                _ => vec![],
+            },
+            205 => match aa_tag {
+                Repeat => vec![Action::Shift(216)],
+                Derive | Functional | Inject | Module | NewSection | Prefix => {
+                    vec![Action::Reduce(43)]
+                }
+                _ => vec![],
+            },
+            206 => match aa_tag {
+                Ident => vec![Action::Shift(217)],
+                _ => vec![],
+            },
+            207 => match aa_tag {
+                ShiftReduce => vec![Action::Shift(200)],
+                _ => vec![],
+            },
+            208 => match aa_tag {
+                ReduceReduce => vec![Action::Shift(199)],
+                _ => vec![],
+            },
+            209 => match aa_tag {
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => vec![Action::Reduce(38)],
+                _ => vec![],
+            },
+            210 => match aa_tag {
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat | ShiftReduce => vec![Action::Reduce(39)],
+                _ => vec![],
+            },
+            211 => match aa_tag {
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | ReduceReduce | Repeat => vec![Action::Reduce(40)],
+                _ => vec![],
+            },
+            212 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                DefaultConflict | Derive | Expect | Functional | Module | NewSection | Prefix
+                | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            213 => match aa_tag {
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(31)],
+                _ => vec![],
+            },
+            214 => match aa_tag {
+                Repeat => vec![Action::Shift(216)],
+                Derive | Functional | Inject | Module | NewSection | Prefix => {
+                    vec![Action::Reduce(43)]
+                }
+                _ => vec![],
+            },
+            215 => match aa_tag {
+                Derive => vec![Action::Shift(223)],
+                Functional | Inject | Module | NewSection | Prefix => vec![Action::Reduce(45)],
+                _ => vec![],
+            },
+            216 => match aa_tag {
+                Ident => vec![Action::Shift(224)],
+                _ => vec![],
+            },
+            217 => match aa_tag {
+                Derive | Functional | Inject | Module | NewSection | Prefix | Repeat => {
+                    vec![Action::Reduce(42)]
+                }
+                _ => vec![],
+            },
+            218 => match aa_tag {
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => vec![Action::Reduce(34)],
+                _ => vec![],
+            },
+            219 => match aa_tag {
+                DefaultConflict | Derive | Functional | Inject | Module | NewSection | Prefix
+                | Repeat => vec![Action::Reduce(35)],
+                _ => vec![],
+            },
+            220 => match aa_tag {
+                DefaultConflict | Derive | Expect | Functional | Inject | Module | NewSection
+                | Prefix | ReduceReduce | Repeat | ShiftReduce | Start => vec![Action::Reduce(32)],
+                _ => vec![],
+            },
+            221 => match aa_tag {
+                Derive => vec![Action::Shift(223)],
+                Functional | Inject | Module | NewSection | Prefix => vec![Action::Reduce(45)],
+                _ => vec![],
+            },
+            222 => match aa_tag {
+                Functional => vec![Action::Shift(227)],
+                Inject | Module | NewSection | Prefix => vec![Action::Reduce(47)],
+                _ => vec![],
+            },
+            223 => match aa_tag {
+                Literal => vec![Action::Shift(228)],
+                _ => vec![],
+            },
+            224 => match aa_tag {
+                Derive | Functional | Inject | Module | NewSection | Prefix => {
+                    vec![Action::Reduce(44)]
+                }
+                _ => vec![],
+            },
+            225 => match aa_tag {
+                Functional => vec![Action::Shift(227)],
+                Inject | Module | NewSection | Prefix => vec![Action::Reduce(47)],
+                _ => vec![],
+            },
+            226 => match aa_tag {
+                Module => vec![Action::Shift(231)],
+                Inject | NewSection | Prefix => vec![Action::Reduce(49)],
+                _ => vec![],
+            },
+            227 => match aa_tag {
+                Inject | Module | NewSection | Prefix => vec![Action::Reduce(48)],
+                _ => vec![],
+            },
+            228 => match aa_tag {
+                Functional | Inject | Module | NewSection | Prefix => vec![Action::Reduce(46)],
+                _ => vec![],
+            },
+            229 => match aa_tag {
+                Module => vec![Action::Shift(231)],
+                Inject | NewSection | Prefix => vec![Action::Reduce(49)],
+                _ => vec![],
+            },
+            230 => match aa_tag {
+                Prefix => vec![Action::Shift(234)],
+                Inject | NewSection => vec![Action::Reduce(51)],
+                _ => vec![],
+            },
+            231 => match aa_tag {
+                Ident => vec![Action::Shift(235)],
+                _ => vec![],
+            },
+            232 => match aa_tag {
+                Prefix => vec![Action::Shift(234)],
+                Inject | NewSection => vec![Action::Reduce(51)],
+                _ => vec![],
+            },
+            233 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                NewSection => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            234 => match aa_tag {
+                Ident => vec![Action::Shift(238)],
+                _ => vec![],
+            },
+            235 => match aa_tag {
+                Inject | NewSection | Prefix => vec![Action::Reduce(50)],
+                _ => vec![],
+            },
+            236 => match aa_tag {
+                Inject => vec![Action::Shift(4)],
+                NewSection => vec![Action::Reduce(5)],
+                _ => vec![],
+            },
+            237 => match aa_tag {
+                NewSection => vec![Action::Reduce(11)],
+                _ => vec![],
+            },
+            238 => match aa_tag {
+                Inject | NewSection => vec![Action::Reduce(52)],
+                _ => vec![],
+            },
+            239 => match aa_tag {
+                NewSection => vec![Action::Reduce(12)],
+                _ => vec![],
             },
             _ => panic!("illegal state: {aa_state}"),
-        };
+        }
     }
 
     fn production_data(production_id: u32) -> (AANonTerminal, usize) {
         match production_id {
             0 => (AANonTerminal::AAStart, 1),
-            1 => (AANonTerminal::Specification, 6),
-            2 => (AANonTerminal::OptionalInjection, 0),
-            3 => (AANonTerminal::OptionalInjection, 1),
-            4 => (AANonTerminal::InjectionHead, 2),
-            5 => (AANonTerminal::Injection, 2),
-            6 => (AANonTerminal::Preamble, 0),
-            7 => (AANonTerminal::Preamble, 3),
-            8 => (AANonTerminal::Configuration, 6),
-            9 => (AANonTerminal::Configuration, 6),
-            10 => (AANonTerminal::AttributeType, 2),
-            11 => (AANonTerminal::TargetType, 2),
-            12 => (AANonTerminal::ExpectedConflicts, 0),
-            13 => (AANonTerminal::ExpectedConflicts, 3),
-            14 => (AANonTerminal::ExpectedConflicts, 3),
-            15 => (AANonTerminal::ExpectedConflicts, 1),
-            16 => (AANonTerminal::ExpectedConflicts, 1),
-            17 => (AANonTerminal::ExpectedRRConflicts, 2),
-            18 => (AANonTerminal::ExpectedSRConflicts, 2),
-            19 => (AANonTerminal::Number, 1),
-            20 => (AANonTerminal::Definitions, 3),
-            21 => (AANonTerminal::TokenDefinitions, 2),
-            22 => (AANonTerminal::TokenDefinitions, 4),
-            23 => (AANonTerminal::TokenDefinition, 3),
-            24 => (AANonTerminal::TokenDefinition, 3),
-            25 => (AANonTerminal::RegularExpression, 1),
-            26 => (AANonTerminal::NewTokenName, 1),
-            27 => (AANonTerminal::NewTokenName, 1),
-            28 => (AANonTerminal::SkipDefinitions, 0),
-            29 => (AANonTerminal::SkipDefinitions, 4),
-            30 => (AANonTerminal::SkipDefinition, 2),
-            31 => (AANonTerminal::PrecedenceDefinitions, 0),
-            32 => (AANonTerminal::PrecedenceDefinitions, 4),
-            33 => (AANonTerminal::PrecedenceDefinition, 2),
-            34 => (AANonTerminal::PrecedenceDefinition, 2),
-            35 => (AANonTerminal::PrecedenceDefinition, 2),
-            36 => (AANonTerminal::TagList, 1),
-            37 => (AANonTerminal::TagList, 2),
-            38 => (AANonTerminal::Tag, 1),
-            39 => (AANonTerminal::Tag, 1),
-            40 => (AANonTerminal::ProductionRules, 3),
-            41 => (AANonTerminal::ProductionRules, 3),
-            42 => (AANonTerminal::ProductionGroup, 3),
-            43 => (AANonTerminal::ProductionGroupHead, 2),
-            44 => (AANonTerminal::ProductionTailList, 1),
-            45 => (AANonTerminal::ProductionTailList, 3),
-            46 => (AANonTerminal::ProductionTail, 0),
-            47 => (AANonTerminal::ProductionTail, 1),
-            48 => (AANonTerminal::ProductionTail, 2),
-            49 => (AANonTerminal::ProductionTail, 1),
-            50 => (AANonTerminal::ProductionTail, 4),
-            51 => (AANonTerminal::ProductionTail, 3),
-            52 => (AANonTerminal::ProductionTail, 3),
-            53 => (AANonTerminal::ProductionTail, 2),
-            54 => (AANonTerminal::ProductionTail, 3),
-            55 => (AANonTerminal::ProductionTail, 2),
-            56 => (AANonTerminal::ProductionTail, 2),
-            57 => (AANonTerminal::ProductionTail, 1),
-            58 => (AANonTerminal::Action, 1),
-            59 => (AANonTerminal::Predicate, 1),
-            60 => (AANonTerminal::TaggedPrecedence, 2),
-            61 => (AANonTerminal::TaggedPrecedence, 2),
-            62 => (AANonTerminal::SymbolList, 1),
-            63 => (AANonTerminal::SymbolList, 2),
-            64 => (AANonTerminal::Symbol, 1),
-            65 => (AANonTerminal::Symbol, 1),
-            66 => (AANonTerminal::Symbol, 1),
+            1 => (AANonTerminal::Specification, 7),
+            2 => (AANonTerminal::SpecificationTests, 0),
+            3 => (AANonTerminal::SpecificationTests, 2),
+            4 => (AANonTerminal::SpecificationTest, 4),
+            5 => (AANonTerminal::OptionalInjection, 0),
+            6 => (AANonTerminal::OptionalInjection, 1),
+            7 => (AANonTerminal::InjectionHead, 2),
+            8 => (AANonTerminal::Injection, 2),
+            9 => (AANonTerminal::Preamble, 0),
+            10 => (AANonTerminal::Preamble, 3),
+            11 => (AANonTerminal::Configuration, 18),
+            12 => (AANonTerminal::Configuration, 18),
+            13 => (AANonTerminal::AttributeType, 2),
+            14 => (AANonTerminal::AttributeType, 3),
+            15 => (AANonTerminal::AttributeTypeName, 1),
+            16 => (AANonTerminal::TargetType, 2),
+            17 => (AANonTerminal::TargetType, 3),
+            18 => (AANonTerminal::TargetType, 3),
+            19 => (AANonTerminal::Interning, 0),
+            20 => (AANonTerminal::Interning, 3),
+            21 => (AANonTerminal::CoverageRecording, 0),
+            22 => (AANonTerminal::CoverageRecording, 3),
+            23 => (AANonTerminal::DerivationRecording, 0),
+            24 => (AANonTerminal::DerivationRecording, 3),
+            25 => (AANonTerminal::VisitorGeneration, 0),
+            26 => (AANonTerminal::VisitorGeneration, 2),
+            27 => (AANonTerminal::Observing, 0),
+            28 => (AANonTerminal::Observing, 3),
+            29 => (AANonTerminal::StartSymbol, 0),
+            30 => (AANonTerminal::StartSymbol, 1),
+            31 => (AANonTerminal::StartSymbols, 3),
+            32 => (AANonTerminal::StartSymbols, 4),
+            33 => (AANonTerminal::ExpectedConflicts, 0),
+            34 => (AANonTerminal::ExpectedConflicts, 3),
+            35 => (AANonTerminal::ExpectedConflicts, 3),
+            36 => (AANonTerminal::ExpectedConflicts, 1),
+            37 => (AANonTerminal::ExpectedConflicts, 1),
+            38 => (AANonTerminal::ExpectedConflicts, 2),
+            39 => (AANonTerminal::ExpectedRRConflicts, 2),
+            40 => (AANonTerminal::ExpectedSRConflicts, 2),
+            41 => (AANonTerminal::DefaultConflictResolution, 0),
+            42 => (AANonTerminal::DefaultConflictResolution, 2),
+            43 => (AANonTerminal::RepetitionRecursion, 0),
+            44 => (AANonTerminal::RepetitionRecursion, 2),
+            45 => (AANonTerminal::Derives, 0),
+            46 => (AANonTerminal::Derives, 2),
+            47 => (AANonTerminal::FunctionalInterface, 0),
+            48 => (AANonTerminal::FunctionalInterface, 1),
+            49 => (AANonTerminal::ModuleName, 0),
+            50 => (AANonTerminal::ModuleName, 2),
+            51 => (AANonTerminal::SymbolPrefix, 0),
+            52 => (AANonTerminal::SymbolPrefix, 2),
+            53 => (AANonTerminal::Number, 1),
+            54 => (AANonTerminal::Definitions, 5),
+            55 => (AANonTerminal::TokenDefinitions, 2),
+            56 => (AANonTerminal::TokenDefinitions, 4),
+            57 => (AANonTerminal::TokenDefinition, 1),
+            58 => (AANonTerminal::TokenDefinition, 2),
+            59 => (AANonTerminal::TokenDefinition, 2),
+            60 => (AANonTerminal::TokenDefinition, 3),
+            61 => (AANonTerminal::TokenDefinition, 1),
+            62 => (AANonTerminal::KeywordDefinition, 2),
+            63 => (AANonTerminal::KeywordDefinition, 2),
+            64 => (AANonTerminal::TokenDefinitionHead, 4),
+            65 => (AANonTerminal::TokenDefinitionHead, 4),
+            66 => (AANonTerminal::TokenDefinitionHead, 6),
+            67 => (AANonTerminal::OptionalDisplayName, 0),
+            68 => (AANonTerminal::OptionalDisplayName, 1),
+            69 => (AANonTerminal::ModeSwitch, 2),
+            70 => (AANonTerminal::RegularExpression, 1),
+            71 => (AANonTerminal::NewTokenName, 1),
+            72 => (AANonTerminal::NewTokenName, 1),
+            73 => (AANonTerminal::SkipDefinitions, 0),
+            74 => (AANonTerminal::SkipDefinitions, 4),
+            75 => (AANonTerminal::SkipDefinition, 2),
+            76 => (AANonTerminal::ModeDefinitions, 0),
+            77 => (AANonTerminal::ModeDefinitions, 2),
+            78 => (AANonTerminal::ModeDefinition, 3),
+            79 => (AANonTerminal::ModeHead, 2),
+            80 => (AANonTerminal::PrecedenceDefinitions, 0),
+            81 => (AANonTerminal::PrecedenceDefinitions, 4),
+            82 => (AANonTerminal::PrecedenceDefinition, 2),
+            83 => (AANonTerminal::PrecedenceDefinition, 3),
+            84 => (AANonTerminal::PrecedenceDefinition, 2),
+            85 => (AANonTerminal::PrecedenceDefinition, 3),
+            86 => (AANonTerminal::PrecedenceDefinition, 2),
+            87 => (AANonTerminal::PrecedenceDefinition, 3),
+            88 => (AANonTerminal::PrecedenceDefinition, 3),
+            89 => (AANonTerminal::TagList, 1),
+            90 => (AANonTerminal::TagList, 2),
+            91 => (AANonTerminal::Tag, 1),
+            92 => (AANonTerminal::Tag, 1),
+            93 => (AANonTerminal::TypeDefinitions, 0),
+            94 => (AANonTerminal::TypeDefinitions, 3),
+            95 => (AANonTerminal::TypeDefinitions, 3),
+            96 => (AANonTerminal::TypeDefinitions, 3),
+            97 => (AANonTerminal::TypeDefinition, 3),
+            98 => (AANonTerminal::NoSkipDefinition, 2),
+            99 => (AANonTerminal::NoSkipDefinition, 2),
+            100 => (AANonTerminal::RecoveryDefinition, 2),
+            101 => (AANonTerminal::ProductionRules, 3),
+            102 => (AANonTerminal::ProductionRules, 3),
+            103 => (AANonTerminal::ProductionGroup, 3),
+            104 => (AANonTerminal::ProductionGroupHead, 2),
+            105 => (AANonTerminal::ProductionTailList, 1),
+            106 => (AANonTerminal::ProductionTailList, 3),
+            107 => (AANonTerminal::ProductionTail, 0),
+            108 => (AANonTerminal::ProductionTail, 1),
+            109 => (AANonTerminal::ProductionTail, 2),
+            110 => (AANonTerminal::ProductionTail, 1),
+            111 => (AANonTerminal::ProductionTail, 4),
+            112 => (AANonTerminal::ProductionTail, 3),
+            113 => (AANonTerminal::ProductionTail, 3),
+            114 => (AANonTerminal::ProductionTail, 3),
+            115 => (AANonTerminal::ProductionTail, 2),
+            116 => (AANonTerminal::ProductionTail, 3),
+            117 => (AANonTerminal::ProductionTail, 2),
+            118 => (AANonTerminal::ProductionTail, 2),
+            119 => (AANonTerminal::ProductionTail, 1),
+            120 => (AANonTerminal::Action, 1),
+            121 => (AANonTerminal::Predicate, 1),
+            122 => (AANonTerminal::Rejection, 2),
+            123 => (AANonTerminal::TaggedPrecedence, 2),
+            124 => (AANonTerminal::TaggedPrecedence, 2),
+            125 => (AANonTerminal::SymbolList, 1),
+            126 => (AANonTerminal::SymbolList, 2),
+            127 => (AANonTerminal::Element, 1),
+            128 => (AANonTerminal::Element, 1),
+            129 => (AANonTerminal::Element, 2),
+            130 => (AANonTerminal::Element, 2),
+            131 => (AANonTerminal::Element, 2),
+            132 => (AANonTerminal::SeparatedList, 3),
+            133 => (AANonTerminal::SeparatedList, 3),
+            134 => (AANonTerminal::Symbol, 1),
+            135 => (AANonTerminal::Symbol, 1),
+            136 => (AANonTerminal::Symbol, 1),
+            137 => (AANonTerminal::Symbol, 3),
+            138 => (AANonTerminal::Symbol, 4),
             _ => panic!("malformed production data table"),
         }
     }
@@ -1167,253 +5756,554 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 AANonTerminal::OptionalInjection => 17,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
+            10 => match lhs {
+                AANonTerminal::AttributeTypeName => 18,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
             14 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 20,
+                AANonTerminal::OptionalInjection => 21,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
             15 => match lhs {
-                AANonTerminal::Definitions => 21,
+                AANonTerminal::Definitions => 22,
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 23,
-                AANonTerminal::TokenDefinitions => 22,
+                AANonTerminal::OptionalInjection => 24,
+                AANonTerminal::TokenDefinitions => 23,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
             16 => match lhs {
-                AANonTerminal::TargetType => 24,
+                AANonTerminal::TargetType => 25,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
             17 => match lhs {
-                AANonTerminal::AttributeType => 25,
+                AANonTerminal::AttributeType => 26,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            22 => match lhs {
+            23 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 28,
-                AANonTerminal::SkipDefinitions => 27,
+                AANonTerminal::OptionalInjection => 32,
+                AANonTerminal::SkipDefinitions => 31,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            23 => match lhs {
-                AANonTerminal::TokenDefinition => 29,
+            24 => match lhs {
+                AANonTerminal::KeywordDefinition => 35,
+                AANonTerminal::TokenDefinition => 33,
+                AANonTerminal::TokenDefinitionHead => 34,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            24 => match lhs {
+            25 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 31,
+                AANonTerminal::OptionalInjection => 38,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            25 => match lhs {
+            26 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 39,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            30 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 41,
+                AANonTerminal::ProductionRules => 40,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            31 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::ModeDefinitions => 42,
+                AANonTerminal::OptionalInjection => 43,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            32 => match lhs {
+                AANonTerminal::KeywordDefinition => 35,
+                AANonTerminal::TokenDefinition => 44,
+                AANonTerminal::TokenDefinitionHead => 34,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            34 => match lhs {
+                AANonTerminal::Action => 45,
+                AANonTerminal::ModeSwitch => 46,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            37 => match lhs {
+                AANonTerminal::NewTokenName => 51,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            38 => match lhs {
+                AANonTerminal::Interning => 53,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            39 => match lhs {
+                AANonTerminal::Interning => 55,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            40 => match lhs {
+                AANonTerminal::ProductionGroup => 57,
+                AANonTerminal::ProductionGroupHead => 58,
+                AANonTerminal::SpecificationTests => 56,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            41 => match lhs {
+                AANonTerminal::ProductionGroup => 60,
+                AANonTerminal::ProductionGroupHead => 58,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            42 => match lhs {
+                AANonTerminal::ModeDefinition => 62,
+                AANonTerminal::ModeHead => 63,
+                AANonTerminal::PrecedenceDefinitions => 61,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            43 => match lhs {
+                AANonTerminal::SkipDefinition => 65,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            44 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 67,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            46 => match lhs {
+                AANonTerminal::Action => 68,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            51 => match lhs {
+                AANonTerminal::RegularExpression => 71,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            53 => match lhs {
+                AANonTerminal::CoverageRecording => 73,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            55 => match lhs {
+                AANonTerminal::CoverageRecording => 76,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            56 => match lhs {
+                AANonTerminal::SpecificationTest => 77,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            57 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 79,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            58 => match lhs {
+                AANonTerminal::Action => 82,
+                AANonTerminal::Element => 86,
+                AANonTerminal::Predicate => 83,
+                AANonTerminal::ProductionTail => 81,
+                AANonTerminal::ProductionTailList => 80,
+                AANonTerminal::SeparatedList => 88,
+                AANonTerminal::Symbol => 87,
+                AANonTerminal::SymbolList => 84,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            60 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 94,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            61 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 96,
+                AANonTerminal::TypeDefinitions => 95,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            63 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 24,
+                AANonTerminal::TokenDefinitions => 97,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            65 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 99,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            66 => match lhs {
+                AANonTerminal::RegularExpression => 100,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            70 => match lhs {
+                AANonTerminal::OptionalDisplayName => 101,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            71 => match lhs {
+                AANonTerminal::OptionalDisplayName => 103,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            73 => match lhs {
+                AANonTerminal::DerivationRecording => 104,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            75 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 107,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            76 => match lhs {
+                AANonTerminal::DerivationRecording => 108,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            83 => match lhs {
+                AANonTerminal::Action => 112,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            84 => match lhs {
+                AANonTerminal::Action => 115,
+                AANonTerminal::Element => 117,
+                AANonTerminal::Predicate => 113,
+                AANonTerminal::SeparatedList => 88,
+                AANonTerminal::Symbol => 87,
+                AANonTerminal::TaggedPrecedence => 114,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            92 => match lhs {
+                AANonTerminal::Element => 86,
+                AANonTerminal::SeparatedList => 88,
+                AANonTerminal::Symbol => 87,
+                AANonTerminal::SymbolList => 123,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            95 => match lhs {
+                AANonTerminal::NoSkipDefinition => 125,
+                AANonTerminal::RecoveryDefinition => 126,
+                AANonTerminal::TypeDefinition => 124,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            96 => match lhs {
+                AANonTerminal::PrecedenceDefinition => 130,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            97 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
                 AANonTerminal::OptionalInjection => 32,
+                AANonTerminal::SkipDefinitions => 135,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            26 => match lhs {
+            104 => match lhs {
+                AANonTerminal::VisitorGeneration => 137,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            106 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 140,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            108 => match lhs {
+                AANonTerminal::VisitorGeneration => 141,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            111 => match lhs {
+                AANonTerminal::Action => 82,
+                AANonTerminal::Element => 86,
+                AANonTerminal::Predicate => 83,
+                AANonTerminal::ProductionTail => 143,
+                AANonTerminal::SeparatedList => 88,
+                AANonTerminal::Symbol => 87,
+                AANonTerminal::SymbolList => 84,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            113 => match lhs {
+                AANonTerminal::Action => 145,
+                AANonTerminal::Rejection => 146,
+                AANonTerminal::TaggedPrecedence => 144,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            114 => match lhs {
+                AANonTerminal::Action => 148,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            121 => match lhs {
+                AANonTerminal::Symbol => 151,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            122 => match lhs {
+                AANonTerminal::Symbol => 152,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            123 => match lhs {
+                AANonTerminal::Element => 117,
+                AANonTerminal::SeparatedList => 88,
+                AANonTerminal::Symbol => 87,
+                AANonTerminal::TaggedPrecedence => 154,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            124 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 155,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            125 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 156,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            126 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 158,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            129 => match lhs {
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 162,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            130 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 165,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            131 => match lhs {
+                AANonTerminal::Number => 168,
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 167,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            132 => match lhs {
+                AANonTerminal::Number => 170,
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 169,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            133 => match lhs {
+                AANonTerminal::Number => 172,
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 171,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            135 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 43,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            136 => match lhs {
+                AANonTerminal::Action => 174,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            137 => match lhs {
+                AANonTerminal::Observing => 175,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            138 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 34,
-                AANonTerminal::ProductionRules => 33,
+                AANonTerminal::OptionalInjection => 177,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            27 => match lhs {
+            139 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 36,
-                AANonTerminal::PrecedenceDefinitions => 35,
+                AANonTerminal::OptionalInjection => 178,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            28 => match lhs {
-                AANonTerminal::TokenDefinition => 37,
+            141 => match lhs {
+                AANonTerminal::Observing => 179,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            30 => match lhs {
-                AANonTerminal::NewTokenName => 38,
+            144 => match lhs {
+                AANonTerminal::Action => 181,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            31 => match lhs {
-                AANonTerminal::ExpectedConflicts => 40,
-                AANonTerminal::ExpectedRRConflicts => 41,
-                AANonTerminal::ExpectedSRConflicts => 42,
+            162 => match lhs {
+                AANonTerminal::Tag => 185,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            32 => match lhs {
-                AANonTerminal::ExpectedConflicts => 45,
-                AANonTerminal::ExpectedRRConflicts => 41,
-                AANonTerminal::ExpectedSRConflicts => 42,
+            167 => match lhs {
+                AANonTerminal::Tag => 185,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            33 => match lhs {
-                AANonTerminal::ProductionGroup => 46,
-                AANonTerminal::ProductionGroupHead => 47,
+            168 => match lhs {
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 186,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            34 => match lhs {
-                AANonTerminal::ProductionGroup => 49,
-                AANonTerminal::ProductionGroupHead => 47,
+            169 => match lhs {
+                AANonTerminal::Tag => 185,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            35 => match lhs {
-                AANonTerminal::Injection => 3,
-                AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 50,
+            170 => match lhs {
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 187,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            36 => match lhs {
-                AANonTerminal::SkipDefinition => 51,
+            171 => match lhs {
+                AANonTerminal::Tag => 185,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            37 => match lhs {
-                AANonTerminal::Injection => 3,
-                AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 53,
+            172 => match lhs {
+                AANonTerminal::Tag => 161,
+                AANonTerminal::TagList => 188,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            38 => match lhs {
-                AANonTerminal::RegularExpression => 55,
+            173 => match lhs {
+                AANonTerminal::Tag => 189,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            40 => match lhs {
-                AANonTerminal::Injection => 3,
-                AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 57,
+            175 => match lhs {
+                AANonTerminal::StartSymbol => 190,
+                AANonTerminal::StartSymbols => 191,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            41 => match lhs {
-                AANonTerminal::Injection => 3,
-                AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 58,
+            179 => match lhs {
+                AANonTerminal::StartSymbol => 194,
+                AANonTerminal::StartSymbols => 191,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            42 => match lhs {
+            186 => match lhs {
+                AANonTerminal::Tag => 185,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            187 => match lhs {
+                AANonTerminal::Tag => 185,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            188 => match lhs {
+                AANonTerminal::Tag => 185,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            190 => match lhs {
+                AANonTerminal::ExpectedConflicts => 195,
+                AANonTerminal::ExpectedRRConflicts => 196,
+                AANonTerminal::ExpectedSRConflicts => 197,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            193 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 59,
+                AANonTerminal::OptionalInjection => 203,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            43 => match lhs {
-                AANonTerminal::Number => 60,
+            194 => match lhs {
+                AANonTerminal::ExpectedConflicts => 204,
+                AANonTerminal::ExpectedRRConflicts => 196,
+                AANonTerminal::ExpectedSRConflicts => 197,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            44 => match lhs {
-                AANonTerminal::Number => 62,
+            195 => match lhs {
+                AANonTerminal::DefaultConflictResolution => 205,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            45 => match lhs {
+            196 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 63,
+                AANonTerminal::OptionalInjection => 207,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            46 => match lhs {
+            197 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 64,
+                AANonTerminal::OptionalInjection => 208,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            47 => match lhs {
-                AANonTerminal::Action => 67,
-                AANonTerminal::Predicate => 68,
-                AANonTerminal::ProductionTail => 66,
-                AANonTerminal::ProductionTailList => 65,
-                AANonTerminal::Symbol => 72,
-                AANonTerminal::SymbolList => 69,
+            198 => match lhs {
+                AANonTerminal::Number => 209,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            49 => match lhs {
-                AANonTerminal::Injection => 3,
-                AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 77,
+            199 => match lhs {
+                AANonTerminal::Number => 210,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            50 => match lhs {
-                AANonTerminal::PrecedenceDefinition => 78,
+            200 => match lhs {
+                AANonTerminal::Number => 211,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            51 => match lhs {
+            202 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 82,
-                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-            },
-            52 => match lhs {
-                AANonTerminal::RegularExpression => 83,
+                AANonTerminal::OptionalInjection => 213,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            58 => match lhs {
-                AANonTerminal::ExpectedSRConflicts => 84,
+            204 => match lhs {
+                AANonTerminal::DefaultConflictResolution => 214,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            59 => match lhs {
-                AANonTerminal::ExpectedRRConflicts => 85,
+            205 => match lhs {
+                AANonTerminal::RepetitionRecursion => 215,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            68 => match lhs {
-                AANonTerminal::Action => 88,
+            207 => match lhs {
+                AANonTerminal::ExpectedSRConflicts => 218,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            69 => match lhs {
-                AANonTerminal::Action => 91,
-                AANonTerminal::Predicate => 89,
-                AANonTerminal::Symbol => 93,
-                AANonTerminal::TaggedPrecedence => 90,
+            208 => match lhs {
+                AANonTerminal::ExpectedRRConflicts => 219,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            78 => match lhs {
+            212 => match lhs {
                 AANonTerminal::Injection => 3,
                 AANonTerminal::InjectionHead => 5,
-                AANonTerminal::OptionalInjection => 94,
+                AANonTerminal::OptionalInjection => 220,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            79 => match lhs {
-                AANonTerminal::Tag => 96,
-                AANonTerminal::TagList => 95,
+            214 => match lhs {
+                AANonTerminal::RepetitionRecursion => 221,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            80 => match lhs {
-                AANonTerminal::Tag => 96,
-                AANonTerminal::TagList => 99,
+            215 => match lhs {
+                AANonTerminal::Derives => 222,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            81 => match lhs {
-                AANonTerminal::Tag => 96,
-                AANonTerminal::TagList => 100,
+            221 => match lhs {
+                AANonTerminal::Derives => 225,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            87 => match lhs {
-                AANonTerminal::Action => 67,
-                AANonTerminal::Predicate => 68,
-                AANonTerminal::ProductionTail => 101,
-                AANonTerminal::Symbol => 72,
-                AANonTerminal::SymbolList => 69,
+            222 => match lhs {
+                AANonTerminal::FunctionalInterface => 226,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            89 => match lhs {
-                AANonTerminal::Action => 103,
-                AANonTerminal::TaggedPrecedence => 102,
+            225 => match lhs {
+                AANonTerminal::FunctionalInterface => 229,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            90 => match lhs {
-                AANonTerminal::Action => 104,
+            226 => match lhs {
+                AANonTerminal::ModuleName => 230,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            95 => match lhs {
-                AANonTerminal::Tag => 107,
+            229 => match lhs {
+                AANonTerminal::ModuleName => 232,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            99 => match lhs {
-                AANonTerminal::Tag => 107,
+            230 => match lhs {
+                AANonTerminal::SymbolPrefix => 233,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            100 => match lhs {
-                AANonTerminal::Tag => 107,
+            232 => match lhs {
+                AANonTerminal::SymbolPrefix => 236,
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            },
+            233 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 237,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
-            102 => match lhs {
-                AANonTerminal::Action => 108,
+            236 => match lhs {
+                AANonTerminal::Injection => 3,
+                AANonTerminal::InjectionHead => 5,
+                AANonTerminal::OptionalInjection => 239,
                 _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
             },
             _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
@@ -1423,73 +6313,194 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
     fn do_semantic_action<F: FnMut(String, String)>(
         &mut self,
         aa_production_id: u32,
-        aa_rhs: Vec<AttributeData>,
+        mut aa_rhs: Vec<AttributeData>,
         mut aa_inject: F,
     ) -> AttributeData {
-        let mut aa_lhs = if let Some(a) = aa_rhs.first() {
-            a.clone()
-        } else {
-            AttributeData::default()
+        let mut aa_lhs = match aa_production_id {
+            8 | 15 | 38 | 53 | 58 | 59 | 60 | 70 | 71 | 82 | 83 | 84 | 85 | 86 | 87 | 89 | 91
+            | 92 | 100 | 103 | 104 | 105 | 108 | 109 | 110 | 111 | 112 | 113 | 114 | 115 | 116
+            | 117 | 118 | 119 | 120 | 121 | 125 | 129 | 130 | 131 | 132 | 133 | 134 | 135 | 136
+            | 137 | 138 => aa_rhs.first().cloned().unwrap_or_default(),
+            _ => aa_rhs.first_mut().map(core::mem::take).unwrap_or_default(),
         };
         match aa_production_id {
-            2 => {
+            4 => {
+                // SpecificationTest: "%test" Literal "=>" Ident #(NonAssoc, 0)
+
+                let (text, location) = aa_rhs[1].text_and_location();
+                let (outcome, outcome_location) = aa_rhs[3].text_and_location();
+                if self.target_type_parameter.is_some() {
+                    self.error(location, "%test: not supported for generic targets");
+                }
+                match outcome.as_str() {
+                    "ok" => self.tests.push((text.to_string(), true, location.clone())),
+                    "error" => self.tests.push((text.to_string(), false, location.clone())),
+                    _ => self.error(
+                        outcome_location,
+                        &format!("{outcome}: expected \"ok\" or \"error\""),
+                    ),
+                }
+            }
+            5 => {
                 // OptionalInjection: <empty> #(NonAssoc, 0)
                 // no injection so nothing to do
             }
-            4 => {
+            7 => {
                 // InjectionHead: "%inject" Literal #(Right, 1)
+                aa_lhs = aa_rhs[1].clone();
+            }
+            8 => {
+                // Injection: InjectionHead "." #(NonAssoc, 0)
 
-                let (text, location) = aa_rhs[1].text_and_location();
+                let (text, location) = aa_rhs[0].text_and_location();
                 let file_path = text.trim_matches('"');
-                match File::open(file_path) {
-                    Ok(mut file) => {
-                        let mut text = String::new();
-                        if let Err(err) = file.read_to_string(&mut text) {
-                            self.error(location, &format!("Injecting: {err}"));
-                        } else if text.is_empty() {
-                            self.error(
-                                location,
-                                &format!("Injected file \"{file_path}\" is empty."),
-                            );
-                        } else {
-                            aa_inject(text, file_path.to_string());
-                        }
-                    }
-                    Err(err) => self.error(location, &format!("Injecting: {err}.")),
-                };
+                if let Some(text) = self.read_injection(file_path, location) {
+                    aa_inject(text, file_path.to_string());
+                }
             }
-            6 => {
+            9 => {
                 // Preamble: <empty> #(NonAssoc, 0)
 
                 // no Preamble defined so there's nothing to do
             }
-            7 => {
+            10 => {
                 // Preamble: OptionalInjection RustCode OptionalInjection #(NonAssoc, 0)
 
                 let text = aa_rhs[1].matched_text();
                 self.set_preamble(&text[2..text.len() - 2]);
             }
-            10 => {
-                // AttributeType: "%attr" Ident #(NonAssoc, 0)
+            14 => {
+                // AttributeType: "%attr" AttributeTypeName "%spanned" #(NonAssoc, 0)
 
-                self.attribute_type = aa_rhs[1].matched_text().to_string();
+                self.spanned_attributes = true;
             }
-            11 => {
+            15 => {
+                // AttributeTypeName: AttrType #(NonAssoc, 0)
+
+                let (text, location) = aa_rhs[0].text_and_location();
+                self.set_attribute_data_type(text, location);
+            }
+            16 => {
                 // TargetType: "%target" Ident #(NonAssoc, 0)
 
                 self.target_type = aa_rhs[1].matched_text().to_string();
             }
             17 => {
+                // TargetType: "%target" Ident Lifetime #(NonAssoc, 0)
+
+                self.target_type = aa_rhs[1].matched_text().to_string();
+                let text = aa_rhs[2].matched_text();
+                self.target_lifetime = Some(text[1..text.len() - 1].to_string());
+            }
+            18 => {
+                // TargetType: "%target" Ident TypeParameter #(NonAssoc, 0)
+
+                self.target_type = aa_rhs[1].matched_text().to_string();
+                let text = aa_rhs[2].matched_text();
+                self.target_type_parameter = Some(text[1..text.len() - 1].trim().to_string());
+            }
+            20 => {
+                // Interning: "%intern" Ident OptionalInjection #(NonAssoc, 0)
+
+                self.interner_field = Some(aa_rhs[1].matched_text().to_string());
+            }
+            22 => {
+                // CoverageRecording: "%coverage" Ident OptionalInjection #(NonAssoc, 0)
+
+                self.coverage_field = Some(aa_rhs[1].matched_text().to_string());
+            }
+            24 => {
+                // DerivationRecording: "%derivation" Ident OptionalInjection #(NonAssoc, 0)
+
+                self.derivation_field = Some(aa_rhs[1].matched_text().to_string());
+            }
+            26 => {
+                // VisitorGeneration: "%visitor" OptionalInjection #(NonAssoc, 0)
+
+                self.visitor = true;
+            }
+            28 => {
+                // Observing: "%observer" Ident OptionalInjection #(NonAssoc, 0)
+
+                self.observer_field = Some(aa_rhs[1].matched_text().to_string());
+            }
+            31 => {
+                // StartSymbols: "%start" Ident OptionalInjection #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                self.add_start_symbol(name, location);
+            }
+            32 => {
+                // StartSymbols: StartSymbols "%start" Ident OptionalInjection #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[2].text_and_location();
+                self.add_start_symbol(name, location);
+            }
+            38 => {
+                // ExpectedConflicts: "%expect" Number #(NonAssoc, 0)
+
+                self.expected_conflicts = Some((aa_rhs[1].number(), aa_rhs[0].location().clone()));
+            }
+            39 => {
                 // ExpectedRRConflicts: "%reduce_reduce" Number #(NonAssoc, 0)
 
                 self.expected_rr_conflicts = aa_rhs[1].number();
             }
-            18 => {
+            40 => {
                 // ExpectedSRConflicts: "%shift_reduce" Number #(NonAssoc, 0)
 
                 self.expected_sr_conflicts = aa_rhs[1].number();
             }
-            19 => {
+            42 => {
+                // DefaultConflictResolution: "%default-conflict" Ident #(NonAssoc, 0)
+
+                if aa_rhs[1].matched_text() == "shift" {
+                    self.default_sr_shift = true;
+                } else {
+                    self.error(
+                        aa_rhs[1].location(),
+                        &format!(
+                            "{}: unknown default conflict resolution (expected \"shift\")",
+                            aa_rhs[1].matched_text()
+                        ),
+                    );
+                }
+            }
+            44 => {
+                // RepetitionRecursion: "%repeat" Ident #(NonAssoc, 0)
+
+                match aa_rhs[1].matched_text().as_str() {
+                    "left" => (),
+                    "right" => self.right_recursive_repetition = true,
+                    text => self.error(
+                        aa_rhs[1].location(),
+                        &format!("{text}: unknown recursion (expected \"left\" or \"right\")"),
+                    ),
+                }
+            }
+            46 => {
+                // Derives: "%derive" Literal #(NonAssoc, 0)
+
+                self.set_derives(aa_rhs[1].matched_text(), aa_rhs[1].location());
+            }
+            48 => {
+                // FunctionalInterface: "%functional" #(NonAssoc, 0)
+
+                self.functional = true;
+            }
+            50 => {
+                // ModuleName: "%module" Ident #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                self.set_module(name, location);
+            }
+            52 => {
+                // SymbolPrefix: "%prefix" Ident #(NonAssoc, 0)
+
+                let (prefix, location) = aa_rhs[1].text_and_location();
+                self.set_prefix(prefix, location);
+            }
+            53 => {
                 // Number: NumberExpr #(NonAssoc, 0)
 
                 match u32::from_str(aa_rhs[0].matched_text()) {
@@ -1500,25 +6511,101 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     }
                 }
             }
-            23 => {
-                // TokenDefinition: "%token" NewTokenName Literal #(NonAssoc, 0)
+            58 => {
+                // TokenDefinition: TokenDefinitionHead Action #(NonAssoc, 0)
+
+                if let TagOrToken::Token(token) = aa_rhs[0].tag_or_token() {
+                    token.set_conversion(aa_rhs[1].action());
+                }
+            }
+            59 => {
+                // TokenDefinition: TokenDefinitionHead ModeSwitch #(NonAssoc, 0)
+
+                if let TagOrToken::Token(token) = aa_rhs[0].tag_or_token() {
+                    let (name, location) = aa_rhs[1].text_and_location();
+                    token.set_mode_switch(name, location);
+                }
+            }
+            60 => {
+                // TokenDefinition: TokenDefinitionHead ModeSwitch Action #(NonAssoc, 0)
+
+                if let TagOrToken::Token(token) = aa_rhs[0].tag_or_token() {
+                    let (name, location) = aa_rhs[1].text_and_location();
+                    token.set_mode_switch(name, location);
+                    token.set_conversion(aa_rhs[2].action());
+                }
+            }
+            62 => {
+                // KeywordDefinition: "%keywords" Ident #(NonAssoc, 0)
+
+                let (keyword, location) = aa_rhs[1].text_and_location();
+                self.new_keyword(keyword, location);
+            }
+            63 => {
+                // KeywordDefinition: KeywordDefinition Ident #(NonAssoc, 0)
+
+                let (keyword, location) = aa_rhs[1].text_and_location();
+                self.new_keyword(keyword, location);
+            }
+            64 => {
+                // TokenDefinitionHead: "%token" NewTokenName TokenLiteral OptionalDisplayName #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                let text = aa_rhs[2].matched_text();
+                match self.symbol_table.new_literal_token(name, text, location) {
+                    Ok(token) => {
+                        self.set_display_name(&token, &aa_rhs[3]);
+                        aa_lhs = AttributeData::TagOrToken((&token).into());
+                    }
+                    Err(err) => {
+                        self.error(location, &err.to_string());
+                        aa_lhs = AttributeData::TagOrToken(TagOrToken::Invalid);
+                    }
+                }
+            }
+            65 => {
+                // TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName #(NonAssoc, 0)
 
                 let (name, location) = aa_rhs[1].text_and_location();
                 let text = aa_rhs[2].matched_text();
-                if let Err(err) = self.symbol_table.new_literal_token(name, text, location) {
-                    self.error(location, &err.to_string());
+                match self.symbol_table.new_regex_token(name, text, location) {
+                    Ok(token) => {
+                        self.set_display_name(&token, &aa_rhs[3]);
+                        aa_lhs = AttributeData::TagOrToken((&token).into());
+                    }
+                    Err(err) => {
+                        self.error(location, &err.to_string());
+                        aa_lhs = AttributeData::TagOrToken(TagOrToken::Invalid);
+                    }
                 }
             }
-            24 => {
-                // TokenDefinition: "%token" NewTokenName RegularExpression #(NonAssoc, 0)
+            66 => {
+                // TokenDefinitionHead: "%token" NewTokenName RegularExpression OptionalDisplayName "%transform" Action #(NonAssoc, 0)
 
                 let (name, location) = aa_rhs[1].text_and_location();
                 let text = aa_rhs[2].matched_text();
-                if let Err(err) = self.symbol_table.new_regex_token(name, text, location) {
-                    self.error(location, &err.to_string());
+                match self.symbol_table.new_regex_token(name, text, location) {
+                    Ok(token) => {
+                        self.set_display_name(&token, &aa_rhs[3]);
+                        token.set_transform(aa_rhs[5].action());
+                        aa_lhs = AttributeData::TagOrToken((&token).into());
+                    }
+                    Err(err) => {
+                        self.error(location, &err.to_string());
+                        aa_lhs = AttributeData::TagOrToken(TagOrToken::Invalid);
+                    }
                 }
             }
-            25 => {
+            67 => {
+                // OptionalDisplayName: <empty> #(NonAssoc, 0)
+                // use the token's text or name
+            }
+            69 => {
+                // ModeSwitch: "%begin" Ident #(NonAssoc, 0)
+
+                aa_lhs = aa_rhs[1].clone();
+            }
+            70 => {
                 // RegularExpression: RegEx #(NonAssoc, 0)
                 let (text, location) = aa_rhs[0].text_and_location();
                 if let Err(err) = regex::Regex::new(text) {
@@ -1526,10 +6613,15 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                         location,
                         &format!("malformed regular expression \"{text}\": {err}"),
                     );
+                } else if Self::matches_empty_string(text) {
+                    self.error(
+                        location,
+                        &format!("regular expression \"{text}\" matches the empty string"),
+                    );
                 }
             }
-            26 => {
-                // NewTokenName: Ident #(NonAssoc, 0) ?( !Self::is_allowable_name($1.matched_text()) ?)
+            71 => {
+                // NewTokenName: TokenName #(NonAssoc, 0) ?( !self.is_allowable_name($1.matched_text()) ?)
 
                 let (name, location) = aa_rhs[0].text_and_location();
                 self.warning(
@@ -1537,12 +6629,12 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     &format!("token name \"{name}\" may clash with generated code"),
                 );
             }
-            28 => {
+            73 => {
                 // SkipDefinitions: <empty> #(NonAssoc, 0)
 
                 // do nothing
             }
-            30 => {
+            75 => {
                 // SkipDefinition: "%skip" RegularExpression #(NonAssoc, 0)
 
                 let skip_rule = aa_rhs[1].matched_text();
@@ -1550,73 +6642,140 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     self.error(aa_rhs[1].location(), &err.to_string());
                 };
             }
-            31 => {
+            76 => {
+                // ModeDefinitions: <empty> #(NonAssoc, 0)
+
+                // do nothing
+            }
+            79 => {
+                // ModeHead: "%mode" Ident #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                if let Err(err) = self.symbol_table.new_lexical_mode(name) {
+                    self.error(location, &err.to_string());
+                }
+            }
+            80 => {
                 // PrecedenceDefinitions: <empty> #(NonAssoc, 0)
 
                 // do nothing
             }
-            33 => {
+            82 => {
                 // PrecedenceDefinition: "%left" TagList #(NonAssoc, 0)
 
-                let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::Left, tag_list);
+                self.set_precedences(
+                    Associativity::Left,
+                    None,
+                    aa_rhs[1].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
+            }
+            83 => {
+                // PrecedenceDefinition: "%left" Number TagList #(NonAssoc, 0)
+
+                self.set_precedences(
+                    Associativity::Left,
+                    Some(aa_rhs[1].number()),
+                    aa_rhs[2].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
             }
-            34 => {
+            84 => {
                 // PrecedenceDefinition: "%right" TagList #(NonAssoc, 0)
 
-                let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::Right, tag_list);
+                self.set_precedences(
+                    Associativity::Right,
+                    None,
+                    aa_rhs[1].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
+            }
+            85 => {
+                // PrecedenceDefinition: "%right" Number TagList #(NonAssoc, 0)
+
+                self.set_precedences(
+                    Associativity::Right,
+                    Some(aa_rhs[1].number()),
+                    aa_rhs[2].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
             }
-            35 => {
+            86 => {
                 // PrecedenceDefinition: "%nonassoc" TagList #(NonAssoc, 0)
 
-                let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::NonAssoc, tag_list);
+                self.set_precedences(
+                    Associativity::NonAssoc,
+                    None,
+                    aa_rhs[1].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
+            }
+            87 => {
+                // PrecedenceDefinition: "%nonassoc" Number TagList #(NonAssoc, 0)
+
+                self.set_precedences(
+                    Associativity::NonAssoc,
+                    Some(aa_rhs[1].number()),
+                    aa_rhs[2].tag_or_token_list(),
+                    aa_rhs[0].location(),
+                );
+            }
+            88 => {
+                // PrecedenceDefinition: "%prec-for" Ident Tag #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                let (associativity, precedence) = match aa_rhs[2].tag_or_token() {
+                    TagOrToken::Tag(tag) => {
+                        tag.add_used_at(location);
+                        (tag.associativity(), tag.precedence())
+                    }
+                    TagOrToken::Token(token) => token.associativity_and_precedence(),
+                    TagOrToken::Invalid => (Associativity::default(), 0),
+                };
+                let entry = (associativity, precedence, location.clone());
+                if self
+                    .precedence_for
+                    .insert(name.to_string(), entry)
+                    .is_some()
+                {
+                    self.error(location, &format!("{name}: precedence already declared"));
+                }
             }
-            36 => {
+            89 => {
                 // TagList: Tag #(NonAssoc, 0)
 
                 let tag_or_token = aa_rhs[0].tag_or_token();
                 aa_lhs = AttributeData::TagOrTokenList(vec![tag_or_token.clone()]);
             }
-            37 => {
+            90 => {
                 // TagList: TagList Tag #(NonAssoc, 0)
 
                 let tag_or_token = aa_rhs[1].tag_or_token();
                 aa_lhs.tag_or_token_list_mut().push(tag_or_token.clone());
             }
-            38 => {
+            91 => {
                 // Tag: Literal #(NonAssoc, 0)
 
                 let lexeme = aa_rhs[0].matched_text();
                 if let Some(token) = self.symbol_table.get_literal_token(lexeme) {
                     aa_lhs = AttributeData::TagOrToken(token.into());
-                    if token.precedence_has_been_set() {
-                        let msg = format!("Token \"{lexeme}\" precedence has been set already");
-                        self.error(aa_rhs[0].location(), &msg);
-                    }
                 } else {
                     let msg = format!("Literal token \"{lexeme}\" is not known");
                     self.error(aa_rhs[0].location(), &msg);
                     aa_lhs = AttributeData::TagOrToken(TagOrToken::Invalid);
                 }
             }
-            39 => {
+            92 => {
                 // Tag: Ident #(NonAssoc, 0)
 
                 let name = aa_rhs[0].matched_text();
                 if let Some(token) = self.symbol_table.get_token(name) {
                     aa_lhs = AttributeData::TagOrToken(token.into());
-                    if token.precedence_has_been_set() {
-                        let msg = format!("Token \"{name}\" precedence has been set already");
-                        self.error(aa_rhs[0].location(), &msg);
-                    }
+                } else if let Some(tag) = self.symbol_table.get_tag(name) {
+                    aa_lhs = AttributeData::TagOrToken(tag.into());
                 } else {
                     let location = aa_rhs[0].location();
-                    if !Self::is_allowable_name(name) {
+                    if !self.is_allowable_name(name) {
                         self.warning(
                             location,
                             &format!("tag name \"{name}\" may clash with generated code"),
@@ -1628,7 +6787,35 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     }
                 }
             }
-            42 => {
+            93 => {
+                // TypeDefinitions: <empty> #(NonAssoc, 0)
+
+                // do nothing
+            }
+            97 => {
+                // TypeDefinition: "%type" TypedName RustType #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                self.set_attribute_type(name, aa_rhs[2].matched_text(), location);
+            }
+            98 => {
+                // NoSkipDefinition: "%noskip" Ident #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                self.add_no_skip_non_terminal(name, location);
+            }
+            99 => {
+                // NoSkipDefinition: NoSkipDefinition Ident #(NonAssoc, 0)
+
+                let (name, location) = aa_rhs[1].text_and_location();
+                self.add_no_skip_non_terminal(name, location);
+            }
+            100 => {
+                // RecoveryDefinition: "%recover" TagList #(NonAssoc, 0)
+
+                self.add_recovery_tokens(aa_rhs[1].tag_or_token_list(), aa_rhs[0].location());
+            }
+            103 => {
                 // ProductionGroup: ProductionGroupHead ProductionTailList "." #(NonAssoc, 0)
 
                 let lhs = aa_rhs[0].left_hand_side();
@@ -1636,8 +6823,9 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 for tail in tails.iter() {
                     self.new_production(lhs, tail);
                 }
+                self.add_pending_productions();
             }
-            43 => {
+            104 => {
                 // ProductionGroupHead: Ident ":" #(NonAssoc, 0)
 
                 let (name, location) = aa_rhs[0].text_and_location();
@@ -1648,13 +6836,13 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     Err(err) => self.error(location, &err.to_string()),
                 }
             }
-            44 => {
+            105 => {
                 // ProductionTailList: ProductionTail #(NonAssoc, 0)
 
                 let production_tail = aa_rhs[0].production_tail().clone();
                 aa_lhs = AttributeData::ProductionTailList(vec![production_tail]);
             }
-            45 => {
+            106 => {
                 // ProductionTailList: ProductionTailList "|" ProductionTail #(NonAssoc, 0)
 
                 let production_tail = aa_rhs[2].production_tail();
@@ -1662,19 +6850,19 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     .production_tail_list_mut()
                     .push(production_tail.clone());
             }
-            46 => {
+            107 => {
                 // ProductionTail: <empty> #(NonAssoc, 0)
 
                 let tail = ProductionTail::default();
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            47 => {
+            108 => {
                 // ProductionTail: Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(&[], None, None, Some(aa_rhs[0].action()));
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            48 => {
+            109 => {
                 // ProductionTail: Predicate Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1685,13 +6873,13 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            49 => {
+            110 => {
                 // ProductionTail: Predicate #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(&[], Some(aa_rhs[0].predicate()), None, None);
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            50 => {
+            111 => {
                 // ProductionTail: SymbolList Predicate TaggedPrecedence Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1702,7 +6890,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            51 => {
+            112 => {
                 // ProductionTail: SymbolList Predicate TaggedPrecedence #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1713,7 +6901,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            52 => {
+            113 => {
                 // ProductionTail: SymbolList Predicate Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1724,7 +6912,18 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            53 => {
+            114 => {
+                // ProductionTail: SymbolList Predicate Rejection #(NonAssoc, 0)
+
+                let tail = ProductionTail::new(
+                    aa_rhs[0].symbol_list(),
+                    Some(aa_rhs[1].predicate()),
+                    None,
+                    None,
+                );
+                aa_lhs = AttributeData::ProductionTail(tail.with_rejection(aa_rhs[2].rejection()))
+            }
+            115 => {
                 // ProductionTail: SymbolList Predicate #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1735,7 +6934,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            54 => {
+            116 => {
                 // ProductionTail: SymbolList TaggedPrecedence Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1746,7 +6945,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            55 => {
+            117 => {
                 // ProductionTail: SymbolList TaggedPrecedence #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1757,7 +6956,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            56 => {
+            118 => {
                 // ProductionTail: SymbolList Action #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(
@@ -1768,25 +6967,30 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 );
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            57 => {
+            119 => {
                 // ProductionTail: SymbolList #(NonAssoc, 0)
 
                 let tail = ProductionTail::new(aa_rhs[0].symbol_list(), None, None, None);
                 aa_lhs = AttributeData::ProductionTail(tail)
             }
-            58 => {
+            120 => {
                 // Action: ActionCode #(NonAssoc, 0)
 
                 let text = aa_rhs[0].matched_text();
                 aa_lhs = AttributeData::Action(text[2..text.len() - 2].to_string());
             }
-            59 => {
+            121 => {
                 // Predicate: PredicateExpr #(NonAssoc, 0)
 
                 let text = aa_rhs[0].matched_text();
                 aa_lhs = AttributeData::Predicate(text[2..text.len() - 2].to_string());
             }
-            60 => {
+            122 => {
+                // Rejection: "%reject" Literal #(NonAssoc, 0)
+
+                aa_lhs = AttributeData::Rejection(aa_rhs[1].matched_text().to_string());
+            }
+            123 => {
                 // TaggedPrecedence: "%prec" Ident #(NonAssoc, 0)
 
                 let (name, location) = aa_rhs[1].text_and_location();
@@ -1806,7 +7010,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     aa_lhs = AttributeData::AssociativityAndPrecedence(Associativity::default(), 0);
                 };
             }
-            61 => {
+            124 => {
                 // TaggedPrecedence: "%prec" Literal #(NonAssoc, 0)
 
                 let (lexeme, location) = aa_rhs[1].text_and_location();
@@ -1820,26 +7024,66 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     aa_lhs = AttributeData::AssociativityAndPrecedence(Associativity::default(), 0);
                 };
             }
-            62 => {
-                // SymbolList: Symbol #(NonAssoc, 0)
+            125 => {
+                // SymbolList: Element #(NonAssoc, 0)
 
                 let symbol = aa_rhs[0].symbol();
                 aa_lhs = AttributeData::SymbolList(vec![symbol.clone()]);
             }
-            63 => {
-                // SymbolList: SymbolList Symbol #(NonAssoc, 0)
+            126 => {
+                // SymbolList: SymbolList Element #(NonAssoc, 0)
 
                 let symbol = aa_rhs[1].symbol();
                 aa_lhs.symbol_list_mut().push(symbol.clone());
             }
-            64 => {
+            129 => {
+                // Element: Symbol "?" #(NonAssoc, 0)
+
+                let optional = self.optional(aa_rhs[0].symbol(), aa_rhs[1].location());
+                aa_lhs = AttributeData::Symbol(optional);
+            }
+            130 => {
+                // Element: Symbol "*" #(NonAssoc, 0)
+
+                let repetition = self.repetition(aa_rhs[0].symbol(), true, aa_rhs[1].location());
+                aa_lhs = AttributeData::Symbol(repetition);
+            }
+            131 => {
+                // Element: Symbol "+" #(NonAssoc, 0)
+
+                let repetition = self.repetition(aa_rhs[0].symbol(), false, aa_rhs[1].location());
+                aa_lhs = AttributeData::Symbol(repetition);
+            }
+            132 => {
+                // SeparatedList: Symbol "%" Symbol #(NonAssoc, 0)
+
+                let list = self.separated_list(
+                    aa_rhs[0].symbol(),
+                    aa_rhs[2].symbol(),
+                    false,
+                    aa_rhs[1].location(),
+                );
+                aa_lhs = AttributeData::Symbol(list);
+            }
+            133 => {
+                // SeparatedList: Symbol "%%" Symbol #(NonAssoc, 0)
+
+                let list = self.separated_list(
+                    aa_rhs[0].symbol(),
+                    aa_rhs[2].symbol(),
+                    true,
+                    aa_rhs[1].location(),
+                );
+                aa_lhs = AttributeData::Symbol(list);
+            }
+            134 => {
                 // Symbol: Ident #(NonAssoc, 0)
 
                 let (name, location) = aa_rhs[0].text_and_location();
                 let symbol = self.symbol_table.symbol_used_at(name, location);
                 aa_lhs = AttributeData::Symbol(symbol);
             }
-            65 => {
+            135 => {
                 // Symbol: Literal #(NonAssoc, 0)
 
                 let (lexeme, location) = aa_rhs[0].text_and_location();
@@ -1852,13 +7096,26 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                     aa_lhs = AttributeData::Symbol(symbol);
                 }
             }
-            66 => {
+            136 => {
                 // Symbol: "%error" #(NonAssoc, 0)
 
                 let location = aa_rhs[0].location();
                 let symbol = self.symbol_table.error_symbol_used_at(location);
                 aa_lhs = AttributeData::Symbol(symbol);
             }
+            137 => {
+                // Symbol: "(" SymbolList ")" #(NonAssoc, 0)
+
+                let group = self.group(aa_rhs[1].symbol_list(), aa_rhs[0].location());
+                aa_lhs = AttributeData::Symbol(group);
+            }
+            138 => {
+                // Symbol: "(" SymbolList TaggedPrecedence ")" #(NonAssoc, 0)
+
+                self.error(aa_rhs[0].location(), "%prec: can't be in a group");
+                let group = self.group(aa_rhs[1].symbol_list(), aa_rhs[0].location());
+                aa_lhs = AttributeData::Symbol(group);
+            }
             _ => aa_inject(String::new(), String::new()),
         };
         aa_lhs
@@ -1,7 +1,5 @@
 // Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
 
-
-
 #[cfg(not(feature = "bootstrap"))]
 use crate::alap_gen::AATerminal;
 #[cfg(feature = "bootstrap")]
@@ -27,6 +25,7 @@ pub enum AttributeData {
     ProductionTailList(Vec<ProductionTail>),
     Action(String),
     Predicate(String),
+    Rejection(String),
     AssociativityAndPrecedence(Associativity, u16),
     Default,
 }
@@ -168,6 +167,13 @@ impl AttributeData {
         }
     }
 
+    pub fn rejection(&self) -> &str {
+        match self {
+            AttributeData::Rejection(message) => message,
+            _ => panic!("{self:?}: Wrong attribute variant."),
+        }
+    }
+
     pub fn associativity_and_precedence(&self) -> (Associativity, u16) {
         match self {
             AttributeData::AssociativityAndPrecedence(associativity, precedence) => {
@@ -193,6 +199,11 @@ impl From<lalr1_plus::Error<AATerminal>> for AttributeData {
             lalr1_plus::Error::SyntaxError(token, expected) => {
                 AttributeData::SyntaxError(token, expected)
             }
+            // alap_gen doesn't limit the depth of its parse stack, its tables are complete
+            // and it rejects no reductions
+            lalr1_plus::Error::StackOverflow(..)
+            | lalr1_plus::Error::InternalTableError(..)
+            | lalr1_plus::Error::SemanticError(..) => AttributeData::Default,
         }
     }
 }
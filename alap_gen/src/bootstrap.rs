@@ -562,8 +562,8 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
             },
             39 => match aa_tag {
                 LITERAL | REGEX => {
-                    if !Self::is_allowable_name(aa_attributes.at_len_minus_n(1).matched_text()) {
-                        // NewTokenName: IDENT #(NonAssoc, 0) ?( !Self::is_allowable_name($1.matched_text()) ?)
+                    if !self.is_allowable_name(aa_attributes.at_len_minus_n(1).matched_text()) {
+                        // NewTokenName: IDENT #(NonAssoc, 0) ?( !self.is_allowable_name($1.matched_text()) ?)
                         Action::Reduce(26)
                     } else {
                         // NewTokenName: IDENT #(NonAssoc, 0)
@@ -1440,7 +1440,7 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 }
             }
             26 => {
-                // NewTokenName: IDENT #(NonAssoc, 0) ?( !Self::is_allowable_name($1.matched_text()) ?)
+                // NewTokenName: IDENT #(NonAssoc, 0) ?( !self.is_allowable_name($1.matched_text()) ?)
 
                 let (name, location) = aa_rhs[0].text_and_location();
                 self.warning(
@@ -1470,22 +1470,37 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 // PrecedenceDefinition: "%left" TagList #(NonAssoc, 0)
 
                 let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::Left, tag_list);
+                let location = aa_rhs[0].location();
+                for err in
+                    self.symbol_table
+                        .set_precedences(Associativity::Left, None, tag_list, location)
+                {
+                    self.error(location, &err.to_string());
+                }
             }
             34 => {
                 // PrecedenceDefinition: "%right" TagList #(NonAssoc, 0)
 
                 let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::Right, tag_list);
+                let location = aa_rhs[0].location();
+                for err in
+                    self.symbol_table
+                        .set_precedences(Associativity::Right, None, tag_list, location)
+                {
+                    self.error(location, &err.to_string());
+                }
             }
             35 => {
                 // PrecedenceDefinition: "%nonassoc" TagList #(NonAssoc, 0)
 
                 let tag_list = aa_rhs[1].tag_or_token_list();
-                self.symbol_table
-                    .set_precedences(Associativity::NonAssoc, tag_list);
+                let location = aa_rhs[0].location();
+                for err in
+                    self.symbol_table
+                        .set_precedences(Associativity::NonAssoc, None, tag_list, location)
+                {
+                    self.error(location, &err.to_string());
+                }
             }
             36 => {
                 // TagList: Tag #(NonAssoc, 0)
@@ -1505,10 +1520,6 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 let lexeme = aa_rhs[0].matched_text();
                 if let Some(token) = self.symbol_table.get_literal_token(lexeme) {
                     aa_lhs = AttributeData::TagOrToken(token.into());
-                    if token.precedence_has_been_set() {
-                        let msg = format!("Token \"{}\" precedence has been set already", lexeme);
-                        self.error(aa_rhs[0].location(), &msg);
-                    }
                 } else {
                     let msg = format!("Literal token \"{}\" is not known", lexeme);
                     self.error(aa_rhs[0].location(), &msg);
@@ -1521,13 +1532,9 @@ impl lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Specificat
                 let name = aa_rhs[0].matched_text();
                 if let Some(token) = self.symbol_table.get_token(name) {
                     aa_lhs = AttributeData::TagOrToken(token.into());
-                    if token.precedence_has_been_set() {
-                        let msg = format!("Token \"{}\" precedence has been set already", name);
-                        self.error(aa_rhs[0].location(), &msg);
-                    }
                 } else {
                     let location = aa_rhs[0].location();
-                    if !Self::is_allowable_name(name) {
+                    if !self.is_allowable_name(name) {
                         self.warning(
                             location,
                             &format!("tag name \"{}\" may clash with generated code", name),
@@ -0,0 +1,77 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+//! Generating parsers from build scripts so that their code needn't be committed.
+
+use std::{convert::TryFrom, fs, path::Path};
+
+use crate::{
+    grammar::{Grammar, InjectLimits, Specification},
+    GenError,
+};
+
+/// Generate the parser specified in the file at `spec_path` writing its code to
+/// `out_path` (and its states' description alongside with the extension "states") as
+/// `alap_gen_ng -f -o <out_path> <spec_path>` would but failing with the `GenError`
+/// that says what went wrong rather than exiting.  E.g. in a `build.rs`:
+///
+/// ```ignore
+/// fn main() {
+///     println!("cargo:rerun-if-changed=src/calc.alaps");
+///     let out_path = format!("{}/calc.rs", std::env::var("OUT_DIR").unwrap());
+///     alap_gen::build::compile("src/calc.alaps", &out_path).unwrap();
+/// }
+/// ```
+///
+/// with the parser's code included in the crate by
+/// `include!(concat!(env!("OUT_DIR"), "/calc.rs"));`.
+pub fn compile<P: AsRef<Path>, Q: AsRef<Path>>(spec_path: P, out_path: Q) -> Result<(), GenError> {
+    let spec_path = spec_path.as_ref();
+    let out_path = out_path.as_ref();
+    let text = fs::read_to_string(spec_path).map_err(GenError::ReadSpecification)?;
+    let label = spec_path.to_string_lossy().to_string();
+    let specification =
+        Specification::with_inject_limits(&text, &label, &[], InjectLimits::default())
+            .map_err(|error| GenError::ParseFailed(error.to_string()))?;
+    let grammar = Grammar::try_from((specification, false, false))?;
+    grammar
+        .write_parser_code_to_file(out_path)
+        .map_err(|err| GenError::WriteCode(out_path.to_path_buf(), err))?;
+    let description_path = out_path.with_extension("states");
+    grammar
+        .write_description_to_file(&description_path)
+        .map_err(|err| GenError::WriteFile(description_path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_fails_with_errors() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_build_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let out_path = dir.join("sum.rs");
+        match compile(dir.join("missing.alaps"), &out_path) {
+            Err(GenError::ReadSpecification(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::NotFound)
+            }
+            result => panic!("unexpected: {result:?}"),
+        }
+        let spec_path = dir.join("sum.alaps");
+        let text = "%attr AttributeData\n%target Sum\n%%\n%token NUMBER ([0-9]+)\n%%\n";
+        fs::write(
+            &spec_path,
+            format!("{text}Sum: Sum \"+\" NUMBER | NUMBER.\n"),
+        )
+        .unwrap();
+        let error = compile(&spec_path, &out_path).unwrap_err();
+        assert!(matches!(error, GenError::Grammar(_)));
+        assert_eq!(error.to_string(), "Too many errors: 1.");
+        fs::write(&spec_path, format!("{text}Sum: NUMBER.\n")).unwrap();
+        compile(&spec_path, &out_path).unwrap();
+        assert!(fs::read_to_string(&out_path)
+            .unwrap()
+            .contains("impl lalr1_plus::Parser<"));
+        assert!(dir.join("sum.states").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
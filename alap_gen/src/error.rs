@@ -0,0 +1,62 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+
+use std::{fmt, io, path::PathBuf};
+
+use crate::grammar;
+
+/// The ways in which generating a parser can fail
+#[derive(Debug)]
+pub enum GenError {
+    /// The output file exists (and overwriting it wasn't forced)
+    OutputExists(PathBuf),
+    OpenSpecification(io::Error),
+    ReadSpecification(io::Error),
+    /// The specification isn't valid (with the parse error's description)
+    ParseFailed(String),
+    /// The grammar has errors, e.g. undefined symbols or unexpected conflicts
+    Grammar(grammar::Error),
+    /// The output file's recorded checksum doesn't match the specification's (paths)
+    OutOfDate(PathBuf, PathBuf),
+    /// Writing the parser's code to the file failed
+    WriteCode(PathBuf, io::Error),
+    /// Writing one of the other output files (e.g. the states' description) failed
+    WriteFile(PathBuf, io::Error),
+}
+
+impl fmt::Display for GenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutputExists(path) => {
+                write!(f, "{}: output file already exists", path.to_string_lossy())
+            }
+            Self::OpenSpecification(error) => {
+                write!(f, "Error opening specification file: {error:?}")
+            }
+            Self::ReadSpecification(error) => {
+                write!(f, "Error reading specification file: {error:?}")
+            }
+            Self::ParseFailed(error) => write!(f, "Parse failed: {error}"),
+            Self::Grammar(error) => write!(f, "{error}"),
+            Self::OutOfDate(output, specification) => write!(
+                f,
+                "{}: out of date with respect to {}.",
+                output.to_string_lossy(),
+                specification.to_string_lossy()
+            ),
+            Self::WriteCode(path, error) | Self::WriteFile(path, error) => write!(
+                f,
+                "{}: problems writing file: {:?}.",
+                path.to_string_lossy(),
+                error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+impl From<grammar::Error> for GenError {
+    fn from(error: grammar::Error) -> Self {
+        Self::Grammar(error)
+    }
+}
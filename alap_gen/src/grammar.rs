@@ -2,28 +2,120 @@
 
 #[cfg(not(feature = "bootstrap"))]
 use crate::alap_gen::AATerminal;
+use crate::attributes::AttributeData;
 #[cfg(feature = "bootstrap")]
 use crate::bootstrap::AATerminal;
-use crate::production::{GrammarItemKey, GrammarItemSet, Production, ProductionTail};
-use crate::state::ParserState;
+use crate::production::{GrammarItemKey, GrammarItemSet, Production, ProductionId, ProductionTail};
+use crate::state::{ConflictKind, ParserState, Resolution};
 use crate::symbol::non_terminal::NonTerminal;
+use crate::symbol::tag::TagOrToken;
 use crate::symbol::terminal::{Token, TokenSet};
-use crate::symbol::{Symbol, SymbolTable};
+use crate::symbol::{Associativity, Symbol, SymbolTable};
+use crate::tables::{self, CompressedTable};
 use lalr1_plus::Parser;
 use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
+use std::fs::File;
 use std::io;
-use std::io::{stderr, Write};
-use std::path::Path;
+use std::io::{stderr, Read, Write};
+use std::path::{Path, PathBuf};
 
 pub fn report_error(location: &lexan::Location, what: &str) {
     writeln!(stderr(), "{location}: Error: {what}.").expect("what?");
 }
 
+// The name of the entry point for parsing a `name` (e.g. "expr_list" for "ExprList")
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+// The injection label used (with empty text) by actions to request a lexical mode switch
+pub fn mode_switch_label(mode: &str) -> String {
+    format!("%mode {mode}")
+}
+
+// Rust's strict and reserved keywords (which can't name the generated code's module)
+const RUST_KEYWORDS: [&str; 51] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+pub fn is_rust_keyword(name: &str) -> bool {
+    RUST_KEYWORDS.contains(&name)
+}
+
+const CHECKSUM_PREFIX: &str = "// specification checksum: ";
+
+// FNV-1a: unlike DefaultHasher its values are the same for every build of alap_gen
+pub fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The specification checksum recorded in generated parser code (if any).
+pub fn recorded_checksum(code: &str) -> Option<u64> {
+    code.lines()
+        .take_while(|line| line.starts_with("//") || line.is_empty())
+        .find_map(|line| line.strip_prefix(CHECKSUM_PREFIX))
+        .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())
+}
+
+fn json_string(text: &str) -> String {
+    let mut string = "\"".to_string();
+    for c in text.chars() {
+        match c {
+            '"' => string += "\\\"",
+            '\\' => string += "\\\\",
+            c if c.is_control() => string += &format!("\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string + "\""
+}
+
 pub fn report_warning(location: &lexan::Location, what: &str) {
     writeln!(stderr(), "{location}: Warning: {what}.").expect("what?");
 }
 
+pub fn report_note(location: &lexan::Location, what: &str) {
+    writeln!(stderr(), "{location}: Note: {what}.").expect("what?");
+}
+
+/// Bounds on what "%inject" may splice into a specification
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InjectLimits {
+    /// The greatest nesting depth of injections (i.e. of injected files injecting others)
+    pub max_depth: Option<usize>,
+    /// The greatest number of bytes injected in total
+    pub max_bytes: Option<usize>,
+}
+
+// The traits always derived for AATerminal and AANonTerminal
+const DERIVES: [&str; 7] = [
+    "Debug",
+    "Clone",
+    "Copy",
+    "PartialOrd",
+    "Ord",
+    "PartialEq",
+    "Eq",
+];
+
 #[derive(Debug, Default)]
 pub struct Specification {
     pub symbol_table: SymbolTable,
@@ -31,22 +123,111 @@ pub struct Specification {
     preamble: String,
     pub attribute_type: String,
     pub target_type: String,
+    pub target_lifetime: Option<String>,
+    pub target_type_parameter: Option<String>,
     pub error_count: u32,
     pub warning_count: u32,
     pub expected_rr_conflicts: u32,
     pub expected_sr_conflicts: u32,
+    // the total number of unresolved conflicts given by "%expect" (instead of the above)
+    pub expected_conflicts: Option<(u32, lexan::Location)>,
+    pub default_sr_shift: bool,
+    // whether "%repeat right" asked for repetitions to be right (rather than left) recursive
+    pub right_recursive_repetition: bool,
+    // the extra traits (given by "%derive") to derive for AATerminal and AANonTerminal
+    pub derives: Vec<String>,
+    // whether "%functional" asked for free parse functions (that hide the target)
+    pub functional: bool,
+    // the module (given by "%module") to wrap the generated code in
+    pub module: Option<String>,
+    pub interner_field: Option<String>,
+    pub coverage_field: Option<String>,
+    pub derivation_field: Option<String>,
+    // whether "%visitor" asked for a trait to fold recorded derivations
+    pub visitor: bool,
+    pub observer_field: Option<String>,
+    // the non terminal named by "%start" (instead of the first production's)
+    pub start_symbol: Option<(String, lexan::Location)>,
+    // any further "%start" non terminals (each gets its own start state and entry point)
+    pub extra_start_symbols: Vec<(String, lexan::Location)>,
+    pub spanned_attributes: bool,
+    pub precedence_for: BTreeMap<String, (Associativity, u16, lexan::Location)>,
+    // the types given to non terminals' attributes by "%type"
+    pub attribute_types: BTreeMap<String, (String, lexan::Location)>,
+    // the non terminals (given by "%noskip") within which skip rules are suspended
+    pub no_skip_non_terminals: BTreeMap<String, lexan::Location>,
+    // the tokens (given by "%recover") at which error recovery may resume (if restricted)
+    pub recovery_tokens: BTreeMap<String, lexan::Location>,
+    pub tests: Vec<(String, bool, lexan::Location)>,
+    features: BTreeSet<String>,
+    inject_limits: InjectLimits,
+    inject_depths: BTreeMap<String, usize>,
+    injected_bytes: usize,
+    // the label of the text that injected each injected file (for finding cycles)
+    inject_parents: BTreeMap<String, String>,
+    // the (canonical) paths of the files injected so far
+    injected_paths: BTreeSet<PathBuf>,
+    // the productions of helper non terminals waiting for the current group to be added
+    pending_productions: Vec<(NonTerminal, ProductionTail)>,
+    // the number of (distinct) parenthesized groups so far
+    groups: usize,
+    // the prefix given (e.g. by "--prefix") in place of any "%prefix"
+    prefix_override: Option<String>,
 }
 
 impl lalr1_plus::ReportError<AATerminal> for Specification {}
 
 impl Specification {
-    pub fn new(text: &str, label: &str) -> Result<Self, lalr1_plus::Error<AATerminal>> {
+    #[cfg(test)]
+    pub fn new(
+        text: &str,
+        label: &str,
+        features: &[String],
+    ) -> Result<Self, lalr1_plus::Error<AATerminal>> {
+        Self::with_inject_limits(text, label, features, InjectLimits::default())
+    }
+
+    pub fn with_inject_limits(
+        text: &str,
+        label: &str,
+        features: &[String],
+        inject_limits: InjectLimits,
+    ) -> Result<Self, lalr1_plus::Error<AATerminal>> {
+        Self::with_prefix(text, label, features, inject_limits, None)
+    }
+
+    /// As `with_inject_limits()` but with `prefix` (if any) used for the generated names
+    /// in place of any "%prefix" (whose use is reported as a warning).
+    pub fn with_prefix(
+        text: &str,
+        label: &str,
+        features: &[String],
+        inject_limits: InjectLimits,
+        prefix: Option<&str>,
+    ) -> Result<Self, lalr1_plus::Error<AATerminal>> {
         let mut spec = Specification {
             attribute_type: "AttributeData".to_string(),
             target_type: "Specification".to_string(),
+            features: features.iter().cloned().collect(),
+            inject_limits,
             ..Specification::default()
         };
-        spec.parse_text(text, label)?;
+        if let Some(prefix) = prefix {
+            // so that the names are checked against it as they're parsed
+            spec.set_prefix(prefix, &lexan::Location::default());
+            spec.prefix_override = Some(prefix.to_string());
+        }
+        let text = spec.exclude_inactive_sections(text, label);
+        spec.parse_text(&text, label)?;
+        spec.add_extra_start_productions();
+        spec.check_token_conversions();
+        spec.check_mode_switches();
+        spec.check_keywords();
+        spec.check_overlapping_tokens();
+        spec.check_precedence_for();
+        spec.check_attribute_types();
+        spec.check_no_skip_non_terminals();
+        spec.check_action_spans();
         // Add dummy error production last so that it has lowest precedence during conflict resolution
         let symbol = spec.symbol_table.error_non_terminal.clone();
         let tail = ProductionTail::default();
@@ -59,6 +240,9 @@ impl Specification {
         spec.symbol_table
             .error_non_terminal()
             .set_firsts_data(&spec.productions);
+        for non_terminal in spec.symbol_table.extra_start_non_terminals() {
+            non_terminal.set_firsts_data(&spec.productions)
+        }
         for non_terminal in spec.symbol_table.non_terminals() {
             //if non_terminal.firsts_data_is_none() {
             non_terminal.set_firsts_data(&spec.productions)
@@ -67,625 +251,4700 @@ impl Specification {
         Ok(spec)
     }
 
-    pub fn is_allowable_name(name: &str) -> bool {
-        !(name.starts_with("aa") || name.starts_with("AA"))
+    // The literal's contents must be a comma separated list of (possibly qualified)
+    // trait names that aren't already derived.
+    pub fn set_derives(&mut self, literal: &str, location: &lexan::Location) {
+        let list = &literal[1..literal.len() - 1];
+        let path =
+            regex::Regex::new(r"\A[a-zA-Z_][a-zA-Z0-9_]*(::[a-zA-Z_][a-zA-Z0-9_]*)*\z").unwrap();
+        for name in list.split(',').map(|x| x.trim()) {
+            if !path.is_match(name) {
+                self.error(
+                    location,
+                    &format!("\"{name}\": expected a comma separated list of trait names"),
+                );
+            } else if DERIVES.contains(&name) || self.derives.iter().any(|x| x == name) {
+                self.error(location, &format!("{name}: already derived"));
+            } else {
+                self.derives.push(name.to_string());
+            }
+        }
     }
 
-    pub fn error(&mut self, location: &lexan::Location, what: &str) {
-        report_error(location, what);
-        self.error_count += 1;
+    // Any Rust type will do as long as its brackets balance
+    pub fn set_attribute_data_type(&mut self, text: &str, location: &lexan::Location) {
+        if Self::is_balanced(text) {
+            self.attribute_type = text.to_string();
+        } else {
+            self.error(location, &format!("\"{text}\": malformed type"));
+        }
     }
 
-    pub fn warning(&mut self, location: &lexan::Location, what: &str) {
-        report_warning(location, what);
-        self.warning_count += 1;
+    // The type is the rest of the line (less any comment) and only non terminals have them
+    pub fn set_attribute_type(&mut self, name: &str, text: &str, location: &lexan::Location) {
+        let text = text.split("//").next().unwrap_or_default().trim();
+        if text.is_empty() {
+            self.error(location, &format!("{name}: missing type"));
+        } else if !Self::is_balanced(text) {
+            self.error(location, &format!("\"{text}\": malformed type"));
+        } else if self.symbol_table.get_token(name).is_some() {
+            self.error(location, &format!("{name}: tokens can't be given types"));
+        } else if self.attribute_types.contains_key(name) {
+            self.error(location, &format!("{name}: type already declared"));
+        } else {
+            let entry = (text.to_string(), location.clone());
+            self.attribute_types.insert(name.to_string(), entry);
+        }
     }
 
-    pub fn set_preamble(&mut self, preamble: &str) {
-        self.preamble = preamble.to_string();
+    // The display name (if any) is a literal whose quotes aren't part of the name
+    pub fn set_display_name(&mut self, token: &Token, display_name: &AttributeData) {
+        if let AttributeData::Token(_) = display_name {
+            let (text, location) = display_name.text_and_location();
+            let name = text[1..text.len() - 1].replace("\\\"", "\"");
+            if name.trim().is_empty() {
+                self.error(location, &format!("{}: empty display name", token.name()));
+            } else {
+                token.set_display_name(&name);
+            }
+        }
     }
 
-    pub fn new_production(&mut self, left_hand_side: &NonTerminal, tail: &ProductionTail) {
-        if self.productions.is_empty() {
-            let location = left_hand_side
-                .first_definition()
-                .expect("should be defined");
-            left_hand_side.add_used_at(&location);
-            let start_symbol = self.symbol_table.start_non_terminal_used_at(&location);
-            let start_tail = ProductionTail::new(&[left_hand_side.into()], None, None, None);
-            let start_production = Production::new(start_symbol, start_tail);
-            self.productions.push(start_production);
+    // An unusable explicit level is reported and the next implicit level used instead
+    pub fn set_precedences(
+        &mut self,
+        associativity: Associativity,
+        level: Option<u32>,
+        tag_or_token_list: &[TagOrToken],
+        location: &lexan::Location,
+    ) {
+        let level = match level.map(u16::try_from) {
+            Some(Ok(0)) => {
+                self.error(location, "precedence levels start at 1");
+                None
+            }
+            Some(Ok(level)) => Some(level),
+            Some(Err(_)) => {
+                self.error(location, "precedence level too large");
+                None
+            }
+            None => None,
+        };
+        for err in
+            self.symbol_table
+                .set_precedences(associativity, level, tag_or_token_list, location)
+        {
+            self.error(location, &err.to_string());
         }
-        self.productions
-            .push(Production::new(left_hand_side.clone(), tail.clone()));
     }
 
-    fn closure(&self, mut closure_set: GrammarItemSet) -> GrammarItemSet {
-        let mut additions_made = true;
-        while additions_made {
-            additions_made = false;
-            // Closables extraction as a new separate map necessary to avoid borrow conflict
-            for (item_key, look_ahead_set) in closure_set.closable_set() {
-                if let Some(symbol) = item_key.next_symbol() {
-                    match symbol {
-                        Symbol::Terminal(_) => debug_assert!(!item_key.is_closable()),
-                        Symbol::NonTerminal(prospective_lhs) => {
-                            debug_assert!(item_key.is_closable());
-                            for look_ahead_symbol in look_ahead_set.iter() {
-                                let firsts = TokenSet::first_all_caps(
-                                    item_key.rhs_tail(),
-                                    look_ahead_symbol,
-                                );
-                                for production in self
-                                    .productions
-                                    .iter()
-                                    .filter(|x| x.left_hand_side() == prospective_lhs)
-                                {
-                                    let prospective_key = GrammarItemKey::from(production);
-                                    if let Some(set) = closure_set.get_mut(&prospective_key) {
-                                        let len = set.len();
-                                        *set |= &firsts;
-                                        additions_made = additions_made || set.len() > len;
-                                    } else {
-                                        closure_set.insert(prospective_key, firsts.clone());
-                                        additions_made = true;
-                                    }
-                                }
-                            }
-                        }
+    // Only non terminals can be whitespace sensitive
+    pub fn add_no_skip_non_terminal(&mut self, name: &str, location: &lexan::Location) {
+        if self.symbol_table.get_token(name).is_some() {
+            self.error(location, &format!("{name}: tokens can't be marked %noskip"));
+        } else if self.no_skip_non_terminals.contains_key(name) {
+            self.warning(location, &format!("{name}: already marked %noskip"));
+        } else {
+            self.no_skip_non_terminals
+                .insert(name.to_string(), location.clone());
+        }
+    }
+
+    // Only tokens (not tags) can be synchronisation points
+    pub fn add_recovery_tokens(
+        &mut self,
+        tag_or_token_list: &[TagOrToken],
+        location: &lexan::Location,
+    ) {
+        for tag_or_token in tag_or_token_list.iter() {
+            match tag_or_token {
+                TagOrToken::Token(token) => {
+                    if self
+                        .recovery_tokens
+                        .insert(token.name().to_string(), location.clone())
+                        .is_some()
+                    {
+                        let name = token.name();
+                        self.warning(location, &format!("{name}: already a %recover token"));
                     }
-                } else {
-                    debug_assert!(!item_key.is_closable());
                 }
+                TagOrToken::Tag(tag) => {
+                    self.error(location, &format!("{}: not a token", tag.name()));
+                }
+                TagOrToken::Invalid => (),
             }
         }
-        closure_set
     }
 
-    pub fn write_preamble_text<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(self.preamble.as_bytes())?;
-        Ok(())
+    fn is_balanced(text: &str) -> bool {
+        let mut open = vec![];
+        let mut previous = ' ';
+        for c in text.chars() {
+            match c {
+                '<' | '(' | '[' => open.push(c),
+                // the arrow in "Fn() -> T" isn't a bracket
+                '>' if previous == '-' => (),
+                '>' if open.pop() != Some('<') => return false,
+                ')' if open.pop() != Some('(') => return false,
+                ']' if open.pop() != Some('[') => return false,
+                _ => (),
+            }
+            previous = c;
+        }
+        open.is_empty()
     }
 
-    pub fn write_production_data_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"    fn production_data(production_id: u32) -> (AANonTerminal, usize) {\n")?;
-        wtr.write_all(b"        match production_id {\n")?;
+    // Give the non terminals their types and make sure that every production of one
+    // with a type sets $$ (unless it can start out as $1)
+    fn check_attribute_types(&mut self) {
+        let mut problems = vec![];
+        for (name, (attribute_type, location)) in self.attribute_types.iter() {
+            match self
+                .symbol_table
+                .non_terminals()
+                .find(|n| n.name() == name.as_str() && n.first_definition().is_some())
+            {
+                Some(non_terminal) => non_terminal.set_attribute_type(attribute_type),
+                None => problems.push((location.clone(), format!("{name}: unknown non terminal"))),
+            }
+        }
         for production in self.productions.iter() {
-            wtr.write_fmt(format_args!(
-                "            {} => (AANonTerminal::{}, {}),\n",
-                production.ident(),
-                production.left_hand_side().name(),
-                production.len(),
-            ))?;
+            let lhs = production.left_hand_side();
+            if let Some(attribute_type) = lhs.attribute_type() {
+                let sets_lhs = production
+                    .action()
+                    .is_some_and(|action| action.contains("$$"));
+                if !sets_lhs
+                    && !production.starts_with_lhs_type()
+                    && production.rejection().is_none()
+                {
+                    let location = lhs.first_definition().expect("defined");
+                    let msg = format!(
+                        "\"{production}\": no action setting $$ (of type {attribute_type})"
+                    );
+                    problems.push((location, msg));
+                }
+            }
+        }
+        for (location, msg) in problems.iter() {
+            self.error(location, msg);
         }
-        wtr.write_all(b"            _ => panic!(\"malformed production data table\"),\n")?;
-        wtr.write_all(b"        }\n")?;
-        wtr.write_all(b"    }\n\n")?;
-        Ok(())
     }
 
-    pub fn write_semantic_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"    fn do_semantic_action<F: FnMut(String, String)>(\n")?;
-        wtr.write_all(b"        &mut self,\n")?;
-        wtr.write_all(b"        aa_production_id: u32,\n")?;
-        wtr.write_fmt(format_args!(
-            "        aa_rhs: Vec<{}>,\n",
-            self.attribute_type
-        ))?;
-        wtr.write_all(b"        mut aa_inject: F,\n")?;
-        wtr.write_fmt(format_args!("    ) -> {} {{\n", self.attribute_type))?;
-        wtr.write_all(b"        let mut aa_lhs = if let Some(a) = aa_rhs.first() {\n")?;
-        wtr.write_all(b"            a.clone()\n")?;
-        wtr.write_all(b"        } else {\n")?;
-        wtr.write_fmt(format_args!(
-            "           {}::default()\n",
-            self.attribute_type
-        ))?;
-        wtr.write_all(b"        };\n")?;
-        wtr.write_all(b"        match aa_production_id {\n")?;
+    fn check_no_skip_non_terminals(&mut self) {
+        let unknown: Vec<(String, lexan::Location)> = self
+            .no_skip_non_terminals
+            .iter()
+            .filter(|(name, _)| {
+                !self
+                    .symbol_table
+                    .non_terminals()
+                    .any(|n| n.name() == name.as_str() && n.first_definition().is_some())
+            })
+            .map(|(name, location)| (name.clone(), location.clone()))
+            .collect();
+        for (name, location) in unknown.iter() {
+            self.error(location, &format!("{name}: unknown non terminal"));
+        }
+    }
+
+    // Only spanned attributes have spans for $@ to cover
+    fn check_action_spans(&mut self) {
+        if self.spanned_attributes {
+            return;
+        }
+        let mut problems = vec![];
         for production in self.productions.iter() {
-            if let Some(action_code) = production.expanded_action() {
-                wtr.write_fmt(format_args!("            {} => {{\n", production.ident()))?;
-                wtr.write_fmt(format_args!("                // {production}\n"))?;
-                wtr.write_fmt(format_args!("                {action_code}\n"))?;
-                wtr.write_all(b"            }\n")?;
+            if production
+                .action()
+                .is_some_and(|action| action.contains("$@"))
+            {
+                let location = production
+                    .left_hand_side()
+                    .first_definition()
+                    .expect("defined");
+                problems.push((
+                    location,
+                    format!("\"{production}\": $@ needs %spanned attributes"),
+                ));
             }
         }
-        wtr.write_all(b"            _ => aa_inject(String::new(), String::new()),\n")?;
-        wtr.write_all(b"        };\n")?;
-        wtr.write_all(b"        aa_lhs\n")?;
-        wtr.write_all(b"    }\n\n")?;
-        Ok(())
+        for (location, msg) in problems.iter() {
+            self.error(location, msg);
+        }
     }
-}
-
-pub struct Grammar {
-    specification: Specification,
-    parser_states: Vec<ParserState>,
-}
 
-#[derive(Debug)]
-pub enum Error {
-    TooManyErrors(u32),
-    UndefinedSymbols(u32),
-    UnexpectedSRConflicts(u32, u32, String),
-    UnexpectedRRConflicts(u32, u32, String),
-}
+    pub fn prefix(&self) -> &str {
+        self.symbol_table.prefix()
+    }
 
-impl TryFrom<(Specification, bool, bool)> for Grammar {
-    type Error = Error;
+    // That of the generated code's variables (e.g. "aa_" for "aa_rhs")
+    pub fn variable_prefix(&self) -> String {
+        format!("{}_", self.prefix().to_lowercase())
+    }
 
-    fn try_from(arg: (Specification, bool, bool)) -> Result<Self, Error> {
-        let specification = arg.0;
-        let ignore_sr_conflicts = arg.1;
-        let ignore_rr_conflicts = arg.2;
-        for token in specification.symbol_table.unused_tokens() {
-            report_warning(
-                token.defined_at(),
-                &format!("Token \"{}\" is not used", token.name()),
-            )
+    /// "%prefix" renames the special symbols so it's only accepted before any symbols.
+    pub fn set_prefix(&mut self, prefix: &str, location: &lexan::Location) {
+        if let Some(prefix_override) = &self.prefix_override {
+            let what = format!("%prefix {prefix} overridden by --prefix {prefix_override}");
+            self.warning(location, &what);
+            return;
         }
-
-        for tag in specification.symbol_table.unused_tags() {
-            report_warning(
-                tag.defined_at(),
-                &format!("Tag \"{}\" is not used", tag.name()),
-            )
+        let mut chars = prefix.chars();
+        if chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            self.symbol_table.set_prefix(prefix);
+        } else {
+            self.error(
+                location,
+                &format!("{prefix}: a prefix must be upper case letters or digits (starting with a letter)"),
+            );
         }
+    }
 
-        for non_terminal in specification.symbol_table.unused_non_terminals() {
-            report_warning(
-                &non_terminal
-                    .first_definition()
-                    .expect("can't be both unused and undefined"),
-                &format!("Non terminal \"{}\" is not used", non_terminal.name()),
-            )
+    pub fn is_allowable_name(&self, name: &str) -> bool {
+        let prefix = self.prefix();
+        !(name.starts_with(prefix) || name.starts_with(&prefix.to_lowercase()))
+    }
+
+    // The lexical analyzer anchors regular expressions at the current position so one
+    // that matches the empty string would produce zero width tokens (or skips).
+    pub fn matches_empty_string(regex_text: &str) -> bool {
+        match regex::Regex::new(&format!("\\A(?:{regex_text})")) {
+            Ok(regex) => regex.is_match(""),
+            Err(_) => false,
         }
+    }
 
-        let mut undefined_symbols = 0;
-        for non_terminal in specification.symbol_table.undefined_non_terminals() {
-            for location in non_terminal.used_at() {
-                report_error(
-                    &location,
-                    &format!("Non terminal \"{}\" is not defined", non_terminal.name()),
+    pub fn error(&mut self, location: &lexan::Location, what: &str) {
+        report_error(location, what);
+        self.error_count += 1;
+    }
+
+    pub fn warning(&mut self, location: &lexan::Location, what: &str) {
+        report_warning(location, what);
+        self.warning_count += 1;
+    }
+
+    // The text of the file to be injected at `location` (or None if there's an error or
+    // it has already been injected). The files' labels record how deeply they are nested
+    // and what injected them.
+    pub fn read_injection(
+        &mut self,
+        file_path: &str,
+        location: &lexan::Location,
+    ) -> Option<String> {
+        let depth = self
+            .inject_depths
+            .get(location.label())
+            .copied()
+            .unwrap_or(0)
+            + 1;
+        if let Some(max_depth) = self.inject_limits.max_depth {
+            if depth > max_depth {
+                self.error(
+                    location,
+                    &format!("Injecting \"{file_path}\": more than {max_depth} nested injections"),
                 );
+                return None;
             }
-            undefined_symbols += 1;
         }
-
-        if undefined_symbols > 0 {
-            Err(Error::UndefinedSymbols(undefined_symbols))
-        } else if specification.error_count > 0 {
-            Err(Error::TooManyErrors(specification.error_count))
+        let mut file = match File::open(file_path) {
+            Ok(file) => file,
+            Err(err) => {
+                self.error(location, &format!("Injecting: {err}"));
+                return None;
+            }
+        };
+        let path = std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.into());
+        let mut chain = vec![location.label().to_string()];
+        while let Some(parent) = self.inject_parents.get(chain.last().expect("not empty")) {
+            chain.push(parent.to_string());
+        }
+        if chain
+            .iter()
+            .any(|label| std::fs::canonicalize(label).is_ok_and(|other| other == path))
+        {
+            chain.reverse();
+            chain.push(file_path.to_string());
+            let chain = chain.join(" -> ");
+            self.error(
+                location,
+                &format!("Injecting \"{file_path}\": cyclic injection ({chain})"),
+            );
+            return None;
+        } else if self.injected_paths.contains(&path) {
+            self.warning(
+                location,
+                &format!("Injecting \"{file_path}\": already injected so skipped"),
+            );
+            return None;
+        }
+        // Read no more than needed to find that the limit is exceeded
+        let remaining = self
+            .inject_limits
+            .max_bytes
+            .map(|max_bytes| max_bytes.saturating_sub(self.injected_bytes));
+        let mut text = String::new();
+        let result = match remaining {
+            Some(remaining) => file.take(remaining as u64 + 1).read_to_string(&mut text),
+            None => file.read_to_string(&mut text),
+        };
+        if let Err(err) = result {
+            self.error(location, &format!("Injecting: {err}"));
+        } else if remaining.is_some_and(|remaining| text.len() > remaining) {
+            let max_bytes = self.inject_limits.max_bytes.unwrap_or_default();
+            self.error(
+                location,
+                &format!("Injecting \"{file_path}\": more than {max_bytes} bytes injected"),
+            );
+        } else if text.is_empty() {
+            self.error(location, &format!("Injected file \"{file_path}\" is empty"));
         } else {
-            let start_item_key = GrammarItemKey::from(&specification.productions[0]);
-            let mut start_look_ahead_set = TokenSet::new();
-            start_look_ahead_set.insert(&Token::EndToken);
-            let mut map = BTreeMap::<GrammarItemKey, TokenSet>::new();
-            map.insert(start_item_key, start_look_ahead_set);
-            let start_kernel = specification.closure(GrammarItemSet::from(map));
-            let mut grammar = Self {
-                specification,
-                parser_states: vec![],
+            self.injected_bytes += text.len();
+            self.inject_depths.insert(file_path.to_string(), depth);
+            let parent = location.label().to_string();
+            self.inject_parents.insert(file_path.to_string(), parent);
+            self.injected_paths.insert(path);
+            return Some(self.exclude_inactive_sections(&text, file_path));
+        }
+        None
+    }
+
+    // Blank out the lines of "%if FEATURE" ... "%endif" sections whose feature is not
+    // active (and the directives themselves) leaving line numbers unchanged.
+    fn exclude_inactive_sections(&mut self, text: &str, label: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut sections: Vec<(bool, lexan::Location)> = vec![];
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            let trimmed = line.trim();
+            let location = || {
+                let offset = line.len() - line.trim_start().len() + 1;
+                lexan::Location::at(label, index + 1, offset)
             };
-            grammar.new_parser_state(start_kernel);
-            while let Some(unprocessed_state) = grammar.first_unprocessed_state() {
-                let first_time = !unprocessed_state.needs_reprocessing();
-                unprocessed_state.mark_as_processed();
-                let mut already_done = BTreeSet::<Symbol>::new();
-                for item_key in unprocessed_state.non_kernel_key_set().iter() {
-                    let symbol_x = item_key.next_symbol().expect("not reducible");
-                    if !already_done.insert(symbol_x.clone()) {
-                        continue;
-                    };
-                    let kernel_x = unprocessed_state.generate_goto_kernel(symbol_x);
-                    let item_set_x = grammar.specification.closure(kernel_x);
-                    let goto_state =
-                        if let Some(equivalent_state) = grammar.equivalent_state(&item_set_x) {
-                            equivalent_state.merge_lookahead_sets(&item_set_x);
-                            equivalent_state.clone()
-                        } else {
-                            grammar.new_parser_state(item_set_x)
-                        };
-                    if first_time {
-                        match symbol_x {
-                            Symbol::Terminal(token) => {
-                                unprocessed_state.add_shift_action(token.clone(), goto_state)
-                            }
-                            Symbol::NonTerminal(non_terminal) => {
-                                if non_terminal.is_error() {
-                                    unprocessed_state.set_error_recovery_state(&goto_state);
-                                }
-                                unprocessed_state.add_goto(non_terminal.clone(), goto_state);
-                            }
-                        }
-                    }
+            let feature = trimmed
+                .strip_prefix("%if")
+                .filter(|rest| rest.starts_with(char::is_whitespace));
+            let include = if let Some(feature) = feature {
+                let active = self.features.contains(feature.trim());
+                sections.push((active, location()));
+                false
+            } else if trimmed == "%endif" {
+                if sections.pop().is_none() {
+                    self.error(&location(), "\"%endif\" without matching \"%if\"");
                 }
-            }
-            let (sr_conflicts, rr_conflicts) = grammar.resolve_conflicts();
-            if !ignore_sr_conflicts && sr_conflicts != grammar.specification.expected_sr_conflicts {
-                Err(Error::UnexpectedSRConflicts(
-                    sr_conflicts,
-                    grammar.specification.expected_sr_conflicts,
-                    grammar.describe_sr_conflict_states(),
-                ))
-            } else if !ignore_rr_conflicts
-                && rr_conflicts != grammar.specification.expected_rr_conflicts
-            {
-                Err(Error::UnexpectedRRConflicts(
-                    rr_conflicts,
-                    grammar.specification.expected_rr_conflicts,
-                    grammar.describe_rr_conflict_states(),
-                ))
+                false
+            } else {
+                sections.iter().all(|(active, _)| *active)
+            };
+            if include {
+                result += line;
             } else {
-                Ok(grammar)
+                result += &line[line.trim_end_matches(['\r', '\n']).len()..];
             }
         }
+        for (_, location) in sections {
+            self.error(&location, "\"%if\" without matching \"%endif\"");
+        }
+        result
     }
-}
 
-impl Grammar {
-    fn resolve_conflicts(&mut self) -> (u32, u32) {
-        let mut sr_conflicts = 0_u32;
-        let mut rr_conflicts = 0_u32;
-        for parser_state in self.parser_states.iter_mut() {
-            sr_conflicts += parser_state.resolve_shift_reduce_conflicts() as u32;
-            rr_conflicts += parser_state.resolve_reduce_reduce_conflicts() as u32;
+    fn check_precedence_for(&mut self) {
+        let unknown: Vec<(String, lexan::Location)> = self
+            .precedence_for
+            .iter()
+            .filter(|(name, _)| {
+                !self
+                    .symbol_table
+                    .non_terminals()
+                    .any(|n| n.name() == name.as_str() && n.first_definition().is_some())
+            })
+            .map(|(name, (_, _, location))| (name.clone(), location.clone()))
+            .collect();
+        for (name, location) in unknown.iter() {
+            self.error(location, &format!("{name}: unknown non terminal"));
         }
-        (sr_conflicts, rr_conflicts)
     }
 
-    fn first_unprocessed_state(&self) -> Option<ParserState> {
-        Some(
-            self.parser_states
-                .iter()
-                .find(|x| !x.is_processed())?
-                .clone(),
-        )
+    // Once any token has conversion code they all must so that the generated
+    // conversion match has an explicit arm for every terminal.
+    fn check_token_conversions(&mut self) {
+        if self.symbol_table.tokens().all(|t| t.conversion().is_none()) {
+            return;
+        }
+        let unconverted: Vec<Token> = self
+            .symbol_table
+            .tokens()
+            .filter(|t| t.conversion().is_none())
+            .cloned()
+            .collect();
+        for token in unconverted.iter() {
+            let msg = format!(
+                "token \"{}\" has no attribute conversion code",
+                token.name()
+            );
+            self.error(token.defined_at(), &msg);
+        }
     }
 
-    fn new_parser_state(&mut self, grammar_items: GrammarItemSet) -> ParserState {
-        let ident = self.parser_states.len() as u32;
-        let parser_state = ParserState::new(ident, grammar_items);
-        self.parser_states.push(parser_state.clone());
-        parser_state
+    // Mode switches may refer to modes defined after the token
+    fn check_mode_switches(&mut self) {
+        let mut unknown = vec![];
+        for token in self.symbol_table.tokens() {
+            if let Some((mode, location)) = token.mode_switch() {
+                if self.symbol_table.lexical_mode_index(&mode).is_none() {
+                    unknown.push((mode, location));
+                }
+            }
+        }
+        for (mode, location) in unknown.iter() {
+            self.error(location, &format!("{mode}: unknown lexical mode"));
+        }
     }
 
-    fn equivalent_state(&self, item_set: &GrammarItemSet) -> Option<&ParserState> {
-        let target_key_set = item_set.kernel_key_set();
-        if !target_key_set.is_empty() {
-            for parser_state in self.parser_states.iter() {
-                if target_key_set == parser_state.kernel_key_set() {
-                    return Some(parser_state);
+    // Longest match makes a keyword a whole word only if some (identifier) regular
+    // expression in its mode would match it (and hence any longer word) too.
+    fn check_keywords(&mut self) {
+        let mut unmatched = vec![];
+        for keyword in self.symbol_table.keywords() {
+            let word = &keyword.text()[1..keyword.text().len() - 1];
+            if !self.symbol_table.regex_tokens().any(|token| {
+                token.mode() == keyword.mode()
+                    && match regex::Regex::new(&format!("\\A(?:{})\\z", token.text())) {
+                        Ok(regex) => regex.is_match(word),
+                        Err(_) => false,
+                    }
+            }) {
+                unmatched.push((word.to_string(), keyword.defined_at().clone()));
+            }
+        }
+        for (word, location) in unmatched.iter() {
+            self.warning(
+                location,
+                &format!("{word}: keyword not matched by any regular expression token so it may be matched at the start of longer words"),
+            );
+        }
+    }
+
+    // A best effort (the continuations tried are single printable characters) search
+    // for literals that would lex as (or as the start of) a regular expression token.
+    fn check_overlapping_tokens(&mut self) {
+        let regexes: Vec<(&Token, regex::Regex, regex::Regex)> = self
+            .symbol_table
+            .regex_tokens()
+            .filter_map(|token| {
+                let whole = regex::Regex::new(&format!("\\A(?:{})\\z", token.text())).ok()?;
+                let start = regex::Regex::new(&format!("\\A(?:{})", token.text())).ok()?;
+                Some((token, whole, start))
+            })
+            .collect();
+        let mut overlaps = vec![];
+        for literal in self.symbol_table.literal_tokens() {
+            let text = literal.text();
+            let lexeme = text[1..text.len() - 1]
+                .replace("\\\"", "\"")
+                .replace("\\\\", "\\");
+            let is_keyword = self
+                .symbol_table
+                .keywords()
+                .any(|keyword| keyword == literal);
+            for (token, whole, start) in regexes.iter() {
+                if token.mode() != literal.mode() {
+                    continue;
+                }
+                if whole.is_match(&lexeme) {
+                    if !is_keyword {
+                        overlaps.push((
+                            literal.defined_at().clone(),
+                            format!(
+                                "{text}: also matched by {}'s regular expression (use %keywords if it's a keyword)",
+                                token.name()
+                            ),
+                        ));
+                    }
+                } else if let Some(longer) = (' '..='~')
+                    .map(|c| format!("{lexeme}{c}"))
+                    .find(|longer| start.find(longer).is_some_and(|m| m.end() > lexeme.len()))
+                {
+                    overlaps.push((
+                        literal.defined_at().clone(),
+                        format!(
+                            "{text}: may be lexed as the start of {} (e.g. \"{longer}\")",
+                            token.name()
+                        ),
+                    ));
                 }
             }
-        };
-        None
+        }
+        for (location, msg) in overlaps.iter() {
+            self.warning(location, msg);
+        }
     }
-}
 
-impl Grammar {
-    fn write_parser_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"// generated by alap_gen.\n\n")?;
+    pub fn new_keyword(&mut self, keyword: &str, location: &lexan::Location) {
+        if !self.is_allowable_name(&keyword.to_uppercase()) {
+            self.warning(
+                location,
+                &format!("keyword \"{keyword}\" may clash with generated code"),
+            );
+        }
+        if let Err(err) = self.symbol_table.new_keyword(keyword, location) {
+            self.error(location, &err.to_string());
+        }
+    }
 
-        self.specification.write_preamble_text(wtr)?;
-        self.write_symbol_enum_code(wtr)?;
-        self.write_parser_implementation_code(wtr)?;
-        Ok(())
+    pub fn set_preamble(&mut self, preamble: &str) {
+        self.preamble = preamble.to_string();
     }
 
-    pub fn write_parser_code_to_file(&self, file_path: &Path) -> io::Result<()> {
-        let mut file = std::fs::File::create(file_path)?;
-        self.write_parser_code(&mut file)?;
-        Ok(())
+    pub fn new_production(&mut self, left_hand_side: &NonTerminal, tail: &ProductionTail) {
+        let tail = match self.precedence_for.get(left_hand_side.name()) {
+            Some((associativity, precedence, _)) if !tail.has_explicit_precedence() => {
+                tail.with_associativity_and_precedence((*associativity, *precedence))
+            }
+            _ => tail.clone(),
+        };
+        for mode in tail.mode_switches() {
+            if self.symbol_table.lexical_mode_index(&mode).is_none() {
+                let location = left_hand_side
+                    .first_definition()
+                    .expect("should be defined");
+                self.error(&location, &format!("{mode}: unknown lexical mode"));
+            }
+        }
+        if self.productions.is_empty() {
+            let (start, location) = self.start_non_terminal(left_hand_side);
+            let start_symbol = self.symbol_table.start_non_terminal_used_at(&location);
+            let start_tail = ProductionTail::new(&[Symbol::from(&start)], None, None, None);
+            let start_production = Production::new(start_symbol, start_tail);
+            self.productions.push(start_production);
+        }
+        self.productions
+            .push(Production::new(left_hand_side.clone(), tail));
     }
 
-    fn write_symbol_enum_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        let special_tokens = [Token::EndToken];
-        let special_non_terminals = self.specification.symbol_table.used_non_terminal_specials();
+    pub fn set_module(&mut self, name: &str, location: &lexan::Location) {
+        if is_rust_keyword(name) {
+            self.error(location, &format!("{name}: a keyword can't name a module"));
+        } else {
+            self.module = Some(name.to_string());
+        }
+    }
 
-        wtr.write_all(b"use std::collections::BTreeSet;\n\n")?;
-        wtr.write_all(b"macro_rules! btree_set {\n")?;
-        wtr.write_all(b"    () => { BTreeSet::new() };\n")?;
-        wtr.write_all(b"    ( $( $x:expr ),* ) => {\n")?;
-        wtr.write_all(b"        {\n")?;
-        wtr.write_all(b"            let mut set = BTreeSet::new();\n")?;
-        wtr.write_all(b"            $( set.insert($x); )*\n")?;
-        wtr.write_all(b"            set\n")?;
-        wtr.write_all(b"        }\n")?;
-        wtr.write_all(b"    };\n")?;
-        wtr.write_all(b"    ( $( $x:expr ),+ , ) => {\n")?;
-        wtr.write_all(b"        btree_set![ $( $x ), * ]\n")?;
-        wtr.write_all(b"    };\n")?;
-        wtr.write_all(b"}\n\n")?;
-        wtr.write_all(b"#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]\n")?;
-        wtr.write_all(b"pub enum AATerminal {\n")?;
-        for token in special_tokens
+    pub fn add_start_symbol(&mut self, name: &str, location: &lexan::Location) {
+        let is_start = |(other, _): &(String, lexan::Location)| other == name;
+        if self.start_symbol.is_none() {
+            self.start_symbol = Some((name.to_string(), location.clone()));
+        } else if self
+            .start_symbol
             .iter()
-            .chain(self.specification.symbol_table.tokens())
+            .chain(self.extra_start_symbols.iter())
+            .any(is_start)
         {
-            wtr.write_fmt(format_args!("    {},\n", token.name()))?;
+            self.error(location, &format!("{name}: already a start symbol"));
+        } else {
+            self.extra_start_symbols
+                .push((name.to_string(), location.clone()));
         }
-        wtr.write_all(b"}\n\n")?;
-        wtr.write_all(b"impl std::fmt::Display for AATerminal {\n")?;
-        wtr.write_all(b"    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n")?;
-        wtr.write_all(b"        match self {\n")?;
-        for token in special_tokens
-            .iter()
-            .chain(self.specification.symbol_table.tokens())
-        {
-            wtr.write_all(b"            AATerminal::")?;
-            match token {
-                Token::Literal(token_data) => {
-                    wtr.write_fmt(format_args!(
-                        "{} => write!(f, r###\"{}\"###),\n",
-                        token_data.name, token_data.text
-                    ))?;
-                }
-                Token::Regex(token_data) => {
-                    wtr.write_fmt(format_args!(
-                        "{} => write!(f, r###\"{}\"###),\n",
-                        token_data.name, token_data.name
-                    ))?;
-                }
-                Token::EndToken => {
-                    wtr.write_fmt(format_args!(
-                        "{} => write!(f, r###\"{}\"###),\n",
-                        token.name(),
-                        token.name()
-                    ))?;
+    }
+
+    // Each start symbol after the first has its own start production (after those of
+    // the grammar so that the others keep their numbers)
+    fn add_extra_start_productions(&mut self) {
+        for (name, location) in self.extra_start_symbols.clone() {
+            match self.symbol_table.symbol_used_at(&name, &location) {
+                Symbol::NonTerminal(non_terminal) if !non_terminal.is_error() => {
+                    let start_symbol = self
+                        .symbol_table
+                        .new_extra_start_non_terminal(&name, &location);
+                    let start_tail =
+                        ProductionTail::new(&[Symbol::from(&non_terminal)], None, None, None);
+                    self.productions
+                        .push(Production::new(start_symbol, start_tail));
                 }
+                _ => self.error(
+                    &location,
+                    &format!("{name}: %start must name a non terminal"),
+                ),
             }
         }
-        wtr.write_all(b"        }\n")?;
-        wtr.write_all(b"    }\n")?;
-        wtr.write_all(b"}\n\n")?;
-        self.write_lexical_analyzer_code(wtr)?;
-        wtr.write_all(b"#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]\n")?;
-        wtr.write_all(b"pub enum AANonTerminal {\n")?;
-        for non_terminal in special_non_terminals
-            .iter()
-            .chain(self.specification.symbol_table.non_terminals())
-        {
-            wtr.write_fmt(format_args!("    {},\n", non_terminal.name()))?;
+    }
+
+    // The non terminal to be derived from the input (and where it was chosen): the one
+    // named by "%start" if any and, otherwise, that of the first production (`first`)
+    fn start_non_terminal(&mut self, first: &NonTerminal) -> (NonTerminal, lexan::Location) {
+        if let Some((name, location)) = self.start_symbol.clone() {
+            match self.symbol_table.symbol_used_at(&name, &location) {
+                Symbol::NonTerminal(non_terminal) if !non_terminal.is_error() => {
+                    return (non_terminal, location);
+                }
+                _ => self.error(
+                    &location,
+                    &format!("{name}: %start must name a non terminal"),
+                ),
+            }
         }
-        wtr.write_all(b"}\n\n")?;
-        wtr.write_all(b"impl std::fmt::Display for AANonTerminal {\n")?;
-        wtr.write_all(b"    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n")?;
-        wtr.write_all(b"        match self {\n")?;
-        for non_terminal in special_non_terminals
-            .iter()
-            .chain(self.specification.symbol_table.non_terminals())
-        {
-            wtr.write_all(b"            AANonTerminal::")?;
-            let name = non_terminal.name();
-            wtr.write_fmt(format_args!("{name} => write!(f, r\"{name}\"),\n"))?;
+        let location = first.first_definition().expect("should be defined");
+        first.add_used_at(&location);
+        (first.clone(), location)
+    }
+
+    /// Add the productions of any helper non terminals used by the group just added.
+    pub fn add_pending_productions(&mut self) {
+        for (left_hand_side, tail) in std::mem::take(&mut self.pending_productions) {
+            self.new_production(&left_hand_side, &tail);
         }
-        wtr.write_all(b"        }\n")?;
-        wtr.write_all(b"    }\n")?;
-        wtr.write_all(b"}\n\n")?;
-        Ok(())
     }
 
-    fn write_lexical_analyzer_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"lazy_static::lazy_static! {\n")?;
-        wtr.write_all(b"    static ref AALEXAN: lexan::LexicalAnalyzer<AATerminal> = {\n")?;
-        wtr.write_all(b"        use AATerminal::*;\n")?;
-        wtr.write_all(b"        lexan::LexicalAnalyzer::new(\n")?;
-        wtr.write_all(b"            &[\n")?;
-        for token in self.specification.symbol_table.literal_tokens() {
-            wtr.write_all(b"                ")?;
-            wtr.write_fmt(format_args!(
-                "({}, r###{}###),\n",
-                token.name(),
-                token.text()
-            ))?;
+    /// The (helper) non terminal for one or more `element`s separated by `separator`
+    /// (or, if `may_be_empty`, zero or more) whose attribute collects the elements'
+    /// attributes using `lalr1_plus::ListAttribute`.
+    pub fn separated_list(
+        &mut self,
+        element: &Symbol,
+        separator: &Symbol,
+        may_be_empty: bool,
+        location: &lexan::Location,
+    ) -> Symbol {
+        for symbol in [element, separator] {
+            if let Symbol::NonTerminal(non_terminal) = symbol {
+                if non_terminal.is_error() {
+                    self.error(location, "%error: can't be in a separated list");
+                }
+            }
         }
-        wtr.write_all(b"            ],\n")?;
-        wtr.write_all(b"            &[\n")?;
-        for token in self.specification.symbol_table.regex_tokens() {
-            wtr.write_all(b"                ")?;
-            wtr.write_fmt(format_args!(
-                "({}, r###\"{}\"###),\n",
-                token.name(),
-                token.text()
-            ))?;
+        let name = |symbol: &Symbol| match symbol {
+            Symbol::Terminal(token) => token.name().to_string(),
+            Symbol::NonTerminal(non_terminal) => non_terminal.name().to_string(),
+        };
+        let (prefix, aa) = (self.prefix().to_string(), self.variable_prefix());
+        let list_name = format!("{prefix}{}List{}", name(element), name(separator));
+        let list = self.symbol_table.symbol_used_at(&list_name, location);
+        if let Symbol::NonTerminal(non_terminal) = &list {
+            if non_terminal.is_undefined() {
+                non_terminal.add_defined_at(location);
+                let notation = format!("{} % {}", name(element), name(separator));
+                self.symbol_table.note_helper(&list_name, notation);
+                // helpers' actions refer to the elements' attributes directly (as "$3" would
+                // be converted should the elements have a "%type")
+                let first = format!("let {aa}element = core::mem::replace(&mut $$, lalr1_plus::ListAttribute::empty_list()); lalr1_plus::ListAttribute::push_element(&mut $$, {aa}element);");
+                let next = format!(
+                    "lalr1_plus::ListAttribute::push_element(&mut $$, core::mem::take(&mut {aa}rhs[2]));"
+                );
+                let tails = [
+                    ProductionTail::new(std::slice::from_ref(element), None, None, Some(&first)),
+                    ProductionTail::new(
+                        &[list.clone(), separator.clone(), element.clone()],
+                        None,
+                        None,
+                        Some(&next),
+                    ),
+                ];
+                for tail in tails {
+                    self.pending_productions.push((non_terminal.clone(), tail));
+                }
+            }
         }
-        wtr.write_all(b"            ],\n")?;
-        wtr.write_all(b"            &[\n")?;
-        for skip_rule in self.specification.symbol_table.skip_rules() {
-            wtr.write_all(b"                ")?;
-            wtr.write_fmt(format_args!("r###\"{skip_rule}\"###,\n"))?;
+        if !may_be_empty {
+            return list;
         }
-        wtr.write_all(b"            ],\n")?;
-        wtr.write_fmt(format_args!("            {},\n", Token::EndToken.name()))?;
-        wtr.write_all(b"        )\n")?;
-        wtr.write_all(b"    };\n")?;
-        wtr.write_all(b"}\n\n")?;
-        Ok(())
+        let optional_name = format!("{prefix}{}OptList{}", name(element), name(separator));
+        let optional = self.symbol_table.symbol_used_at(&optional_name, location);
+        if let Symbol::NonTerminal(non_terminal) = &optional {
+            if non_terminal.is_undefined() {
+                non_terminal.add_defined_at(location);
+                let notation = format!("{} %% {}", name(element), name(separator));
+                self.symbol_table.note_helper(&optional_name, notation);
+                let empty = "$$ = lalr1_plus::ListAttribute::empty_list();";
+                let tails = [
+                    ProductionTail::new(&[], None, None, Some(empty)),
+                    ProductionTail::new(&[list], None, None, None),
+                ];
+                for tail in tails {
+                    self.pending_productions.push((non_terminal.clone(), tail));
+                }
+            }
+        }
+        optional
     }
 
-    fn write_parser_implementation_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        let attr = &self.specification.attribute_type;
-        let parser = &self.specification.target_type;
-        let text =
-            format!("impl lalr1_plus::Parser<AATerminal, AANonTerminal, {attr}> for {parser} {{\n");
-        wtr.write_all(text.as_bytes())?;
-        wtr.write_all(
-            b"    fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<AATerminal> {\n",
-        )?;
-        wtr.write_all(b"        &AALEXAN\n")?;
-        wtr.write_all(b"    }\n\n")?;
-        self.write_error_recovery_code(wtr)?;
-        self.write_look_ahead_set_code(wtr)?;
-        self.write_next_action_code(wtr)?;
-        self.specification.write_production_data_code(wtr)?;
-        self.write_goto_table_code(wtr)?;
-        self.specification.write_semantic_action_code(wtr)?;
-        wtr.write_all(b"}\n")?;
-        Ok(())
+    /// The (helper) non terminal for zero or one `element` whose attribute wraps the
+    /// element's attribute (if any) using `lalr1_plus::OptionalAttribute`.
+    pub fn optional(&mut self, element: &Symbol, location: &lexan::Location) -> Symbol {
+        let element_name = match element {
+            Symbol::Terminal(token) => token.name().to_string(),
+            Symbol::NonTerminal(non_terminal) => {
+                if non_terminal.is_error() {
+                    self.error(location, "%error: can't be optional");
+                }
+                non_terminal.name().to_string()
+            }
+        };
+        let optional_name = format!("{}{element_name}Opt", self.prefix());
+        let optional = self.symbol_table.symbol_used_at(&optional_name, location);
+        if let Symbol::NonTerminal(non_terminal) = &optional {
+            if non_terminal.is_undefined() {
+                non_terminal.add_defined_at(location);
+                self.symbol_table
+                    .note_helper(&optional_name, format!("{element_name}?"));
+                let absent = "$$ = lalr1_plus::OptionalAttribute::absent();";
+                let present =
+                    "$$ = lalr1_plus::OptionalAttribute::present(core::mem::take(&mut $$));";
+                let tails = [
+                    ProductionTail::new(&[], None, None, Some(absent)),
+                    ProductionTail::new(std::slice::from_ref(element), None, None, Some(present)),
+                ];
+                for tail in tails {
+                    self.pending_productions.push((non_terminal.clone(), tail));
+                }
+            }
+        }
+        optional
     }
 
-    fn error_recovery_state_set_for_token(&self, token: &Token) -> BTreeSet<u32> {
-        self.parser_states
-            .iter()
-            .filter(|x| x.is_recovery_state_for_token(token))
-            .map(|x| x.ident())
-            .collect()
+    /// The (helper) non terminal for one (or, if `may_be_empty`, zero) or more `element`s
+    /// whose attribute collects the elements' attributes using `lalr1_plus::ListAttribute`
+    /// or, if repetitions are right recursive, `lalr1_plus::FrontListAttribute`.  The
+    /// helper's productions have no precedence so that the element's can't resolve (and
+    /// so hide) conflicts.
+    pub fn repetition(
+        &mut self,
+        element: &Symbol,
+        may_be_empty: bool,
+        location: &lexan::Location,
+    ) -> Symbol {
+        let element_name = match element {
+            Symbol::Terminal(token) => token.name().to_string(),
+            Symbol::NonTerminal(non_terminal) => {
+                if non_terminal.is_error() {
+                    self.error(location, "%error: can't be repeated");
+                }
+                non_terminal.name().to_string()
+            }
+        };
+        let (prefix, aa) = (self.prefix().to_string(), self.variable_prefix());
+        let (name, operator) = if may_be_empty {
+            (format!("{prefix}{element_name}Star"), "*")
+        } else {
+            (format!("{prefix}{element_name}Plus"), "+")
+        };
+        let repetition = self.symbol_table.symbol_used_at(&name, location);
+        if let Symbol::NonTerminal(non_terminal) = &repetition {
+            if non_terminal.is_undefined() {
+                non_terminal.add_defined_at(location);
+                self.symbol_table
+                    .note_helper(&name, format!("{element_name}{operator}"));
+                let neutral = Some((Associativity::default(), 0));
+                let first = if may_be_empty {
+                    ProductionTail::new(
+                        &[],
+                        None,
+                        neutral,
+                        Some("$$ = lalr1_plus::ListAttribute::empty_list();"),
+                    )
+                } else {
+                    let action = format!("let {aa}element = core::mem::replace(&mut $$, lalr1_plus::ListAttribute::empty_list()); lalr1_plus::ListAttribute::push_element(&mut $$, {aa}element);");
+                    ProductionTail::new(std::slice::from_ref(element), None, neutral, Some(&action))
+                };
+                let next = if self.right_recursive_repetition {
+                    let action = format!("let {aa}element = core::mem::replace(&mut $$, core::mem::take(&mut {aa}rhs[1])); lalr1_plus::FrontListAttribute::push_front_element(&mut $$, {aa}element);");
+                    ProductionTail::new(
+                        &[element.clone(), repetition.clone()],
+                        None,
+                        neutral,
+                        Some(&action),
+                    )
+                } else {
+                    let action = format!(
+                        "lalr1_plus::ListAttribute::push_element(&mut $$, core::mem::take(&mut {aa}rhs[1]));"
+                    );
+                    ProductionTail::new(
+                        &[repetition.clone(), element.clone()],
+                        None,
+                        neutral,
+                        Some(&action),
+                    )
+                };
+                for tail in [first, next] {
+                    self.pending_productions.push((non_terminal.clone(), tail));
+                }
+            }
+        }
+        repetition
     }
 
-    fn format_u32_set(set: &BTreeSet<u32>) -> String {
-        let mut string = "btree_set![".to_string();
-        for (index, number) in set.iter().enumerate() {
-            if index == 0 {
-                string += &format!("{number}");
-            } else {
-                string += &format!(", {number}");
+    /// The (helper) non terminal for the parenthesized group of `members` whose attribute
+    /// tuples up the members' attributes using `lalr1_plus::GroupAttribute`.
+    pub fn group(&mut self, members: &[Symbol], location: &lexan::Location) -> Symbol {
+        let mut names = vec![];
+        for member in members.iter() {
+            match member {
+                Symbol::Terminal(token) => names.push(token.name().to_string()),
+                Symbol::NonTerminal(non_terminal) => {
+                    if non_terminal.is_error() {
+                        self.error(location, "%error: can't be in a group");
+                    }
+                    names.push(non_terminal.name().to_string())
+                }
             }
         }
-        string += "]";
-        string
+        let notation = format!("({})", names.join(" "));
+        let name = match self.symbol_table.helper_for(&notation) {
+            Some(name) => name.to_string(),
+            None => format!("{}Group{}", self.prefix(), self.groups + 1),
+        };
+        let group = self.symbol_table.symbol_used_at(&name, location);
+        if let Symbol::NonTerminal(non_terminal) = &group {
+            if non_terminal.is_undefined() {
+                non_terminal.add_defined_at(location);
+                self.groups += 1;
+                self.symbol_table.note_helper(&name, notation);
+                let aa = self.variable_prefix();
+                let mut members_code = vec![format!("{aa}first")];
+                for n in 2..=members.len() {
+                    members_code.push(format!("core::mem::take(&mut {aa}rhs[{}])", n - 1));
+                }
+                let action = format!(
+                    "let {aa}first = core::mem::take(&mut $$); $$ = lalr1_plus::GroupAttribute::group(vec![{}]);",
+                    members_code.join(", ")
+                );
+                let tail = ProductionTail::new(members, None, None, Some(&action));
+                self.pending_productions.push((non_terminal.clone(), tail));
+            }
+        }
+        group
     }
 
-    fn write_error_recovery_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        let mut default_required = false;
-        let mut recovery_states = Vec::<(&str, BTreeSet<u32>)>::new();
-        for token in [Token::EndToken]
-            .iter()
-            .chain(self.specification.symbol_table.tokens())
-        {
-            let set = self.error_recovery_state_set_for_token(token);
-            if !set.is_empty() {
-                recovery_states.push((token.name(), set));
-            } else {
-                default_required = true;
+    // The (defined) non terminals that can't derive a string of terminals as every one of
+    // their productions includes a non terminal that can't
+    fn non_productive_non_terminals(&self) -> Vec<NonTerminal> {
+        let mut productive = BTreeSet::<String>::new();
+        loop {
+            let productive_count = productive.len();
+            for production in self.productions.iter() {
+                if production
+                    .right_hand_side_symbols()
+                    .all(|symbol| match symbol {
+                        Symbol::Terminal(_) => true,
+                        Symbol::NonTerminal(non_terminal) => {
+                            non_terminal.is_error() || productive.contains(non_terminal.name())
+                        }
+                    })
+                {
+                    productive.insert(production.left_hand_side().name().to_string());
+                }
+            }
+            if productive.len() == productive_count {
+                break;
             }
         }
-        if recovery_states.is_empty() {
-            wtr.write_all(
-                b"    fn viable_error_recovery_states(_token: &AATerminal) -> BTreeSet<u32> {\n",
-            )?;
-            wtr.write_all(b"        btree_set![]\n")?;
-            wtr.write_all(b"    }\n\n")?;
-        } else {
-            wtr.write_all(
-                b"    fn viable_error_recovery_states(token: &AATerminal) -> BTreeSet<u32> {\n",
-            )?;
-            wtr.write_all(b"        match token {\n")?;
-            for (name, set) in recovery_states {
-                wtr.write_fmt(format_args!(
-                    "            AATerminal::{} => {},\n",
-                    name,
-                    Self::format_u32_set(&set)
-                ))?;
+        self.symbol_table
+            .non_terminals()
+            .filter(|x| !x.is_error() && !x.is_undefined() && !productive.contains(x.name()))
+            .cloned()
+            .collect()
+    }
+
+    // The (used) non terminals that can't be reached from the start symbol as they're only
+    // used by the productions of others that can't
+    fn unreachable_non_terminals(&self) -> Vec<NonTerminal> {
+        let mut reachable = BTreeSet::<String>::new();
+        let mut unvisited: Vec<String> = self
+            .productions
+            .iter()
+            .filter(|production| production.is_start_production())
+            .map(|production| production.left_hand_side().name().to_string())
+            .collect();
+        while let Some(name) = unvisited.pop() {
+            if !reachable.insert(name.clone()) {
+                continue;
             }
-            if default_required {
-                wtr.write_all(b"            _ => btree_set![],\n")?;
+            for production in self.productions.iter() {
+                if production.left_hand_side().name() == name {
+                    for symbol in production.right_hand_side_symbols() {
+                        if let Symbol::NonTerminal(non_terminal) = symbol {
+                            unvisited.push(non_terminal.name().to_string());
+                        }
+                    }
+                }
             }
-            wtr.write_all(b"        }\n")?;
-            wtr.write_all(b"    }\n\n")?;
-            wtr.write_all(b"    fn error_goto_state(state: u32) -> u32 {\n")?;
-            wtr.write_all(b"        match state {\n")?;
-            for parser_state in self.parser_states.iter() {
-                if let Some(goto_state_id) = parser_state.error_goto_state_ident() {
-                    wtr.write_fmt(format_args!(
-                        "            {:1} => {:1},\n",
-                        parser_state.ident(),
-                        goto_state_id
-                    ))?;
+        }
+        self.symbol_table
+            .non_terminals()
+            .filter(|x| !x.is_unused() && !x.is_undefined() && !reachable.contains(x.name()))
+            .cloned()
+            .collect()
+    }
+
+    // The terminals that may follow each non terminal (keyed by name)
+    fn follow_sets(&self) -> BTreeMap<String, TokenSet> {
+        let mut follow_sets = BTreeMap::<String, TokenSet>::new();
+        for start_production in self.productions.iter().filter(|x| x.is_start_production()) {
+            let mut token_set = TokenSet::new();
+            token_set.insert(self.symbol_table.end_token());
+            follow_sets.insert(
+                start_production.left_hand_side().name().to_string(),
+                token_set,
+            );
+        }
+        let total = |sets: &BTreeMap<String, TokenSet>| sets.values().map(|x| x.len()).sum();
+        loop {
+            let previous_total: usize = total(&follow_sets);
+            for production in self.productions.iter() {
+                let lhs = production.left_hand_side().name().to_string();
+                let rhs: Vec<&Symbol> = production.right_hand_side_symbols().collect();
+                for (index, symbol) in rhs.iter().enumerate() {
+                    if let Symbol::NonTerminal(non_terminal) = symbol {
+                        let mut token_set = TokenSet::new();
+                        let mut transparent = true;
+                        for symbol in rhs[index + 1..].iter() {
+                            match symbol {
+                                Symbol::Terminal(token) => {
+                                    token_set.insert(token);
+                                    transparent = false;
+                                }
+                                Symbol::NonTerminal(non_terminal) => {
+                                    let firsts_data = non_terminal.firsts_data();
+                                    token_set |= &firsts_data.token_set;
+                                    transparent = firsts_data.transparent;
+                                }
+                            }
+                            if !transparent {
+                                break;
+                            }
+                        }
+                        if transparent {
+                            if let Some(lhs_follow_set) = follow_sets.get(&lhs) {
+                                token_set |= lhs_follow_set;
+                            }
+                        }
+                        *follow_sets
+                            .entry(non_terminal.name().to_string())
+                            .or_default() |= &token_set;
+                    }
                 }
             }
-            wtr.write_all(b"            _ => panic!(\"No error go to state for {state}\")\n")?;
-            wtr.write_all(b"        }\n")?;
-            wtr.write_all(b"    }\n\n")?;
+            if total(&follow_sets) == previous_total {
+                return follow_sets;
+            }
+        }
+    }
+
+    pub fn write_sets_description<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let follow_sets = self.follow_sets();
+        let names = |token_set: &TokenSet| {
+            let names: Vec<String> = token_set.iter().map(|x| x.to_string()).collect();
+            names.join(", ")
+        };
+        wtr.write_all(b"\nNon Terminal Sets:\n")?;
+        for non_terminal in self
+            .symbol_table
+            .used_non_terminal_specials()
+            .iter()
+            .chain(self.symbol_table.non_terminals())
+        {
+            let firsts_data = non_terminal.firsts_data();
+            let follow_set = follow_sets
+                .get(non_terminal.name())
+                .cloned()
+                .unwrap_or_default();
+            wtr.write_fmt(format_args!("  {}:\n", non_terminal.name()))?;
+            wtr.write_fmt(format_args!("    Nullable: {}\n", firsts_data.transparent))?;
+            wtr.write_fmt(format_args!(
+                "    First: {}\n",
+                names(&firsts_data.token_set)
+            ))?;
+            wtr.write_fmt(format_args!("    Follow: {}\n", names(&follow_set)))?;
         }
         Ok(())
     }
 
-    fn write_look_ahead_set_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"    fn look_ahead_set(state: u32) -> BTreeSet<AATerminal> {\n")?;
-        wtr.write_all(b"        use AATerminal::*;\n")?;
-        wtr.write_all(b"        return match state {\n")?;
-        for parser_state in self.parser_states.iter() {
+    fn closure(&self, mut closure_set: GrammarItemSet) -> GrammarItemSet {
+        let mut additions_made = true;
+        while additions_made {
+            additions_made = false;
+            // Closables extraction as a new separate map necessary to avoid borrow conflict
+            for (item_key, look_ahead_set) in closure_set.closable_set() {
+                if let Some(symbol) = item_key.next_symbol() {
+                    match symbol {
+                        Symbol::Terminal(_) => debug_assert!(!item_key.is_closable()),
+                        Symbol::NonTerminal(prospective_lhs) => {
+                            debug_assert!(item_key.is_closable());
+                            for look_ahead_symbol in look_ahead_set.iter() {
+                                let firsts = TokenSet::first_all_caps(
+                                    item_key.rhs_tail(),
+                                    look_ahead_symbol,
+                                );
+                                for production in self
+                                    .productions
+                                    .iter()
+                                    .filter(|x| x.left_hand_side() == prospective_lhs)
+                                {
+                                    let prospective_key = GrammarItemKey::from(production);
+                                    if let Some(set) = closure_set.get_mut(&prospective_key) {
+                                        let len = set.len();
+                                        *set |= &firsts;
+                                        additions_made = additions_made || set.len() > len;
+                                    } else {
+                                        closure_set.insert(prospective_key, firsts.clone());
+                                        additions_made = true;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    debug_assert!(!item_key.is_closable());
+                }
+            }
+        }
+        closure_set
+    }
+
+    pub fn write_preamble_text<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        wtr.write_all(self.preamble.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn write_production_data_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        wtr.write_fmt(format_args!(
+            "    fn production_data(production_id: u32) -> ({prefix}NonTerminal, usize) {{\n"
+        ))?;
+        wtr.write_all(b"        match production_id {\n")?;
+        for production in self.productions.iter() {
             wtr.write_fmt(format_args!(
-                "            {} => {},\n",
-                parser_state.ident(),
-                parser_state.look_ahead_set().formated_as_macro_call()
+                "            {} => ({prefix}NonTerminal::{}, {}),\n",
+                production.ident(),
+                production.left_hand_side().name(),
+                production.len(),
             ))?;
         }
-        wtr.write_all(b"            _ => panic!(\"illegal state: {state}\"),\n")?;
+        wtr.write_all(b"            _ => panic!(\"malformed production data table\"),\n")?;
         wtr.write_all(b"        }\n")?;
         wtr.write_all(b"    }\n\n")?;
         Ok(())
     }
 
-    fn write_next_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"    fn next_action(\n")?;
-        wtr.write_all(b"        &self,\n")?;
-        wtr.write_all(b"        aa_state: u32,\n")?;
+    pub fn write_production_table_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        wtr.write_all(
+            b"/// The left and right hand side symbols of each production (by production id).\n",
+        )?;
         wtr.write_fmt(format_args!(
-            "        aa_attributes: &lalr1_plus::ParseStack<AATerminal, AANonTerminal, {}>,\n",
-            self.specification.attribute_type
+            "pub const {prefix}PRODUCTIONS: &[(&str, &[&str])] = &[\n"
         ))?;
-        wtr.write_all(b"        aa_token: &lexan::Token<AATerminal>,\n")?;
-        wtr.write_all(b"    ) -> lalr1_plus::Action {\n")?;
-        wtr.write_all(b"        use lalr1_plus::Action;\n")?;
-        wtr.write_all(b"        use AATerminal::*;\n")?;
-        wtr.write_all(b"        let aa_tag = *aa_token.tag();\n")?;
-        wtr.write_all(b"        return match aa_state {\n")?;
-        for parser_state in self.parser_states.iter() {
-            parser_state.write_next_action_code(wtr, "            ")?;
+        for production in self.productions.iter() {
+            let rhs: Vec<String> = production
+                .right_hand_side_symbols()
+                .map(|symbol| format!("r###\"{symbol}\"###"))
+                .collect();
+            wtr.write_fmt(format_args!(
+                "    // {}\n    (r###\"{}\"###, &[{}]),\n",
+                production.ident(),
+                production.left_hand_side().name(),
+                rhs.join(", ")
+            ))?;
         }
-        wtr.write_all(b"            _ => panic!(\"illegal state: {aa_state}\"),\n")?;
-        wtr.write_all(b"        }\n")?;
-        wtr.write_all(b"    }\n\n")?;
+        wtr.write_all(b"];\n\n")?;
         Ok(())
     }
 
-    fn write_goto_table_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
-        wtr.write_all(b"    fn goto_state(lhs: &AANonTerminal, current_state: u32) -> u32 {\n")?;
-        wtr.write_all(b"        return match current_state {\n")?;
-        for parser_state in self.parser_states.iter() {
-            parser_state.write_goto_table_code(wtr, "            ")?;
+    /// A trait with a method per non terminal (defaulting to `visit_default()`) and a
+    /// `walk()` that calls them bottom up (in reduction order) over a derivation with the
+    /// results of visiting each production's non terminals.
+    pub fn write_visitor_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        let productions: Vec<&Production> = self
+            .productions
+            .iter()
+            .filter(|p| !p.left_hand_side().is_start() && !p.left_hand_side().is_error())
+            .collect();
+        let mut non_terminals: Vec<&NonTerminal> = vec![];
+        for production in productions.iter() {
+            if !non_terminals.contains(&production.left_hand_side()) {
+                non_terminals.push(production.left_hand_side());
+            }
         }
         wtr.write_all(
-            b"            _ => panic!(\"Malformed goto table: ({lhs}, {current_state})\"),\n",
+            b"/// Folds a derivation (see `%derivation`) bottom up, i.e. in the order that its\n",
         )?;
+        wtr.write_all(b"/// productions were reduced, with the results for each production's non terminals.\n")?;
+        wtr.write_fmt(format_args!("pub trait {prefix}Visitor {{\n"))?;
+        wtr.write_all(b"    type Output: Default;\n\n")?;
+        wtr.write_all(b"    fn visit_default(&mut self, _production: u32, _children: Vec<Self::Output>) -> Self::Output {\n")?;
+        wtr.write_all(b"        Self::Output::default()\n")?;
+        wtr.write_all(b"    }\n")?;
+        for non_terminal in non_terminals.iter() {
+            wtr.write_all(b"\n")?;
+            for production in productions
+                .iter()
+                .filter(|p| p.left_hand_side() == *non_terminal)
+            {
+                let rhs: Vec<String> = production
+                    .right_hand_side_symbols()
+                    .map(|symbol| symbol.to_string())
+                    .collect();
+                wtr.write_fmt(format_args!(
+                    "    /// {}: {}: {}\n",
+                    production.ident(),
+                    non_terminal.name(),
+                    rhs.join(" ")
+                ))?;
+            }
+            wtr.write_fmt(format_args!(
+                "    fn visit_{}(&mut self, production: u32, children: Vec<Self::Output>) -> Self::Output {{\n",
+                snake_case(non_terminal.name())
+            ))?;
+            wtr.write_all(b"        self.visit_default(production, children)\n")?;
+            wtr.write_all(b"    }\n")?;
+        }
+        wtr.write_all(b"\n    /// The result for the root of `derivation` (if any).\n")?;
+        wtr.write_all(b"    fn walk(&mut self, derivation: &[u32]) -> Option<Self::Output> {\n")?;
+        wtr.write_fmt(format_args!(
+            "        let mut {aa}stack: Vec<Self::Output> = vec![];\n"
+        ))?;
+        wtr.write_fmt(format_args!(
+            "        for {aa}production in derivation.iter().copied() {{\n"
+        ))?;
+        wtr.write_fmt(format_args!(
+            "            let {aa}output = match {aa}production {{\n"
+        ))?;
+        for non_terminal in non_terminals.iter() {
+            // productions with the same number of non terminals share an arm
+            let mut arms: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+            for production in productions
+                .iter()
+                .filter(|p| p.left_hand_side() == *non_terminal)
+            {
+                let children = production
+                    .right_hand_side_symbols()
+                    .filter(|symbol| match symbol {
+                        Symbol::NonTerminal(non_terminal) => !non_terminal.is_error(),
+                        Symbol::Terminal(_) => false,
+                    })
+                    .count();
+                let ids = arms.entry(children).or_default();
+                ids.push(production.ident().to_string());
+            }
+            for (children, ids) in arms.iter() {
+                wtr.write_fmt(format_args!("                {} => {{\n", ids.join(" | ")))?;
+                wtr.write_fmt(format_args!(
+                    "                    let {aa}children = {aa}stack.split_off({aa}stack.len().saturating_sub({children}));\n"
+                ))?;
+                wtr.write_fmt(format_args!(
+                    "                    self.visit_{}({aa}production, {aa}children)\n",
+                    snake_case(non_terminal.name())
+                ))?;
+                wtr.write_all(b"                }\n")?;
+            }
+        }
+        wtr.write_fmt(format_args!(
+            "                _ => panic!(\"{{{aa}production}}: unknown production\"),\n"
+        ))?;
+        wtr.write_all(b"            };\n")?;
+        wtr.write_fmt(format_args!("            {aa}stack.push({aa}output);\n"))?;
         wtr.write_all(b"        }\n")?;
+        wtr.write_fmt(format_args!("        {aa}stack.pop()\n"))?;
+        wtr.write_all(b"    }\n")?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    /// The type of the attributes on the parse stack
+    pub fn stack_attribute_type(&self) -> String {
+        if self.spanned_attributes {
+            format!("lalr1_plus::Spanned<{}>", self.attribute_type)
+        } else {
+            self.attribute_type.clone()
+        }
+    }
+
+    // Generic attribute types need a turbofish in expressions and those that aren't
+    // paths (e.g. references or tuples) need to be qualified
+    fn attribute_type_path(&self) -> String {
+        if self
+            .attribute_type
+            .starts_with(|c: char| c.is_alphabetic() || c == '_')
+        {
+            self.attribute_type.replacen('<', "::<", 1)
+        } else {
+            format!("<{}>", self.attribute_type)
+        }
+    }
+
+    // The tokens' conversion code goes in the attribute type's From<lexan::Token<_>>
+    // (which the default token_attribute() uses) except in "%intern" grammars where
+    // it goes in token_attribute() so that it may use the parser's interner.
+    fn converts_tokens_in_parser(&self) -> bool {
+        self.interner_field.is_some()
+            && self.symbol_table.tokens().any(|t| t.conversion().is_some())
+    }
+
+    pub fn write_token_conversion_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if self.symbol_table.tokens().all(|t| t.conversion().is_none())
+            || self.converts_tokens_in_parser()
+        {
+            return Ok(());
+        }
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        wtr.write_fmt(format_args!(
+            "impl From<lexan::Token<{prefix}Terminal>> for {} {{\n",
+            self.attribute_type
+        ))?;
+        wtr.write_all(b"    #[allow(unused_assignments, unused_mut)]\n")?;
+        wtr.write_fmt(format_args!(
+            "    fn from({aa}token: lexan::Token<{prefix}Terminal>) -> Self {{\n"
+        ))?;
+        self.write_token_conversion_match_code(wtr)?;
+        wtr.write_all(b"    }\n")?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    pub fn write_token_attribute_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if self.symbol_table.tokens().all(|t| t.transform().is_none())
+            && !self.converts_tokens_in_parser()
+        {
+            return Ok(());
+        }
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        wtr.write_all(b"    #[allow(unused_assignments, unused_mut)]\n")?;
+        wtr.write_fmt(format_args!(
+            "    fn token_attribute(&mut self, {aa}token: lexan::Token<{prefix}Terminal>) -> {} {{\n",
+            self.stack_attribute_type()
+        ))?;
+        if self.spanned_attributes {
+            // the span is taken before any "%transform" changes the lexeme
+            wtr.write_fmt(format_args!("        let {aa}span = {aa}token.span();\n"))?;
+            wtr.write_fmt(format_args!(
+                "        let {aa}value: {} = {{\n",
+                self.attribute_type
+            ))?;
+            self.write_token_value_code(wtr)?;
+            wtr.write_all(b"        };\n")?;
+            wtr.write_fmt(format_args!(
+                "        lalr1_plus::Spanned::new({aa}value, {aa}span)\n"
+            ))?;
+        } else {
+            self.write_token_value_code(wtr)?;
+        }
         wtr.write_all(b"    }\n\n")?;
         Ok(())
     }
 
-    pub fn write_description(&self, file_path: &Path) -> io::Result<()> {
-        let mut file = std::fs::File::create(file_path)?;
-        file.write_all(self.specification.symbol_table.description().as_bytes())?;
-        file.write_all(b"\nProductions:\n")?;
-        for production in self.specification.productions.iter() {
-            file.write_fmt(format_args!("  {production}\n"))?;
+    fn write_token_value_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let aa = self.variable_prefix();
+        let attr = self.attribute_type_path();
+        self.write_token_transform_code(wtr)?;
+        if self.converts_tokens_in_parser() {
+            self.write_token_conversion_match_code(wtr)
+        } else {
+            wtr.write_fmt(format_args!("        {attr}::from({aa}token)\n"))
         }
-        for parser_state in self.parser_states.iter() {
-            file.write_all(parser_state.description().as_bytes())?;
+    }
+
+    fn write_token_conversion_match_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        let attr = self.attribute_type_path();
+        wtr.write_fmt(format_args!("        match {aa}token.tag() {{\n"))?;
+        wtr.write_fmt(format_args!(
+            "            {prefix}Terminal::{} => {attr}::default(),\n",
+            self.symbol_table.end_token().name()
+        ))?;
+        for token in self.symbol_table.tokens() {
+            let conversion = token.conversion().expect("checked at specification time");
+            let code = conversion
+                .replace("$$", &format!("{aa}lhs"))
+                .replace("$1", &format!("{aa}token"));
+            wtr.write_fmt(format_args!(
+                "            {prefix}Terminal::{} => {{\n",
+                token.name()
+            ))?;
+            wtr.write_fmt(format_args!(
+                "                let mut {aa}lhs = {attr}::default();\n"
+            ))?;
+            wtr.write_fmt(format_args!("                {{ {code} }}\n"))?;
+            wtr.write_fmt(format_args!("                {aa}lhs\n"))?;
+            wtr.write_all(b"            }\n")?;
         }
+        wtr.write_all(b"        }\n")?;
         Ok(())
     }
 
-    pub fn describe_sr_conflict_states(&self) -> String {
-        let mut string = String::new();
-        for parser_state in self.parser_states.iter() {
-            if parser_state.shift_reduce_conflict_count() > 0 {
-                string += parser_state.description().as_str();
+    // Tokens with a "%transform" have their lexemes replaced before conversion
+    fn write_token_transform_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        if self.symbol_table.tokens().all(|t| t.transform().is_none()) {
+            return Ok(());
+        }
+        wtr.write_fmt(format_args!(
+            "        let {aa}token = match {aa}token.tag() {{\n"
+        ))?;
+        for token in self.symbol_table.tokens() {
+            if let Some(transform) = token.transform() {
+                let code = transform.replace("$1", &format!("{aa}token.lexeme()"));
+                wtr.write_fmt(format_args!(
+                    "            {prefix}Terminal::{} => {{\n",
+                    token.name()
+                ))?;
+                wtr.write_fmt(format_args!(
+                    "                let {aa}lexeme = String::from({{ {code} }});\n"
+                ))?;
+                wtr.write_fmt(format_args!(
+                    "                {aa}token.with_lexeme({aa}lexeme)\n"
+                ))?;
+                wtr.write_all(b"            }\n")?;
             }
         }
-        string
+        wtr.write_fmt(format_args!("            _ => {aa}token,\n"))?;
+        wtr.write_all(b"        };\n")?;
+        Ok(())
     }
 
-    pub fn describe_rr_conflict_states(&self) -> String {
-        let mut string = String::new();
-        for parser_state in self.parser_states.iter() {
-            if parser_state.reduce_reduce_conflict_count() > 0 {
-                string += parser_state.description().as_str();
-            }
+    // Each "%test" becomes a test that parses its text using the target's default
+    pub fn write_specification_tests_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let aa = self.variable_prefix();
+        if self.tests.is_empty() {
+            return Ok(());
         }
-        string
+        wtr.write_all(b"\n#[cfg(test)]\n")?;
+        wtr.write_fmt(format_args!("mod {aa}specification_tests {{\n"))?;
+        wtr.write_all(b"    use super::*;\n")?;
+        wtr.write_all(b"    use lalr1_plus::Parser;\n")?;
+        for (index, (text, ok, location)) in self.tests.iter().enumerate() {
+            let check = if *ok { "is_ok" } else { "is_err" };
+            wtr.write_fmt(format_args!("\n    // {location}\n"))?;
+            wtr.write_all(b"    #[test]\n")?;
+            wtr.write_fmt(format_args!("    fn {aa}test_{}() {{\n", index + 1))?;
+            wtr.write_fmt(format_args!(
+                "        let mut {aa}parser = {}::default();\n",
+                self.target_type
+            ))?;
+            wtr.write_fmt(format_args!(
+                "        assert!({aa}parser.parse_text({text}, \"%test\").{check}());\n"
+            ))?;
+            wtr.write_all(b"    }\n")?;
+        }
+        wtr.write_all(b"}\n")?;
+        Ok(())
+    }
+
+    pub fn write_interner_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if let Some(field) = &self.interner_field {
+            wtr.write_all(b"    fn interner(&mut self) -> Option<&mut lalr1_plus::Interner> {\n")?;
+            wtr.write_fmt(format_args!("        Some(&mut self.{field})\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_coverage_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if let Some(field) = &self.coverage_field {
+            wtr.write_all(b"    fn coverage(&self) -> Option<&lalr1_plus::BitSet> {\n")?;
+            wtr.write_fmt(format_args!("        Some(&self.{field})\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+            wtr.write_all(
+                b"    fn coverage_mut(&mut self) -> Option<&mut lalr1_plus::BitSet> {\n",
+            )?;
+            wtr.write_fmt(format_args!("        Some(&mut self.{field})\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_derivation_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if let Some(field) = &self.derivation_field {
+            wtr.write_all(b"    fn derivation_mut(&mut self) -> Option<&mut Vec<u32>> {\n")?;
+            wtr.write_fmt(format_args!("        Some(&mut self.{field})\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+            wtr.write_all(b"    fn derivation(&self) -> &[u32] {\n")?;
+            wtr.write_fmt(format_args!("        &self.{field}\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_observer_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        if let Some(field) = &self.observer_field {
+            wtr.write_all(b"    fn observer(\n")?;
+            wtr.write_all(b"        &mut self,\n")?;
+            wtr.write_fmt(format_args!(
+                "    ) -> Option<&mut dyn lalr1_plus::ParseObserver<{prefix}Terminal, {prefix}NonTerminal>> {{\n",
+            ))?;
+            wtr.write_fmt(format_args!("        Some(&mut self.{field})\n"))?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_semantic_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if self.spanned_attributes {
+            return self.write_spanned_semantic_action_code(wtr);
+        }
+        let aa = self.variable_prefix();
+        wtr.write_all(b"    fn do_semantic_action<F: FnMut(String, String)>(\n")?;
+        wtr.write_all(b"        &mut self,\n")?;
+        wtr.write_fmt(format_args!("        {aa}production_id: u32,\n"))?;
+        let attr = self.stack_attribute_type();
+        wtr.write_fmt(format_args!("        mut {aa}rhs: Vec<{attr}>,\n"))?;
+        wtr.write_fmt(format_args!("        mut {aa}inject: F,\n"))?;
+        wtr.write_fmt(format_args!("    ) -> {attr} {{\n"))?;
+        self.write_semantic_action_match_code(wtr)?;
+        wtr.write_fmt(format_args!("        {aa}lhs\n"))?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    // Sets $$ (starting as $1) by running the production's action (if any)
+    fn write_semantic_action_match_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let aa = self.variable_prefix();
+        // $$ starts as $1 which need only be cloned (rather than moved) if the action uses it
+        let cloning: Vec<String> = self
+            .productions
+            .iter()
+            .filter(|production| {
+                production
+                    .expanded_action(&aa)
+                    .is_some_and(|action| action.contains(&format!("{aa}rhs[0]")))
+            })
+            .map(|production| production.ident().to_string())
+            .collect();
+        if cloning.is_empty() {
+            wtr.write_fmt(format_args!(
+                "        let mut {aa}lhs = {aa}rhs.first_mut().map(core::mem::take).unwrap_or_default();\n",
+            ))?;
+        } else {
+            wtr.write_fmt(format_args!(
+                "        let mut {aa}lhs = match {aa}production_id {{\n"
+            ))?;
+            wtr.write_fmt(format_args!(
+                "            {} => {aa}rhs.first().cloned().unwrap_or_default(),\n",
+                cloning.join(" | ")
+            ))?;
+            wtr.write_fmt(format_args!(
+                "            _ => {aa}rhs.first_mut().map(core::mem::take).unwrap_or_default(),\n",
+            ))?;
+            wtr.write_all(b"        };\n")?;
+        }
+        wtr.write_fmt(format_args!("        match {aa}production_id {{\n"))?;
+        for production in self.productions.iter() {
+            if let Some(action_code) = production.expanded_action(&aa) {
+                wtr.write_fmt(format_args!("            {} => {{\n", production.ident()))?;
+                wtr.write_fmt(format_args!("                // {production}\n"))?;
+                wtr.write_fmt(format_args!("                {action_code}\n"))?;
+                wtr.write_all(b"            }\n")?;
+            }
+        }
+        wtr.write_fmt(format_args!(
+            "            _ => {aa}inject(String::new(), String::new()),\n"
+        ))?;
+        wtr.write_all(b"        };\n")?;
+        Ok(())
+    }
+
+    // $$'s span covers the right hand side's (or is empty at the look ahead if that's empty)
+    // and, as the actions may refer to it as $@, the actions are run here
+    fn write_spanned_semantic_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.prefix();
+        let aa = self.variable_prefix();
+        let attr = self.stack_attribute_type();
+        wtr.write_all(b"    fn do_semantic_action_la<F: FnMut(String, String)>(\n")?;
+        wtr.write_all(b"        &mut self,\n")?;
+        wtr.write_fmt(format_args!("        {aa}production_id: u32,\n"))?;
+        wtr.write_fmt(format_args!("        mut {aa}rhs: Vec<{attr}>,\n"))?;
+        wtr.write_fmt(format_args!(
+            "        {aa}look_ahead: &lexan::Token<{prefix}Terminal>,\n"
+        ))?;
+        wtr.write_fmt(format_args!("        mut {aa}inject: F,\n"))?;
+        wtr.write_fmt(format_args!("    ) -> {attr} {{\n"))?;
+        wtr.write_fmt(format_args!(
+            "        let {aa}span = lalr1_plus::Spanned::span_of(&{aa}rhs, {aa}look_ahead.location());\n",
+        ))?;
+        self.write_semantic_action_match_code(wtr)?;
+        wtr.write_fmt(format_args!("        {aa}lhs.span = {aa}span;\n"))?;
+        wtr.write_fmt(format_args!("        {aa}lhs\n"))?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+}
+
+pub struct Grammar {
+    specification: Specification,
+    parser_states: Vec<ParserState>,
+    pub emit_production_table: bool,
+    // include an unparser (see `write_unparser_code()`) in the parser's code
+    pub emit_unparser: bool,
+    pub strict_predicates: bool,
+    pub checked_predicates: bool,
+    pub compress_tables: bool,
+    // look actions, gotos and look ahead sets up in static arrays rather than matches
+    pub table_driven: bool,
+    // take the alloc items that std's prelude would supply from lalr1_plus::alloc_prelude
+    pub no_std: bool,
+    pub diagnose_panics: bool,
+    pub namespace: Option<String>,
+    pub specification_checksum: Option<u64>,
+    // the start symbols' names (the "%start" ones in order) and start states
+    start_states: Vec<(String, u32)>,
+    // the numbers of unresolved shift/reduce and reduce/reduce conflicts
+    sr_conflicts: u32,
+    rr_conflicts: u32,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    TooManyErrors(u32),
+    UndefinedSymbols(u32),
+    // the counts, the states' descriptions and the conflicts as JSON
+    UnexpectedSRConflicts(u32, u32, String, String),
+    UnexpectedRRConflicts(u32, u32, String, String),
+    UnexpectedConflicts(u32, u32, String, String),
+    NonProductiveSymbols(u32),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::TooManyErrors(count) => write!(f, "Too many errors: {count:?}."),
+            Error::UndefinedSymbols(count) => write!(f, "Undefined symbols: {count:?}."),
+            Error::NonProductiveSymbols(count) => write!(f, "Non productive symbols: {count:?}."),
+            Error::UnexpectedSRConflicts(count, expected, report, _) => write!(
+                f,
+                "{report}\nUnexpected shift/reduce conflicts: {count} expected: {expected}."
+            ),
+            Error::UnexpectedRRConflicts(count, expected, report, _) => write!(
+                f,
+                "{report}\nUnexpected reduce/reduce conflicts: {count} expected: {expected}."
+            ),
+            Error::UnexpectedConflicts(count, expected, report, _) => write!(
+                f,
+                "{report}\nUnexpected conflicts: {count} expected: {expected}."
+            ),
+        }
+    }
+}
+
+impl Error {
+    /// The conflicts as JSON (for "--conflicts-json") if the error is about them
+    pub fn conflicts_json(&self) -> Option<&str> {
+        match self {
+            Error::UnexpectedSRConflicts(.., json)
+            | Error::UnexpectedRRConflicts(.., json)
+            | Error::UnexpectedConflicts(.., json) => Some(json),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<(Specification, bool, bool)> for Grammar {
+    type Error = Error;
+
+    fn try_from(arg: (Specification, bool, bool)) -> Result<Self, Error> {
+        let specification = arg.0;
+        let ignore_sr_conflicts = arg.1;
+        let ignore_rr_conflicts = arg.2;
+        for token in specification.symbol_table.unused_tokens() {
+            report_warning(
+                token.defined_at(),
+                &format!("Token \"{}\" is not used", token.name()),
+            )
+        }
+
+        for tag in specification.symbol_table.unused_tags() {
+            report_warning(
+                tag.defined_at(),
+                &format!("Tag \"{}\" is not used", tag.name()),
+            )
+        }
+
+        for non_terminal in specification.symbol_table.unused_non_terminals() {
+            report_warning(
+                &non_terminal
+                    .first_definition()
+                    .expect("can't be both unused and undefined"),
+                &format!("Non terminal \"{}\" is not used", non_terminal.name()),
+            )
+        }
+
+        for non_terminal in specification.unreachable_non_terminals() {
+            report_warning(
+                &non_terminal.first_definition().expect("should be defined"),
+                &format!(
+                    "Non terminal \"{}\" can't be reached from the start symbol",
+                    non_terminal.name()
+                ),
+            )
+        }
+
+        let mut undefined_symbols = 0;
+        for non_terminal in specification.symbol_table.undefined_non_terminals() {
+            for location in non_terminal.used_at() {
+                report_error(
+                    &location,
+                    &format!("Non terminal \"{}\" is not defined", non_terminal.name()),
+                );
+            }
+            undefined_symbols += 1;
+        }
+
+        let mut non_productive_symbols = 0;
+        if undefined_symbols == 0 {
+            for non_terminal in specification.non_productive_non_terminals() {
+                report_error(
+                    &non_terminal.first_definition().expect("should be defined"),
+                    &format!(
+                        "Non terminal \"{}\" can't derive a string of terminals",
+                        non_terminal.name()
+                    ),
+                );
+                non_productive_symbols += 1;
+            }
+        }
+
+        if undefined_symbols > 0 {
+            Err(Error::UndefinedSymbols(undefined_symbols))
+        } else if non_productive_symbols > 0 {
+            Err(Error::NonProductiveSymbols(non_productive_symbols))
+        } else if specification.error_count > 0 {
+            Err(Error::TooManyErrors(specification.error_count))
+        } else {
+            let start_kernels: Vec<(String, GrammarItemSet)> = specification
+                .productions
+                .iter()
+                .filter(|production| production.is_start_production())
+                .map(|production| {
+                    let start_item_key = GrammarItemKey::from(production);
+                    let mut start_look_ahead_set = TokenSet::new();
+                    start_look_ahead_set.insert(specification.symbol_table.end_token());
+                    let mut map = BTreeMap::<GrammarItemKey, TokenSet>::new();
+                    map.insert(start_item_key, start_look_ahead_set);
+                    let name = production
+                        .right_hand_side_symbols()
+                        .next()
+                        .expect("start productions have one symbol")
+                        .to_string();
+                    (name, specification.closure(GrammarItemSet::from(map)))
+                })
+                .collect();
+            let namespace = specification.module.clone();
+            let mut grammar = Self {
+                specification,
+                parser_states: vec![],
+                emit_production_table: false,
+                emit_unparser: false,
+                strict_predicates: false,
+                checked_predicates: false,
+                compress_tables: false,
+                table_driven: false,
+                no_std: false,
+                diagnose_panics: false,
+                namespace,
+                specification_checksum: None,
+                start_states: vec![],
+                sr_conflicts: 0,
+                rr_conflicts: 0,
+            };
+            // the start symbols share the one automaton (each with its own start state)
+            for (name, start_kernel) in start_kernels {
+                let start_state = grammar.new_parser_state(start_kernel);
+                grammar.start_states.push((name, start_state.ident()));
+            }
+            while let Some(unprocessed_state) = grammar.first_unprocessed_state() {
+                let first_time = !unprocessed_state.needs_reprocessing();
+                unprocessed_state.mark_as_processed();
+                let mut already_done = BTreeSet::<Symbol>::new();
+                for item_key in unprocessed_state.non_kernel_key_set().iter() {
+                    let symbol_x = item_key.next_symbol().expect("not reducible");
+                    if !already_done.insert(symbol_x.clone()) {
+                        continue;
+                    };
+                    let kernel_x = unprocessed_state.generate_goto_kernel(symbol_x);
+                    let item_set_x = grammar.specification.closure(kernel_x);
+                    let goto_state =
+                        if let Some(equivalent_state) = grammar.equivalent_state(&item_set_x) {
+                            equivalent_state.merge_lookahead_sets(&item_set_x);
+                            equivalent_state.clone()
+                        } else {
+                            grammar.new_parser_state(item_set_x)
+                        };
+                    if first_time {
+                        match symbol_x {
+                            Symbol::Terminal(token) => {
+                                unprocessed_state.add_shift_action(token.clone(), goto_state)
+                            }
+                            Symbol::NonTerminal(non_terminal) => {
+                                if non_terminal.is_error() {
+                                    unprocessed_state.set_error_recovery_state(&goto_state);
+                                }
+                                unprocessed_state.add_goto(non_terminal.clone(), goto_state);
+                            }
+                        }
+                    }
+                }
+            }
+            let (sr_conflicts, rr_conflicts) = grammar.resolve_conflicts();
+            grammar.sr_conflicts = sr_conflicts;
+            grammar.rr_conflicts = rr_conflicts;
+            grammar.check_for_unreducible_productions();
+            grammar.check_recovery_tokens();
+            grammar.check_conflicts(ignore_sr_conflicts, ignore_rr_conflicts)?;
+            Ok(grammar)
+        }
+    }
+}
+
+impl Grammar {
+    /// The numbers of unresolved shift/reduce and reduce/reduce conflicts.
+    pub fn unresolved_conflicts(&self) -> (u32, u32) {
+        (self.sr_conflicts, self.rr_conflicts)
+    }
+
+    // A total ("%expect") covers the shift/reduce conflicts and (unless ignored) the
+    // reduce/reduce ones.  Otherwise, and if shift/reduce conflicts are ignored, each kind
+    // must match its own expected number so reduce/reduce conflicts (almost always bugs)
+    // are never excused by ignoring shift/reduce ones.
+    fn check_conflicts(
+        &self,
+        ignore_sr_conflicts: bool,
+        ignore_rr_conflicts: bool,
+    ) -> Result<(), Error> {
+        let (sr_conflicts, rr_conflicts) = self.unresolved_conflicts();
+        match self.specification.expected_conflicts {
+            Some((expected, _)) if !ignore_sr_conflicts => {
+                let conflicts = if ignore_rr_conflicts {
+                    sr_conflicts
+                } else {
+                    sr_conflicts + rr_conflicts
+                };
+                if conflicts != expected {
+                    let report =
+                        self.describe_sr_conflict_states() + &self.describe_rr_conflict_states();
+                    return Err(Error::UnexpectedConflicts(
+                        conflicts,
+                        expected,
+                        report,
+                        self.conflicts_json(),
+                    ));
+                }
+            }
+            _ => {
+                let expected_sr_conflicts = self.specification.expected_sr_conflicts;
+                let expected_rr_conflicts = self.specification.expected_rr_conflicts;
+                if !ignore_sr_conflicts && sr_conflicts != expected_sr_conflicts {
+                    return Err(Error::UnexpectedSRConflicts(
+                        sr_conflicts,
+                        expected_sr_conflicts,
+                        self.describe_sr_conflict_states(),
+                        self.conflicts_json(),
+                    ));
+                } else if !ignore_rr_conflicts && rr_conflicts != expected_rr_conflicts {
+                    return Err(Error::UnexpectedRRConflicts(
+                        rr_conflicts,
+                        expected_rr_conflicts,
+                        self.describe_rr_conflict_states(),
+                        self.conflicts_json(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_conflicts(&mut self) -> (u32, u32) {
+        let mut sr_conflicts = 0_u32;
+        let mut rr_conflicts = 0_u32;
+        for parser_state in self.parser_states.iter_mut() {
+            sr_conflicts += parser_state.resolve_shift_reduce_conflicts() as u32;
+            if self.specification.default_sr_shift {
+                for (token, item) in parser_state.accept_shift_reduce_conflicts() {
+                    let production = item.production();
+                    report_note(
+                        &production
+                            .left_hand_side()
+                            .first_definition()
+                            .expect("should be defined"),
+                        &format!(
+                            "State<{}>: shift/reduce conflict between {token} and {production} resolved by shifting",
+                            parser_state.ident()
+                        ),
+                    );
+                    sr_conflicts -= 1;
+                }
+            }
+            rr_conflicts += parser_state.resolve_reduce_reduce_conflicts() as u32;
+        }
+        (sr_conflicts, rr_conflicts)
+    }
+
+    fn first_unprocessed_state(&self) -> Option<ParserState> {
+        Some(
+            self.parser_states
+                .iter()
+                .find(|x| !x.is_processed())?
+                .clone(),
+        )
+    }
+
+    fn new_parser_state(&mut self, grammar_items: GrammarItemSet) -> ParserState {
+        let ident = self.parser_states.len() as u32;
+        let parser_state = ParserState::new(ident, grammar_items);
+        self.parser_states.push(parser_state.clone());
+        parser_state
+    }
+
+    fn equivalent_state(&self, item_set: &GrammarItemSet) -> Option<&ParserState> {
+        let target_key_set = item_set.kernel_key_set();
+        if !target_key_set.is_empty() {
+            for parser_state in self.parser_states.iter() {
+                if target_key_set == parser_state.kernel_key_set() {
+                    return Some(parser_state);
+                }
+            }
+        };
+        None
+    }
+}
+
+impl Grammar {
+    fn write_parser_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        wtr.write_all(b"// generated by alap_gen.\n")?;
+        if let Some(checksum) = self.specification_checksum {
+            wtr.write_fmt(format_args!("{CHECKSUM_PREFIX}{checksum:016x}\n"))?;
+        }
+        wtr.write_all(b"\n")?;
+        if let Some(namespace) = &self.namespace {
+            wtr.write_fmt(format_args!("pub mod {namespace} {{\n"))?;
+        }
+
+        self.specification.write_preamble_text(wtr)?;
+        self.write_symbol_enum_code(wtr)?;
+        self.specification.write_token_conversion_code(wtr)?;
+        if self.emit_production_table {
+            self.specification.write_production_table_code(wtr)?;
+            self.write_token_precedence_table_code(wtr)?;
+        }
+        if self.compress_tables || self.table_driven {
+            self.write_action_table_code(wtr)?;
+        }
+        if self.table_driven {
+            self.write_goto_and_look_ahead_tables_code(wtr)?;
+        }
+        if self.specification.visitor {
+            self.specification.write_visitor_code(wtr)?;
+        }
+        if self.emit_unparser {
+            self.write_unparser_code(wtr, "")?;
+        }
+        self.write_parser_implementation_code(wtr)?;
+        self.specification.write_specification_tests_code(wtr)?;
+        if self.namespace.is_some() {
+            wtr.write_all(b"}\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_parser_code_to_file(&self, file_path: &Path) -> io::Result<()> {
+        let mut file = std::fs::File::create(file_path)?;
+        self.write_parser_code(&mut file)?;
+        Ok(())
+    }
+
+    fn write_symbol_enum_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let special_tokens = [self.specification.symbol_table.end_token().clone()];
+        let special_non_terminals = self.specification.symbol_table.used_non_terminal_specials();
+
+        if self.no_std {
+            wtr.write_all(b"#[allow(unused_imports)]\n")?;
+            wtr.write_all(b"use lalr1_plus::alloc_prelude::*;\n\n")?;
+        } else {
+            wtr.write_all(b"use std::collections::BTreeSet;\n\n")?;
+        }
+        wtr.write_all(b"macro_rules! btree_set {\n")?;
+        wtr.write_all(b"    () => { BTreeSet::new() };\n")?;
+        wtr.write_all(b"    ( $( $x:expr ),* ) => {\n")?;
+        wtr.write_all(b"        {\n")?;
+        wtr.write_all(b"            let mut set = BTreeSet::new();\n")?;
+        wtr.write_all(b"            $( set.insert($x); )*\n")?;
+        wtr.write_all(b"            set\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    };\n")?;
+        wtr.write_all(b"    ( $( $x:expr ),+ , ) => {\n")?;
+        wtr.write_all(b"        btree_set![ $( $x ), * ]\n")?;
+        wtr.write_all(b"    };\n")?;
+        wtr.write_all(b"}\n\n")?;
+        let derives = DERIVES
+            .iter()
+            .map(|x| x.to_string())
+            .chain(self.specification.derives.iter().cloned())
+            .collect::<Vec<String>>()
+            .join(", ");
+        wtr.write_fmt(format_args!("#[derive({derives})]\n"))?;
+        wtr.write_fmt(format_args!("pub enum {prefix}Terminal {{\n"))?;
+        for token in special_tokens
+            .iter()
+            .chain(self.specification.symbol_table.tokens())
+        {
+            wtr.write_fmt(format_args!("    {},\n", token.name()))?;
+        }
+        wtr.write_all(b"}\n\n")?;
+        wtr.write_fmt(format_args!(
+            "impl core::fmt::Display for {prefix}Terminal {{\n"
+        ))?;
+        wtr.write_all(b"    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {\n")?;
+        wtr.write_all(b"        match self {\n")?;
+        for token in special_tokens
+            .iter()
+            .chain(self.specification.symbol_table.tokens())
+        {
+            wtr.write_fmt(format_args!("            {prefix}Terminal::"))?;
+            match token {
+                _ if token.display_name().is_some() => {
+                    wtr.write_fmt(format_args!(
+                        "{} => write!(f, r###\"{}\"###),\n",
+                        token.name(),
+                        // it's a format string
+                        token
+                            .display_name()
+                            .expect("checked")
+                            .replace('{', "{{")
+                            .replace('}', "}}")
+                    ))?;
+                }
+                Token::Literal(token_data) => {
+                    wtr.write_fmt(format_args!(
+                        "{} => write!(f, r###\"{}\"###),\n",
+                        token_data.name, token_data.text
+                    ))?;
+                }
+                Token::Regex(token_data) => {
+                    wtr.write_fmt(format_args!(
+                        "{} => write!(f, r###\"{}\"###),\n",
+                        token_data.name, token_data.name
+                    ))?;
+                }
+                Token::EndToken(_) => {
+                    wtr.write_fmt(format_args!(
+                        "{} => write!(f, r###\"{}\"###),\n",
+                        token.name(),
+                        token.name()
+                    ))?;
+                }
+            }
+        }
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n")?;
+        wtr.write_all(b"}\n\n")?;
+        self.write_keyword_code(wtr)?;
+        self.write_lexical_analyzer_code(wtr)?;
+        wtr.write_fmt(format_args!("#[derive({derives})]\n"))?;
+        wtr.write_fmt(format_args!("pub enum {prefix}NonTerminal {{\n"))?;
+        for non_terminal in special_non_terminals
+            .iter()
+            .chain(self.specification.symbol_table.non_terminals())
+        {
+            wtr.write_fmt(format_args!("    {},\n", non_terminal.name()))?;
+        }
+        wtr.write_all(b"}\n\n")?;
+        wtr.write_fmt(format_args!(
+            "impl core::fmt::Display for {prefix}NonTerminal {{\n"
+        ))?;
+        wtr.write_all(b"    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {\n")?;
+        wtr.write_all(b"        match self {\n")?;
+        for non_terminal in special_non_terminals
+            .iter()
+            .chain(self.specification.symbol_table.non_terminals())
+        {
+            wtr.write_fmt(format_args!("            {prefix}NonTerminal::"))?;
+            let name = non_terminal.name();
+            wtr.write_fmt(format_args!("{name} => write!(f, r\"{name}\"),\n"))?;
+        }
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n")?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    fn write_keyword_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        if self.specification.symbol_table.keywords().next().is_none() {
+            return Ok(());
+        }
+        wtr.write_fmt(format_args!("impl {prefix}Terminal {{\n"))?;
+        wtr.write_all(b"    /// The keyword (if any) spelt `text`.\n")?;
+        wtr.write_all(b"    pub fn keyword(text: &str) -> Option<Self> {\n")?;
+        wtr.write_all(b"        match text {\n")?;
+        for keyword in self.specification.symbol_table.keywords() {
+            wtr.write_fmt(format_args!(
+                "            {} => Some({prefix}Terminal::{}),\n",
+                keyword.text(),
+                keyword.name()
+            ))?;
+        }
+        wtr.write_all(b"            _ => None,\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n")?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    fn write_lexical_analyzer_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        wtr.write_all(b"lazy_static::lazy_static! {\n")?;
+        wtr.write_fmt(format_args!(
+            "    static ref {prefix}LEXAN: lexan::LexicalAnalyzer<{prefix}Terminal> = {{\n"
+        ))?;
+        wtr.write_fmt(format_args!("        use {prefix}Terminal::*;\n"))?;
+        wtr.write_all(b"        lexan::LexicalAnalyzer::new(\n")?;
+        self.write_lexical_mode_code(wtr, 0)?;
+        wtr.write_fmt(format_args!(
+            "            {},\n",
+            self.specification.symbol_table.end_token().name()
+        ))?;
+        wtr.write_all(b"        )\n")?;
+        for (mode, name) in self
+            .specification
+            .symbol_table
+            .lexical_modes()
+            .enumerate()
+            .skip(1)
+        {
+            wtr.write_fmt(format_args!("        // %mode {name}\n"))?;
+            wtr.write_all(b"        .with_mode(\n")?;
+            self.write_lexical_mode_code(wtr, mode)?;
+            wtr.write_all(b"        )\n")?;
+        }
+        for token in self.specification.symbol_table.tokens() {
+            if let Some((name, _)) = token.mode_switch() {
+                let mode = self
+                    .specification
+                    .symbol_table
+                    .lexical_mode_index(&name)
+                    .expect("checked at specification time");
+                wtr.write_fmt(format_args!(
+                    "        .with_mode_switch({}, {mode})\n",
+                    token.name()
+                ))?;
+            }
+        }
+        wtr.write_all(b"    };\n")?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    fn write_lexical_mode_code<W: Write>(&self, wtr: &mut W, mode: usize) -> io::Result<()> {
+        let symbol_table = &self.specification.symbol_table;
+        wtr.write_all(b"            &[\n")?;
+        for token in symbol_table.literal_tokens().filter(|t| t.mode() == mode) {
+            wtr.write_all(b"                ")?;
+            wtr.write_fmt(format_args!(
+                "({}, r###{}###),\n",
+                token.name(),
+                token.text()
+            ))?;
+        }
+        wtr.write_all(b"            ],\n")?;
+        wtr.write_all(b"            &[\n")?;
+        for token in symbol_table.regex_tokens().filter(|t| t.mode() == mode) {
+            wtr.write_all(b"                ")?;
+            wtr.write_fmt(format_args!(
+                "({}, r###\"{}\"###),\n",
+                token.name(),
+                token.text()
+            ))?;
+        }
+        wtr.write_all(b"            ],\n")?;
+        wtr.write_all(b"            &[\n")?;
+        for skip_rule in symbol_table.skip_rules(mode) {
+            wtr.write_all(b"                ")?;
+            wtr.write_fmt(format_args!("r###\"{skip_rule}\"###,\n"))?;
+        }
+        wtr.write_all(b"            ],\n")?;
+        Ok(())
+    }
+
+    fn write_lexical_mode_switch_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let symbol_table = &self.specification.symbol_table;
+        if symbol_table.lexical_modes().count() < 2 {
+            return Ok(());
+        }
+        wtr.write_all(b"    fn lexical_mode(label: &str) -> Option<usize> {\n")?;
+        wtr.write_all(b"        match label {\n")?;
+        for (mode, name) in symbol_table.lexical_modes().enumerate() {
+            wtr.write_fmt(format_args!(
+                "            \"{}\" => Some({mode}),\n",
+                mode_switch_label(name)
+            ))?;
+        }
+        wtr.write_all(b"            _ => None,\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    // The states part way through (i.e. with a kernel item yet to be reduced for) a
+    // production of a "%noskip" non terminal
+    pub fn no_skip_states(&self) -> Vec<u32> {
+        let no_skip = &self.specification.no_skip_non_terminals;
+        self.parser_states
+            .iter()
+            .filter(|state| {
+                state.kernel_key_set().iter().any(|key| {
+                    !key.is_reducible()
+                        && no_skip.contains_key(key.production().left_hand_side().name())
+                })
+            })
+            .map(|state| state.ident())
+            .collect()
+    }
+
+    fn write_skips_suppressed_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if self.specification.no_skip_non_terminals.is_empty() {
+            return Ok(());
+        }
+        let states: Vec<String> = self
+            .no_skip_states()
+            .iter()
+            .map(|state| state.to_string())
+            .collect();
+        wtr.write_all(
+            b"    fn skips_suppressed_in(state: u32) -> bool {
+",
+        )?;
+        if states.is_empty() {
+            wtr.write_all(
+                b"        let _ = state;
+",
+            )?;
+            wtr.write_all(
+                b"        false
+",
+            )?;
+        } else {
+            wtr.write_fmt(format_args!(
+                "        matches!(state, {})
+",
+                states.join(" | ")
+            ))?;
+        }
+        wtr.write_all(
+            b"    }
+
+",
+        )?;
+        Ok(())
+    }
+
+    // The generic parameters for the target's impl blocks and its arguments
+    fn target_generics(&self) -> (String, String) {
+        match (
+            &self.specification.target_lifetime,
+            &self.specification.target_type_parameter,
+        ) {
+            (Some(lifetime), _) => (format!("<{lifetime}>"), format!("<{lifetime}>")),
+            (None, Some(parameter)) => {
+                let name = parameter.split(':').next().unwrap_or_default().trim();
+                (format!("<{parameter}>"), format!("<{name}>"))
+            }
+            (None, None) => (String::new(), String::new()),
+        }
+    }
+
+    fn write_parser_implementation_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let attr = self.specification.stack_attribute_type();
+        let parser = &self.specification.target_type;
+        let (parameters, arguments) = self.target_generics();
+        let text = format!(
+            "impl{parameters} lalr1_plus::Parser<{prefix}Terminal, {prefix}NonTerminal, {attr}> for {parser}{arguments} {{\n"
+        );
+        wtr.write_all(text.as_bytes())?;
+        wtr.write_fmt(format_args!(
+            "    fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<{prefix}Terminal> {{\n",
+        ))?;
+        wtr.write_fmt(format_args!("        &{prefix}LEXAN\n"))?;
+        wtr.write_all(b"    }\n\n")?;
+        self.specification.write_token_attribute_code(wtr)?;
+        self.specification.write_interner_code(wtr)?;
+        self.specification.write_coverage_code(wtr)?;
+        self.specification.write_derivation_code(wtr)?;
+        self.specification.write_observer_code(wtr)?;
+        if self.diagnose_panics {
+            wtr.write_all(b"    fn diagnose_panics(&self) -> bool {\n")?;
+            wtr.write_all(b"        true\n")?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        self.write_lexical_mode_switch_code(wtr)?;
+        self.write_skips_suppressed_code(wtr)?;
+        self.write_error_recovery_code(wtr)?;
+        self.write_look_ahead_set_code(wtr)?;
+        self.write_next_action_code(wtr)?;
+        self.write_possible_actions_code(wtr)?;
+        self.specification.write_production_data_code(wtr)?;
+        self.write_goto_table_code(wtr)?;
+        self.specification.write_semantic_action_code(wtr)?;
+        wtr.write_all(b"}\n")?;
+        self.write_entry_points_code(wtr)?;
+        self.write_functional_code(wtr)?;
+        Ok(())
+    }
+
+    // A parse function for each start symbol (if there's more than one)
+    fn write_entry_points_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        if self.start_states.len() < 2 {
+            return Ok(());
+        }
+        let attr = self.specification.stack_attribute_type();
+        let parser = &self.specification.target_type;
+        let (parameters, arguments) = self.target_generics();
+        wtr.write_fmt(format_args!("\nimpl{parameters} {parser}{arguments} {{\n"))?;
+        for (name, start_state) in self.start_states.iter() {
+            wtr.write_fmt(format_args!(
+                "    /// Parse `text` as a `{name}` returning its attribute.\n"
+            ))?;
+            wtr.write_fmt(format_args!(
+                "    pub fn parse_{}(&mut self, text: &str, label: &str) -> Result<{attr}, lalr1_plus::Error<{prefix}Terminal>> {{\n",
+                snake_case(name)
+            ))?;
+            wtr.write_fmt(format_args!(
+                "        lalr1_plus::Parser::parse_to_from(self, {start_state}, text, label)\n"
+            ))?;
+            wtr.write_all(b"    }\n")?;
+        }
+        wtr.write_all(b"}\n")?;
+        Ok(())
+    }
+
+    // Free functions that parse text with a default target (for "%functional")
+    fn write_functional_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        if !self.specification.functional {
+            return Ok(());
+        }
+        let attr = self.specification.stack_attribute_type();
+        let parser = &self.specification.target_type;
+        let (parameters, arguments) = self.target_generics();
+        for (index, (name, start_state)) in self.start_states.iter().enumerate() {
+            let function = if index == 0 {
+                "parse".to_string()
+            } else {
+                format!("parse_{}", snake_case(name))
+            };
+            let (output, conversion) = match self.specification.attribute_types.get(name) {
+                Some((attribute_type, _)) => (
+                    attribute_type.to_string(),
+                    format!(".map(lalr1_plus::TypedAttribute::<{attribute_type}>::into_typed)"),
+                ),
+                None => (attr.to_string(), String::new()),
+            };
+            wtr.write_fmt(format_args!(
+                "\n/// Parse `text` as a `{name}` (using a default `{parser}`) returning its attribute.\n"
+            ))?;
+            wtr.write_fmt(format_args!(
+                "pub fn {function}{parameters}(text: &str) -> Result<{output}, lalr1_plus::Error<{prefix}Terminal>>\nwhere\n    {parser}{arguments}: Default,\n{{\n"
+            ))?;
+            wtr.write_fmt(format_args!("    let mut parser = {parser}::default();\n"))?;
+            wtr.write_fmt(format_args!(
+                "    lalr1_plus::Parser::parse_to_from(&mut parser, {start_state}, text, \"\"){conversion}\n"
+            ))?;
+            wtr.write_all(b"}\n")?;
+        }
+        Ok(())
+    }
+
+    // Precedence resolution may remove every reduction of a production (and hence
+    // make states unreachable) so find the productions that are still reduced in
+    // states reachable from the start state and warn about the rest.
+    fn check_for_unreducible_productions(&mut self) {
+        let mut producible = BTreeSet::<String>::new();
+        let mut reduced = BTreeSet::<ProductionId>::new();
+        loop {
+            let producible_count = producible.len();
+            let mut reachable = BTreeSet::<u32>::new();
+            let mut unvisited: Vec<u32> = self.start_states.iter().map(|x| x.1).collect();
+            while let Some(ident) = unvisited.pop() {
+                if !reachable.insert(ident) {
+                    continue;
+                }
+                let parser_state = &self.parser_states[ident as usize];
+                for production in parser_state.reduced_productions() {
+                    reduced.insert(production.ident());
+                    producible.insert(production.left_hand_side().name().to_string());
+                }
+                unvisited.extend(
+                    parser_state.successors(|x| x.is_error() || producible.contains(x.name())),
+                );
+            }
+            if producible.len() == producible_count {
+                break;
+            }
+        }
+        let unreducible: Vec<Production> = self
+            .specification
+            .productions
+            .iter()
+            .filter(|x| !x.is_start_production() && !reduced.contains(&x.ident()))
+            .cloned()
+            .collect();
+        for production in unreducible {
+            let location = production
+                .left_hand_side()
+                .first_definition()
+                .expect("should be defined");
+            self.specification.warning(
+                &location,
+                &format!("{production}: can never be reduced after precedence resolution"),
+            );
+        }
+    }
+
+    // Recovery can only resume at a "%recover" token that can follow "%error" somewhere
+    fn check_recovery_tokens(&mut self) {
+        let mut unviable = vec![];
+        for (name, location) in self.specification.recovery_tokens.iter() {
+            let token = self.specification.symbol_table.get_token(name);
+            if token.is_none_or(|token| self.error_recovery_state_set_for_token(token).is_empty()) {
+                unviable.push((name.clone(), location.clone()));
+            }
+        }
+        for (name, location) in unviable.iter() {
+            self.specification.warning(
+                location,
+                &format!("{name}: can't follow %error so recovery can never resume at it"),
+            );
+        }
+    }
+
+    /// The associativity and precedence given to the token called `name` (if any).
+    pub fn token_precedence(&self, name: &str) -> Option<(Associativity, u32)> {
+        let token = self.specification.symbol_table.get_token(name)?;
+        token.precedence_declared_at()?;
+        let (associativity, precedence) = token.associativity_and_precedence();
+        Some((associativity, u32::from(precedence)))
+    }
+
+    fn write_token_precedence_table_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        wtr.write_all(
+            b"/// The associativity and precedence of each token given them (by token name).\n",
+        )?;
+        wtr.write_fmt(format_args!(
+            "pub const {prefix}TOKEN_PRECEDENCES: &[(&str, &str, u32)] = &[\n"
+        ))?;
+        for token in self.specification.symbol_table.tokens() {
+            if let Some((associativity, precedence)) = self.token_precedence(token.name()) {
+                wtr.write_fmt(format_args!(
+                    "    (r###\"{}\"###, \"{associativity}\", {precedence}),\n",
+                    token.name()
+                ))?;
+            }
+        }
+        wtr.write_all(b"];\n\n")?;
+        Ok(())
+    }
+
+    /// The grammar in a deterministic form for committing and diffing: sorted symbols and
+    /// productions (numbered within their left hand side) with explicit precedences but
+    /// without locations or actions.
+    pub fn canonical_text(&self) -> String {
+        let mut buffer = vec![];
+        self.specification
+            .symbol_table
+            .write_canonical_text(&mut buffer)
+            .expect("writing to a Vec can't fail");
+        let mut text = String::from_utf8(buffer).expect("descriptions are UTF-8");
+        let mut groups = BTreeMap::<&str, Vec<&Production>>::new();
+        for production in self.specification.productions.iter() {
+            groups
+                .entry(production.left_hand_side().name())
+                .or_default()
+                .push(production);
+        }
+        text += "Productions:\n";
+        for (name, productions) in groups {
+            for (index, production) in productions.iter().enumerate() {
+                let tail = production.to_string().split_off(name.len() + 1);
+                text += &format!("  {name}.{}:{tail}\n", index + 1);
+            }
+        }
+        text
+    }
+
+    /// Constructs that are prone to ambiguity (whether or not they cause conflicts) found
+    /// by heuristics: symmetric binary productions without precedence, optional suffixes
+    /// after right recursion (the "dangling else") and nested optionals.
+    pub fn ambiguity_report(&self) -> Vec<(lexan::Location, String)> {
+        let productions = &self.specification.productions;
+        let location = |non_terminal: &NonTerminal| {
+            non_terminal.first_definition().expect("should be defined")
+        };
+        let is_nullable = |symbol: &Symbol| match symbol {
+            Symbol::Terminal(_) => false,
+            Symbol::NonTerminal(non_terminal) => non_terminal.firsts_data().transparent,
+        };
+        // The non terminals that may end the derivations of each non terminal
+        let mut right_ends = BTreeMap::<String, BTreeSet<String>>::new();
+        loop {
+            let previous_total: usize = right_ends.values().map(|x| x.len()).sum();
+            for production in productions.iter() {
+                let lhs = production.left_hand_side().name().to_string();
+                let rhs: Vec<&Symbol> = production.right_hand_side_symbols().collect();
+                for symbol in rhs.iter().rev() {
+                    if let Symbol::NonTerminal(non_terminal) = symbol {
+                        let mut ends = right_ends
+                            .get(non_terminal.name())
+                            .cloned()
+                            .unwrap_or_default();
+                        ends.insert(non_terminal.name().to_string());
+                        right_ends.entry(lhs.clone()).or_default().extend(ends);
+                    }
+                    if !is_nullable(symbol) {
+                        break;
+                    }
+                }
+            }
+            if right_ends.values().map(|x| x.len()).sum::<usize>() == previous_total {
+                break;
+            }
+        }
+        let mut report = vec![];
+        for production in productions.iter().skip(1) {
+            let lhs = production.left_hand_side();
+            let rhs: Vec<&Symbol> = production.right_hand_side_symbols().collect();
+            if production.precedence() > 0 {
+                continue;
+            }
+            let recursive = Symbol::NonTerminal(lhs.clone());
+            if rhs.len() == 3 && *rhs[0] == recursive && *rhs[2] == recursive {
+                report.push((
+                    location(lhs),
+                    format!("{production}: symmetric binary production without precedence"),
+                ));
+            }
+            if let Some(Symbol::NonTerminal(last)) = rhs.last() {
+                if last == lhs
+                    || right_ends
+                        .get(last.name())
+                        .is_some_and(|x| x.contains(lhs.name()))
+                {
+                    for longer in productions
+                        .iter()
+                        .filter(|x| x.left_hand_side() == lhs && x.len() > rhs.len())
+                    {
+                        if longer
+                            .right_hand_side_symbols()
+                            .take(rhs.len())
+                            .eq(rhs.iter().copied())
+                        {
+                            report.push((
+                                location(lhs),
+                                format!("{production}: right recursion followed by an optional suffix (as in {longer})"),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        for non_terminal in self.specification.symbol_table.non_terminals() {
+            let count = productions
+                .iter()
+                .filter(|x| {
+                    x.left_hand_side() == non_terminal
+                        && x.right_hand_side_symbols().all(is_nullable)
+                })
+                .count();
+            if count > 1 {
+                report.push((
+                    location(non_terminal),
+                    format!(
+                        "{}: {count} productions derive the empty string (nested optionals)",
+                        non_terminal.name()
+                    ),
+                ));
+            }
+        }
+        report
+    }
+
+    fn error_recovery_state_set_for_token(&self, token: &Token) -> BTreeSet<u32> {
+        self.parser_states
+            .iter()
+            .filter(|x| x.is_recovery_state_for_token(token))
+            .map(|x| x.ident())
+            .collect()
+    }
+
+    fn format_u32_set(set: &BTreeSet<u32>) -> String {
+        let mut string = "btree_set![".to_string();
+        for (index, number) in set.iter().enumerate() {
+            if index == 0 {
+                string += &format!("{number}");
+            } else {
+                string += &format!(", {number}");
+            }
+        }
+        string += "]";
+        string
+    }
+
+    fn write_error_recovery_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let mut default_required = false;
+        let mut recovery_states = Vec::<(&str, BTreeSet<u32>)>::new();
+        let recovery_tokens = &self.specification.recovery_tokens;
+        for token in std::iter::once(self.specification.symbol_table.end_token())
+            .chain(self.specification.symbol_table.tokens())
+        {
+            let set = if recovery_tokens.is_empty() || recovery_tokens.contains_key(token.name()) {
+                self.error_recovery_state_set_for_token(token)
+            } else {
+                BTreeSet::new()
+            };
+            if !set.is_empty() {
+                recovery_states.push((token.name(), set));
+            } else {
+                default_required = true;
+            }
+        }
+        if recovery_states.is_empty() {
+            wtr.write_fmt(format_args!(
+                "    fn viable_error_recovery_states(_token: &{prefix}Terminal) -> BTreeSet<u32> {{\n",
+            ))?;
+            wtr.write_all(b"        btree_set![]\n")?;
+            wtr.write_all(b"    }\n\n")?;
+        } else {
+            wtr.write_fmt(format_args!(
+                "    fn viable_error_recovery_states(token: &{prefix}Terminal) -> BTreeSet<u32> {{\n",
+            ))?;
+            wtr.write_all(b"        match token {\n")?;
+            for (name, set) in recovery_states {
+                wtr.write_fmt(format_args!(
+                    "            {prefix}Terminal::{} => {},\n",
+                    name,
+                    Self::format_u32_set(&set)
+                ))?;
+            }
+            if default_required {
+                wtr.write_all(b"            _ => btree_set![],\n")?;
+            }
+            wtr.write_all(b"        }\n")?;
+            wtr.write_all(b"    }\n\n")?;
+            wtr.write_all(b"    fn error_goto_state(state: u32) -> u32 {\n")?;
+            wtr.write_all(b"        match state {\n")?;
+            for parser_state in self.parser_states.iter() {
+                if let Some(goto_state_id) = parser_state.error_goto_state_ident() {
+                    wtr.write_fmt(format_args!(
+                        "            {:1} => {:1},\n",
+                        parser_state.ident(),
+                        goto_state_id
+                    ))?;
+                }
+            }
+            wtr.write_all(b"            _ => panic!(\"No error go to state for {state}\")\n")?;
+            wtr.write_all(b"        }\n")?;
+            wtr.write_all(b"    }\n\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_look_ahead_set_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        wtr.write_fmt(format_args!(
+            "    fn look_ahead_set(state: u32) -> BTreeSet<{prefix}Terminal> {{\n"
+        ))?;
+        if self.table_driven {
+            wtr.write_fmt(format_args!("        let {aa}state = state as usize;\n"))?;
+            wtr.write_fmt(format_args!(
+                "        if {aa}state + 1 >= {prefix}LOOK_AHEAD_ROWS.len() {{\n"
+            ))?;
+            wtr.write_all(b"            panic!(\"illegal state: {state}\");\n")?;
+            wtr.write_all(b"        }\n")?;
+            wtr.write_fmt(format_args!(
+                "        {prefix}LOOK_AHEAD[{prefix}LOOK_AHEAD_ROWS[{aa}state]..{prefix}LOOK_AHEAD_ROWS[{aa}state + 1]]\n",
+            ))?;
+            wtr.write_all(b"            .iter()\n")?;
+            wtr.write_all(b"            .copied()\n")?;
+            wtr.write_all(b"            .collect()\n")?;
+            wtr.write_all(b"    }\n\n")?;
+            return Ok(());
+        }
+        wtr.write_fmt(format_args!("        use {prefix}Terminal::*;\n"))?;
+        wtr.write_all(b"        return match state {\n")?;
+        for parser_state in self.parser_states.iter() {
+            wtr.write_fmt(format_args!(
+                "            {} => {},\n",
+                parser_state.ident(),
+                parser_state.look_ahead_set().formated_as_macro_call()
+            ))?;
+        }
+        wtr.write_all(b"            _ => panic!(\"illegal state: {state}\"),\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    fn action_table(&self) -> CompressedTable {
+        let tokens: Vec<Token> = std::iter::once(self.specification.symbol_table.end_token())
+            .chain(self.specification.symbol_table.tokens())
+            .cloned()
+            .collect();
+        let rows: Vec<Vec<u32>> = self
+            .parser_states
+            .iter()
+            .map(|x| x.action_table_row(&tokens))
+            .collect();
+        CompressedTable::new(&rows)
+    }
+
+    fn write_action_table_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let table = self.action_table();
+        for (name, array) in [
+            ("BASE", &table.base),
+            ("DEFAULT", &table.default),
+            ("CHECK", &table.check),
+            ("NEXT", &table.next),
+        ] {
+            let entries: Vec<String> = array.iter().map(|x| x.to_string()).collect();
+            wtr.write_fmt(format_args!(
+                "const {}{name}: &[u32] = &[{}];\n",
+                self.specification.prefix(),
+                entries.join(", ")
+            ))?;
+        }
+        wtr.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn write_compressed_next_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        wtr.write_fmt(format_args!(
+            "        if {aa}state as usize >= {prefix}BASE.len() {{\n"
+        ))?;
+        wtr.write_all(b"            return Action::IllegalState;\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_fmt(format_args!(
+            "        let {aa}index = {prefix}BASE[{aa}state as usize] as usize + {aa}tag as usize;\n",
+        ))?;
+        wtr.write_fmt(format_args!(
+            "        let {aa}entry = if {prefix}CHECK.get({aa}index) == Some(&{aa}state) {{\n"
+        ))?;
+        wtr.write_fmt(format_args!("            {prefix}NEXT[{aa}index]\n"))?;
+        wtr.write_all(b"        } else {\n")?;
+        wtr.write_fmt(format_args!(
+            "            {prefix}DEFAULT[{aa}state as usize]\n"
+        ))?;
+        wtr.write_all(b"        };\n")?;
+        wtr.write_fmt(format_args!("        match {aa}entry {{\n"))?;
+        wtr.write_fmt(format_args!(
+            "            {} => Action::SyntaxError,\n",
+            tables::SYNTAX_ERROR
+        ))?;
+        wtr.write_fmt(format_args!(
+            "            {} => Action::Accept,\n",
+            tables::ACCEPT
+        ))?;
+        wtr.write_fmt(format_args!(
+            "            {} => match {aa}state {{\n",
+            tables::PREDICATED
+        ))?;
+        for parser_state in self.parser_states.iter() {
+            if parser_state.has_predicated_reductions() {
+                parser_state.write_next_action_code(
+                    wtr,
+                    "                ",
+                    &aa,
+                    self.strict_predicates,
+                    self.checked_predicates,
+                )?;
+            }
+        }
+        wtr.write_all(b"                _ => Action::IllegalState,\n")?;
+        wtr.write_all(b"            },\n")?;
+        wtr.write_fmt(format_args!(
+            "            _ if {aa}entry % 2 == {} => Action::Shift(({aa}entry - {}) / 2),\n",
+            tables::shift(0) % 2,
+            tables::shift(0)
+        ))?;
+        wtr.write_fmt(format_args!(
+            "            _ => Action::Reduce(({aa}entry - {}) / 2),\n",
+            tables::reduce(0)
+        ))?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    fn write_next_action_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        wtr.write_all(b"    fn next_action(\n")?;
+        wtr.write_all(b"        &self,\n")?;
+        wtr.write_fmt(format_args!("        {aa}state: u32,\n"))?;
+        wtr.write_fmt(format_args!(
+            "        {aa}attributes: &lalr1_plus::ParseStack<{prefix}Terminal, {prefix}NonTerminal, {}>,\n",
+            self.specification.stack_attribute_type()
+        ))?;
+        wtr.write_fmt(format_args!(
+            "        {aa}token: &lexan::Token<{prefix}Terminal>,\n"
+        ))?;
+        wtr.write_all(b"    ) -> lalr1_plus::Action {\n")?;
+        wtr.write_all(b"        use lalr1_plus::Action;\n")?;
+        let compressed = self.compress_tables || self.table_driven;
+        if !compressed
+            || self
+                .parser_states
+                .iter()
+                .any(|x| x.has_predicated_reductions())
+        {
+            wtr.write_fmt(format_args!("        use {prefix}Terminal::*;\n"))?;
+        }
+        wtr.write_fmt(format_args!("        let {aa}tag = *{aa}token.tag();\n"))?;
+        if compressed {
+            return self.write_compressed_next_action_code(wtr);
+        }
+        wtr.write_fmt(format_args!("        return match {aa}state {{\n"))?;
+        for parser_state in self.parser_states.iter() {
+            parser_state.write_next_action_code(
+                wtr,
+                "            ",
+                &aa,
+                self.strict_predicates,
+                self.checked_predicates,
+            )?;
+        }
+        wtr.write_all(b"            _ => Action::IllegalState,\n")?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    fn write_possible_actions_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        wtr.write_fmt(format_args!(
+            "    fn possible_actions({aa}state: u32, {aa}tag: &{prefix}Terminal) -> Vec<lalr1_plus::Action> {{\n",
+        ))?;
+        wtr.write_all(b"        use lalr1_plus::Action;\n")?;
+        wtr.write_fmt(format_args!("        use {prefix}Terminal::*;\n"))?;
+        wtr.write_fmt(format_args!("        match {aa}state {{\n"))?;
+        for parser_state in self.parser_states.iter() {
+            parser_state.write_possible_actions_code(wtr, "            ", &aa)?;
+        }
+        wtr.write_fmt(format_args!(
+            "            _ => panic!(\"illegal state: {{{aa}state}}\"),\n"
+        ))?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    // Each state's row of gotos (sorted by non terminal for binary search) and look ahead
+    // set are the slices of a flattened array between consecutive offsets
+    fn write_goto_and_look_ahead_tables_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let symbol_table = &self.specification.symbol_table;
+        let order: Vec<String> = symbol_table
+            .used_non_terminal_specials()
+            .iter()
+            .chain(symbol_table.non_terminals())
+            .map(|x| x.name().to_string())
+            .collect();
+        let mut goto_rows = vec!["0".to_string()];
+        let mut gotos = vec![];
+        let mut look_ahead_rows = vec!["0".to_string()];
+        let mut look_aheads = vec![];
+        for parser_state in self.parser_states.iter() {
+            let mut entries = parser_state.goto_entries();
+            entries.sort_by_key(|(non_terminal, _)| {
+                order.iter().position(|name| name == non_terminal.name())
+            });
+            for (non_terminal, state) in entries {
+                gotos.push(format!(
+                    "({prefix}NonTerminal::{}, {state})",
+                    non_terminal.name()
+                ));
+            }
+            goto_rows.push(gotos.len().to_string());
+            for token in parser_state.look_ahead_set().iter() {
+                look_aheads.push(format!("{prefix}Terminal::{}", token.name()));
+            }
+            look_ahead_rows.push(look_aheads.len().to_string());
+        }
+        wtr.write_fmt(format_args!(
+            "const {prefix}GOTO_ROWS: &[usize] = &[{}];\n",
+            goto_rows.join(", ")
+        ))?;
+        wtr.write_fmt(format_args!(
+            "const {prefix}GOTO: &[({prefix}NonTerminal, u32)] = &[{}];\n",
+            gotos.join(", ")
+        ))?;
+        wtr.write_fmt(format_args!(
+            "const {prefix}LOOK_AHEAD_ROWS: &[usize] = &[{}];\n",
+            look_ahead_rows.join(", ")
+        ))?;
+        wtr.write_fmt(format_args!(
+            "const {prefix}LOOK_AHEAD: &[{prefix}Terminal] = &[{}];\n\n",
+            look_aheads.join(", ")
+        ))?;
+        Ok(())
+    }
+
+    fn write_goto_table_code<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        wtr.write_fmt(format_args!(
+            "    fn goto_state(lhs: &{prefix}NonTerminal, current_state: u32) -> u32 {{\n"
+        ))?;
+        if self.table_driven {
+            wtr.write_fmt(format_args!(
+                "        let {aa}state = current_state as usize;\n"
+            ))?;
+            wtr.write_fmt(format_args!(
+                "        if {aa}state + 1 < {prefix}GOTO_ROWS.len() {{\n"
+            ))?;
+            wtr.write_fmt(format_args!(
+                "            let {aa}row = &{prefix}GOTO[{prefix}GOTO_ROWS[{aa}state]..{prefix}GOTO_ROWS[{aa}state + 1]];\n",
+            ))?;
+            wtr.write_fmt(format_args!(
+                "            if let Ok({aa}index) = {aa}row.binary_search_by(|x| x.0.cmp(lhs)) {{\n",
+            ))?;
+            wtr.write_fmt(format_args!(
+                "                return {aa}row[{aa}index].1;\n"
+            ))?;
+            wtr.write_all(b"            }\n")?;
+            wtr.write_all(b"        }\n")?;
+            wtr.write_all(b"        panic!(\"Malformed goto table: ({lhs}, {current_state})\")\n")?;
+            wtr.write_all(b"    }\n\n")?;
+            return Ok(());
+        }
+        wtr.write_all(b"        return match current_state {\n")?;
+        for parser_state in self.parser_states.iter() {
+            parser_state.write_goto_table_code(wtr, "            ", prefix)?;
+        }
+        wtr.write_all(
+            b"            _ => panic!(\"Malformed goto table: ({lhs}, {current_state})\"),\n",
+        )?;
+        wtr.write_all(b"        }\n")?;
+        wtr.write_all(b"    }\n\n")?;
+        Ok(())
+    }
+
+    // How the generated unparser is to treat the nodes of `production` (as the code for a
+    // `lalr1_plus::ProductionShape`): operators are recognised by the precedences of
+    // their tokens and parentheses by their text.
+    fn production_shape(&self, production: &Production) -> String {
+        let lhs = production.left_hand_side();
+        let rhs: Vec<&Symbol> = production.right_hand_side_symbols().collect();
+        let is_lhs = |symbol: &Symbol| matches!(symbol, Symbol::NonTerminal(nt) if nt == lhs);
+        match rhs.as_slice() {
+            [Symbol::Terminal(open), operand, Symbol::Terminal(close)]
+                if is_lhs(operand) && open.text() == "\"(\"" && close.text() == "\")\"" =>
+            {
+                "lalr1_plus::ProductionShape::Parenthesized".to_string()
+            }
+            [left, Symbol::Terminal(operator), right] if is_lhs(left) && is_lhs(right) => {
+                match self.token_precedence(operator.name()) {
+                    Some((associativity, precedence)) => format!(
+                        "lalr1_plus::ProductionShape::Infix(lalr1_plus::Associativity::{associativity}, {precedence})"
+                    ),
+                    None => "lalr1_plus::ProductionShape::Plain".to_string(),
+                }
+            }
+            [Symbol::Terminal(_), operand] if is_lhs(operand) && production.precedence() > 0 => {
+                format!(
+                    "lalr1_plus::ProductionShape::Prefix({})",
+                    production.precedence()
+                )
+            }
+            _ => "lalr1_plus::ProductionShape::Plain".to_string(),
+        }
+    }
+
+    /// Write an unparser for the parser's parse trees: `aa_production_shape()`, giving
+    /// the shape of each production, and `aa_unparse()` rendering a tree with only the
+    /// parentheses that its operators' precedences need (see `lalr1_plus::unparse()`).
+    /// The parser's symbol types are named with `path` (e.g. "calc::" to reach into its
+    /// namespace).
+    pub fn write_unparser_code<W: Write>(&self, wtr: &mut W, path: &str) -> io::Result<()> {
+        let prefix = self.specification.prefix();
+        let aa = self.specification.variable_prefix();
+        let mut arms = BTreeMap::<String, Vec<String>>::new();
+        for production in self.specification.productions.iter() {
+            let shape = self.production_shape(production);
+            if !shape.ends_with("Plain") {
+                arms.entry(shape)
+                    .or_default()
+                    .push(production.ident().to_string());
+            }
+        }
+        wtr.write_all(b"/// The shape of each production for `lalr1_plus::unparse()`.\n")?;
+        wtr.write_fmt(format_args!(
+            "pub fn {aa}production_shape({aa}production_id: u32) -> lalr1_plus::ProductionShape {{\n"
+        ))?;
+        wtr.write_fmt(format_args!("    match {aa}production_id {{\n"))?;
+        for (shape, idents) in arms.iter() {
+            wtr.write_fmt(format_args!("        {} => {shape},\n", idents.join(" | ")))?;
+        }
+        wtr.write_all(b"        _ => lalr1_plus::ProductionShape::Plain,\n")?;
+        wtr.write_all(b"    }\n}\n\n")?;
+        wtr.write_all(
+            b"/// Render `tree` back into text with only the parentheses that its operators need.\n",
+        )?;
+        wtr.write_fmt(format_args!(
+            "pub fn {aa}unparse(tree: &lalr1_plus::ParseTree<{path}{prefix}Terminal, {path}{prefix}NonTerminal>) -> String {{\n"
+        ))?;
+        wtr.write_fmt(format_args!(
+            "    lalr1_plus::unparse(tree, &{aa}production_shape)\n"
+        ))?;
+        wtr.write_all(b"}\n\n")?;
+        Ok(())
+    }
+
+    /// Write the unparser (see `write_unparser_code()`) to the file at `file_path` for
+    /// inclusion alongside the parser's code.
+    pub fn write_unparser(&self, file_path: &Path) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(file_path)?);
+        file.write_all(b"// generated by alap_gen.\n\n")?;
+        let path = match &self.namespace {
+            Some(namespace) => format!("{namespace}::"),
+            None => String::new(),
+        };
+        self.write_unparser_code(&mut file, &path)?;
+        file.flush()
+    }
+
+    pub fn write_description<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        self.specification.symbol_table.write_description(wtr)?;
+        wtr.write_all(b"\nProductions:\n")?;
+        for production in self.specification.productions.iter() {
+            wtr.write_fmt(format_args!("  {production}\n"))?;
+        }
+        self.specification.write_sets_description(wtr)?;
+        self.write_start_states_description(wtr)?;
+        for parser_state in self.parser_states.iter() {
+            parser_state.write_description(wtr)?;
+        }
+        Ok(())
+    }
+
+    // The start symbols share the states that they can (rather than each having its own
+    // automaton) so say how many states each adds to those of the first
+    fn write_start_states_description<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        if self.start_states.len() < 2 {
+            return Ok(());
+        }
+        wtr.write_all(b"\nStart States:\n")?;
+        let mut reachable = BTreeSet::<u32>::new();
+        for (name, start_state) in self.start_states.iter() {
+            let mut added = 0;
+            let mut unvisited = vec![*start_state];
+            while let Some(ident) = unvisited.pop() {
+                if reachable.insert(ident) {
+                    added += 1;
+                    unvisited.extend(self.parser_states[ident as usize].successors(|_| true));
+                }
+            }
+            wtr.write_fmt(format_args!(
+                "  {name}: State<{start_state}> (adding {added} states)\n"
+            ))?;
+        }
+        wtr.write_fmt(format_args!(
+            "  (the start symbols share one automaton of {} states)\n",
+            self.parser_states.len()
+        ))?;
+        Ok(())
+    }
+
+    pub fn write_description_to_file(&self, file_path: &Path) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(file_path)?);
+        self.write_description(&mut file)?;
+        file.flush()
+    }
+
+    /// Write the parser's states as a Graphviz (DOT) digraph: solid edges for shifts and
+    /// dashed ones for gotos.
+    pub fn write_automaton_dot<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        wtr.write_all(b"digraph automaton {\n")?;
+        wtr.write_all(b"  node [shape=box, fontname=monospace];\n")?;
+        for parser_state in self.parser_states.iter() {
+            parser_state.write_dot_code(wtr)?;
+        }
+        wtr.write_all(b"}\n")
+    }
+
+    pub fn write_automaton_dot_to_file(&self, file_path: &Path) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(file_path)?);
+        self.write_automaton_dot(&mut file)?;
+        file.flush()
+    }
+
+    /// Every conflict found (whether resolved by precedence, by default or not at all) as
+    /// a JSON object with a "conflicts" array whose elements give the state, the kind of
+    /// conflict, the token, the ids of the productions (that reduced first) and the
+    /// resolution.
+    pub fn conflicts_json(&self) -> String {
+        let mut conflicts = vec![];
+        for parser_state in self.parser_states.iter() {
+            for record in parser_state.conflict_records() {
+                let kind = match record.kind {
+                    ConflictKind::ShiftReduce => "shift/reduce",
+                    ConflictKind::ReduceReduce => "reduce/reduce",
+                };
+                let resolution = match record.resolution {
+                    Resolution::Shift => "shift",
+                    Resolution::Reduce => "reduce",
+                    Resolution::Unresolved => "unresolved",
+                };
+                let productions: Vec<String> =
+                    record.productions.iter().map(|x| x.to_string()).collect();
+                conflicts.push(format!(
+                    "    {{\"state\": {}, \"kind\": \"{kind}\", \"token\": {}, \"productions\": [{}], \"resolution\": \"{resolution}\"}}",
+                    parser_state.ident(),
+                    json_string(record.token.name()),
+                    productions.join(", "),
+                ));
+            }
+        }
+        if conflicts.is_empty() {
+            "{\"conflicts\": []}\n".to_string()
+        } else {
+            format!("{{\"conflicts\": [\n{}\n]}}\n", conflicts.join(",\n"))
+        }
+    }
+
+    pub fn describe_sr_conflict_states(&self) -> String {
+        let mut string = String::new();
+        for parser_state in self.parser_states.iter() {
+            if parser_state.shift_reduce_conflict_count() > 0 {
+                string += parser_state.description().as_str();
+            }
+        }
+        string
+    }
+
+    pub fn describe_rr_conflict_states(&self) -> String {
+        let mut string = String::new();
+        for parser_state in self.parser_states.iter() {
+            if parser_state.reduce_reduce_conflict_count() > 0 {
+                string += parser_state.description().as_str();
+            }
+        }
+        string
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIALECTS: &str = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%if power
+%token POWER "**"
+%endif
+%%
+Expr: Expr "+" Expr
+%if power
+    | Expr "**" Expr
+%endif
+    | NUMBER
+    .
+"#;
+
+    // The code generated for `grammar`
+    fn generated_code(grammar: &Grammar) -> String {
+        let mut code = vec![];
+        grammar.write_parser_code(&mut code).unwrap();
+        String::from_utf8(code).unwrap()
+    }
+
+    // The code generated (ignoring conflicts) from `text` which mustn't have errors
+    fn parser_code(text: &str, features: &[String]) -> String {
+        let specification = Specification::new(text, "test", features).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        generated_code(&grammar)
+    }
+
+    #[test]
+    fn conditional_sections() {
+        let plain = parser_code(DIALECTS, &[]);
+        let power = parser_code(DIALECTS, &["power".to_string()]);
+        assert!(!plain.contains("AATerminal::POWER"));
+        assert!(power.contains("AATerminal::POWER"));
+        assert!(power.contains("Expr \"**\" Expr"));
+
+        let text = DIALECTS.to_string() + "%if power\n";
+        let specification = Specification::new(&text, "unterminated", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn extra_derives() {
+        let text = DIALECTS.replace(
+            "%target Calc\n",
+            "%target Calc\n%derive \"Hash, serde::Serialize\"\n",
+        );
+        let specification = Specification::new(&text, "derives", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert_eq!(specification.derives, vec!["Hash", "serde::Serialize"]);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let code = generated_code(&grammar);
+        let derive = "#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash, serde::Serialize)]\n";
+        assert!(code.contains(&(derive.to_string() + "pub enum AATerminal {")));
+        assert!(code.contains(&(derive.to_string() + "pub enum AANonTerminal {")));
+
+        for list in ["Hash, Eq", "Hash, Hash", "Hash Ord", "", "Hash,", "serde::"] {
+            let text = DIALECTS.replace(
+                "%target Calc\n",
+                &format!("%target Calc\n%derive \"{list}\"\n"),
+            );
+            let specification = Specification::new(&text, "derives", &[]).unwrap();
+            assert_eq!(specification.error_count, 1, "{list}");
+        }
+    }
+
+    #[test]
+    fn no_std_parser() {
+        let code = |no_std: bool| {
+            let specification = Specification::new(DIALECTS, "dialects", &[]).unwrap();
+            let mut grammar = Grammar::try_from((specification, true, true)).unwrap();
+            grammar.no_std = no_std;
+            generated_code(&grammar)
+        };
+        let with_std = code(false);
+        assert!(with_std.contains("use std::collections::BTreeSet;\n"));
+        assert!(!with_std.contains("alloc_prelude"));
+        let without_std = code(true);
+        assert!(!without_std.contains("std::"));
+        assert!(
+            without_std.contains("#[allow(unused_imports)]\nuse lalr1_plus::alloc_prelude::*;\n")
+        );
+    }
+
+    #[test]
+    fn token_display_names() {
+        let text = DIALECTS
+            .replace("\"+\"\n", "\"+\" \"plus sign\"\n")
+            .replace("([0-9]+)", "([0-9]+) \"number {n}\"");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains("AATerminal::PLUS => write!(f, r###\"plus sign\"###),"));
+        assert!(code.contains("AATerminal::NUMBER => write!(f, r###\"number {{n}}\"###),"));
+        // the default is the literal's text
+        assert!(
+            parser_code(DIALECTS, &[]).contains("AATerminal::PLUS => write!(f, r###\"\"+\"\"###),")
+        );
+
+        let text = DIALECTS.replace("\"+\"\n", "\"+\" \" \"\n");
+        let specification = Specification::new(&text, "display", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    const TYPED: &str = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%type Expr f64 // the value
+%%
+Exprs: Expr+ !{ self.count = $1.len(); !} .
+Expr: Expr "+" Expr ?($3 == 0.0?)
+    | Expr "+" Expr !{ $$ = $1 + $3; !}
+    | NUMBER !{ $$ = $1.value(); !}
+    .
+"#;
+
+    #[test]
+    fn typed_attributes() {
+        let code = parser_code(TYPED, &[]);
+        let take = |what: &str| {
+            format!("lalr1_plus::TypedAttribute::<f64>::into_typed(core::mem::take(&mut {what}))")
+        };
+        assert!(code.contains(&format!("let mut aa_v0: f64 = {};", take("aa_lhs"))));
+        assert!(code.contains(&format!("let mut aa_v3: f64 = {};", take("aa_rhs[2]"))));
+        assert!(code.contains("aa_v0 = aa_v1 + aa_v3;"));
+        assert!(code.contains("let mut aa_v0: f64;\n"));
+        assert!(code.contains("aa_v0 = aa_rhs[0].value();"));
+        assert!(code.contains("aa_lhs = lalr1_plus::TypedAttribute::<f64>::from_typed(aa_v0);"));
+        assert!(code.contains(
+            "(*lalr1_plus::TypedAttribute::<f64>::typed(aa_attributes.at_len_minus_n(1))) == 0.0"
+        ));
+        // the helpers (and the untyped) see the raw attributes
+        assert!(code.contains("self.count = aa_rhs[0].len();"));
+        assert!(code.contains(
+            "lalr1_plus::ListAttribute::push_element(&mut aa_lhs, core::mem::take(&mut aa_rhs[1]));"
+        ));
+    }
+
+    #[test]
+    fn functional_interface() {
+        assert!(!parser_code(TYPED, &[]).contains("pub fn parse("));
+        let text = TYPED.replace(
+            "%target Calc\n",
+            "%target Calc\n%start Exprs\n%start Expr\n%functional\n",
+        );
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "pub fn parse(text: &str) -> Result<AttributeData, lalr1_plus::Error<AATerminal>>\nwhere\n    Calc: Default,\n"
+        ));
+        assert!(code.contains("lalr1_plus::Parser::parse_to_from(&mut parser, 0, text, \"\")\n"));
+        // typed start symbols' functions return their values
+        assert!(code.contains(
+            "pub fn parse_expr(text: &str) -> Result<f64, lalr1_plus::Error<AATerminal>>"
+        ));
+        assert!(code.contains(
+            "lalr1_plus::Parser::parse_to_from(&mut parser, 1, text, \"\").map(lalr1_plus::TypedAttribute::<f64>::into_typed)\n"
+        ));
+    }
+
+    #[test]
+    fn typed_attribute_errors() {
+        for (old, new) in [
+            ("f64 // the value", "// no type"),
+            ("f64 // the value", "Vec<f64"),
+            ("%type Expr f64", "%type NUMBER f64"),
+            ("%type Expr f64", "%type Expr f64\n%type Expr f32"),
+            ("%type Expr f64", "%type Term f64"),
+            ("NUMBER !{ $$ = $1.value(); !}", "NUMBER"),
+        ] {
+            let text = TYPED.replace(old, new);
+            let specification = Specification::new(&text, "typed", &[]).unwrap();
+            assert_eq!(specification.error_count, 1, "{new}");
+        }
+    }
+
+    #[test]
+    fn rejected_reductions() {
+        let text = TYPED.replace(
+            "Expr: Expr \"+\" Expr ?($3 == 0.0?)\n",
+            "Expr: Expr \"+\" Expr ?($3 < 0.0?) %reject \"negative summand\"\n",
+        );
+        // rejections don't need to set $$
+        let code = parser_code(&text, &[]);
+        assert!(code.contains("        Action::SemanticError(\"negative summand\")\n"));
+    }
+
+    #[test]
+    fn token_precedences() {
+        let text = include_str!("../../examples/calc/src/calc.alaps");
+        let specification = Specification::new(text, "calc.alaps", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let mut grammar = Grammar::try_from((specification, false, false)).unwrap();
+        assert_eq!(
+            grammar.token_precedence("EOL"),
+            Some((Associativity::Left, 1))
+        );
+        for (name, precedence) in [("PLUS", 2), ("MINUS", 2), ("TIMES", 3), ("DIVIDE", 3)] {
+            assert_eq!(
+                grammar.token_precedence(name),
+                Some((Associativity::Left, precedence))
+            );
+        }
+        // neither operands nor tags (e.g. UMINUS) are tokens with precedences
+        assert_eq!(grammar.token_precedence("NUMBER"), None);
+        assert_eq!(grammar.token_precedence("UMINUS"), None);
+        grammar.emit_production_table = true;
+        let code = generated_code(&grammar);
+        assert!(code.contains("pub const AATOKEN_PRECEDENCES: &[(&str, &str, u32)] = &[\n"));
+        assert!(code.contains("    (r###\"TIMES\"###, \"Left\", 3),\n"));
+        assert!(!code.contains("    (r###\"NUMBER\"###"));
+    }
+
+    #[test]
+    fn explicit_precedence_levels() {
+        const LEVELS: &str = "%attr AttributeData\n%target Calc\n%%\n%token PLUS \"+\"\n%token TIMES \"*\"\n%token POW \"^\"\n%token NUMBER ([0-9]+)\n%left 20 TIMES\n%left 10 PLUS\n%left POW\n%%\nExpr: Expr PLUS Expr | Expr TIMES Expr | Expr POW Expr | NUMBER .\n";
+        let specification = Specification::new(LEVELS, "levels", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let precedence = |name: &str| {
+            specification
+                .symbol_table
+                .get_token(name)
+                .unwrap()
+                .associativity_and_precedence()
+        };
+        assert_eq!(precedence("TIMES"), (Associativity::Left, 20));
+        assert_eq!(precedence("PLUS"), (Associativity::Left, 10));
+        // implicit levels follow the highest level declared so far
+        assert_eq!(precedence("POW"), (Associativity::Left, 21));
+        assert!(Grammar::try_from((specification, false, false)).is_ok());
+
+        for level in ["0", "65536"] {
+            let text = LEVELS.replace("%left 10 PLUS", &format!("%left {level} PLUS"));
+            let specification = Specification::new(&text, "levels", &[]).unwrap();
+            assert_eq!(specification.error_count, 1, "{level}");
+        }
+    }
+
+    #[test]
+    fn default_shift_reduce_resolution() {
+        let specification = Specification::new(DIALECTS, "dialects", &[]).unwrap();
+        assert!(Grammar::try_from((specification, false, false)).is_err());
+
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%default-conflict shift\n");
+        let specification = Specification::new(&text, "default", &[]).unwrap();
+        assert!(specification.default_sr_shift);
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        assert!(grammar.describe_sr_conflict_states().is_empty());
+
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%default-conflict reduce\n");
+        let specification = Specification::new(&text, "unknown", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn conflict_items_described() {
+        let description = |text: &str| {
+            let specification = Specification::new(text, "conflicts", &[]).unwrap();
+            let grammar = Grammar::try_from((specification, true, true)).unwrap();
+            let mut description = vec![];
+            grammar.write_description(&mut description).unwrap();
+            String::from_utf8(description).unwrap()
+        };
+        let text = description(DIALECTS);
+        assert!(text.contains(
+            "    Expr: Expr . \"+\" Expr #(NonAssoc, 0): TokenSet{\"+\", AAEnd}  [kernel; shift \"+\"]\n"
+        ));
+        assert!(text.contains("  [kernel; reduce on \"+\"]\n"));
+        assert!(text
+            .contains("      shift -> State<3>\n        Expr: Expr . \"+\" Expr #(NonAssoc, 0)\n"));
+        // only the states with conflicts are annotated
+        assert_eq!(text.matches("  [kernel").count(), 2);
+
+        let text = description(
+            "%attr AttributeData\n%target Calc\n%%\n%token X \"x\"\n%%\nS: A | B .\nA: X .\nB: X .\n",
+        );
+        assert!(text.contains(
+            "    A: \"x\" .  #(NonAssoc, 0): TokenSet{AAEnd}  [kernel; reduce on AAEnd (chosen)]\n"
+        ));
+        assert!(text.contains("  [kernel; reduce on AAEnd (overruled)]\n"));
+    }
+
+    #[test]
+    fn first_attribute_cloned_only_if_used() {
+        let code = parser_code(DIALECTS, &[]);
+        assert!(code.contains("aa_rhs.first_mut().map(core::mem::take)"));
+        assert!(!code.contains("cloned()"));
+
+        let text = DIALECTS.replace("    | NUMBER\n", "    | NUMBER !{ $$ = $1.clone(); !}\n");
+        let specification = Specification::new(&text, "cloning", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let code = generated_code(&grammar);
+        let ident = grammar.specification.productions[2].ident();
+        assert!(code.contains(&format!(
+            "            {ident} => aa_rhs.first().cloned().unwrap_or_default(),\n"
+        )));
+    }
+
+    #[test]
+    fn look_ahead_sets_in_terminal_order() {
+        let code = parser_code(DIALECTS, &["power".to_string()]);
+        assert!(code
+            .contains("pub enum AATerminal {\n    AAEnd,\n    NUMBER,\n    PLUS,\n    POWER,\n}"));
+        // the end token would otherwise come last (and literals before regular expressions)
+        assert!(code.contains(" => btree_set![AAEnd, PLUS, POWER],\n"));
+        assert!(code.contains(" AAEnd | PLUS | POWER => Action::Reduce("));
+        assert!(code.contains(" => btree_set![NUMBER],\n"));
+    }
+
+    #[test]
+    fn state_kernels_commented() {
+        let code = parser_code(DIALECTS, &[]);
+        assert!(code.contains(
+            "            // State<1>:
+            //   AAStart: Expr .  #(NonAssoc, 0)
+            //   Expr: Expr . \"+\" Expr #(NonAssoc, 0)
+            1 => match aa_tag {
+"
+        ));
+        // only the kernel items (not the closure's)
+        assert!(code.contains(
+            "            // State<3>:
+            //   Expr: Expr \"+\" . Expr #(NonAssoc, 0)
+            3 => match aa_tag {
+"
+        ));
+    }
+
+    #[test]
+    fn delimiters_quoted_in_code() {
+        let text = DIALECTS.replace(
+            "    | NUMBER\n",
+            r#"    | NUMBER ?( $1.matched_text() != "?)" /* ?) */ ?)
+        !{ println!("{}", format!("?) !}} {}", '}')); /* !} */ // "quoted" it's !}
+    | NUMBER !{ // a plain comment !} | "+" NUMBER
+"#,
+        );
+        let code = parser_code(&text, &[]);
+        assert!(code
+            .contains(r#"if  aa_attributes.at_len_minus_n(1).matched_text() != "?)" /* ?) */  {"#));
+        assert!(code
+            .contains(r#"println!("{}", format!("?) !}} {}", '}')); /* !} */ // "quoted" it's"#));
+        assert!(code.contains("// a plain comment \n"));
+        assert!(code.contains("// Expr: \"+\" NUMBER"));
+    }
+
+    #[test]
+    fn rust_attribute_types() {
+        // a "%transform" makes token_attribute() name the attribute type
+        let text = |attr: &str, target: &str| {
+            DIALECTS
+                .replace(
+                    "%attr AttributeData\n%target Calc\n",
+                    &format!("%attr {attr}\n%target {target}\n"),
+                )
+                .replace(
+                    "%token NUMBER ([0-9]+)\n",
+                    "%token NUMBER ([0-9]+)\n    %transform !{ $1.trim() !}\n",
+                )
+        };
+        let code = parser_code(&text("AttributeData<'a> // borrowed", "Calc<'a>"), &[]);
+        assert!(code.contains(
+            "impl<'a> lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData<'a>> for Calc<'a> {\n"
+        ));
+        assert!(code.contains("        AttributeData::<'a>::from(aa_token)\n"));
+
+        let code = parser_code(
+            &text("(u32, Box<dyn Fn(u32) -> u32>) %spanned", "Calc"),
+            &[],
+        );
+        assert!(code.contains("lalr1_plus::Spanned<(u32, Box<dyn Fn(u32) -> u32>)>"));
+        assert!(code.contains("        <(u32, Box<dyn Fn(u32) -> u32>)>::from(aa_token)\n"));
+
+        let specification = Specification::new(&text("Vec<(u32, u32>)", "Calc"), "generic", &[]);
+        assert_eq!(specification.unwrap().error_count, 1);
+    }
+
+    #[test]
+    fn visitor_scaffold() {
+        assert!(!parser_code(DIALECTS, &[]).contains("pub trait AAVisitor"));
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%visitor\n");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains("pub trait AAVisitor {\n    type Output: Default;\n"));
+        assert!(code.contains(": Expr: Expr \"+\" Expr\n"));
+        assert!(code.contains(
+            "    fn visit_expr(&mut self, production: u32, children: Vec<Self::Output>) -> Self::Output {\n        self.visit_default(production, children)\n"
+        ));
+        // the start production is never reduced
+        assert!(!code.contains("fn visit_a_a_start"));
+        assert!(code.contains("aa_stack.split_off(aa_stack.len().saturating_sub(2));\n                    self.visit_expr(aa_production, aa_children)\n"));
+        assert!(code.contains("aa_stack.split_off(aa_stack.len().saturating_sub(0));\n                    self.visit_expr(aa_production, aa_children)\n"));
+    }
+
+    #[test]
+    fn derivation_recording() {
+        assert!(!parser_code(DIALECTS, &[]).contains("fn derivation"));
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%derivation reductions\n");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains("        Some(&mut self.reductions)\n"));
+        assert!(code.contains("    fn derivation(&self) -> &[u32] {\n        &self.reductions\n"));
+    }
+
+    #[test]
+    fn no_skip_non_terminals() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token NEWLINE "\n"
+%token SPACES ([ ]+)
+%token WORD ([a-z]+)
+%skip ([ ]+)
+%noskip Indent
+%%
+Lines: Line | Lines Line .
+Line: Indent WORD NEWLINE | WORD NEWLINE .
+Indent: NEWLINE SPACES .
+"#;
+        assert!(!parser_code(DIALECTS, &[]).contains("fn skips_suppressed_in"));
+        let specification = Specification::new(text, "noskip", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        // only the state after "Indent: NEWLINE . SPACES"
+        let states = grammar.no_skip_states();
+        assert_eq!(states.len(), 1);
+        let code = generated_code(&grammar);
+        assert!(code.contains(&format!(
+            "    fn skips_suppressed_in(state: u32) -> bool {{\n        matches!(state, {})\n",
+            states[0]
+        )));
+
+        for (old, new) in [
+            ("%noskip Indent", "%noskip Ident"),
+            ("%noskip Indent", "%noskip SPACES"),
+        ] {
+            let specification = Specification::new(&text.replace(old, new), "noskip", &[]).unwrap();
+            assert_eq!(specification.error_count, 1, "{new}");
+        }
+        let specification =
+            Specification::new(&text.replace("Indent\n", "Indent Indent\n"), "noskip", &[])
+                .unwrap();
+        assert_eq!(specification.warning_count, 1);
+    }
+
+    #[test]
+    fn recovery_tokens() {
+        let text = |recover: &str| {
+            format!(
+                "%attr AttributeData\n%target Calc\n%%\n%token SEMI \";\"\n%token ASSIGN \"=\"\n%token NUMBER ([0-9]+)\n%token ID ([a-z]+)\n{recover}\n%%\nStmts: Stmt | Stmts Stmt .\nStmt: ID \"=\" NUMBER \";\" | %error .\n"
+            )
+        };
+        let code = |recover: &str| {
+            let specification = Specification::new(&text(recover), "recover", &[]).unwrap();
+            assert_eq!(specification.error_count, 0);
+            let grammar = Grammar::try_from((specification, true, true)).unwrap();
+            assert_eq!(grammar.specification.warning_count, 0);
+            generated_code(&grammar)
+        };
+        // by default recovery may resume at any token that can follow %error
+        let unrestricted = code("");
+        assert!(unrestricted.contains("            AATerminal::AAEnd => btree_set!["));
+        assert!(unrestricted.contains("            AATerminal::ID => btree_set!["));
+        let restricted = code("%recover ID");
+        assert!(!restricted.contains("            AATerminal::AAEnd => btree_set!["));
+        assert!(restricted.contains("            AATerminal::ID => btree_set!["));
+        assert!(restricted.contains("    fn error_goto_state(state: u32) -> u32 {\n"));
+
+        // ";" never follows %error
+        let specification = Specification::new(&text("%recover \";\""), "recover", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        assert_eq!(grammar.specification.warning_count, 1);
+        let specification =
+            Specification::new(&text("%left PREC\n%recover PREC"), "recover", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn error_followed_by_sync_token() {
+        let text = "%attr AttributeData\n%target Calc\n%%\n%token SEMI \";\"\n%token ASSIGN \"=\"\n%token NUMBER ([0-9]+)\n%token ID ([a-z]+)\n%%\nStmts: Stmt | Stmts Stmt .\nStmt: ID \"=\" NUMBER \";\" | %error \";\" .\n";
+        let code = parser_code(text, &[]);
+        // recovery resumes at the ";" (which is then shifted) and nowhere else
+        assert!(code.contains("            AATerminal::SEMI => btree_set!["));
+        assert!(!code.contains("            AATerminal::ID => btree_set!["));
+        assert!(!code.contains("            AATerminal::AAEnd => btree_set!["));
+    }
+
+    #[test]
+    fn separated_lists() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token IDENT ([a-z]+)
+%token LPAREN "("
+%token RPAREN ")"
+%token COMMA ","
+%%
+Call: IDENT "(" Arg %% "," ")" .
+Arg: IDENT | Call .
+"#;
+        let bad_text = text.replace("Arg %% \",\"", "%error %% \",\"");
+        let specification = Specification::new(&bad_text, "arguments", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+        let specification = Specification::new(text, "arguments", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let productions: Vec<String> = specification
+            .productions
+            .iter()
+            .map(|production| production.to_string())
+            .collect();
+        assert_eq!(productions[0], "AAStart: Call #(NonAssoc, 0)");
+        assert!(productions[1].starts_with("Call: IDENT \"(\" AAArgOptListCOMMA \")\""));
+        assert!(productions
+            .iter()
+            .any(|p| p.starts_with("AAArgOptListCOMMA: AAArgListCOMMA ")));
+        assert!(productions
+            .iter()
+            .any(|p| p.starts_with("AAArgListCOMMA: AAArgListCOMMA \",\" Arg ")));
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let code = generated_code(&grammar);
+        assert!(code.contains("lalr1_plus::ListAttribute::empty_list()"));
+    }
+
+    #[test]
+    fn optional_symbols() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token IDENT ([a-z]+)
+%token COLON ":"
+%token SEMI ";"
+%%
+Stmt: IDENT? Tail? SEMI | %error? .
+Tail: COLON IDENT? .
+"#;
+        let specification = Specification::new(text, "optional", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+        let text = text.replace(" | %error? ", " ");
+        let specification = Specification::new(&text, "optional", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let productions: Vec<String> = specification
+            .productions
+            .iter()
+            .map(|production| production.to_string())
+            .collect();
+        assert!(productions[1].starts_with("Stmt: AAIDENTOpt AATailOpt \";\" #"));
+        assert!(productions[2].starts_with("AAIDENTOpt: <empty> #"));
+        assert!(productions[3].starts_with("AAIDENTOpt: IDENT #"));
+        // the same helper serves both uses of IDENT?
+        assert_eq!(
+            productions
+                .iter()
+                .filter(|p| p.starts_with("AAIDENTOpt:"))
+                .count(),
+            2
+        );
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let code = generated_code(&grammar);
+        assert!(
+            code.contains("lalr1_plus::OptionalAttribute::present(core::mem::take(&mut aa_lhs))")
+        );
+        let mut description = vec![];
+        grammar.write_description(&mut description).unwrap();
+        let description = String::from_utf8(description).unwrap();
+        assert!(description.contains(
+            "  Helper Non Terminals:\n    AAIDENTOpt: desugared from IDENT?\n    AATailOpt: desugared from Tail?\n"
+        ));
+    }
+
+    #[test]
+    fn repetitions() {
+        let text = |repeat: &str| {
+            format!(
+                r#"%attr AttributeData
+%target Calc
+{repeat}
+%%
+%token WORD ([a-z]+)
+%token DOT "."
+%left DOT
+%%
+Text: Sentence+ .
+Sentence: WORD* DOT .
+"#
+            )
+        };
+        let productions = |repeat: &str| {
+            let specification = Specification::new(&text(repeat), "repetitions", &[]).unwrap();
+            assert_eq!(specification.error_count, 0);
+            let productions: Vec<String> = specification
+                .productions
+                .iter()
+                .map(|production| production.to_string())
+                .collect();
+            let grammar = Grammar::try_from((specification, false, false)).unwrap();
+            (productions, generated_code(&grammar))
+        };
+        let (left, code) = productions("");
+        assert!(left.contains(&"Text: AASentencePlus #(NonAssoc, 0)".to_string()));
+        assert!(left.contains(&"AASentencePlus: Sentence #(NonAssoc, 0)".to_string()));
+        assert!(
+            left.contains(&"AASentencePlus: AASentencePlus Sentence #(NonAssoc, 0)".to_string())
+        );
+        assert!(left.contains(&"AAWORDStar: <empty> #(NonAssoc, 0)".to_string()));
+        assert!(left.contains(&"AAWORDStar: AAWORDStar WORD #(NonAssoc, 0)".to_string()));
+        // the helpers don't take on the precedence of their elements
+        assert!(left.contains(&"Sentence: AAWORDStar \".\" #(Left, 1)".to_string()));
+        assert!(code.contains(
+            "lalr1_plus::ListAttribute::push_element(&mut aa_lhs, core::mem::take(&mut aa_rhs[1]));"
+        ));
+        assert_eq!(productions("%repeat left").0, left);
+        let (right, code) = productions("%repeat right");
+        assert!(
+            right.contains(&"AASentencePlus: Sentence AASentencePlus #(NonAssoc, 0)".to_string())
+        );
+        assert!(right.contains(&"AAWORDStar: WORD AAWORDStar #(NonAssoc, 0)".to_string()));
+        assert!(code.contains(
+            "lalr1_plus::FrontListAttribute::push_front_element(&mut aa_lhs, aa_element);"
+        ));
+    }
+
+    #[test]
+    fn groups() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token WORD ([a-z]+)
+%token COMMA ","
+%token OPEN (\()
+%%
+Words: WORD (COMMA WORD)* ((WORD WORD) OPEN)? .
+More: COMMA (COMMA WORD) .
+"#;
+        let specification = Specification::new(text, "groups", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let productions: Vec<String> = specification
+            .productions
+            .iter()
+            .map(|production| production.to_string())
+            .collect();
+        // identical groups share a helper and nested groups are added first
+        assert!(productions.contains(&"AAGroup1: \",\" WORD #(NonAssoc, 0)".to_string()));
+        assert!(productions.contains(&"AAGroup2: WORD WORD #(NonAssoc, 0)".to_string()));
+        assert!(productions.contains(&"AAGroup3: AAGroup2 OPEN #(NonAssoc, 0)".to_string()));
+        assert!(productions.contains(&"More: \",\" AAGroup1 #(NonAssoc, 0)".to_string()));
+        assert!(productions
+            .contains(&"Words: WORD AAAAGroup1Star AAAAGroup3Opt #(NonAssoc, 0)".to_string()));
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let code = generated_code(&grammar);
+        assert!(code.contains(
+            "lalr1_plus::GroupAttribute::group(vec![aa_first, core::mem::take(&mut aa_rhs[1])]);"
+        ));
+        let text = text.replacen("(COMMA WORD)", "(COMMA WORD %prec COMMA)", 1);
+        let specification = Specification::new(&text, "groups", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn namespaced_code() {
+        let specification = Specification::new(DIALECTS, "dialects", &[]).unwrap();
+        let mut grammar = Grammar::try_from((specification, true, true)).unwrap();
+        grammar.namespace = Some("dialects".to_string());
+        let code = generated_code(&grammar);
+        assert!(code.starts_with("// generated by alap_gen.\n\npub mod dialects {\n"));
+        assert!(code.ends_with("}\n}\n"));
+    }
+
+    #[test]
+    fn module_directive() {
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%module dialects\n");
+        let specification = Specification::new(&text, "module", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        assert_eq!(grammar.namespace, Some("dialects".to_string()));
+        let code = generated_code(&grammar);
+        assert!(code.starts_with("// generated by alap_gen.\n\npub mod dialects {\n"));
+
+        let text = DIALECTS.replace("%target Calc\n", "%target Calc\n%module type\n");
+        let specification = Specification::new(&text, "module", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+        assert!(specification.module.is_none());
+    }
+
+    // the generated code is compiled and run (with "--prefix XX") by examples/prefix
+    #[test]
+    fn custom_prefix() {
+        let text = TYPED
+            .replace(
+                "%target Calc\n",
+                "%target Calc\n%start Exprs\n%start Expr\n%functional\n%prefix XX\n",
+            )
+            .replace("| NUMBER !{", "| NUMBER (\"+\" NUMBER)? !{");
+        for (compress_tables, table_driven, option) in [
+            (false, false, false),
+            (true, false, true),
+            (false, true, true),
+            (true, true, false),
+        ] {
+            let specification = Specification::new(&text, "prefix", &[]).unwrap();
+            assert_eq!(specification.error_count, 0);
+            assert!(specification.is_allowable_name("AAFoo"));
+            assert!(!specification.is_allowable_name("XXFoo"));
+            assert!(!specification.is_allowable_name("xx_foo"));
+            let mut grammar = Grammar::try_from((specification, true, true)).unwrap();
+            grammar.compress_tables = compress_tables;
+            grammar.table_driven = table_driven;
+            grammar.emit_production_table = option;
+            grammar.strict_predicates = option;
+            grammar.checked_predicates = option;
+            grammar.diagnose_panics = option;
+            grammar.no_std = option;
+            let code = generated_code(&grammar);
+            assert!(!code.contains("AA"));
+            assert!(!code.contains("aa_"));
+            assert!(code.contains("pub enum XXTerminal {"));
+            assert!(code.contains("XXTerminal::XXEnd"));
+            assert!(code.contains("XXNonTerminal::XXStart"));
+            assert!(code.contains("XXNonTerminal::XXExprPlus"));
+            assert!(code.contains("XXNonTerminal::XXGroup1"));
+            assert!(code.contains("xx_rhs"));
+        }
+
+        for prefix in ["Xy", "x1", "X_1"] {
+            let text = DIALECTS.replace(
+                "%target Calc\n",
+                &format!("%target Calc\n%prefix {prefix}\n"),
+            );
+            let specification = Specification::new(&text, "prefix", &[]).unwrap();
+            assert_eq!(specification.error_count, 1);
+            assert_eq!(specification.prefix(), "AA");
+        }
+    }
+
+    #[test]
+    fn keywords() {
+        let text = |keywords: &str, ident: &str| {
+            format!(
+                "%attr AttributeData\n%target Calc\n%%\n%keywords {keywords}\n%token IDENT {ident}\n%%\nStmt: \"if\" IDENT \"then\" Stmt \"else\" Stmt | IDENT .\n"
+            )
+        };
+        let specification =
+            Specification::new(&text("if then else", "([a-z]+)"), "keywords", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert_eq!(specification.warning_count, 0);
+        let keywords: Vec<&str> = specification
+            .symbol_table
+            .keywords()
+            .map(|token| token.name())
+            .collect();
+        assert_eq!(keywords, vec!["IF", "THEN", "ELSE"]);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let code = generated_code(&grammar);
+        assert!(code.contains("    pub fn keyword(text: &str) -> Option<Self> {\n"));
+        assert!(code.contains("            \"then\" => Some(AATerminal::THEN),\n"));
+        assert!(!parser_code(DIALECTS, &[]).contains("pub fn keyword("));
+
+        // a keyword no regular expression matches may be found at the start of words
+        let specification =
+            Specification::new(&text("if then else", "([A-Z]+)"), "keywords", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert_eq!(specification.warning_count, 3);
+        let specification =
+            Specification::new(&text("if then else aa", "([a-z]+)"), "keywords", &[]).unwrap();
+        assert_eq!(specification.warning_count, 1);
+        let specification =
+            Specification::new(&text("if then else if", "([a-z]+)"), "keywords", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn overlapping_tokens() {
+        let warnings = |tokens: &str| {
+            let text = format!(
+                "%attr AttributeData\n%target Calc\n%%\n%token IDENT ([a-z]+)\n%token NUMBER (-?[0-9]+)\n{tokens}\n%%\nExpr: IDENT | NUMBER .\n"
+            );
+            let specification = Specification::new(&text, "overlapping", &[]).unwrap();
+            assert_eq!(specification.error_count, 0);
+            specification.warning_count
+        };
+        assert_eq!(warnings("%token PLUS \"+\""), 0);
+        assert_eq!(warnings("%token IF \"if\""), 1);
+        assert_eq!(warnings("%keywords if"), 0);
+        assert_eq!(warnings("%token MINUS \"-\""), 1);
+        assert_eq!(warnings("%token IF \"if\"\n%token MINUS \"-\""), 2);
+        // only the tokens in the same lexical mode overlap
+        assert_eq!(warnings("%mode OTHER\n%token IF \"if\""), 0);
+    }
+
+    #[test]
+    fn borrowing_target() {
+        let text = DIALECTS.replace("%target Calc", "%target Calc<'env>");
+        let specification = Specification::new(&text, "test", &[]).unwrap();
+        assert_eq!(specification.target_lifetime.as_deref(), Some("'env"));
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "impl<'env> lalr1_plus::Parser<AATerminal, AANonTerminal, AttributeData> for Calc<'env> {\n"
+        ));
+    }
+
+    #[test]
+    fn generic_target() {
+        let text = DIALECTS
+            .replace("%attr AttributeData", "%attr AttributeData<N> %spanned")
+            .replace("%target Calc", "%target Calc<N: std::ops::Add + Default>");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "impl<N: std::ops::Add + Default> lalr1_plus::Parser<AATerminal, AANonTerminal, lalr1_plus::Spanned<AttributeData<N>>> for Calc<N> {\n"
+        ));
+        // the default token_attribute() suffices
+        assert!(!code.contains("fn token_attribute("));
+    }
+
+    #[test]
+    fn coverage_field() {
+        assert!(!parser_code(DIALECTS, &[]).contains("fn coverage("));
+        let text = DIALECTS.replace("%target Calc", "%target Calc\n%coverage covered");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "    fn coverage(&self) -> Option<&lalr1_plus::BitSet> {\n        Some(&self.covered)\n"
+        ));
+        assert!(code.contains(
+            "    fn coverage_mut(&mut self) -> Option<&mut lalr1_plus::BitSet> {\n        Some(&mut self.covered)\n"
+        ));
+    }
+
+    #[test]
+    fn production_table() {
+        let specification = Specification::new(DIALECTS, "test", &[]).unwrap();
+        let mut grammar = Grammar::try_from((specification, true, true)).unwrap();
+        assert!(!generated_code(&grammar).contains("AAPRODUCTIONS"));
+        grammar.emit_production_table = true;
+        let code = generated_code(&grammar);
+        assert!(code.contains("pub const AAPRODUCTIONS: &[(&str, &[&str])] = &[\n"));
+        assert!(code.contains("    (r###\"AAStart\"###, &[r###\"Expr\"###]),\n"));
+        assert!(code.contains(
+            "    (r###\"Expr\"###, &[r###\"Expr\"###, r###\"\"+\"\"###, r###\"Expr\"###]),\n"
+        ));
+        assert!(code.contains("    (r###\"Expr\"###, &[r###\"NUMBER\"###]),\n"));
+    }
+
+    #[test]
+    fn interner_field() {
+        assert!(!parser_code(DIALECTS, &[]).contains("fn interner("));
+        let text = DIALECTS.replace("%target Calc", "%target Calc\n%intern names");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "    fn interner(&mut self) -> Option<&mut lalr1_plus::Interner> {\n        Some(&mut self.names)\n"
+        ));
+    }
+
+    #[test]
+    fn possible_actions() {
+        let code = parser_code(DIALECTS, &[]);
+        assert!(code.contains(
+            "    fn possible_actions(aa_state: u32, aa_tag: &AATerminal) -> Vec<lalr1_plus::Action> {\n"
+        ));
+        assert!(code.contains("                NUMBER => vec![Action::Shift(2)],\n"));
+        assert!(code.contains("                AAEnd => vec![Action::Accept],\n"));
+        assert!(code.contains("                AAEnd | PLUS => vec![Action::Reduce("));
+    }
+
+    #[test]
+    fn token_transforms() {
+        assert!(!parser_code(DIALECTS, &[]).contains("with_lexeme"));
+        let text = DIALECTS.replace(
+            "%token NUMBER ([0-9]+)\n",
+            "%token NUMBER ([0-9][0-9_]*)\n    %transform !{ $1.replace('_', \"\") !}\n",
+        );
+        let code = parser_code(&text, &[]);
+        // the lexeme is replaced before the token is converted
+        assert!(code.contains(
+            "            AATerminal::NUMBER => {\n                let aa_lexeme = String::from({  aa_token.lexeme().replace('_', \"\")  });\n                aa_token.with_lexeme(aa_lexeme)\n"
+        ));
+        assert!(code.contains(
+            "            _ => aa_token,\n        };\n        AttributeData::from(aa_token)\n"
+        ));
+    }
+
+    #[test]
+    fn token_conversions() {
+        let text = DIALECTS
+            .replace(
+                "%token NUMBER ([0-9]+)\n",
+                "%token NUMBER ([0-9]+)\n    !{ $$ = AttributeData::Number($1.lexeme().len()); !}\n",
+            )
+            .replace(
+                "%token PLUS \"+\"\n",
+                "%token PLUS \"+\" !{ $$ = AttributeData::Token($1); !}\n",
+            );
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "impl From<lexan::Token<AATerminal>> for AttributeData {\n    #[allow(unused_assignments, unused_mut)]\n    fn from(aa_token: lexan::Token<AATerminal>) -> Self {\n        match aa_token.tag() {\n"
+        ));
+        assert!(!code.contains("fn token_attribute("));
+        assert!(code.contains("            AATerminal::AAEnd => AttributeData::default(),\n"));
+        assert!(code.contains("{  aa_lhs = AttributeData::Number(aa_token.lexeme().len());  }"));
+        assert!(code.contains("{  aa_lhs = AttributeData::Token(aa_token);  }"));
+        // whereas "%intern" grammars' conversion code may use the parser's interner
+        let interning = text.replace("%target Calc", "%target Calc\n%intern names");
+        let code = parser_code(&interning, &[]);
+        assert!(!code.contains("impl From<lexan::Token<AATerminal>>"));
+        assert!(code.contains(
+            "    fn token_attribute(&mut self, aa_token: lexan::Token<AATerminal>) -> AttributeData {\n        match aa_token.tag() {\n"
+        ));
+        assert!(code.contains("{  aa_lhs = AttributeData::Token(aa_token);  }"));
+        // once one token has conversion code they all need it
+        let text = text.replace(" !{ $$ = AttributeData::Token($1); !}", "");
+        let specification = Specification::new(&text, "test", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn spanned_attributes() {
+        assert!(!parser_code(DIALECTS, &[]).contains("Spanned"));
+        let text = DIALECTS.replace("%attr AttributeData", "%attr AttributeData %spanned");
+        let code = parser_code(&text, &[]);
+        assert!(code.contains(
+            "lalr1_plus::Parser<AATerminal, AANonTerminal, lalr1_plus::Spanned<AttributeData>>"
+        ));
+        // the span is added by Spanned's From<lexan::Token<_>>
+        assert!(!code.contains("fn token_attribute("));
+        let transforming = text.replace(
+            "%token NUMBER ([0-9]+)\n",
+            "%token NUMBER ([0-9]+)\n    %transform !{ $1.trim() !}\n",
+        );
+        assert!(
+            parser_code(&transforming, &[]).contains("        let aa_value: AttributeData = {\n")
+        );
+        assert!(code.contains("    fn do_semantic_action_la<F: FnMut(String, String)>(\n"));
+        assert!(code.contains("        aa_lhs.span = aa_span;\n"));
+    }
+
+    #[test]
+    fn action_spans() {
+        // the actions are run where the right hand side's span ($@) is known
+        let text = DIALECTS.replace("%attr AttributeData", "%attr AttributeData %spanned");
+        let text = text.replace(
+            "    | NUMBER\n",
+            "    | NUMBER !{ self.spans.push($@.clone()); !}\n",
+        );
+        let code = parser_code(&text, &[]);
+        assert!(!code.contains("fn do_semantic_action<"));
+        assert!(code.contains("self.spans.push(aa_span.clone());"));
+        let text = text.replace("%attr AttributeData %spanned", "%attr AttributeData");
+        let specification = Specification::new(&text, "spanned", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn canonical_text() {
+        let canonical = |text: &str| {
+            let specification = Specification::new(text, "canonical", &[]).unwrap();
+            Grammar::try_from((specification, true, true))
+                .unwrap()
+                .canonical_text()
+        };
+        let text = UNIFORM.replace("%left ADDITIVE", "%left \"+\" \"-\"");
+        let expected = canonical(&text);
+        assert!(expected.contains("  PLUS: \"+\" #(Left, 1)\n"));
+        assert!(expected.contains("  Expr.1: Expr \"+\" Expr #(Left, 1)\n"));
+        assert!(expected.ends_with("  Expr.3: NUMBER #(NonAssoc, 0)\n"));
+        // neither the order of the token definitions nor layout matters
+        let reordered = text
+            .replace(
+                "%token PLUS \"+\"\n%token MINUS \"-\"",
+                "%token MINUS \"-\"\n%token PLUS \"+\"",
+            )
+            .replace("\n    | ", " | ");
+        assert_ne!(reordered, text);
+        assert_eq!(canonical(&reordered), expected);
+    }
+
+    #[test]
+    fn ambiguity_heuristics() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%token IF "if"
+%token ELSE "else"
+%token X "x"
+%%
+Stmt: Expr | "if" Expr Stmt | "if" Expr Stmt "else" Stmt | Opt "x" .
+Expr: Expr "+" Expr | NUMBER .
+Opt: | Inner .
+Inner: | "x" .
+"#;
+        let specification = Specification::new(text, "ambiguous", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let report: Vec<String> = grammar
+            .ambiguity_report()
+            .into_iter()
+            .map(|(_, finding)| finding)
+            .collect();
+        assert_eq!(report.len(), 3, "{report:?}");
+        assert!(report[0].contains("right recursion followed by an optional suffix"));
+        assert!(report[1].contains("symmetric binary production"));
+        assert!(report[2].starts_with("Opt: 2 productions derive the empty string"));
+
+        let text = UNIFORM.replace("%left ADDITIVE", "%left \"+\" \"-\"");
+        let specification = Specification::new(&text, "precedence", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        assert!(grammar.ambiguity_report().is_empty());
+    }
+
+    #[test]
+    fn inject_limits() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_inject_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let inner = dir.join("inner.alaps");
+        std::fs::write(&inner, "// nothing to see here\n").unwrap();
+        let outer = dir.join("outer.alaps");
+        let outer_text = format!("%target Calc\n%inject \"{}\".\n", inner.display());
+        std::fs::write(&outer, &outer_text).unwrap();
+        let text = format!(
+            "%attr AttributeData\n%inject \"{}\".\n%%\n%token A \"a\"\n%%\nS: \"a\" .\n",
+            outer.display()
+        );
+        let parse = |max_depth, max_bytes| {
+            let limits = InjectLimits {
+                max_depth,
+                max_bytes,
+            };
+            Specification::with_inject_limits(&text, "limits", &[], limits)
+        };
+        let specification = parse(None, None).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert_eq!(specification.target_type, "Calc");
+        assert_eq!(parse(Some(2), None).unwrap().error_count, 0);
+        assert_eq!(parse(Some(1), None).unwrap().error_count, 1);
+        let all_bytes = outer_text.len() + 23;
+        assert_eq!(parse(None, Some(all_bytes)).unwrap().error_count, 0);
+        assert_eq!(parse(None, Some(all_bytes - 1)).unwrap().error_count, 1);
+        // without the injected "%target" the specification is incomplete
+        assert!(parse(None, Some(10)).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn inject_once() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_once_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let shared = dir.join("shared.alaps");
+        std::fs::write(&shared, "%token B \"b\"\n").unwrap();
+        let text = format!(
+            "%attr AttributeData\n%target Calc\n%%\n%inject \"{0}\".\n%token A \"a\"\n%inject \"{0}\".\n%%\nS: \"a\" \"b\" .\n",
+            shared.display()
+        );
+        let specification = Specification::new(&text, "once", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert_eq!(specification.warning_count, 1);
+
+        // a file that (indirectly) injects itself
+        let root = dir.join("root.alaps");
+        let inner = dir.join("inner.alaps");
+        let text = format!(
+            "%attr AttributeData\n%inject \"{}\".\n%%\n%token A \"a\"\n%%\nS: \"a\" .\n",
+            inner.display()
+        );
+        std::fs::write(&root, &text).unwrap();
+        let inner_text = format!("%target Calc\n%inject \"{}\".\n", root.display());
+        std::fs::write(&inner, inner_text).unwrap();
+        let label = root.to_string_lossy().to_string();
+        let specification = Specification::new(&text, &label, &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn inject_conditional_sections() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_if_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let power = dir.join("power.alaps");
+        std::fs::write(&power, "%if power\n%token POWER \"**\"\n%endif\n").unwrap();
+        let text = DIALECTS.replace(
+            "%if power\n%token POWER \"**\"\n%endif\n",
+            &format!("%inject \"{}\".\n", power.display()),
+        );
+        let plain = parser_code(&text, &[]);
+        let power_code = parser_code(&text, &["power".to_string()]);
+        assert!(!plain.contains("AATerminal::POWER"));
+        assert!(power_code.contains("AATerminal::POWER"));
+
+        // the injected file's sections must be matched within it
+        let unmatched = dir.join("unmatched.alaps");
+        std::fs::write(&unmatched, "%if power\n%token POWER \"**\"\n").unwrap();
+        let text = format!(
+            "%attr AttributeData\n%target Calc\n%%\n%inject \"{}\".\n%token A \"a\"\n%%\nS: \"a\" .\n",
+            unmatched.display()
+        );
+        let specification = Specification::new(&text, "unmatched", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const UNIFORM: &str = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%token MINUS "-"
+%left ADDITIVE
+%%
+Expr: Expr "+" Expr
+    | Expr "-" Expr
+    | NUMBER
+    .
+"#;
+
+    #[test]
+    fn precedence_for_non_terminal() {
+        let specification = Specification::new(UNIFORM, "uniform", &[]).unwrap();
+        match Grammar::try_from((specification, false, false)) {
+            Err(Error::UnexpectedSRConflicts(count, _, _, _)) => assert_eq!(count, 4),
+            _ => panic!("expected shift/reduce conflicts"),
+        }
+        let text = UNIFORM.replace("%left ADDITIVE", "%left ADDITIVE\n%prec-for Expr ADDITIVE");
+        let specification = Specification::new(&text, "uniform", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        assert!(Grammar::try_from((specification, false, false)).is_ok());
+
+        let text = UNIFORM.replace("%left ADDITIVE", "%left ADDITIVE\n%prec-for Term ADDITIVE");
+        let specification = Specification::new(&text, "uniform", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn repetition_recursion() {
+        let recursion = |repeat: &str| {
+            let text = DIALECTS.replace("%target Calc\n", &format!("%target Calc\n{repeat}"));
+            let specification = Specification::new(&text, "recursion", &[]).unwrap();
+            (
+                specification.right_recursive_repetition,
+                specification.error_count,
+            )
+        };
+        assert_eq!(recursion(""), (false, 0));
+        assert_eq!(recursion("%repeat left\n"), (false, 0));
+        assert_eq!(recursion("%repeat right\n"), (true, 0));
+        assert_eq!(recursion("%repeat up\n").1, 1);
+    }
+
+    #[test]
+    fn expected_conflicts() {
+        let text =
+            |expect: &str| UNIFORM.replace("%target Calc", &format!("%target Calc\n{expect}"));
+        let specification = Specification::new(&text("%expect 4"), "uniform", &[]).unwrap();
+        assert!(Grammar::try_from((specification, false, false)).is_ok());
+        let specification = Specification::new(&text("%expect 3"), "uniform", &[]).unwrap();
+        match Grammar::try_from((specification, false, false)) {
+            Err(Error::UnexpectedConflicts(count, expected, _, _)) => {
+                assert_eq!((count, expected), (4, 3))
+            }
+            _ => panic!("expected unexpected conflicts"),
+        }
+        // "%expect" takes the place of "%shift_reduce" and "%reduce_reduce"
+        let text = text("%expect 4\n%shift_reduce 4");
+        assert!(Specification::new(&text, "uniform", &[]).is_err());
+    }
+
+    #[test]
+    fn reduce_reduce_conflicts_kept_apart() {
+        let text = |expect: &str| {
+            format!(
+                "%attr AttributeData\n%target Calc\n{expect}\n%%\n%token NUMBER ([0-9]+)\n%%\nExpr: A | B .\nA: NUMBER .\nB: NUMBER .\n"
+            )
+        };
+        let grammar = |expect: &str, ignore_sr: bool, ignore_rr: bool| {
+            let specification = Specification::new(&text(expect), "rr", &[]).unwrap();
+            Grammar::try_from((specification, ignore_sr, ignore_rr))
+        };
+        let accepted = grammar("%reduce_reduce 1", false, false).unwrap();
+        assert_eq!(accepted.unresolved_conflicts(), (0, 1));
+        assert!(grammar("%expect 1", false, false).is_ok());
+        // ignoring shift/reduce conflicts doesn't excuse reduce/reduce ones
+        match grammar("%expect 1", true, false) {
+            Err(Error::UnexpectedRRConflicts(count, expected, _, _)) => {
+                assert_eq!((count, expected), (1, 0))
+            }
+            _ => panic!("expected unexpected reduce/reduce conflicts"),
+        }
+        match grammar("%expect 1", false, true) {
+            Err(Error::UnexpectedConflicts(count, expected, _, _)) => {
+                assert_eq!((count, expected), (0, 1))
+            }
+            _ => panic!("expected unexpected conflicts"),
+        }
+        assert!(grammar("", true, true).is_ok());
+    }
+
+    #[test]
+    fn non_productive_non_terminals() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token A "a"
+%token B "b"
+%%
+Start: Loop | Pair | B .
+Loop: Loop "a" .
+Pair: Loop "b" | "a" Pair .
+"#;
+        let specification = Specification::new(text, "non-productive", &[]).unwrap();
+        let names: Vec<String> = specification
+            .non_productive_non_terminals()
+            .iter()
+            .map(|x| x.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Loop", "Pair"]);
+        match Grammar::try_from((specification, false, false)) {
+            Err(Error::NonProductiveSymbols(count)) => assert_eq!(count, 2),
+            _ => panic!("expected non productive symbols"),
+        }
+        let text = text.replace("Loop: Loop \"a\" .", "Loop: Loop \"a\" | .");
+        let specification = Specification::new(&text, "non-productive", &[]).unwrap();
+        assert!(specification.non_productive_non_terminals().is_empty());
+    }
+
+    #[test]
+    fn unreachable_non_terminals() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token A "a"
+%token B "b"
+%%
+Start: "a" .
+Orphan: Branch "b" | Leaf .
+Branch: "a" Leaf Leaf .
+Leaf: "b" | Branch "a" .
+"#;
+        let specification = Specification::new(text, "unreachable", &[]).unwrap();
+        // "Orphan" itself is merely unused
+        let names: Vec<String> = specification
+            .unreachable_non_terminals()
+            .iter()
+            .map(|x| x.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Branch", "Leaf"]);
+        let text = text.replace("Start: \"a\" .", "Start: \"a\" | Orphan .");
+        let specification = Specification::new(&text, "unreachable", &[]).unwrap();
+        assert!(specification.unreachable_non_terminals().is_empty());
+    }
+
+    #[test]
+    fn automaton_dot() {
+        let specification = Specification::new(UNIFORM, "uniform", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, false)).unwrap();
+        let mut dot = vec![];
+        grammar.write_automaton_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph automaton {\n"));
+        assert!(dot.ends_with("}\n"));
+        let nodes = dot
+            .lines()
+            .filter(|line| line.contains("[label=\"State<"))
+            .count();
+        assert_eq!(nodes, grammar.parser_states.len());
+        // literal tokens are escaped and conflicted states are highlighted
+        assert!(dot.contains("[label=\"\\\"+\\\"\"];"));
+        assert!(dot.contains("[label=\"Expr\", style=dashed];"));
+        assert!(dot.contains("fillcolor=salmon"));
+    }
+
+    #[test]
+    fn conflicts_as_json() {
+        let specification = Specification::new(UNIFORM, "uniform", &[]).unwrap();
+        let json = match Grammar::try_from((specification, false, false)) {
+            Err(Error::UnexpectedSRConflicts(_, _, _, json)) => json,
+            _ => panic!("expected shift/reduce conflicts"),
+        };
+        assert!(json.starts_with("{\"conflicts\": [\n"));
+        assert_eq!(json.matches("\"resolution\": \"unresolved\"").count(), 4);
+        assert!(json.contains("\"kind\": \"shift/reduce\", \"token\": \"PLUS\""));
+        // conflicts resolved by precedence are included too
+        let text = UNIFORM.replace("%left ADDITIVE", "%left PLUS MINUS");
+        let specification = Specification::new(&text, "uniform", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let json = grammar.conflicts_json();
+        assert_eq!(json.matches("\"resolution\": \"reduce\"").count(), 4);
+        assert!(!json.contains("unresolved"));
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+    }
+
+    #[test]
+    fn specification_checksum() {
+        let mut grammar = Grammar::try_from((
+            Specification::new(UNIFORM, "uniform", &[]).unwrap(),
+            true,
+            true,
+        ))
+        .unwrap();
+        grammar.specification_checksum = Some(checksum(UNIFORM));
+        let code = generated_code(&grammar);
+        assert_eq!(recorded_checksum(&code), Some(checksum(UNIFORM)));
+        assert_ne!(checksum(UNIFORM), checksum(DIALECTS));
+        assert_eq!(recorded_checksum(&parser_code(DIALECTS, &[])), None);
+    }
+
+    #[test]
+    fn empty_matching_regex() {
+        assert!(Specification::matches_empty_string("(x*)"));
+        assert!(!Specification::matches_empty_string("(x+)"));
+        let text = DIALECTS.replace("%token PLUS \"+\"", "%token PLUS \"+\"\n%token XS (x*)");
+        let specification = Specification::new(&text, "empty", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn unparser_shapes() {
+        let text = include_str!("../../examples/calc/src/calc.alaps");
+        let specification = Specification::new(text, "calc.alaps", &[]).unwrap();
+        let mut grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let mut code = vec![];
+        grammar.write_unparser_code(&mut code, "").unwrap();
+        let code = String::from_utf8(code).unwrap();
+        for shape in [
+            "Infix(lalr1_plus::Associativity::Left, 2),\n",
+            "Infix(lalr1_plus::Associativity::Left, 3),\n",
+            "Parenthesized,\n",
+            "Prefix(4),\n",
+        ] {
+            assert_eq!(code.matches(shape).count(), 1);
+        }
+        assert!(code.contains(
+            "pub fn aa_unparse(tree: &lalr1_plus::ParseTree<AATerminal, AANonTerminal>) -> String {\n"
+        ));
+        grammar.namespace = Some("calc".to_string());
+        let path =
+            std::env::temp_dir().join(format!("alap_gen_unparser_{}.rs", std::process::id()));
+        grammar.write_unparser(&path).unwrap();
+        let code = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(code.contains("lalr1_plus::ParseTree<calc::AATerminal, calc::AANonTerminal>"));
+        grammar.emit_unparser = true;
+        assert!(
+            generated_code(&grammar).contains("lalr1_plus::unparse(tree, &aa_production_shape)")
+        );
+    }
+
+    #[test]
+    fn streamed_description() {
+        let text = include_str!("alap_gen.alaps");
+        let specification = Specification::new(text, "alap_gen.alaps", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let mut streamed = vec![];
+        grammar.write_description(&mut streamed).unwrap();
+        let streamed = String::from_utf8(streamed).unwrap();
+
+        let mut expected = vec![];
+        let symbol_table = &grammar.specification.symbol_table;
+        symbol_table.write_description(&mut expected).unwrap();
+        let mut expected = String::from_utf8(expected).unwrap() + "\nProductions:\n";
+        for production in grammar.specification.productions.iter() {
+            expected += &format!("  {production}\n");
+        }
+        let mut sets = vec![];
+        grammar
+            .specification
+            .write_sets_description(&mut sets)
+            .unwrap();
+        expected += &String::from_utf8(sets).unwrap();
+        for parser_state in grammar.parser_states.iter() {
+            expected += &parser_state.description();
+        }
+        assert_eq!(streamed, expected);
+        assert!(streamed.starts_with("Symbols:\n  Tokens:\n    AAEnd(): #(NonAssoc, 0)\n"));
+        assert!(streamed.contains("\nState<0>:\n  Grammar Items:\n"));
+    }
+
+    #[test]
+    fn compressed_action_table() {
+        let text = include_str!("alap_gen.alaps");
+        let specification = Specification::new(text, "alap_gen.alaps", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        let tokens: Vec<Token> = std::iter::once(grammar.specification.symbol_table.end_token())
+            .chain(grammar.specification.symbol_table.tokens())
+            .cloned()
+            .collect();
+        let table = grammar.action_table();
+        for (row, parser_state) in grammar.parser_states.iter().enumerate() {
+            for (column, entry) in parser_state.action_table_row(&tokens).iter().enumerate() {
+                assert_eq!(table.entry(row, column), *entry);
+            }
+        }
+        let dense_size = grammar.parser_states.len() * tokens.len();
+        assert!(table.size() * 2 < dense_size);
+    }
+
+    #[test]
+    fn table_driven_parser() {
+        let text = include_str!("alap_gen.alaps");
+        let code = |table_driven: bool| {
+            let specification = Specification::new(text, "alap_gen.alaps", &[]).unwrap();
+            let mut grammar = Grammar::try_from((specification, false, false)).unwrap();
+            grammar.table_driven = table_driven;
+            generated_code(&grammar)
+        };
+        let matches = code(false);
+        assert!(matches.contains("return match current_state {"));
+        assert!(!matches.contains("AAGOTO"));
+        let tables = code(true);
+        assert!(!tables.contains("return match current_state {"));
+        assert!(!tables.contains("return match state {"));
+        assert!(tables.contains("const AAGOTO_ROWS: &[usize]"));
+        assert!(tables.contains("const AAGOTO: &[(AANonTerminal, u32)]"));
+        assert!(tables.contains("const AALOOK_AHEAD: &[AATerminal]"));
+        assert!(tables.contains("const AANEXT"));
+        assert!(tables.contains("binary_search_by(|x| x.0.cmp(lhs))"));
+        assert!(tables.lines().count() < matches.lines().count());
+    }
+
+    #[test]
+    fn unreducible_productions() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token A "a"
+%token B "b"
+%nonassoc LOW
+%nonassoc "b"
+%%
+S: X "b" | "a" "b" .
+X: "a" %prec LOW .
+"#;
+        let specification = Specification::new(text, "shadowed", &[]).unwrap();
+        assert_eq!(specification.warning_count, 0);
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        // both "X: a" and (as X can't be produced) "S: X b"
+        assert_eq!(grammar.specification.warning_count, 2);
+
+        let specification = Specification::new(UNIFORM, "uniform", &[]).unwrap();
+        let warnings = specification.warning_count;
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        assert_eq!(grammar.specification.warning_count, warnings);
+    }
+
+    #[test]
+    fn specification_tests() {
+        let text = DIALECTS.to_string() + "%test \"1 + 2\" => ok\n%test \"1 +\" => error\n";
+        let specification = Specification::new(&text, "tests", &[]).unwrap();
+        assert_eq!(specification.tests.len(), 2);
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let code = generated_code(&grammar);
+        assert!(code.contains("mod aa_specification_tests {"));
+        assert!(code.contains("assert!(aa_parser.parse_text(\"1 + 2\", \"%test\").is_ok());"));
+        assert!(code.contains("assert!(aa_parser.parse_text(\"1 +\", \"%test\").is_err());"));
+        assert!(!parser_code(DIALECTS, &[]).contains("aa_specification_tests"));
+
+        let text = DIALECTS.to_string() + "%test \"1\" => maybe\n";
+        let specification = Specification::new(&text, "tests", &[]).unwrap();
+        assert_eq!(specification.error_count, 1);
+    }
+
+    #[test]
+    fn non_terminal_sets() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token A "a"
+%token B "b"
+%%
+S: Opt "b" | S Opt .
+Opt: | "a" .
+"#;
+        let specification = Specification::new(text, "sets", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, true, true)).unwrap();
+        let mut description = vec![];
+        grammar
+            .specification
+            .write_sets_description(&mut description)
+            .unwrap();
+        let description = String::from_utf8(description).unwrap();
+        assert!(description.contains(
+            "  Opt:\n    Nullable: true\n    First: \"a\"\n    Follow: \"a\", \"b\", AAEnd\n"
+        ));
+        assert!(description.contains(
+            "  S:\n    Nullable: false\n    First: \"a\", \"b\"\n    Follow: \"a\", AAEnd\n"
+        ));
+    }
+
+    #[test]
+    fn start_symbol_chosen() {
+        let text = |start: &str| {
+            format!(
+                r#"%attr AttributeData
+%target Calc
+{start}
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%token SEMI ";"
+%%
+Program: Program Expr SEMI | Expr SEMI .
+Expr: Expr PLUS NUMBER | NUMBER .
+"#
+            )
+        };
+        let start = |start: &str| {
+            let specification = Specification::new(&text(start), "start", &[]).unwrap();
+            let productions: Vec<String> = specification
+                .productions
+                .iter()
+                .map(|p| p.to_string())
+                .collect();
+            (productions[0].clone(), specification.error_count)
+        };
+        assert!(start("").0.starts_with("AAStart: Program"));
+        assert_eq!(start("%start Expr").1, 0);
+        assert!(start("%start Expr").0.starts_with("AAStart: Expr"));
+        assert_eq!(start("%start PLUS").1, 1);
+        let specification = Specification::new(&text("%start Stmt"), "start", &[]).unwrap();
+        assert!(Grammar::try_from((specification, false, false)).is_err());
+    }
+
+    #[test]
+    fn multiple_start_symbols() {
+        let text = |starts: &str| {
+            format!(
+                "%attr AttributeData\n%target Calc\n{starts}\n%%\n%token NUMBER ([0-9]+)\n%token PLUS \"+\"\n%token SEMI \";\"\n%%\nProgram: Program Expr SEMI | Expr SEMI .\nExprList: Expr | ExprList Expr .\nExpr: Expr PLUS NUMBER | NUMBER .\n"
+            )
+        };
+        let specification =
+            Specification::new(&text("%start Program\n%start ExprList"), "starts", &[]).unwrap();
+        assert_eq!(specification.error_count, 0);
+        let last = specification.productions.last().unwrap().to_string();
+        assert!(last.starts_with("AAStartExprList: ExprList"));
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        assert_eq!(grammar.start_states[1], ("ExprList".to_string(), 1));
+        let code = generated_code(&grammar);
+        assert!(code.contains("pub fn parse_program(&mut self, text: &str, label: &str)"));
+        assert!(code.contains("pub fn parse_expr_list(&mut self, text: &str, label: &str)"));
+        assert!(code.contains("lalr1_plus::Parser::parse_to_from(self, 1, text, label)"));
+        assert_eq!(code.matches("=> Action::Accept,").count(), 2);
+        let mut description = vec![];
+        grammar.write_description(&mut description).unwrap();
+        let description = String::from_utf8(description).unwrap();
+        assert!(description.contains("\nStart States:\n  Program: State<0> (adding "));
+        // a single start symbol needs no entry points
+        let specification = Specification::new(&text("%start Expr"), "starts", &[]).unwrap();
+        let grammar = Grammar::try_from((specification, false, false)).unwrap();
+        assert!(!generated_code(&grammar).contains("pub fn parse_expr("));
+
+        for starts in [
+            "%start Program\n%start Program",
+            "%start Program\n%start PLUS",
+        ] {
+            let specification = Specification::new(&text(starts), "starts", &[]).unwrap();
+            assert_eq!(specification.error_count, 1, "{starts}");
+        }
+    }
+
+    #[test]
+    fn strict_predicates() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%%
+Expr: NUMBER ?(self.small?) | NUMBER ?(self.even?) | NUMBER .
+"#;
+        let code = |strict: bool| {
+            let specification = Specification::new(text, "predicates", &[]).unwrap();
+            let mut grammar = Grammar::try_from((specification, false, false)).unwrap();
+            grammar.strict_predicates = strict;
+            generated_code(&grammar)
+        };
+        assert!(!code(false).contains("aa_passed"));
+        let strict = code(true);
+        assert!(strict.contains("(self.small, r###\"Expr: NUMBER"));
+        assert!(strict.contains("(self.even, r###\"Expr: NUMBER"));
+        assert!(strict.contains("non exclusive predicates passed"));
+    }
+
+    #[test]
+    fn checked_predicates() {
+        let text = r#"%attr AttributeData
+%target Calc
+%%
+%token NUMBER ([0-9]+)
+%token PLUS "+"
+%%
+Expr: Expr PLUS NUMBER ?($0.is_open()?) | Expr PLUS NUMBER ?(self.small?) | NUMBER .
+"#;
+        let code = |checked: bool| {
+            let specification = Specification::new(text, "predicates", &[]).unwrap();
+            let mut grammar = Grammar::try_from((specification, false, false)).unwrap();
+            grammar.checked_predicates = checked;
+            generated_code(&grammar)
+        };
+        assert!(!code(false).contains("try_at_len_minus_n"));
+        let checked = code(true);
+        assert!(checked.contains(
+            "if aa_attributes.try_at_len_minus_n(4).is_some() && (aa_attributes.at_len_minus_n(4).is_open()) {"
+        ));
+        assert!(checked.contains("} else if self.small {"));
     }
 }
@@ -0,0 +1,18 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+//! The generator behind the `alap_gen_ng` binary as a library, e.g. for use in build
+//! scripts (see `build::compile()`).
+
+#[cfg(not(feature = "bootstrap"))]
+mod alap_gen;
+mod attributes;
+#[cfg(feature = "bootstrap")]
+mod bootstrap;
+pub mod build;
+mod error;
+pub mod grammar;
+mod production;
+mod state;
+mod symbol;
+mod tables;
+
+pub use error::GenError;
@@ -9,15 +9,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[cfg(not(feature = "bootstrap"))]
-mod alap_gen;
-mod attributes;
-#[cfg(feature = "bootstrap")]
-mod bootstrap;
-mod grammar;
-mod production;
-mod state;
-mod symbol;
+use alap_gen::{grammar, GenError};
 
 fn with_changed_extension(path: &Path, new_extension: &str) -> PathBuf {
     let mut new_path = PathBuf::new();
@@ -29,6 +21,36 @@ fn with_changed_extension(path: &Path, new_extension: &str) -> PathBuf {
     new_path
 }
 
+fn is_identifier(text: String) -> Result<(), String> {
+    let mut chars = text.chars();
+    if chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        if grammar::is_rust_keyword(&text) {
+            Err(format!("{text}: a keyword can't name a module"))
+        } else {
+            Ok(())
+        }
+    } else {
+        Err(format!("{text}: not an identifier"))
+    }
+}
+
+fn is_prefix(text: String) -> Result<(), String> {
+    let mut chars = text.chars();
+    if chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "{text}: a prefix must be upper case letters or digits (starting with a letter)"
+        ))
+    }
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "alap_gen_ng",
@@ -45,6 +67,80 @@ struct CLOptions {
     /// Don't fail if reduce/reduce conflicts even if differ from expected.
     #[structopt(long)]
     ignore_rr_conflicts: bool,
+    /// Also emit the productions as a table of symbol names (AAPRODUCTIONS) and the tokens'
+    /// associativities and precedences (AATOKEN_PRECEDENCES).
+    #[structopt(long)]
+    emit_production_table: bool,
+    /// Also emit an unparser for the parser's parse trees (aa_production_shape() and aa_unparse()).
+    #[structopt(long)]
+    unparser: bool,
+    /// Resolve otherwise unresolved shift/reduce conflicts this way (with a note) rather than failing.
+    #[structopt(long, possible_values = &["shift"])]
+    default_sr: Option<String>,
+    /// Fail unless there are exactly this many unresolved conflicts (overriding any "%expect").
+    #[structopt(long, conflicts_with_all = &["expect-sr", "expect-rr"])]
+    expect: Option<u32>,
+    /// Fail unless there are exactly this many unresolved shift/reduce conflicts (overriding
+    /// any "%shift_reduce" or "%expect").
+    #[structopt(long)]
+    expect_sr: Option<u32>,
+    /// Fail unless there are exactly this many unresolved reduce/reduce conflicts (overriding
+    /// any "%reduce_reduce" or "%expect").
+    #[structopt(long)]
+    expect_rr: Option<u32>,
+    /// Include the specification's "%if FEATURE" sections for this feature.
+    #[structopt(long = "feature")]
+    features: Vec<String>,
+    /// In debug builds, panic if more than one of a reduction's predicates is satisfied.
+    #[structopt(long)]
+    strict_predicates: bool,
+    /// Make predicates false (rather than panicking) should they refer to attributes below the bottom of the stack.
+    #[structopt(long)]
+    checked_predicates: bool,
+    /// Emit the parser's action table compressed by row displacement instead of as match code.
+    #[structopt(long)]
+    compress_tables: bool,
+    /// Look actions (compressed), gotos and look ahead sets up in static arrays instead of match code.
+    #[structopt(long)]
+    tables: bool,
+    /// Make the generated code usable in a "no_std" crate (with lalr1_plus's "std" feature off).
+    #[structopt(long)]
+    no_std: bool,
+    /// Have the parser print its stack and recent tokens should a table lookup panic.
+    #[structopt(long)]
+    diagnose_panics: bool,
+    /// Wrap the generated code in a module of this name (so that several parsers can share a module)
+    /// instead of that given by "%module" (if any).
+    #[structopt(long, validator = is_identifier)]
+    namespace: Option<String>,
+    /// Start the generated names (e.g. AATerminal and aa_rhs) with this prefix instead of "AA"
+    /// (or that given by "%prefix").
+    #[structopt(long, validator = is_prefix)]
+    prefix: Option<String>,
+    /// Report (as notes) constructs in the grammar that are prone to ambiguity.
+    #[structopt(long)]
+    ambiguity_report: bool,
+    /// Fail if injections are nested more deeply than this.
+    #[structopt(long)]
+    max_inject_depth: Option<usize>,
+    /// Fail if more than this many bytes are injected in total.
+    #[structopt(long)]
+    max_inject_bytes: Option<usize>,
+    /// Also write the grammar in a canonical form (for diffing) to a ".grammar" file.
+    #[structopt(long)]
+    emit_canonical_text: bool,
+    /// Also write the parser's states as a Graphviz digraph to a ".dot" file.
+    #[structopt(long)]
+    dot: bool,
+    /// Write every conflict (and its resolution) as JSON to this file.
+    #[structopt(long, parse(from_os_str))]
+    conflicts_json: Option<PathBuf>,
+    /// Record a checksum of the specification in the output file.
+    #[structopt(long)]
+    emit_checksum: bool,
+    /// Don't write anything but fail if the output file's recorded checksum doesn't match the specification.
+    #[structopt(long)]
+    verify: bool,
     /// Specify the path of the required output file (if different to the default).
     #[structopt(short, long)]
     output: Option<PathBuf>,
@@ -54,87 +150,251 @@ struct CLOptions {
 }
 
 fn main() {
-    let cl_options = CLOptions::from_args();
+    if let Err(error) = run(CLOptions::from_args()) {
+        eprintln!("{error}");
+        std::process::exit(exit_code(&error));
+    }
+}
+
+// The exit status for each kind of failure
+fn exit_code(error: &GenError) -> i32 {
+    match error {
+        GenError::OutputExists(_) => 1,
+        GenError::OpenSpecification(_)
+        | GenError::ReadSpecification(_)
+        | GenError::ParseFailed(_) => 2,
+        GenError::Grammar(_) => 4,
+        GenError::WriteCode(..) => 6,
+        GenError::WriteFile(..) => 7,
+        GenError::OutOfDate(..) => 8,
+    }
+}
+
+fn run(cl_options: CLOptions) -> Result<(), GenError> {
     let output_path = if let Some(output_path) = cl_options.output {
         output_path
     } else {
         with_changed_extension(&cl_options.specification, "rs")
     };
-    if output_path.exists() && !cl_options.force {
-        eprintln!(
-            "{}: output file already exists",
-            output_path.to_string_lossy()
-        );
-        std::process::exit(1);
-    }
-    let mut file = match fs::File::open(&cl_options.specification) {
-        Ok(file) => file,
-        Err(error) => {
-            eprintln!("Error opening specification file: {error:?}");
-            std::process::exit(2);
-        }
-    };
+    if output_path.exists() && !cl_options.force && !cl_options.verify {
+        return Err(GenError::OutputExists(output_path));
+    }
+    let mut file =
+        fs::File::open(&cl_options.specification).map_err(GenError::OpenSpecification)?;
     let mut specification_text = String::new();
-    if let Err(error) = file.read_to_string(&mut specification_text) {
-        eprintln!("Error reading specification file: {error:?}");
-        std::process::exit(2);
-    };
+    file.read_to_string(&mut specification_text)
+        .map_err(GenError::ReadSpecification)?;
 
-    let specification = match grammar::Specification::new(
+    let inject_limits = grammar::InjectLimits {
+        max_depth: cl_options.max_inject_depth,
+        max_bytes: cl_options.max_inject_bytes,
+    };
+    let mut specification = grammar::Specification::with_prefix(
         &specification_text,
         &cl_options.specification.to_string_lossy().to_string(),
-    ) {
-        Ok(spec) => spec,
-        Err(error) => {
-            eprintln!("Parse failed: {error:?}");
-            std::process::exit(2);
+        &cl_options.features,
+        inject_limits,
+        cl_options.prefix.as_deref(),
+    )
+    .map_err(|error| GenError::ParseFailed(format!("{error:?}")))?;
+    if cl_options.default_sr.is_some() {
+        specification.default_sr_shift = true;
+    }
+    if let Some(expect) = cl_options.expect {
+        let location = match specification.expected_conflicts.take() {
+            Some((expected, location)) => {
+                grammar::report_warning(
+                    &location,
+                    &format!("%expect {expected} overridden by --expect {expect}"),
+                );
+                location
+            }
+            None => lexan::Location::default(),
+        };
+        specification.expected_conflicts = Some((expect, location));
+    }
+    if cl_options.expect_sr.is_some() || cl_options.expect_rr.is_some() {
+        // each kind is then counted separately
+        if let Some((expected, location)) = specification.expected_conflicts.take() {
+            grammar::report_warning(
+                &location,
+                &format!("%expect {expected} overridden by --expect-sr/--expect-rr"),
+            );
         }
-    };
+        if let Some(expect_sr) = cl_options.expect_sr {
+            specification.expected_sr_conflicts = expect_sr;
+        }
+        if let Some(expect_rr) = cl_options.expect_rr {
+            specification.expected_rr_conflicts = expect_rr;
+        }
+    }
 
-    let grammar = match grammar::Grammar::try_from((
+    let write_conflicts_json = |json: &str| match &cl_options.conflicts_json {
+        Some(path) => fs::write(path, json).map_err(|err| GenError::WriteFile(path.clone(), err)),
+        None => Ok(()),
+    };
+    let mut grammar = match grammar::Grammar::try_from((
         specification,
         cl_options.ignore_sr_conflicts,
         cl_options.ignore_rr_conflicts,
     )) {
         Ok(grammar) => grammar,
         Err(err) => {
-            match err {
-                grammar::Error::TooManyErrors(count) => {
-                    eprintln!("Too many errors: {count:?}.");
-                    std::process::exit(4);
-                }
-                grammar::Error::UndefinedSymbols(count) => {
-                    eprintln!("Undefined symbols: {count:?}.");
-                    std::process::exit(4);
-                }
-                grammar::Error::UnexpectedSRConflicts(count, expected, report) => {
-                    eprintln!("{report}\nUnexpected shift/reduce conflicts: {count} expected: {expected}.");
-                    std::process::exit(4);
-                }
-                grammar::Error::UnexpectedRRConflicts(count, expected, report) => {
-                    eprintln!("{report}\nUnexpected reduce/reduce conflicts: {count} expected: {expected}.");
-                    std::process::exit(4);
-                }
+            if let Some(json) = err.conflicts_json() {
+                write_conflicts_json(json)?;
             }
+            return Err(GenError::Grammar(err));
         }
     };
 
-    if let Err(err) = grammar.write_parser_code_to_file(&output_path) {
-        eprintln!(
-            "{}: problems writing file: {:?}.",
-            output_path.to_string_lossy(),
-            err
-        );
-        std::process::exit(6);
+    write_conflicts_json(&grammar.conflicts_json())?;
+
+    if cl_options.ambiguity_report {
+        for (location, finding) in grammar.ambiguity_report() {
+            grammar::report_note(&location, &finding);
+        }
+    }
+
+    if cl_options.verify {
+        let recorded = fs::read_to_string(&output_path)
+            .ok()
+            .and_then(|code| grammar::recorded_checksum(&code));
+        if recorded != Some(grammar::checksum(&specification_text)) {
+            return Err(GenError::OutOfDate(output_path, cl_options.specification));
+        }
+        return Ok(());
+    }
+
+    grammar.emit_production_table = cl_options.emit_production_table;
+    grammar.emit_unparser = cl_options.unparser;
+    grammar.strict_predicates = cl_options.strict_predicates;
+    grammar.checked_predicates = cl_options.checked_predicates;
+    grammar.compress_tables = cl_options.compress_tables;
+    grammar.table_driven = cl_options.tables;
+    grammar.no_std = cl_options.no_std;
+    grammar.diagnose_panics = cl_options.diagnose_panics;
+    if cl_options.namespace.is_some() {
+        grammar.namespace = cl_options.namespace;
+    }
+    if cl_options.emit_checksum {
+        grammar.specification_checksum = Some(grammar::checksum(&specification_text));
+    }
+    grammar
+        .write_parser_code_to_file(&output_path)
+        .map_err(|err| GenError::WriteCode(output_path.clone(), err))?;
+
+    if cl_options.emit_canonical_text {
+        let canonical_file = with_changed_extension(&output_path, "grammar");
+        fs::write(&canonical_file, grammar.canonical_text())
+            .map_err(|err| GenError::WriteFile(canonical_file, err))?;
+    }
+
+    if cl_options.dot {
+        let dot_file = with_changed_extension(&output_path, "dot");
+        grammar
+            .write_automaton_dot_to_file(&dot_file)
+            .map_err(|err| GenError::WriteFile(dot_file, err))?;
     }
 
     let description_file = with_changed_extension(&output_path, "states");
-    if let Err(err) = grammar.write_description(&description_file) {
-        eprintln!(
-            "{}: problems writing file: {:?}.",
-            output_path.to_string_lossy(),
-            err
-        );
-        std::process::exit(7);
-    };
+    grammar
+        .write_description_to_file(&description_file)
+        .map_err(|err| GenError::WriteFile(description_file, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_are_errors() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_run_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("sum.alaps");
+        let text = "%attr AttributeData\n%target Sum\n%%\n%token NUMBER ([0-9]+)\n%%\n";
+        fs::write(&spec_path, format!("{text}Sum: NUMBER.\n")).unwrap();
+        let spec = spec_path.to_string_lossy().to_string();
+        let run_with = |args: &[&str]| {
+            run(CLOptions::from_iter(
+                ["alap_gen_ng"]
+                    .iter()
+                    .chain(args.iter())
+                    .chain([&spec.as_str()]),
+            ))
+        };
+        assert!(run_with(&[]).is_ok());
+        assert!(dir.join("sum.rs").exists() && dir.join("sum.states").exists());
+        let error = run_with(&[]).unwrap_err();
+        assert!(matches!(error, GenError::OutputExists(_)));
+        assert_eq!(exit_code(&error), 1);
+        let error = run_with(&["--verify"]).unwrap_err();
+        assert!(matches!(error, GenError::OutOfDate(..)));
+        assert_eq!(exit_code(&error), 8);
+        assert!(run_with(&["-f", "--emit-checksum"]).is_ok());
+        assert!(run_with(&["--verify"]).is_ok());
+        fs::write(&spec_path, format!("{text}Sum: Sum \"+\" NUMBER.\n")).unwrap();
+        let error = run_with(&["-f"]).unwrap_err();
+        assert!(matches!(error, GenError::Grammar(_)));
+        assert_eq!(exit_code(&error), 4);
+        fs::remove_dir_all(&dir).unwrap();
+        let error = run_with(&[]).unwrap_err();
+        assert!(matches!(error, GenError::OpenSpecification(_)));
+        assert_eq!(exit_code(&error), 2);
+    }
+
+    #[test]
+    fn conflict_kinds_expected_apart() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_expect_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("sum.alaps");
+        let text = "%attr AttributeData\n%target Sum\n%expect 2\n%%\n%token NUMBER ([0-9]+)\n%token PLUS \"+\"\n%%\n";
+        fs::write(
+            &spec_path,
+            format!("{text}Sum: A | B | Sum \"+\" Sum.\nA: NUMBER.\nB: NUMBER.\n"),
+        )
+        .unwrap();
+        let spec = spec_path.to_string_lossy().to_string();
+        let options = |args: &[&str]| {
+            CLOptions::from_iter_safe(
+                ["alap_gen_ng", "-f"]
+                    .iter()
+                    .chain(args.iter())
+                    .chain([&spec.as_str()]),
+            )
+        };
+        assert!(run(options(&[]).unwrap()).is_ok());
+        assert!(run(options(&["--expect-sr", "1", "--expect-rr", "1"]).unwrap()).is_ok());
+        // the total no longer applies
+        let error = run(options(&["--expect-sr", "1"]).unwrap()).unwrap_err();
+        assert!(matches!(
+            error,
+            GenError::Grammar(grammar::Error::UnexpectedRRConflicts(1, 0, ..))
+        ));
+        assert!(options(&["--expect", "2", "--expect-rr", "1"]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prefix_overridden() {
+        let dir = std::env::temp_dir().join(format!("alap_gen_prefix_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("sum.alaps");
+        let text = "%attr AttributeData\n%target Sum\n%prefix YY\n%%\n%token NUMBER ([0-9]+)\n%%\n";
+        fs::write(&spec_path, format!("{text}Sum: NUMBER.\n")).unwrap();
+        let spec = spec_path.to_string_lossy().to_string();
+        let options = |args: &[&str]| {
+            CLOptions::from_iter_safe(
+                ["alap_gen_ng", "-f"]
+                    .iter()
+                    .chain(args.iter())
+                    .chain([&spec.as_str()]),
+            )
+        };
+        assert!(run(options(&["--prefix", "XX"]).unwrap()).is_ok());
+        let code = fs::read_to_string(dir.join("sum.rs")).unwrap();
+        assert!(code.contains("pub enum XXTerminal {\n    XXEnd,\n"));
+        assert!(!code.contains("YYTerminal"));
+        assert!(options(&["--prefix", "xx"]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -18,7 +18,10 @@ pub struct ProductionTailData {
     predicate: Option<String>,
     associativity: Associativity,
     precedence: u16,
+    precedence_is_explicit: bool,
     action: Option<String>,
+    // the message (a string literal) with which "%reject" makes reductions semantic errors
+    rejection: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -39,6 +42,7 @@ impl ProductionTail {
     ) -> Self {
         let predicate = o_predicate.map(|predicate| predicate.to_string());
         let action = o_action.map(|action| action.to_string());
+        let precedence_is_explicit = associative_precedence.is_some();
         let (associativity, precedence) = if let Some(tuple) = associative_precedence {
             tuple
         } else if let Some(tuple) = rhs_associated_precedence(right_hand_side) {
@@ -52,8 +56,56 @@ impl ProductionTail {
             action,
             associativity,
             precedence,
+            precedence_is_explicit,
+            rejection: None,
         }))
     }
+
+    /// Whether the precedence was given by "%prec" rather than derived from the symbols
+    pub fn has_explicit_precedence(&self) -> bool {
+        self.0.precedence_is_explicit
+    }
+
+    /// A copy of this tail with the given associativity and precedence
+    pub fn with_associativity_and_precedence(
+        &self,
+        (associativity, precedence): (Associativity, u16),
+    ) -> Self {
+        Self(Rc::new(ProductionTailData {
+            right_hand_side: self.0.right_hand_side.clone(),
+            predicate: self.0.predicate.clone(),
+            action: self.0.action.clone(),
+            associativity,
+            precedence,
+            precedence_is_explicit: true,
+            rejection: self.0.rejection.clone(),
+        }))
+    }
+
+    /// A copy of this tail whose reductions are rejected with `message` (a literal)
+    pub fn with_rejection(&self, message: &str) -> Self {
+        Self(Rc::new(ProductionTailData {
+            right_hand_side: self.0.right_hand_side.clone(),
+            predicate: self.0.predicate.clone(),
+            action: self.0.action.clone(),
+            associativity: self.0.associativity,
+            precedence: self.0.precedence,
+            precedence_is_explicit: self.0.precedence_is_explicit,
+            rejection: Some(message.to_string()),
+        }))
+    }
+
+    /// The names of the lexical modes that this tail's action switches to
+    pub fn mode_switches(&self) -> Vec<String> {
+        if let Some(action) = &self.0.action {
+            MODE_CRE
+                .captures_iter(action)
+                .map(|caps| caps[1].to_string())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
 }
 
 fn rhs_associated_precedence(symbols: &[Symbol]) -> Option<(Associativity, u16)> {
@@ -65,6 +117,16 @@ fn rhs_associated_precedence(symbols: &[Symbol]) -> Option<(Associativity, u16)>
     None
 }
 
+// Whether two (well formed) type texts denote the same type
+pub fn same_type(lhs: &str, rhs: &str) -> bool {
+    let significant = |text: &str| {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+    };
+    significant(lhs) == significant(rhs)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProductionId(u32);
 
@@ -79,6 +141,12 @@ impl ProductionId {
     }
 }
 
+impl From<ProductionId> for u32 {
+    fn from(ident: ProductionId) -> Self {
+        ident.0
+    }
+}
+
 impl Display for ProductionId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -103,6 +171,7 @@ impl Clone for Production {
 
 lazy_static! {
     static ref RHS_CRE: regex::Regex = regex::Regex::new(r"\$(\d+)").unwrap();
+    static ref MODE_CRE: regex::Regex = regex::Regex::new(r"\$MODE\(\s*(\w+)\s*\)").unwrap();
 }
 
 impl Production {
@@ -119,7 +188,7 @@ impl Production {
     }
 
     pub fn is_start_production(&self) -> bool {
-        self.0.ident.is_zero()
+        self.0.left_hand_side.is_start()
     }
 
     pub fn len(&self) -> usize {
@@ -150,35 +219,119 @@ impl Production {
         self.0.tail.0.predicate.is_some()
     }
 
-    pub fn expanded_predicate(&self) -> Option<String> {
+    // If `checked`, the predicate is false (rather than panicking) when the attributes
+    // that it refers to aren't all on the stack.  The names of the generated code's
+    // variables start with `aa` (the variable prefix).
+    pub fn expanded_predicate(&self, checked: bool, aa: &str) -> Option<String> {
         if let Some(predicate) = &self.0.tail.0.predicate {
             let rhs_len = self.0.tail.0.right_hand_side.len();
+            let mut deepest = 0;
             let string = RHS_CRE
                 .replace_all(predicate, |caps: &regex::Captures| {
-                    format!(
-                        "aa_attributes.at_len_minus_n({})",
-                        rhs_len + 1 - usize::from_str(&caps[1]).unwrap()
-                    )
+                    let index = usize::from_str(&caps[1]).unwrap();
+                    let n = rhs_len + 1 - index;
+                    deepest = deepest.max(n);
+                    match self.typed_symbol(index) {
+                        Some(attribute_type) => format!(
+                            "(*lalr1_plus::TypedAttribute::<{attribute_type}>::typed({aa}attributes.at_len_minus_n({n})))"
+                        ),
+                        None => format!("{aa}attributes.at_len_minus_n({n})"),
+                    }
                 })
                 .to_string();
-            let string = string.replace("$?", "aa_tag");
-            Some(string)
+            let string = string.replace("$?", &format!("{aa}tag"));
+            if checked && deepest > 0 {
+                Some(format!(
+                    "{aa}attributes.try_at_len_minus_n({deepest}).is_some() && ({string})"
+                ))
+            } else {
+                Some(string)
+            }
         } else {
             None
         }
     }
 
-    pub fn expanded_action(&self) -> Option<String> {
+    pub fn action(&self) -> Option<&str> {
+        self.0.tail.0.action.as_deref()
+    }
+
+    /// The message (a literal) of the semantic error that replaces this production's
+    /// reduction (see "%reject"), if any.
+    pub fn rejection(&self) -> Option<&str> {
+        self.0.tail.0.rejection.as_deref()
+    }
+
+    // The type given (by "%type") to the attributes of the `index`th (from 1) symbol
+    fn typed_symbol(&self, index: usize) -> Option<String> {
+        self.0
+            .tail
+            .0
+            .right_hand_side
+            .get(index.checked_sub(1)?)?
+            .attribute_type()
+    }
+
+    // Whether the first symbol's attributes have the same type as the left hand side's so
+    // that (as for untyped attributes) $$ can start out as $1
+    pub fn starts_with_lhs_type(&self) -> bool {
+        match (self.left_hand_side().attribute_type(), self.typed_symbol(1)) {
+            (Some(lhs_type), Some(first_type)) => same_type(&lhs_type, &first_type),
+            _ => false,
+        }
+    }
+
+    pub fn expanded_action(&self, aa: &str) -> Option<String> {
         // TODO: move action expansion to RHS creation
         if let Some(action) = &self.0.tail.0.action {
-            let string = action.replace("$$", "aa_lhs");
-            let string = string.replace("$INJECT", "aa_inject");
+            // Typed attributes are converted into local variables (and $$'s back again)
+            let lhs_type = self.left_hand_side().attribute_type();
+            let mut prologue = String::new();
+            let mut epilogue = String::new();
+            let string = if let Some(lhs_type) = &lhs_type {
+                prologue += "#[allow(unused_mut, unused_assignments)] ";
+                if self.starts_with_lhs_type() {
+                    prologue += &format!(
+                        "let mut {aa}v0: {lhs_type} = lalr1_plus::TypedAttribute::<{lhs_type}>::into_typed(core::mem::take(&mut {aa}lhs));\n"
+                    );
+                } else {
+                    prologue += &format!("let mut {aa}v0: {lhs_type};\n");
+                }
+                epilogue = format!(
+                    "\n{aa}lhs = lalr1_plus::TypedAttribute::<{lhs_type}>::from_typed({aa}v0);"
+                );
+                action.replace("$$", &format!("{aa}v0"))
+            } else {
+                action.replace("$$", &format!("{aa}lhs"))
+            };
+            let string = string.replace("$INJECT", &format!("{aa}inject"));
+            // the span of the right hand side (see "%spanned")
+            let string = string.replace("$@", &format!("{aa}span"));
+            let string = MODE_CRE
+                .replace_all(&string, |caps: &regex::Captures| {
+                    let label = crate::grammar::mode_switch_label(&caps[1]);
+                    format!("{aa}inject(String::new(), \"{label}\".to_string())")
+                })
+                .to_string();
+            let mut typed = BTreeMap::<usize, String>::new();
             let string = RHS_CRE
                 .replace_all(&string, |caps: &regex::Captures| {
-                    format!("aa_rhs[{}]", usize::from_str(&caps[1]).unwrap() - 1)
+                    let index = usize::from_str(&caps[1]).unwrap();
+                    if let Some(attribute_type) = self.typed_symbol(index) {
+                        typed.insert(index, attribute_type);
+                        format!("{aa}v{index}")
+                    } else {
+                        format!("{aa}rhs[{}]", index - 1)
+                    }
                 })
                 .to_string();
-            Some(string)
+            for (index, attribute_type) in typed.iter() {
+                prologue += &format!(
+                    "#[allow(unused_mut)] let mut {aa}v{index}: {attribute_type} = lalr1_plus::TypedAttribute::<{attribute_type}>::into_typed(core::mem::take(&mut {aa}rhs[{}]));\n",
+                    index - 1
+                );
+            }
+            Some(prologue + &string + &epilogue)
         } else {
             None
         }
@@ -358,6 +511,17 @@ impl GrammarItemKey {
     pub fn has_reducible_error_recovery_tail(&self) -> bool {
         self.is_reducible() && self.production.has_error_recovery_tail()
     }
+
+    pub fn follows_error(&self) -> bool {
+        if self.dot > 0 {
+            match &self.production.0.tail.0.right_hand_side[self.dot - 1] {
+                Symbol::Terminal(_) => false,
+                Symbol::NonTerminal(non_terminal) => non_terminal.is_error(),
+            }
+        } else {
+            false
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -457,15 +621,22 @@ impl GrammarItemSet {
         *look_ahead_set = look_ahead_set.difference(symbols).cloned().collect();
     }
 
+    // Tokens that can follow "%error" in this (error recovery) state: the look ahead
+    // sets of reducible "X: ... %error ." items and the synchronization tokens of
+    // "X: ... %error . SYNC ..." items.
     pub fn error_recovery_look_ahead_set_contains(&self, token: &Token) -> bool {
-        for look_ahead_set in self
-            .0
-            .iter()
-            .filter(|x| x.0.has_reducible_error_recovery_tail())
-            .map(|x| x.1)
-        {
-            if look_ahead_set.contains(token) {
-                return true;
+        for (key, look_ahead_set) in self.0.iter() {
+            if key.has_reducible_error_recovery_tail() {
+                if look_ahead_set.contains(token) {
+                    return true;
+                }
+            } else if key.follows_error() {
+                let remainder = &key.production.0.tail.0.right_hand_side[key.dot..];
+                for look_ahead_symbol in look_ahead_set.iter() {
+                    if TokenSet::first_all_caps(remainder, look_ahead_symbol).contains(token) {
+                        return true;
+                    }
+                }
             }
         }
         false
@@ -1,9 +1,10 @@
 // Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
 
-use crate::production::{GrammarItemKey, GrammarItemSet};
+use crate::production::{GrammarItemKey, GrammarItemSet, Production, ProductionId};
 use crate::symbol::non_terminal::NonTerminal;
 use crate::symbol::terminal::{Token, TokenSet};
 use crate::symbol::{Associativity, Symbol};
+use crate::tables;
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
@@ -23,6 +24,29 @@ impl Default for ProcessedState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    ShiftReduce,
+    ReduceReduce,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Shift,
+    Reduce,
+    Unresolved,
+}
+
+/// A conflict on `token` between a shift and the reduction of a production or between
+/// the reductions of two productions (the one reduced, if any, first) and its resolution.
+#[derive(Debug, Clone)]
+pub struct ConflictRecord {
+    pub kind: ConflictKind,
+    pub token: Token,
+    pub productions: Vec<ProductionId>,
+    pub resolution: Resolution,
+}
+
 #[derive(Debug, Default)]
 pub struct ParserStateData {
     pub ident: u32,
@@ -33,6 +57,7 @@ pub struct ParserStateData {
     processed_state: Cell<ProcessedState>,
     shift_reduce_conflicts: RefCell<Vec<(Token, ParserState, GrammarItemKey, TokenSet)>>,
     reduce_reduce_conflicts: RefCell<Vec<((GrammarItemKey, GrammarItemKey), TokenSet)>>,
+    conflict_records: RefCell<Vec<ConflictRecord>>,
 }
 
 #[derive(Debug)]
@@ -171,22 +196,36 @@ impl ParserState {
         let mut shift_reduce_conflicts = self.0.shift_reduce_conflicts.borrow_mut();
         let mut shift_list = self.0.shift_list.borrow_mut();
         let mut grammar_items = self.0.grammar_items.borrow_mut();
+        let mut conflict_records = self.0.conflict_records.borrow_mut();
         for (shift_symbol, goto_state, reducible_item, look_ahead_set) in conflicts.iter() {
+            let mut record = |resolution| {
+                conflict_records.push(ConflictRecord {
+                    kind: ConflictKind::ShiftReduce,
+                    token: shift_symbol.clone(),
+                    productions: vec![reducible_item.production().ident()],
+                    resolution,
+                })
+            };
             if shift_symbol.precedence() < reducible_item.precedence() {
                 shift_list.remove(shift_symbol);
+                record(Resolution::Reduce);
             } else if shift_symbol.precedence() > reducible_item.precedence() {
                 grammar_items
                     .get_mut(reducible_item)
                     .unwrap()
                     .remove(shift_symbol);
+                record(Resolution::Shift);
             } else if reducible_item.associativity() == Associativity::Left {
                 shift_list.remove(shift_symbol);
+                record(Resolution::Reduce);
             } else if reducible_item.has_error_recovery_tail() {
                 grammar_items
                     .get_mut(reducible_item)
                     .unwrap()
                     .remove(shift_symbol);
+                record(Resolution::Shift);
             } else {
+                record(Resolution::Unresolved);
                 // Default: resolve in favour of shift but mark as unresolved
                 // to give the user the option of accepting this resolution
                 grammar_items
@@ -204,6 +243,23 @@ impl ParserState {
         shift_reduce_conflicts.len()
     }
 
+    // Accept the default resolution (shift) of the unresolved shift/reduce conflicts
+    pub fn accept_shift_reduce_conflicts(&self) -> Vec<(Token, GrammarItemKey)> {
+        for record in self.0.conflict_records.borrow_mut().iter_mut() {
+            if record.kind == ConflictKind::ShiftReduce
+                && record.resolution == Resolution::Unresolved
+            {
+                record.resolution = Resolution::Shift;
+            }
+        }
+        self.0
+            .shift_reduce_conflicts
+            .borrow_mut()
+            .drain(..)
+            .map(|(token, _, item, _)| (token, item))
+            .collect()
+    }
+
     pub fn resolve_reduce_reduce_conflicts(&self) -> usize {
         // TODO: think about moving reduce/reduce conflict resolution inside GrammarItemSet
         let reducible_key_set = self.0.grammar_items.borrow().reducible_key_set();
@@ -212,6 +268,20 @@ impl ParserState {
         }
 
         let mut reduce_reduce_conflicts = self.0.reduce_reduce_conflicts.borrow_mut();
+        let mut conflict_records = self.0.conflict_records.borrow_mut();
+        let mut record = |intersection: &TokenSet,
+                          reduced: &GrammarItemKey,
+                          other: &GrammarItemKey,
+                          resolution| {
+            for token in intersection.iter() {
+                conflict_records.push(ConflictRecord {
+                    kind: ConflictKind::ReduceReduce,
+                    token: token.clone(),
+                    productions: vec![reduced.production().ident(), other.production().ident()],
+                    resolution,
+                })
+            }
+        };
         let reducible_key_set_2 = reducible_key_set.clone();
         for key_1 in reducible_key_set.iter() {
             for key_2 in reducible_key_set_2.iter() {
@@ -227,12 +297,15 @@ impl ParserState {
                                 .grammar_items
                                 .borrow_mut()
                                 .remove_look_ahead_symbols(key_1, &intersection);
+                            record(&intersection, key_2, key_1, Resolution::Reduce);
                         } else if key_2.has_error_recovery_tail() {
                             self.0
                                 .grammar_items
                                 .borrow_mut()
                                 .remove_look_ahead_symbols(key_2, &intersection);
+                            record(&intersection, key_1, key_2, Resolution::Reduce);
                         } else {
+                            record(&intersection, key_1, key_2, Resolution::Unresolved);
                             // Default: resolve in favour of first declared production
                             // but mark unresolved to give the user some options
                             self.0
@@ -249,6 +322,10 @@ impl ParserState {
         reduce_reduce_conflicts.len()
     }
 
+    pub fn conflict_records(&self) -> Vec<ConflictRecord> {
+        self.0.conflict_records.borrow().clone()
+    }
+
     pub fn is_recovery_state_for_token(&self, token: &Token) -> bool {
         if let Some(recovery_state) = self.0.error_recovery_state.borrow().clone() {
             if recovery_state
@@ -273,14 +350,26 @@ impl ParserState {
             .collect()
     }
 
+    // The names of the generated code's variables start with `aa` (the variable prefix)
     pub fn write_next_action_code<W: Write>(
         &self,
         wtr: &mut W,
         indent: &str,
+        aa: &str,
+        strict_predicates: bool,
+        checked_predicates: bool,
     ) -> std::io::Result<()> {
         let reductions = self.0.grammar_items.borrow().reductions();
+        // line (rather than doc) comments as rustc ignores (and warns about) the latter
+        // on match arms
+        wtr.write_fmt(format_args!("{indent}// State<{}>:\n", self.ident()))?;
+        for (key, _) in self.0.grammar_items.borrow().iter() {
+            if key.is_kernel_item() {
+                wtr.write_fmt(format_args!("{indent}//   {key}\n"))?;
+            }
+        }
         wtr.write_fmt(format_args!(
-            "{}{} => match aa_tag {{\n",
+            "{}{} => match {aa}tag {{\n",
             indent,
             self.ident()
         ))?;
@@ -318,18 +407,31 @@ impl ParserState {
                     indent,
                     look_ahead_set.formated_as_or_list()
                 ))?;
+                if strict_predicates {
+                    self.write_predicate_exclusion_check(
+                        wtr,
+                        indent,
+                        aa,
+                        productions.iter(),
+                        checked_predicates,
+                    )?;
+                }
                 for (i, production) in productions.iter().enumerate() {
                     if i == 0 {
                         wtr.write_fmt(format_args!(
                             "{}        if {} {{\n",
                             indent,
-                            production.expanded_predicate().expect("more than one")
+                            production
+                                .expanded_predicate(checked_predicates, aa)
+                                .expect("more than one")
                         ))?;
                     } else if production.has_predicate() {
                         wtr.write_fmt(format_args!(
                             "{}        }} else if {} {{\n",
                             indent,
-                            production.expanded_predicate().expect("more than one")
+                            production
+                                .expanded_predicate(checked_predicates, aa)
+                                .expect("more than one")
                         ))?;
                     } else {
                         wtr.write_fmt(format_args!("{indent}        }} else {{\n",))?;
@@ -337,6 +439,10 @@ impl ParserState {
                     wtr.write_fmt(format_args!("{indent}            // {production}\n"))?;
                     if production.is_start_production() {
                         wtr.write_fmt(format_args!("{indent}            Action::Accept\n",))?;
+                    } else if let Some(message) = production.rejection() {
+                        wtr.write_fmt(format_args!(
+                            "{indent}            Action::SemanticError({message})\n",
+                        ))?;
                     } else {
                         wtr.write_fmt(format_args!(
                             "{}            Action::Reduce({})\n",
@@ -354,16 +460,172 @@ impl ParserState {
         Ok(())
     }
 
+    // The encoded action for each of `tokens` (in AATerminal order) in this state
+    pub fn action_table_row(&self, tokens: &[Token]) -> Vec<u32> {
+        let mut row = vec![tables::SYNTAX_ERROR; tokens.len()];
+        let reductions = self.0.grammar_items.borrow().reductions();
+        for (productions, look_ahead_set) in reductions.reductions() {
+            let production = productions.iter().next().expect("never empty");
+            let entry = if productions.len() > 1 {
+                tables::PREDICATED
+            } else if production.is_start_production() {
+                tables::ACCEPT
+            } else {
+                tables::reduce(production.ident().into())
+            };
+            for (column, token) in tokens.iter().enumerate() {
+                if look_ahead_set.contains(token) {
+                    row[column] = entry;
+                }
+            }
+        }
+        // shifts take priority as they do in next_action()
+        for (column, token) in tokens.iter().enumerate() {
+            if let Some(state) = self.0.shift_list.borrow().get(token) {
+                row[column] = tables::shift(state.ident());
+            }
+        }
+        row
+    }
+
+    pub fn reduced_productions(&self) -> Vec<Production> {
+        let reductions = self.0.grammar_items.borrow().reductions();
+        reductions
+            .reductions()
+            .flat_map(|(productions, _)| productions.iter().cloned())
+            .collect()
+    }
+
+    // The states that may follow this one if only the non terminals for which
+    // `is_producible` is true can be produced
+    pub fn successors(&self, is_producible: impl Fn(&NonTerminal) -> bool) -> Vec<u32> {
+        let mut successors: Vec<u32> = self
+            .0
+            .shift_list
+            .borrow()
+            .values()
+            .map(|x| x.ident())
+            .collect();
+        for (non_terminal, state) in self.0.goto_table.borrow().iter() {
+            if is_producible(non_terminal) {
+                successors.push(state.ident());
+            }
+        }
+        successors
+    }
+
+    pub fn goto_entries(&self) -> Vec<(NonTerminal, u32)> {
+        self.0
+            .goto_table
+            .borrow()
+            .iter()
+            .map(|(non_terminal, state)| (non_terminal.clone(), state.ident()))
+            .collect()
+    }
+
+    pub fn has_predicated_reductions(&self) -> bool {
+        self.0
+            .grammar_items
+            .borrow()
+            .reductions()
+            .reductions()
+            .any(|(productions, _)| productions.len() > 1)
+    }
+
+    // Evaluate all of the predicates and panic (in debug builds) if more than one passes
+    fn write_predicate_exclusion_check<'a, W: Write>(
+        &self,
+        wtr: &mut W,
+        indent: &str,
+        aa: &str,
+        productions: impl Iterator<Item = &'a Production>,
+        checked_predicates: bool,
+    ) -> std::io::Result<()> {
+        wtr.write_fmt(format_args!("{indent}        #[cfg(debug_assertions)]\n"))?;
+        wtr.write_fmt(format_args!("{indent}        {{\n"))?;
+        wtr.write_fmt(format_args!(
+            "{indent}            let {aa}passed: Vec<&str> = [\n"
+        ))?;
+        for production in productions {
+            if let Some(predicate) = production.expanded_predicate(checked_predicates, aa) {
+                wtr.write_fmt(format_args!(
+                    "{indent}                ({predicate}, r###\"{production}\"###),\n"
+                ))?;
+            }
+        }
+        wtr.write_fmt(format_args!("{indent}            ]\n"))?;
+        wtr.write_fmt(format_args!("{indent}            .iter()\n"))?;
+        wtr.write_fmt(format_args!(
+            "{indent}            .filter_map(|(passed, production)| passed.then_some(*production))\n"
+        ))?;
+        wtr.write_fmt(format_args!("{indent}            .collect();\n"))?;
+        wtr.write_fmt(format_args!(
+            "{indent}            if {aa}passed.len() > 1 {{\n"
+        ))?;
+        wtr.write_fmt(format_args!(
+            "{indent}                panic!(\"state {}: non exclusive predicates passed for {{{aa}tag}}: {{{aa}passed:?}}\");\n",
+            self.ident()
+        ))?;
+        wtr.write_fmt(format_args!("{indent}            }}\n"))?;
+        wtr.write_fmt(format_args!("{indent}        }}\n"))?;
+        Ok(())
+    }
+
+    // As for next_action but predicates are ignored and all candidate actions listed
+    pub fn write_possible_actions_code<W: Write>(
+        &self,
+        wtr: &mut W,
+        indent: &str,
+        aa: &str,
+    ) -> std::io::Result<()> {
+        let reductions = self.0.grammar_items.borrow().reductions();
+        wtr.write_fmt(format_args!(
+            "{}{} => match {aa}tag {{\n",
+            indent,
+            self.ident()
+        ))?;
+        for (token, state) in self.0.shift_list.borrow().iter() {
+            wtr.write_fmt(format_args!(
+                "{}    {} => vec![Action::Shift({})],\n",
+                indent,
+                token.name(),
+                state.ident()
+            ))?;
+        }
+        for (productions, look_ahead_set) in reductions.reductions() {
+            let actions: Vec<String> = productions
+                .iter()
+                .map(|production| {
+                    if production.is_start_production() {
+                        "Action::Accept".to_string()
+                    } else {
+                        format!("Action::Reduce({})", production.ident())
+                    }
+                })
+                .collect();
+            wtr.write_fmt(format_args!(
+                "{}    {} => vec![{}],\n",
+                indent,
+                look_ahead_set.formated_as_or_list(),
+                actions.join(", ")
+            ))?;
+        }
+        wtr.write_fmt(format_args!("{indent}    _ => vec![],\n",))?;
+        wtr.write_fmt(format_args!("{indent}}},\n"))?;
+        Ok(())
+    }
+
     pub fn write_goto_table_code<W: Write>(
         &self,
         wtr: &mut W,
         indent: &str,
+        prefix: &str,
     ) -> std::io::Result<()> {
         if self.0.goto_table.borrow().len() > 0 {
             wtr.write_fmt(format_args!("{}{} => match lhs {{\n", indent, self.ident()))?;
             for (non_terminal, state) in self.0.goto_table.borrow().iter() {
                 wtr.write_fmt(format_args!(
-                    "{}    AANonTerminal::{} => {},\n",
+                    "{}    {prefix}NonTerminal::{} => {},\n",
                     indent,
                     non_terminal.name(),
                     state.ident()
@@ -377,90 +639,210 @@ impl ParserState {
         Ok(())
     }
 
-    pub fn description(&self) -> String {
-        let mut string = format!("\nState<{}>:\n  Grammar Items:\n", self.0.ident);
+    pub fn write_description<W: Write>(&self, wtr: &mut W) -> std::io::Result<()> {
+        wtr.write_fmt(format_args!(
+            "\nState<{}>:\n  Grammar Items:\n",
+            self.0.ident
+        ))?;
+        let roles = self.conflict_roles();
         for (key, look_ahead_set) in self.0.grammar_items.borrow().iter() {
-            string += &format!("    {key}: {look_ahead_set}\n");
+            if roles.is_empty() {
+                wtr.write_fmt(format_args!("    {key}: {look_ahead_set}\n"))?;
+            } else {
+                let mut notes = vec![if key.is_kernel_item() {
+                    "kernel"
+                } else {
+                    "closure"
+                }];
+                if let Some(key_roles) = roles.get(&key.to_string()) {
+                    notes.extend(key_roles.iter().map(|x| x.as_str()));
+                }
+                let notes = notes.join("; ");
+                wtr.write_fmt(format_args!("    {key}: {look_ahead_set}  [{notes}]\n"))?;
+            }
         }
-        string += "  Parser Action Table:\n";
+        wtr.write_all(b"  Parser Action Table:\n")?;
         let mut empty = true;
         let shift_list = self.0.shift_list.borrow();
         if shift_list.len() > 0 {
             empty = false;
-            string += "    Shifts:\n";
+            wtr.write_all(b"    Shifts:\n")?;
             for (token, state) in shift_list.iter() {
-                string += &format!("      {} -> State<{}>\n", token, state.ident());
+                wtr.write_fmt(format_args!(
+                    "      {} -> State<{}>\n",
+                    token,
+                    state.ident()
+                ))?;
             }
         }
         let reductions = self.0.grammar_items.borrow().reductions();
         if reductions.len() > 0 {
             empty = false;
-            string += "    Reductions:\n";
+            wtr.write_all(b"    Reductions:\n")?;
             for (productions, look_ahead_set) in reductions.reductions() {
                 for production in productions.iter() {
                     if productions.len() == 1 && production.is_start_production() {
-                        string += &format!(
+                        wtr.write_fmt(format_args!(
                             "      {}: accept {}\n",
                             look_ahead_set.display_as_or_list(),
                             production
-                        );
+                        ))?;
                     } else {
-                        string += &format!(
+                        wtr.write_fmt(format_args!(
                             "      {}: reduce {}\n",
                             look_ahead_set.display_as_or_list(),
                             production
-                        );
+                        ))?;
                     }
                 }
             }
         }
         if empty {
-            string += "    <empty>\n";
+            wtr.write_all(b"    <empty>\n")?;
         }
-        string += "  Go To Table:\n";
+        wtr.write_all(b"  Go To Table:\n")?;
         if self.0.goto_table.borrow().len() == 0 {
-            string += "    <empty>\n";
+            wtr.write_all(b"    <empty>\n")?;
         } else {
             for (non_terminal, state) in self.0.goto_table.borrow().iter() {
-                string += &format!("    {} -> State<{}>\n", non_terminal.name(), state.ident());
+                wtr.write_fmt(format_args!(
+                    "    {} -> State<{}>\n",
+                    non_terminal.name(),
+                    state.ident()
+                ))?;
             }
         }
         if let Some(ref state) = self.0.error_recovery_state.borrow().clone() {
-            string += &format!("  Error Recovery State: State<{}>\n", state.ident());
-            string += &format!("    Look Ahead: {}\n", state.look_ahead_set());
+            wtr.write_fmt(format_args!(
+                "  Error Recovery State: State<{}>\n",
+                state.ident()
+            ))?;
+            wtr.write_fmt(format_args!("    Look Ahead: {}\n", state.look_ahead_set()))?;
         } else {
-            string += "  Error Recovery State: <none>\n";
+            wtr.write_all(b"  Error Recovery State: <none>\n")?;
         }
         if self.0.shift_reduce_conflicts.borrow().len() > 0 {
-            string += "  Shift/Reduce Conflicts:\n";
+            wtr.write_all(b"  Shift/Reduce Conflicts:\n")?;
             for (shift_token, goto_state, reducible_item, look_ahead_set) in
                 self.0.shift_reduce_conflicts.borrow().iter()
             {
-                string += &format!("    {shift_token}:\n");
-                string += &format!("      shift -> State<{}>\n", goto_state.ident());
-                string += &format!(
-                    "      reduce {}: {}",
+                wtr.write_fmt(format_args!("    {shift_token}:\n"))?;
+                wtr.write_fmt(format_args!(
+                    "      shift -> State<{}>\n",
+                    goto_state.ident()
+                ))?;
+                for (key, _) in self.0.grammar_items.borrow().iter() {
+                    if Self::shifts(key, shift_token) {
+                        wtr.write_fmt(format_args!("        {key}\n"))?;
+                    }
+                }
+                wtr.write_fmt(format_args!(
+                    "      reduce {}: {}\n",
                     reducible_item.production(),
                     look_ahead_set
-                );
+                ))?;
+                wtr.write_fmt(format_args!("        {reducible_item}\n"))?;
             }
         }
         if self.0.reduce_reduce_conflicts.borrow().len() > 0 {
-            string += "  Reduce/Reduce Conflicts:\n";
+            wtr.write_all(b"  Reduce/Reduce Conflicts:\n")?;
             for (items, intersection) in self.0.reduce_reduce_conflicts.borrow().iter() {
-                string += &format!("    {intersection}\n");
-                string += &format!(
+                wtr.write_fmt(format_args!("    {intersection}\n"))?;
+                wtr.write_fmt(format_args!(
                     "      reduce {} : {}\n",
                     items.0,
                     &self.0.grammar_items.borrow()[&items.0]
-                );
-                string += &format!(
+                ))?;
+                wtr.write_fmt(format_args!(
                     "      reduce {} : {}\n",
                     items.1,
                     &self.0.grammar_items.borrow()[&items.1]
-                );
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn shifts(key: &GrammarItemKey, token: &Token) -> bool {
+        matches!(key.next_symbol(), Some(Symbol::Terminal(next)) if next == token)
+    }
+
+    // What each of the grammar items involved in an unresolved conflict contributes to it
+    // (keyed by the items' text)
+    fn conflict_roles(&self) -> BTreeMap<String, Vec<String>> {
+        let mut roles = BTreeMap::<String, Vec<String>>::new();
+        let grammar_items = self.0.grammar_items.borrow();
+        for (token, _, reducible_item, _) in self.0.shift_reduce_conflicts.borrow().iter() {
+            for (key, _) in grammar_items
+                .iter()
+                .filter(|(key, _)| Self::shifts(key, token))
+            {
+                roles
+                    .entry(key.to_string())
+                    .or_default()
+                    .push(format!("shift {token}"));
             }
+            roles
+                .entry(reducible_item.to_string())
+                .or_default()
+                .push(format!("reduce on {token}"));
+        }
+        for ((chosen, other), intersection) in self.0.reduce_reduce_conflicts.borrow().iter() {
+            let tokens = intersection.display_as_or_list();
+            roles
+                .entry(chosen.to_string())
+                .or_default()
+                .push(format!("reduce on {tokens} (chosen)"));
+            roles
+                .entry(other.to_string())
+                .or_default()
+                .push(format!("reduce on {tokens} (overruled)"));
+        }
+        roles
+    }
+
+    /// Write this state (and its shifts and gotos) as a Graphviz node (and edges) whose
+    /// label lists its grammar items.  States with unresolved conflicts are highlighted.
+    pub fn write_dot_code<W: Write>(&self, wtr: &mut W) -> std::io::Result<()> {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut label = format!("State<{}>\\l", self.0.ident);
+        for (key, look_ahead_set) in self.0.grammar_items.borrow().iter() {
+            label += &escape(&format!("{key}: {look_ahead_set}"));
+            label += "\\l";
+        }
+        let highlight =
+            if self.shift_reduce_conflict_count() + self.reduce_reduce_conflict_count() > 0 {
+                ", style=filled, fillcolor=salmon"
+            } else {
+                ""
+            };
+        wtr.write_fmt(format_args!(
+            "  state{} [label=\"{label}\"{highlight}];\n",
+            self.0.ident
+        ))?;
+        for (token, state) in self.0.shift_list.borrow().iter() {
+            wtr.write_fmt(format_args!(
+                "  state{} -> state{} [label=\"{}\"];\n",
+                self.0.ident,
+                state.ident(),
+                escape(&token.to_string())
+            ))?;
+        }
+        for (non_terminal, state) in self.0.goto_table.borrow().iter() {
+            wtr.write_fmt(format_args!(
+                "  state{} -> state{} [label=\"{}\", style=dashed];\n",
+                self.0.ident,
+                state.ident(),
+                escape(non_terminal.name())
+            ))?;
         }
-        string
+        Ok(())
+    }
+
+    pub fn description(&self) -> String {
+        let mut buffer = vec![];
+        self.write_description(&mut buffer)
+            .expect("writing to a Vec can't fail");
+        String::from_utf8(buffer).expect("descriptions are UTF-8")
     }
 }
@@ -1,5 +1,9 @@
 // Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
-use std::{collections::BTreeMap, fmt};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, Write},
+};
 
 use crate::symbol::non_terminal::NonTerminal;
 use crate::symbol::tag::{Tag, TagOrToken};
@@ -9,6 +13,9 @@ pub mod non_terminal;
 pub mod tag;
 pub mod terminal;
 
+/// The prefix of the generated code's names (e.g. "AATerminal") unless "%prefix" gives another.
+pub const DEFAULT_PREFIX: &str = "AA";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Associativity {
     NonAssoc,
@@ -67,6 +74,14 @@ impl Symbol {
     pub fn is_non_terminal(&self) -> bool {
         matches!(self, Symbol::NonTerminal(_))
     }
+
+    // The type given to the symbol's attributes by "%type" (if any)
+    pub fn attribute_type(&self) -> Option<String> {
+        match self {
+            Symbol::NonTerminal(non_terminal) => non_terminal.attribute_type(),
+            Symbol::Terminal(_) => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -76,6 +91,8 @@ pub enum Error {
     DuplicateTokenDefinition(Token),
     ConflictsWithToken(Token),
     DuplicateSkipRule(String),
+    DuplicateMode(String),
+    DuplicatePrecedence(String, lexan::Location),
 }
 
 impl fmt::Display for Error {
@@ -116,6 +133,12 @@ impl fmt::Display for Error {
             Error::DuplicateSkipRule(string) => {
                 write!(f, "Skip rule \"{string}\" already defined.",)
             }
+            Error::DuplicateMode(name) => {
+                write!(f, "Lexical mode \"{name}\" already defined",)
+            }
+            Error::DuplicatePrecedence(name, declared_at) => {
+                write!(f, "{name}: precedence already declared at {declared_at}",)
+            }
         }
     }
 }
@@ -125,12 +148,22 @@ pub struct SymbolTable {
     tags: BTreeMap<String, Tag>,
     tokens: BTreeMap<String, Token>,
     literal_tokens: BTreeMap<String, Token>,
-    regex_tokens: BTreeMap<String, Token>,
+    // the literal tokens defined by "%keywords" (in order of definition)
+    keywords: Vec<Token>,
+    regex_tokens: BTreeMap<(usize, String), Token>,
     non_terminals: BTreeMap<String, NonTerminal>,
-    skip_rules: Vec<String>,
+    lexical_modes: Vec<String>,
+    skip_rules: Vec<Vec<String>>,
     last_precedence: u16,
     start_non_terminal: NonTerminal,
+    // those of any start symbols after the first
+    extra_start_non_terminals: Vec<NonTerminal>,
     pub error_non_terminal: NonTerminal,
+    // the notation that each helper non terminal was desugared from (keyed by name)
+    helpers: BTreeMap<String, String>,
+    // that of the generated code's names (and hence of the special symbols' names)
+    prefix: String,
+    end_token: Token,
 }
 
 impl Default for SymbolTable {
@@ -139,17 +172,39 @@ impl Default for SymbolTable {
             tags: BTreeMap::new(),
             tokens: BTreeMap::new(),
             literal_tokens: BTreeMap::new(),
+            keywords: vec![],
             regex_tokens: BTreeMap::new(),
             non_terminals: BTreeMap::new(),
-            skip_rules: Vec::new(),
+            lexical_modes: vec!["INITIAL".to_string()],
+            skip_rules: vec![Vec::new()],
             last_precedence: 0,
-            start_non_terminal: NonTerminal::new_start(),
-            error_non_terminal: NonTerminal::new_error(),
+            start_non_terminal: NonTerminal::new_start(DEFAULT_PREFIX),
+            extra_start_non_terminals: vec![],
+            error_non_terminal: NonTerminal::new_error(DEFAULT_PREFIX),
+            helpers: BTreeMap::new(),
+            prefix: DEFAULT_PREFIX.to_string(),
+            end_token: Token::new_end_token(DEFAULT_PREFIX),
         }
     }
 }
 
 impl SymbolTable {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The special symbols are renamed so this must be done before they're used.
+    pub fn set_prefix(&mut self, prefix: &str) {
+        self.prefix = prefix.to_string();
+        self.start_non_terminal = NonTerminal::new_start(prefix);
+        self.error_non_terminal = NonTerminal::new_error(prefix);
+        self.end_token = Token::new_end_token(prefix);
+    }
+
+    pub fn end_token(&self) -> &Token {
+        &self.end_token
+    }
+
     pub fn start_non_terminal(&self) -> &NonTerminal {
         &self.start_non_terminal
     }
@@ -158,15 +213,17 @@ impl SymbolTable {
         &self.error_non_terminal
     }
 
+    pub fn extra_start_non_terminals(&self) -> impl Iterator<Item = &NonTerminal> {
+        self.extra_start_non_terminals.iter()
+    }
+
     pub fn used_non_terminal_specials(&self) -> Vec<NonTerminal> {
-        if self.error_non_terminal.is_unused() {
-            vec![self.start_non_terminal.clone()]
-        } else {
-            vec![
-                self.start_non_terminal.clone(),
-                self.error_non_terminal.clone(),
-            ]
+        let mut specials = vec![self.start_non_terminal.clone()];
+        specials.extend(self.extra_start_non_terminals.iter().cloned());
+        if !self.error_non_terminal.is_unused() {
+            specials.push(self.error_non_terminal.clone());
         }
+        specials
     }
 
     pub fn new_tag(&mut self, name: &str, defined_at: &lexan::Location) -> Result<Tag, Error> {
@@ -192,7 +249,7 @@ impl SymbolTable {
         text: &str,
         defined_at: &lexan::Location,
     ) -> Result<Token, Error> {
-        let token = Token::new_literal_token(name, text, defined_at);
+        let token = Token::new_literal_token(name, text, defined_at, self.current_mode());
         if let Some(other) = self.tokens.insert(name.to_string(), token.clone()) {
             Err(Error::DuplicateToken(other))
         } else if let Some(other) = self.literal_tokens.insert(text.to_string(), token.clone()) {
@@ -202,16 +259,31 @@ impl SymbolTable {
         }
     }
 
+    /// A keyword is a literal token whose name is its text in upper case.
+    pub fn new_keyword(
+        &mut self,
+        keyword: &str,
+        defined_at: &lexan::Location,
+    ) -> Result<Token, Error> {
+        let name = keyword.to_uppercase();
+        let token = self.new_literal_token(&name, &format!("\"{keyword}\""), defined_at)?;
+        self.keywords.push(token.clone());
+        Ok(token)
+    }
+
     pub fn new_regex_token(
         &mut self,
         name: &str,
         text: &str,
         defined_at: &lexan::Location,
     ) -> Result<Token, Error> {
-        let token = Token::new_regex_token(name, text, defined_at);
+        let token = Token::new_regex_token(name, text, defined_at, self.current_mode());
         if let Some(other) = self.tokens.insert(name.to_string(), token.clone()) {
             Err(Error::DuplicateToken(other))
-        } else if let Some(other) = self.regex_tokens.insert(text.to_string(), token.clone()) {
+        } else if let Some(other) = self
+            .regex_tokens
+            .insert((token.mode(), text.to_string()), token.clone())
+        {
             Err(Error::DuplicateTokenDefinition(other))
         } else {
             Ok(token)
@@ -242,6 +314,10 @@ impl SymbolTable {
         self.regex_tokens.values()
     }
 
+    pub fn keywords(&self) -> impl Iterator<Item = &Token> {
+        self.keywords.iter()
+    }
+
     pub fn non_terminal_defined_at(
         &mut self,
         name: &str,
@@ -292,81 +368,234 @@ impl SymbolTable {
         Symbol::from(&self.error_non_terminal)
     }
 
+    /// Record that the helper non terminal `name` was desugared from `notation`.
+    pub fn note_helper(&mut self, name: &str, notation: String) {
+        self.helpers.insert(name.to_string(), notation);
+    }
+
+    pub fn helper_for(&self, notation: &str) -> Option<&str> {
+        self.helpers
+            .iter()
+            .find(|(_, n)| *n == notation)
+            .map(|(name, _)| name.as_str())
+    }
+
     pub fn start_non_terminal_used_at(&self, used_at: &lexan::Location) -> NonTerminal {
         self.start_non_terminal.add_used_at(used_at);
         self.start_non_terminal.clone()
     }
 
+    pub fn new_extra_start_non_terminal(
+        &mut self,
+        name: &str,
+        used_at: &lexan::Location,
+    ) -> NonTerminal {
+        let non_terminal = NonTerminal::new_start_for(&self.prefix, name, used_at);
+        self.extra_start_non_terminals.push(non_terminal.clone());
+        non_terminal
+    }
+
     pub fn add_skip_rule(&mut self, skip_rule: &String) -> Result<(), Error> {
-        if self.skip_rules.contains(skip_rule) {
+        let mode = self.current_mode();
+        if self.skip_rules[mode].contains(skip_rule) {
             Err(Error::DuplicateSkipRule(skip_rule.to_string()))
         } else {
-            self.skip_rules.push(skip_rule.to_string());
+            self.skip_rules[mode].push(skip_rule.to_string());
             Ok(())
         }
     }
 
-    pub fn skip_rules(&self) -> impl Iterator<Item = &String> {
-        self.skip_rules.iter()
+    pub fn skip_rules(&self, mode: usize) -> impl Iterator<Item = &String> {
+        self.skip_rules[mode].iter()
     }
 
+    // Token and skip definitions are added to the most recently defined mode
+    fn current_mode(&self) -> usize {
+        self.lexical_modes.len() - 1
+    }
+
+    pub fn new_lexical_mode(&mut self, name: &str) -> Result<(), Error> {
+        if self.lexical_modes.iter().any(|m| m == name) {
+            Err(Error::DuplicateMode(name.to_string()))
+        } else {
+            self.lexical_modes.push(name.to_string());
+            self.skip_rules.push(Vec::new());
+            Ok(())
+        }
+    }
+
+    pub fn lexical_modes(&self) -> impl Iterator<Item = &String> {
+        self.lexical_modes.iter()
+    }
+
+    pub fn lexical_mode_index(&self, name: &str) -> Option<usize> {
+        self.lexical_modes.iter().position(|m| m == name)
+    }
+
+    /// Symbols whose precedence has already been declared keep their original
+    /// precedence and are returned as errors.  Without an explicit `level` the
+    /// precedence is one higher than the highest declared so far.
     pub fn set_precedences(
         &mut self,
         associativity: Associativity,
+        level: Option<u16>,
         tag_or_token_list: &[TagOrToken],
-    ) {
-        let precedence = self.last_precedence + 1;
-        self.last_precedence = precedence;
+        declared_at: &lexan::Location,
+    ) -> Vec<Error> {
+        let mut errors = vec![];
+        let precedence = level.unwrap_or(self.last_precedence + 1);
+        self.last_precedence = self.last_precedence.max(precedence);
         for tag_or_token in tag_or_token_list.iter() {
             match tag_or_token {
                 TagOrToken::Tag(tag) => {
-                    tag.set_associativity(associativity);
-                    tag.set_precedence(precedence);
+                    if let Some(previous) = tag.precedence_declared_at() {
+                        errors.push(Error::DuplicatePrecedence(tag.name().to_string(), previous));
+                    } else {
+                        tag.set_associativity(associativity);
+                        tag.set_precedence(precedence);
+                        tag.set_precedence_declared_at(declared_at);
+                    }
                 }
                 TagOrToken::Token(token) => {
-                    token.set_associativity(associativity);
-                    token.set_precedence(precedence);
+                    if let Some(previous) = token.precedence_declared_at() {
+                        errors.push(Error::DuplicatePrecedence(token.to_string(), previous));
+                    } else {
+                        token.set_associativity(associativity);
+                        token.set_precedence(precedence);
+                        token.set_precedence_declared_at(declared_at);
+                    }
                 }
                 TagOrToken::Invalid => (),
             }
         }
+        errors
     }
 
-    pub fn description(&self) -> String {
-        let mut string = "Symbols:\n".to_string();
-        string += "  Tokens:\n";
-        for token in [Token::EndToken].iter().chain(self.tokens()) {
-            string += &format!(
-                "    {}({}): #({}, {})\n",
+    pub fn write_description<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        wtr.write_all(b"Symbols:\n")?;
+        wtr.write_all(b"  Tokens:\n")?;
+        for token in std::iter::once(&self.end_token).chain(self.tokens()) {
+            wtr.write_fmt(format_args!(
+                "    {}({}): #({}, {})",
                 token.name(),
                 token.text(),
                 token.associativity(),
                 token.precedence()
-            );
+            ))?;
+            if let Some(declared_at) = token.precedence_declared_at() {
+                wtr.write_fmt(format_args!(" declared at {declared_at}"))?;
+            }
+            if token.mode() > 0 {
+                wtr.write_fmt(format_args!(" %mode {}", self.lexical_modes[token.mode()]))?;
+            }
+            wtr.write_all(b"\n")?;
         }
         if !self.tags.is_empty() {
-            string += "  Tags:\n";
+            wtr.write_all(b"  Tags:\n")?;
             for tag in self.tags.values() {
-                string += &format!(
-                    "    {}: #({}, {})\n",
+                wtr.write_fmt(format_args!(
+                    "    {}: #({}, {})",
                     tag.name(),
                     tag.associativity(),
                     tag.precedence()
-                );
+                ))?;
+                if let Some(declared_at) = tag.precedence_declared_at() {
+                    wtr.write_fmt(format_args!(" declared at {declared_at}"))?;
+                }
+                wtr.write_all(b"\n")?;
             }
         }
-        string += "  Non Terminal Symbols:\n";
+        wtr.write_all(b"  Non Terminal Symbols:\n")?;
         for non_terminal in self
             .used_non_terminal_specials()
             .iter()
             .chain(self.non_terminals())
         {
-            string += &format!(
+            wtr.write_fmt(format_args!(
                 "    {}: {}\n",
                 non_terminal.name(),
                 non_terminal.firsts_data()
-            );
+            ))?;
+        }
+        if !self.helpers.is_empty() {
+            wtr.write_all(b"  Helper Non Terminals:\n")?;
+            for (name, notation) in self.helpers.iter() {
+                wtr.write_fmt(format_args!("    {name}: desugared from {notation}\n"))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Locations are omitted and everything is sorted so that only changes to the
+    // tokens, tags and skip rules themselves change the text
+    pub fn write_canonical_text<W: Write>(&self, wtr: &mut W) -> io::Result<()> {
+        wtr.write_all(b"Tokens:\n")?;
+        for token in self.tokens() {
+            wtr.write_fmt(format_args!(
+                "  {}: {} #({}, {})",
+                token.name(),
+                token.text(),
+                token.associativity(),
+                token.precedence()
+            ))?;
+            if token.mode() > 0 {
+                wtr.write_fmt(format_args!(" %mode {}", self.lexical_modes[token.mode()]))?;
+            }
+            wtr.write_all(b"\n")?;
+        }
+        wtr.write_all(b"Tags:\n")?;
+        for tag in self.tags.values() {
+            wtr.write_fmt(format_args!(
+                "  {}: #({}, {})\n",
+                tag.name(),
+                tag.associativity(),
+                tag.precedence()
+            ))?;
+        }
+        wtr.write_all(b"Skips:\n")?;
+        for (mode, skip_rules) in self.skip_rules.iter().enumerate() {
+            let mut skip_rules: Vec<&String> = skip_rules.iter().collect();
+            skip_rules.sort();
+            for skip_rule in skip_rules {
+                wtr.write_fmt(format_args!(
+                    "  {}: {skip_rule}\n",
+                    self.lexical_modes[mode]
+                ))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_precedence() {
+        let mut symbol_table = SymbolTable::default();
+        let location = lexan::Location::default();
+        let token = symbol_table
+            .new_literal_token("PLUS", "\"+\"", &location)
+            .unwrap();
+        let tag = symbol_table.new_tag("UMINUS", &location).unwrap();
+        let list = [TagOrToken::from(&token), TagOrToken::from(&tag)];
+        let errors = symbol_table.set_precedences(Associativity::Left, None, &list, &location);
+        assert!(errors.is_empty());
+        let errors = symbol_table.set_precedences(Associativity::Right, None, &list, &location);
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            Error::DuplicatePrecedence(name, declared_at) => {
+                assert_eq!(name, "\"+\"");
+                assert_eq!(*declared_at, location);
+            }
+            _ => panic!("wrong error: {}", errors[0]),
         }
-        string
+        assert_eq!(
+            token.associativity_and_precedence(),
+            (Associativity::Left, 1)
+        );
+        assert_eq!(tag.associativity(), Associativity::Left);
+        assert_eq!(tag.precedence(), 1);
     }
 }
@@ -22,6 +22,8 @@ pub struct NonTerminalData {
     defined_at: RefCell<Vec<lexan::Location>>,
     used_at: RefCell<Vec<lexan::Location>>,
     firsts_data: RefCell<Option<FirstsData>>,
+    // the type given by "%type" to the attributes of user defined non terminals
+    attribute_type: RefCell<Option<String>>,
 }
 
 impl PartialEq for NonTerminalData {
@@ -81,17 +83,27 @@ impl NonTerminal {
         NonTerminal::UserDefined(Rc::new(non_terminal_data))
     }
 
-    pub fn new_error() -> Self {
+    pub fn new_error(prefix: &str) -> Self {
         let non_terminal_data = NonTerminalData {
-            name: "AAError".to_string(),
+            name: format!("{prefix}Error"),
             ..NonTerminalData::default()
         };
         NonTerminal::Error(Rc::new(non_terminal_data))
     }
 
-    pub fn new_start() -> Self {
+    pub fn new_start(prefix: &str) -> Self {
         let non_terminal_data = NonTerminalData {
-            name: "AAStart".to_string(),
+            name: format!("{prefix}Start"),
+            ..NonTerminalData::default()
+        };
+        NonTerminal::Start(Rc::new(non_terminal_data))
+    }
+
+    // The start symbol for parsing text as `name` (when there are several start symbols)
+    pub fn new_start_for(prefix: &str, name: &str, used_at: &lexan::Location) -> Self {
+        let non_terminal_data = NonTerminalData {
+            name: format!("{prefix}Start{name}"),
+            used_at: RefCell::new(vec![used_at.clone()]),
             ..NonTerminalData::default()
         };
         NonTerminal::Start(Rc::new(non_terminal_data))
@@ -169,6 +181,24 @@ impl NonTerminal {
         }
     }
 
+    pub fn attribute_type(&self) -> Option<String> {
+        match self {
+            NonTerminal::UserDefined(non_terminal_data) => {
+                non_terminal_data.attribute_type.borrow().clone()
+            }
+            NonTerminal::Error(_) | NonTerminal::Start(_) => None,
+        }
+    }
+
+    pub fn set_attribute_type(&self, attribute_type: &str) {
+        match self {
+            NonTerminal::UserDefined(non_terminal_data) => {
+                *non_terminal_data.attribute_type.borrow_mut() = Some(attribute_type.to_string())
+            }
+            _ => panic!("should not be giving types to special symbols"),
+        }
+    }
+
     pub fn firsts_data(&self) -> FirstsData {
         let msg = format!("{} :should be set", self.name());
         match self {
@@ -12,6 +12,7 @@ pub struct TagData {
     used_at: RefCell<Vec<lexan::Location>>,
     associativity: Cell<Associativity>,
     precedence: Cell<u16>,
+    precedence_declared_at: RefCell<Option<lexan::Location>>,
 }
 
 impl TagData {
@@ -69,6 +70,14 @@ impl Tag {
     pub fn set_precedence(&self, precedence: u16) {
         self.0.precedence.set(precedence)
     }
+
+    pub fn precedence_declared_at(&self) -> Option<lexan::Location> {
+        self.0.precedence_declared_at.borrow().clone()
+    }
+
+    pub fn set_precedence_declared_at(&self, declared_at: &lexan::Location) {
+        *self.0.precedence_declared_at.borrow_mut() = Some(declared_at.clone())
+    }
 }
 
 #[derive(Debug, Clone)]
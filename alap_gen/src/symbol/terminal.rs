@@ -18,14 +18,21 @@ pub struct TokenData {
     used_at: RefCell<Vec<lexan::Location>>,
     associativity: Cell<Associativity>,
     precedence: Cell<u16>,
+    precedence_declared_at: RefCell<Option<lexan::Location>>,
+    conversion: RefCell<Option<String>>,
+    transform: RefCell<Option<String>>,
+    mode: usize,
+    mode_switch: RefCell<Option<(String, lexan::Location)>>,
+    display_name: RefCell<Option<String>>,
 }
 
 impl TokenData {
-    pub fn new(name: &str, text: &str, defined_at: &lexan::Location) -> Self {
+    pub fn new(name: &str, text: &str, defined_at: &lexan::Location, mode: usize) -> Self {
         TokenData {
             name: name.to_string(),
             text: text.to_string(),
             defined_at: defined_at.clone(),
+            mode,
             ..TokenData::default()
         }
     }
@@ -55,7 +62,8 @@ impl Ord for TokenData {
 pub enum Token {
     Literal(Rc<TokenData>),
     Regex(Rc<TokenData>),
-    EndToken,
+    // named by the generated code's prefix (e.g. "AAEnd")
+    EndToken(Rc<str>),
 }
 
 impl Clone for Token {
@@ -64,7 +72,7 @@ impl Clone for Token {
         match self {
             Literal(td) => Literal(Rc::clone(td)),
             Regex(td) => Regex(Rc::clone(td)),
-            EndToken => EndToken,
+            EndToken(name) => EndToken(Rc::clone(name)),
         }
     }
 }
@@ -79,46 +87,85 @@ impl fmt::Display for Token {
 }
 
 impl Token {
-    pub fn new_literal_token(name: &str, text: &str, defined_at: &lexan::Location) -> Self {
-        Token::Literal(Rc::new(TokenData::new(name, text, defined_at)))
+    pub fn new_literal_token(
+        name: &str,
+        text: &str,
+        defined_at: &lexan::Location,
+        mode: usize,
+    ) -> Self {
+        Token::Literal(Rc::new(TokenData::new(name, text, defined_at, mode)))
     }
 
-    pub fn new_regex_token(name: &str, text: &str, defined_at: &lexan::Location) -> Self {
-        Token::Regex(Rc::new(TokenData::new(name, text, defined_at)))
+    pub fn new_end_token(prefix: &str) -> Self {
+        Token::EndToken(Rc::from(format!("{prefix}End")))
+    }
+
+    pub fn new_regex_token(
+        name: &str,
+        text: &str,
+        defined_at: &lexan::Location,
+        mode: usize,
+    ) -> Self {
+        Token::Regex(Rc::new(TokenData::new(name, text, defined_at, mode)))
     }
 
     pub fn name(&self) -> &str {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => &token_data.name,
-            Token::EndToken => "AAEnd",
+            Token::EndToken(name) => name,
         }
     }
 
     pub fn text(&self) -> &str {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => &token_data.text,
-            Token::EndToken => "",
+            Token::EndToken(_) => "",
+        }
+    }
+
+    pub fn mode(&self) -> usize {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => token_data.mode,
+            Token::EndToken(_) => 0,
+        }
+    }
+
+    pub fn mode_switch(&self) -> Option<(String, lexan::Location)> {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                token_data.mode_switch.borrow().clone()
+            }
+            Token::EndToken(_) => None,
+        }
+    }
+
+    pub fn set_mode_switch(&self, mode: &str, location: &lexan::Location) {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                *token_data.mode_switch.borrow_mut() = Some((mode.to_string(), location.clone()))
+            }
+            Token::EndToken(_) => panic!("should not be trying to set end token's mode switch"),
         }
     }
 
     pub fn defined_at(&self) -> &lexan::Location {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => &token_data.defined_at,
-            Token::EndToken => panic!("should not be asking end token's definition location"),
+            Token::EndToken(_) => panic!("should not be asking end token's definition location"),
         }
     }
 
     pub fn associativity(&self) -> Associativity {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => token_data.associativity.get(),
-            Token::EndToken => Associativity::default(),
+            Token::EndToken(_) => Associativity::default(),
         }
     }
 
     pub fn precedence(&self) -> u16 {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => token_data.precedence.get(),
-            Token::EndToken => 0,
+            Token::EndToken(_) => 0,
         }
     }
 
@@ -127,7 +174,7 @@ impl Token {
             Token::Literal(token_data) | Token::Regex(token_data) => {
                 token_data.used_at.borrow().is_empty()
             }
-            Token::EndToken => false,
+            Token::EndToken(_) => false,
         }
     }
 
@@ -136,7 +183,7 @@ impl Token {
             Token::Literal(token_data) | Token::Regex(token_data) => {
                 (token_data.associativity.get(), token_data.precedence.get())
             }
-            Token::EndToken => (Associativity::default(), 0),
+            Token::EndToken(_) => (Associativity::default(), 0),
         }
     }
 
@@ -145,7 +192,9 @@ impl Token {
             Token::Literal(token_data) | Token::Regex(token_data) => {
                 token_data.used_at.borrow_mut().push(used_at.clone())
             }
-            Token::EndToken => panic!("should not be trying to modify end token's usage locations"),
+            Token::EndToken(_) => {
+                panic!("should not be trying to modify end token's usage locations")
+            }
         }
     }
 
@@ -154,7 +203,7 @@ impl Token {
             Token::Literal(token_data) | Token::Regex(token_data) => {
                 token_data.associativity.set(associativity)
             }
-            Token::EndToken => panic!("should not be trying to set end token's associativity"),
+            Token::EndToken(_) => panic!("should not be trying to set end token's associativity"),
         }
     }
 
@@ -164,16 +213,79 @@ impl Token {
             Token::Literal(token_data) | Token::Regex(token_data) => {
                 token_data.precedence.set(precedence)
             }
-            Token::EndToken => panic!("should not be trying to set end token's precedence"),
+            Token::EndToken(_) => panic!("should not be trying to set end token's precedence"),
+        }
+    }
+
+    pub fn conversion(&self) -> Option<String> {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                token_data.conversion.borrow().clone()
+            }
+            Token::EndToken(_) => None,
         }
     }
 
-    pub fn precedence_has_been_set(&self) -> bool {
+    pub fn transform(&self) -> Option<String> {
         match self {
             Token::Literal(token_data) | Token::Regex(token_data) => {
-                token_data.precedence.get() > 0
+                token_data.transform.borrow().clone()
             }
-            Token::EndToken => false,
+            Token::EndToken(_) => None,
+        }
+    }
+
+    pub fn set_transform(&self, transform: &str) {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                *token_data.transform.borrow_mut() = Some(transform.to_string())
+            }
+            Token::EndToken(_) => panic!("should not be trying to set end token's transform"),
+        }
+    }
+
+    pub fn set_conversion(&self, conversion: &str) {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                *token_data.conversion.borrow_mut() = Some(conversion.to_string())
+            }
+            Token::EndToken(_) => panic!("should not be trying to set end token's conversion"),
+        }
+    }
+
+    pub fn display_name(&self) -> Option<String> {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                token_data.display_name.borrow().clone()
+            }
+            Token::EndToken(_) => None,
+        }
+    }
+
+    pub fn set_display_name(&self, display_name: &str) {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                *token_data.display_name.borrow_mut() = Some(display_name.to_string())
+            }
+            Token::EndToken(_) => panic!("should not be trying to set end token's display name"),
+        }
+    }
+
+    pub fn precedence_declared_at(&self) -> Option<lexan::Location> {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                token_data.precedence_declared_at.borrow().clone()
+            }
+            Token::EndToken(_) => None,
+        }
+    }
+
+    pub fn set_precedence_declared_at(&self, declared_at: &lexan::Location) {
+        match self {
+            Token::Literal(token_data) | Token::Regex(token_data) => {
+                *token_data.precedence_declared_at.borrow_mut() = Some(declared_at.clone())
+            }
+            Token::EndToken(_) => panic!("should not be trying to set end token's precedence"),
         }
     }
 }
@@ -239,9 +351,17 @@ impl TokenSet {
         self.0.iter()
     }
 
+    // The tokens in the order of the generated AATerminal's variants (i.e. the end token
+    // then the others by name) so that emitted sets read the same as they sort
+    fn in_terminal_order(&self) -> Vec<&Token> {
+        let mut tokens: Vec<&Token> = self.0.iter().collect();
+        tokens.sort_by_key(|token| (!matches!(token, Token::EndToken(_)), token.name()));
+        tokens
+    }
+
     pub fn formated_as_macro_call(&self) -> String {
         let mut string = "btree_set![".to_string();
-        for (index, token) in self.0.iter().enumerate() {
+        for (index, token) in self.in_terminal_order().iter().enumerate() {
             if index == 0 {
                 string += token.name();
             } else {
@@ -254,7 +374,7 @@ impl TokenSet {
 
     pub fn formated_as_or_list(&self) -> String {
         let mut string = "".to_string();
-        for (index, token) in self.0.iter().enumerate() {
+        for (index, token) in self.in_terminal_order().iter().enumerate() {
             if index == 0 {
                 string += token.name();
             } else {
@@ -0,0 +1,133 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+use std::collections::BTreeMap;
+
+// Action table entry encoding
+pub const SYNTAX_ERROR: u32 = 0;
+pub const ACCEPT: u32 = 1;
+// the action depends on predicates so must be decided by code
+pub const PREDICATED: u32 = 2;
+const UNUSED: u32 = u32::MAX;
+
+pub fn shift(state: u32) -> u32 {
+    3 + 2 * state
+}
+
+pub fn reduce(production: u32) -> u32 {
+    4 + 2 * production
+}
+
+/// An action table compressed using row displacement ("comb vector") into the
+/// `check`/`next` arrays with a `default` entry for each row.  The entry for
+/// (`row`, `column`) is `next[base[row] + column]` if `check` at that index is
+/// `row` and `default[row]` otherwise.
+#[derive(Debug, Default)]
+pub struct CompressedTable {
+    pub base: Vec<u32>,
+    pub default: Vec<u32>,
+    pub check: Vec<u32>,
+    pub next: Vec<u32>,
+}
+
+impl CompressedTable {
+    pub fn new(rows: &[Vec<u32>]) -> Self {
+        let mut table = Self {
+            base: vec![0; rows.len()],
+            default: vec![SYNTAX_ERROR; rows.len()],
+            ..Self::default()
+        };
+        // Placing the densest rows first leaves the sparse ones to fill the gaps
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        order.sort_by_key(|row| {
+            let default = Self::most_common(&rows[*row]);
+            usize::MAX - rows[*row].iter().filter(|x| **x != default).count()
+        });
+        for row in order {
+            let default = Self::most_common(&rows[row]);
+            let columns: Vec<usize> = (0..rows[row].len())
+                .filter(|column| rows[row][*column] != default)
+                .collect();
+            let mut base = 0;
+            while !table.fits(base, &columns) {
+                base += 1;
+            }
+            for column in columns {
+                let index = base + column;
+                if index >= table.check.len() {
+                    table.check.resize(index + 1, UNUSED);
+                    table.next.resize(index + 1, SYNTAX_ERROR);
+                }
+                table.check[index] = row as u32;
+                table.next[index] = rows[row][column];
+            }
+            table.base[row] = base as u32;
+            table.default[row] = default;
+        }
+        table
+    }
+
+    // Ties go to the smallest entry so that rows default to syntax errors where possible
+    fn most_common(row: &[u32]) -> u32 {
+        let mut counts = BTreeMap::<u32, usize>::new();
+        for entry in row.iter() {
+            *counts.entry(*entry).or_default() += 1;
+        }
+        let mut most_common = (SYNTAX_ERROR, 0);
+        for (entry, count) in counts {
+            if count > most_common.1 {
+                most_common = (entry, count);
+            }
+        }
+        most_common.0
+    }
+
+    fn fits(&self, base: usize, columns: &[usize]) -> bool {
+        columns
+            .iter()
+            .all(|column| self.check.get(base + column).is_none_or(|x| *x == UNUSED))
+    }
+
+    #[cfg(test)]
+    pub fn entry(&self, row: usize, column: usize) -> u32 {
+        let index = self.base[row] as usize + column;
+        if self.check.get(index) == Some(&(row as u32)) {
+            self.next[index]
+        } else {
+            self.default[row]
+        }
+    }
+
+    /// The total number of entries in the compressed table's arrays.
+    #[cfg(test)]
+    pub fn size(&self) -> usize {
+        self.base.len() + self.default.len() + self.check.len() + self.next.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_survive_compression() {
+        let rows = vec![
+            vec![shift(1), SYNTAX_ERROR, SYNTAX_ERROR, shift(2), SYNTAX_ERROR],
+            vec![reduce(3), reduce(3), SYNTAX_ERROR, reduce(3), ACCEPT],
+            vec![SYNTAX_ERROR; 5],
+            vec![
+                SYNTAX_ERROR,
+                shift(4),
+                PREDICATED,
+                SYNTAX_ERROR,
+                SYNTAX_ERROR,
+            ],
+        ];
+        let table = CompressedTable::new(&rows);
+        for (row, entries) in rows.iter().enumerate() {
+            for (column, entry) in entries.iter().enumerate() {
+                assert_eq!(table.entry(row, column), *entry);
+            }
+        }
+        assert_eq!(table.default[1], reduce(3));
+        assert!(table.check.len() < rows.len() * rows[0].len());
+    }
+}
@@ -0,0 +1,9 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+// The parser is generated into OUT_DIR (rather than committed) whenever its grammar changes
+fn main() {
+    println!("cargo:rerun-if-changed=src/sum.alaps");
+    let out_path = format!("{}/sum.rs", std::env::var("OUT_DIR").unwrap());
+    if let Err(err) = alap_gen::build::compile("src/sum.alaps", &out_path) {
+        panic!("{err}");
+    }
+}
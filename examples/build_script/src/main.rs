@@ -0,0 +1,33 @@
+extern crate lazy_static;
+
+// generated by build.rs (see alap_gen::build::compile())
+mod sum {
+    include!(concat!(env!("OUT_DIR"), "/sum.rs"));
+}
+
+use lalr1_plus::Parser;
+use sum::AAVisitor;
+
+// counts the numbers summed
+struct Terms;
+
+impl AAVisitor for Terms {
+    type Output = usize;
+
+    fn visit_sum(&mut self, _production: u32, children: Vec<usize>) -> usize {
+        children.iter().sum::<usize>() + 1
+    }
+
+    fn visit_total(&mut self, _production: u32, children: Vec<usize>) -> usize {
+        children[0]
+    }
+}
+
+fn main() {
+    let mut sum = sum::Sum::default();
+    assert!(sum.parse_text("1 + 2 + 39", "sum").is_ok());
+    assert_eq!(sum.total, 42);
+    assert_eq!(Terms.walk(sum.derivation()), Some(3));
+    assert!(sum.parse_text("1 + + 2", "sum").is_err());
+    println!("Hello, world! No crashes!!!");
+}
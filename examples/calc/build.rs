@@ -4,21 +4,26 @@ use std::process::Command;
 fn main() {
     println!("cargo:rerun-if-changed=src/calc.alaps");
     println!("cargo:rerun-if-changed=../../target/debug/alap_gen");
-    match Command::new("../../target/debug/alap_gen")
-        .args(&["-f", "src/calc.alaps"])
-        .status()
-    {
-        Ok(status) => {
-            if status.success() {
-                Command::new("rustfmt")
-                    .args(&["src/calc.rs"])
-                    .status()
-                    .unwrap();
-            } else {
-                panic!("failed prebuild: {}", status);
-            };
+    // the same grammar with both code generation backends (to check their parity)
+    for (output, extra_args) in [
+        ("src/calc.rs", vec!["--unparser"]),
+        ("src/calc_tables.rs", vec!["--tables", "--unparser"]),
+    ] {
+        match Command::new("../../target/debug/alap_gen")
+            .args(&["-f", "-o", output])
+            .args(&extra_args)
+            .arg("src/calc.alaps")
+            .status()
+        {
+            Ok(status) => {
+                if status.success() {
+                    Command::new("rustfmt").args(&[output]).status().unwrap();
+                } else {
+                    panic!("failed prebuild: {}", status);
+                };
+            }
+            Err(err) => panic!("Build error: {}", err),
         }
-        Err(err) => panic!("Build error: {}", err),
     }
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -1,17 +1,42 @@
 extern crate lazy_static;
 
 mod calc;
+mod calc_tables;
 
 use lalr1_plus::Parser;
 
+// The same inputs and expectations for the parsers generated by either backend
+macro_rules! exercise {
+    ($module:ident) => {{
+        let mut calc = $module::Calc::new();
+        calc.parse_text("a = 1 + 8 * 5", &String::new()).unwrap();
+        assert_eq!(calc.variable("a"), Some(41.0));
+        calc.parse_text("b = (1 + 8) * 5", &String::new()).unwrap();
+        assert_eq!(calc.variable("b"), Some(45.0));
+        calc.parse_text("c = a + b", &String::new()).unwrap();
+        assert_eq!(calc.variable("c"), Some(86.0));
+        calc.parse_text("a + b + c", &String::new()).unwrap();
+        // redundant parentheses are dropped when the parse tree is turned back into text
+        let mut trees = lalr1_plus::ParseTreeBuilder::new($module::Calc::production_data);
+        calc.parse_text_observed("f = ((a + b)) * (c - (b - 1))", "tree", &mut trees)
+            .unwrap();
+        let tree = trees.take_tree().unwrap();
+        assert_eq!($module::aa_unparse(&tree), "f = ( a + b ) * ( c - ( b - 1 ) )");
+        let value = calc.parse_expr("(a + b) * 2", "hover").unwrap();
+        assert!(matches!(value, $module::AttributeData::Value(value) if value == 172.0));
+        assert!(calc.parse_expr("c = 2", "hover").is_err());
+        calc.parse_line("d = c", "line").unwrap();
+        assert_eq!(calc.variable("d"), Some(86.0));
+        let error = calc.parse_text("e = z + 1", "undefined").unwrap_err();
+        assert!(matches!(error, lalr1_plus::Error::SemanticError(..)));
+        assert_eq!(calc.variable("e"), None);
+        let explanation = calc.explain_failure("d = a +", "input");
+        assert!(explanation.contains("\nCould continue with: "));
+        explanation
+    }};
+}
+
 fn main() {
-    let mut calc = calc::Calc::new();
-    calc.parse_text("a = 1 + 8 * 5", &String::new()).unwrap();
-    assert_eq!(calc.variable("a"), Some(41.0));
-    calc.parse_text("b = (1 + 8) * 5", &String::new()).unwrap();
-    assert_eq!(calc.variable("b"), Some(45.0));
-    calc.parse_text("c = a + b", &String::new()).unwrap();
-    assert_eq!(calc.variable("c"), Some(86.0));
-    calc.parse_text("a + b + c", &String::new()).unwrap();
+    assert_eq!(exercise!(calc), exercise!(calc_tables));
     println!("Hello, world! No crashes!!!");
 }
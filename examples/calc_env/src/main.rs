@@ -0,0 +1,23 @@
+extern crate lazy_static;
+
+mod calc_env;
+
+use std::collections::HashMap;
+
+use lalr1_plus::Parser;
+
+fn main() {
+    // the environment is seeded before, and outlives, the parser that borrows it
+    let mut variables = HashMap::from([("x".to_string(), 41.0)]);
+    let mut calc = calc_env::Calc::new(&mut variables);
+    calc.parse_text("x + 1", &String::new()).unwrap();
+    assert_eq!(calc.value, Some(42.0));
+    assert!(calc.undefined.is_empty());
+
+    let mut calc = calc_env::Calc::new(&mut variables);
+    calc.parse_text("y = x + x", &String::new()).unwrap();
+    calc.parse_text("z + 1", &String::new()).unwrap();
+    assert_eq!(calc.undefined, vec!["z".to_string()]);
+    assert_eq!(variables.get("y"), Some(&82.0));
+    println!("Hello, world! No crashes!!!");
+}
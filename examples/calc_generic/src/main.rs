@@ -0,0 +1,34 @@
+extern crate lazy_static;
+
+mod calc_generic;
+
+use lalr1_plus::Parser;
+
+use calc_generic::{AttributeData, Calc};
+
+fn main() {
+    // the same grammar evaluates over floating point numbers
+    let mut calc = Calc::<f64>::default();
+    calc.parse_text("(1.5 + 2) * 3 / 2", &String::new()).unwrap();
+    assert_eq!(calc.value, Some(5.25));
+    assert!(calc.errors.is_empty());
+
+    // and integers
+    let mut calc = Calc::<i64>::default();
+    calc.parse_text("(1 + 2) * 3 / 2", &String::new()).unwrap();
+    assert_eq!(calc.value, Some(4));
+    calc.parse_text("1.5 + 7 / 0", &String::new()).unwrap();
+    assert_eq!(
+        calc.errors,
+        vec![
+            "1.5: not a valid number".to_string(),
+            "division by zero".to_string()
+        ]
+    );
+
+    // "%functional" parses with a default target and returns the start symbol's attribute
+    let value = calc_generic::parse::<i64>("(1 + 2) * 3");
+    assert!(matches!(value, Ok(AttributeData::Value(9))));
+    assert!(calc_generic::parse::<f64>("(1 + 2").is_err());
+    println!("Hello, world! No crashes!!!");
+}
@@ -0,0 +1,7 @@
+//! A calculator whose parser (generated with `--no-std`) needs only `core` and `alloc`.
+#![no_std]
+extern crate lazy_static;
+
+mod calc;
+
+pub use calc::Calc;
@@ -0,0 +1,25 @@
+use calc_no_std::Calc;
+use lalr1_plus::Parser;
+
+fn evaluate(text: &str) -> Calc {
+    let mut calc = Calc::default();
+    let _ = calc.parse_text(text, "calc");
+    calc
+}
+
+fn main() {
+    let calc = evaluate("1 + 2 * (3 - 4)");
+    assert_eq!(calc.value, Some(-1));
+    assert!(calc.errors.is_empty());
+
+    // the messages of errors are kept (by Calc's ReportError) rather than printed
+    let calc = evaluate("1 + * 2");
+    assert_eq!(calc.value, None);
+    assert_eq!(calc.errors.len(), 1);
+    assert!(calc.errors[0].starts_with("Syntax Error: expected: "));
+
+    let calc = evaluate("7 / (2 - 2) + 3");
+    assert_eq!(calc.value, Some(3));
+    assert_eq!(calc.errors, vec!["divide by zero".to_string()]);
+    println!("Hello, world! No crashes!!!");
+}
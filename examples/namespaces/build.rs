@@ -0,0 +1,26 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=../../target/debug/alap_gen");
+    for name in ["sum", "list", "words", "words_right", "pairs"] {
+        println!("cargo:rerun-if-changed=src/{name}.alaps");
+        match Command::new("../../target/debug/alap_gen")
+            .args(["-f", "--namespace", name, &format!("src/{name}.alaps")])
+            .status()
+        {
+            Ok(status) => {
+                if status.success() {
+                    Command::new("rustfmt")
+                        .args([&format!("src/{name}.rs")])
+                        .status()
+                        .unwrap();
+                } else {
+                    panic!("failed prebuild: {}", status);
+                };
+            }
+            Err(err) => panic!("Build error: {}", err),
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
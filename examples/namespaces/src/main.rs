@@ -0,0 +1,62 @@
+extern crate lazy_static;
+
+// The grammars' code in one module: "--namespace" keeps their items apart
+mod parsers {
+    include!("sum.rs");
+    include!("list.rs");
+    include!("words.rs");
+    include!("words_right.rs");
+    include!("pairs.rs");
+}
+
+use lalr1_plus::Parser;
+
+fn main() {
+    let mut sum = parsers::sum::Sum::default();
+    sum.parse_text("1 + 2 + 39", &String::new()).unwrap();
+    assert_eq!(sum.total, 42);
+    assert_eq!(sum.seen[&parsers::sum::AATerminal::NUMBER], 3);
+    assert_eq!(sum.seen[&parsers::sum::AATerminal::PLUS], 2);
+
+    let mut list = parsers::list::List::default();
+    list.parse_text("one, two", &String::new()).unwrap();
+    assert_eq!(list.names, vec!["one".to_string(), "two".to_string()]);
+    assert!(list.parse_text("1 + 2", &String::new()).is_err());
+    list.parse_text("three;", &String::new()).unwrap();
+    assert_eq!(list.names, vec!["three".to_string()]);
+
+    // both recursions collect the words in order but right recursion stacks them all
+    let text = format!("first {} last", vec!["word"; 98].join(" "));
+    let mut words = parsers::words::Words::default();
+    let (outcome, left) = words.parse_text_outcome(&text, &String::new());
+    assert!(outcome.is_clean());
+    assert_eq!(words.words.len(), 100);
+    assert_eq!(
+        (words.words[0].as_str(), words.words[99].as_str()),
+        ("first", "last")
+    );
+    let mut words = parsers::words_right::Words::default();
+    let (outcome, right) = words.parse_text_outcome(&text, &String::new());
+    assert!(outcome.is_clean());
+    assert_eq!(words.words.len(), 100);
+    assert_eq!(
+        (words.words[0].as_str(), words.words[99].as_str()),
+        ("first", "last")
+    );
+    assert!(left.max_stack_depth < 5);
+    assert!(right.max_stack_depth > 100);
+
+    // each group's members are tupled up into a pair before being collected (by a parser
+    // whose generated names are given the prefix "PP" rather than "AA")
+    let mut pairs = parsers::pairs::Pairs::default();
+    pairs.parse_text("a = b, c = d", &String::new()).unwrap();
+    assert_eq!(
+        pairs.pairs,
+        vec![
+            ("a".to_string(), "b".to_string()),
+            ("c".to_string(), "d".to_string())
+        ]
+    );
+    assert_eq!(parsers::pairs::PPTerminal::PPEnd.to_string(), "PPEnd");
+    println!("Hello, world! No crashes!!!");
+}
@@ -0,0 +1,52 @@
+// Copyright 2021 Peter Williams <pwil3058@gmail.com> <pwil3058@bigpond.net.au>
+use std::process::Command;
+
+// The one grammar generated (with the prefix "XX") in each of the ways code can be emitted
+const VARIANTS: [(&str, &[&str]); 4] = [
+    ("matched", &[]),
+    (
+        "compressed",
+        &[
+            "--compress-tables",
+            "--emit-production-table",
+            "--strict-predicates",
+            "--checked-predicates",
+            "--diagnose-panics",
+        ],
+    ),
+    (
+        "tabled",
+        &[
+            "--tables",
+            "--emit-production-table",
+            "--strict-predicates",
+            "--checked-predicates",
+            "--diagnose-panics",
+        ],
+    ),
+    ("compressed_tabled", &["--compress-tables", "--tables"]),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=../../target/debug/alap_gen");
+    println!("cargo:rerun-if-changed=src/sums.alaps");
+    for (name, options) in VARIANTS {
+        let output = format!("src/{name}.rs");
+        match Command::new("../../target/debug/alap_gen")
+            .args(["-f", "--prefix", "XX", "--namespace", name, "-o", &output])
+            .args(options)
+            .arg("src/sums.alaps")
+            .status()
+        {
+            Ok(status) => {
+                if status.success() {
+                    Command::new("rustfmt").args([&output]).status().unwrap();
+                } else {
+                    panic!("failed prebuild: {}", status);
+                };
+            }
+            Err(err) => panic!("Build error: {}", err),
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,33 @@
+extern crate lazy_static;
+
+// The grammar's code generated (by build.rs) with "--prefix XX" in each way code can be
+// emitted: by "match" code, compressed tables, tables and both
+mod parsers {
+    include!("matched.rs");
+    include!("compressed.rs");
+    include!("tabled.rs");
+    include!("compressed_tabled.rs");
+}
+
+// each variant's items are of its own types
+macro_rules! check {
+    ( $variant:ident ) => {
+        let totals = parsers::$variant::parse("1 + 2 5 - 1 + 0").unwrap();
+        assert_eq!(totals.values(), vec![3.0, 4.0]);
+        assert!(parsers::$variant::parse("1 + + 2").is_err());
+        assert_eq!(parsers::$variant::parse_expr("7 - 2 + 3").unwrap(), 8.0);
+        assert_eq!(parsers::$variant::XXTerminal::XXEnd.to_string(), "XXEnd");
+    };
+}
+
+fn main() {
+    check!(matched);
+    check!(compressed);
+    check!(tabled);
+    check!(compressed_tabled);
+    assert_eq!(
+        parsers::tabled::XXPRODUCTIONS.len(),
+        parsers::compressed::XXPRODUCTIONS.len()
+    );
+    println!("Hello, world! No crashes!!!");
+}
@@ -0,0 +1,64 @@
+extern crate lazy_static;
+
+mod stmt_list;
+
+use lalr1_plus::Parser;
+
+fn parse(text: &str) -> stmt_list::StmtList {
+    let mut stmt_list = stmt_list::StmtList::default();
+    let _ = stmt_list.parse_text(text, &String::new());
+    stmt_list
+}
+
+fn main() {
+    let stmt_list = parse("a = 1; b = 2; c = 3;");
+    assert_eq!(stmt_list.resynchronizations, 0);
+    assert_eq!(stmt_list.assignments.len(), 3);
+
+    // recovery skips to the next ";" and parsing resumes with the next statement
+    let stmt_list = parse("a = 1; b = = 2; c = 3;");
+    assert_eq!(stmt_list.resynchronizations, 1);
+    assert_eq!(
+        stmt_list.assignments,
+        vec![("a".to_string(), 1), ("c".to_string(), 3)]
+    );
+
+    let stmt_list = parse("a 1 2 b = 3; c = 4; d = ; e = 5;");
+    assert_eq!(stmt_list.resynchronizations, 2);
+    assert_eq!(
+        stmt_list.assignments,
+        vec![("c".to_string(), 4), ("e".to_string(), 5)]
+    );
+
+    // the "%transform" strips digit separators before conversion
+    let stmt_list = parse("x = 1_000; y = 2_50_0;");
+    assert_eq!(stmt_list.resynchronizations, 0);
+    assert_eq!(
+        stmt_list.assignments,
+        vec![("x".to_string(), 1000), ("y".to_string(), 2500)]
+    );
+
+    // the observer sees every statement's span (even those that had errors)
+    let stmt_list = parse("a = 1;\nbb = = 2;\n  c = 3;");
+    let spans: Vec<String> = stmt_list
+        .symbols
+        .statements
+        .iter()
+        .map(|span| format!("{}-{}", span.start, span.end))
+        .collect();
+    assert_eq!(spans, vec!["1:1-1:7", "2:1-2:10", "3:3-3:9"]);
+    assert_eq!(stmt_list.symbols.errors, 1);
+
+    // "%spanned" attributes carry their spans (those of the lexemes before "%transform")
+    let stmt_list = parse("x = 1_000;\ny = 2;");
+    let spans: Vec<String> = stmt_list
+        .value_spans
+        .iter()
+        .map(|span| format!("{}-{}", span.start, span.end))
+        .collect();
+    assert_eq!(spans, vec!["1:5-1:10", "2:5-2:6"]);
+    assert_eq!(stmt_list.statement_spans, stmt_list.symbols.statements);
+    // and "$@" is the span of the production being reduced
+    assert_eq!(stmt_list.assignment_spans, stmt_list.statement_spans);
+    println!("Hello, world! No crashes!!!");
+}
@@ -16,6 +16,91 @@ pub enum Error<T: Ord + Copy + Debug + Display + Eq> {
     SyntaxError(lexan::Token<T>, BTreeSet<T>),
 }
 
+/// Measurements of a parse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStatistics {
+    /// The number of tokens shifted
+    pub shift_count: usize,
+    /// The greatest depth reached by the parse stack
+    pub max_stack_depth: usize,
+}
+
+/// The outcome of a parse.
+///
+/// Neither it nor the results of the parse methods should be ignored so that (with
+/// `unused_must_use` denied)
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// # #[derive(Default)]
+/// # struct Attr;
+/// # impl From<lalr1::Error<u8>> for Attr {
+/// #     fn from(_: lalr1::Error<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// # impl From<lexan::Token<u8>> for Attr {
+/// #     fn from(_: lexan::Token<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// fn parse<P: lalr1::Parser<u8, u8, Attr>>(parser: &mut P) {
+///     parser.parse_text("1", "raw");
+/// }
+/// ```
+///
+/// doesn't compile whereas explicitly discarding the result
+///
+/// ```
+/// #![deny(unused_must_use)]
+/// # #[derive(Default)]
+/// # struct Attr;
+/// # impl From<lalr1::Error<u8>> for Attr {
+/// #     fn from(_: lalr1::Error<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// # impl From<lexan::Token<u8>> for Attr {
+/// #     fn from(_: lexan::Token<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// fn parse<P: lalr1::Parser<u8, u8, Attr>>(parser: &mut P) {
+///     let _ = parser.parse_text("1", "raw");
+/// }
+/// ```
+///
+/// does.
+#[must_use]
+#[derive(Debug, Clone)]
+pub enum ParseOutcome<T: Ord + Copy + Debug + Display + Eq> {
+    /// No errors occurred.
+    Clean,
+    /// The parse completed after recovering from these errors.
+    Recovered(Vec<Error<T>>),
+    /// The parse was abandoned because this error could not be recovered from.
+    Failed(Error<T>),
+}
+
+impl<T: Ord + Copy + Debug + Display + Eq> ParseOutcome<T> {
+    pub fn is_clean(&self) -> bool {
+        matches!(self, ParseOutcome::Clean)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, ParseOutcome::Failed(_))
+    }
+
+    /// The equivalent `parse_text()` result, i.e. the last error (if any).
+    pub fn into_result(self) -> Result<(), Error<T>> {
+        match self {
+            ParseOutcome::Clean => Ok(()),
+            ParseOutcome::Recovered(mut errors) => Err(errors.pop().expect("at least one")),
+            ParseOutcome::Failed(error) => Err(error),
+        }
+    }
+}
+
 fn format_set<T: Ord + Display>(set: &BTreeSet<T>) -> String {
     let mut string = String::new();
     let last = set.len() - 1;
@@ -83,6 +168,7 @@ where
     states: Vec<(Symbol<T, N>, u32)>,
     attributes: Vec<A>,
     last_error_state: Option<u32>,
+    max_depth: usize,
 }
 
 impl<T, N, A> ParseStack<T, N, A>
@@ -95,9 +181,19 @@ where
             states: vec![(Symbol::Start, 0)],
             attributes: vec![],
             last_error_state: None,
+            max_depth: 1,
         }
     }
 
+    /// The greatest number of states that this stack has held
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    fn note_depth(&mut self) {
+        self.max_depth = self.max_depth.max(self.states.len());
+    }
+
     fn current_state(&self) -> u32 {
         self.states.last().unwrap().1
     }
@@ -116,19 +212,22 @@ where
 
     fn push_error(&mut self, state: u32, error: Error<T>) {
         self.states.push((Symbol::Error, state));
-        self.attributes.push(A::from(error))
+        self.attributes.push(A::from(error));
+        self.note_depth();
     }
 
     fn push_terminal(&mut self, token: lexan::Token<T>, new_state: u32) {
         self.states
             .push((Symbol::Terminal(*token.tag()), new_state));
         self.attributes.push(A::from(token));
+        self.note_depth();
     }
 
     fn push_non_terminal(&mut self, non_terminal: N, attribute: A, new_state: u32) {
         self.attributes.push(attribute);
         self.states
             .push((Symbol::NonTerminal(non_terminal), new_state));
+        self.note_depth();
     }
 
     fn is_last_error_state(&self, state: u32) -> bool {
@@ -147,7 +246,7 @@ where
         while !tokens.is_empty() {
             if let Ok(token) = tokens.front() {
                 let viable_states = viable_error_recovery_states(token.tag());
-                for sub in 1..self.states.len() {
+                for sub in 1..=self.states.len() {
                     let candidate = self.states[self.states.len() - sub].1;
                     if !self.is_last_error_state(candidate) && viable_states.contains(&candidate) {
                         self.last_error_state = Some(candidate);
@@ -161,7 +260,7 @@ where
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     Shift(u32),
     Reduce(u32),
@@ -193,6 +292,24 @@ where
         A::default()
     }
 
+    /// Like `do_semantic_action` but also given the look ahead token that triggered the
+    /// reduction.  The default ignores the token and calls `do_semantic_action`.
+    fn do_semantic_action_la<F: FnMut(String, String)>(
+        &mut self,
+        production_id: u32,
+        attributes: Vec<A>,
+        _look_ahead: &lexan::Token<T>,
+        inject: F,
+    ) -> A {
+        self.do_semantic_action(production_id, attributes, inject)
+    }
+
+    /// Map an action's (empty text) injection label to the lexical mode it requests
+    /// a switch to, if any.  The default (for analysers without modes) is `None`.
+    fn lexical_mode(_label: &str) -> Option<usize> {
+        None
+    }
+
     fn viable_error_recovery_states(tag: &T) -> BTreeSet<u32>;
 
     fn error_goto_state(state: u32) -> u32 {
@@ -218,48 +335,169 @@ where
         }
     }
 
+    /// The number of consecutive pieces of unexpected text that are skipped (without
+    /// disturbing the parse stack) before resorting to error recovery.
+    fn lexical_skip_limit(&self) -> usize {
+        3
+    }
+
+    /// Whether `token` marks the end of the input: by default, whether it is the
+    /// lexical analyzer's end marker.  Tokens for which this is true are treated
+    /// as if they were the end marker.
+    fn is_end_of_input(&self, token: &lexan::Token<T>) -> bool {
+        *token.tag() == self.lexical_analyzer().end_marker()
+    }
+
+    /// Called before the parse of the text labelled `label` begins.
+    fn before_parse(&mut self, _label: &str) {}
+
+    /// Called with the outcome of the parse of the text labelled `label`.
+    fn after_parse(&mut self, _label: &str, _outcome: &ParseOutcome<T>) {}
+
+    /// Apply `actions` (without semantic actions or error recovery) to `tokens` for testing
+    /// the parse tables.  Fails with the index of the first action that differs from that
+    /// chosen by the tables and the table's choice.  Stops after an accept or syntax error.
+    fn drive(
+        &self,
+        actions: &[Action],
+        tokens: &mut TokenStream<T>,
+    ) -> Result<ParseStack<T, N, A>, (usize, Action)>
+    where
+        A: Default,
+    {
+        let mut parse_stack = ParseStack::<T, N, A>::new();
+        for (index, action) in actions.iter().enumerate() {
+            let front = tokens.front().map(|token| {
+                if self.is_end_of_input(&token) {
+                    token.with_tag(self.lexical_analyzer().end_marker())
+                } else {
+                    token
+                }
+            });
+            let table_action = match front {
+                Ok(ref token) => self.next_action(parse_stack.current_state(), token),
+                Err(_) => Action::SyntaxError,
+            };
+            if table_action != *action {
+                return Err((index, table_action));
+            }
+            match table_action {
+                Action::Shift(next_state) => {
+                    parse_stack.push_terminal(front.expect("not an error"), next_state);
+                    tokens.advance();
+                }
+                Action::Reduce(production_id) => {
+                    let (lhs, rhs_len) = Self::production_data(production_id);
+                    parse_stack.pop_n(rhs_len);
+                    let next_state = Self::goto_state(&lhs, parse_stack.current_state());
+                    parse_stack.push_non_terminal(lhs, A::default(), next_state);
+                }
+                Action::Accept | Action::SyntaxError => break,
+            }
+        }
+        Ok(parse_stack)
+    }
+
+    #[must_use = "the text may not have parsed successfully"]
     fn parse_text(&mut self, text: &str, label: &str) -> Result<(), Error<T>> {
+        self.parse_text_counting_shifts(text, label).0
+    }
+
+    /// As for `parse_text()` but also returning the number of tokens shifted.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_counting_shifts(
+        &mut self,
+        text: &str,
+        label: &str,
+    ) -> (Result<(), Error<T>>, usize) {
+        let (outcome, statistics) = self.parse_text_outcome(text, label);
+        (outcome.into_result(), statistics.shift_count)
+    }
+
+    /// Parse `text` distinguishing parses that recovered from errors from those that
+    /// failed and also returning some measurements of the parse.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_outcome(
+        &mut self,
+        text: &str,
+        label: &str,
+    ) -> (ParseOutcome<T>, ParseStatistics) {
         let mut tokens = self.lexical_analyzer().token_stream(text, label);
         let mut parse_stack = ParseStack::<T, N, A>::new();
-        let mut result: Result<(), Error<T>> = Ok(());
+        let mut errors: Vec<Error<T>> = vec![];
+        let mut statistics = ParseStatistics::default();
+        let mut lexical_skips = 0;
 
-        loop {
-            match tokens.front() {
+        self.before_parse(label);
+        let outcome = loop {
+            let front = tokens.front().map(|token| {
+                if self.is_end_of_input(&token) {
+                    token.with_tag(self.lexical_analyzer().end_marker())
+                } else {
+                    token
+                }
+            });
+            match front {
                 Err(err) => {
                     let expected_tokens = Self::look_ahead_set(parse_stack.current_state());
+                    let skippable =
+                        err.is_unexpected_text() && lexical_skips < self.lexical_skip_limit();
                     let error = Error::LexicalError(err, expected_tokens);
                     self.report_error(&error);
-                    result = Err(error.clone());
-                    if !Self::recover_from_error(error, &mut parse_stack, &mut tokens) {
-                        return result;
+                    errors.push(error.clone());
+                    if skippable {
+                        lexical_skips += 1;
+                        tokens.advance();
+                    } else if !Self::recover_from_error(
+                        error.clone(),
+                        &mut parse_stack,
+                        &mut tokens,
+                    ) {
+                        break ParseOutcome::Failed(error);
                     }
                 }
                 Ok(token) => match self.next_action(parse_stack.current_state(), &token) {
-                    Action::Accept => return result,
+                    Action::Accept => {
+                        break if errors.is_empty() {
+                            ParseOutcome::Clean
+                        } else {
+                            ParseOutcome::Recovered(errors)
+                        };
+                    }
                     Action::Shift(next_state) => {
                         parse_stack.push_terminal(token, next_state);
                         tokens.advance();
+                        statistics.shift_count += 1;
+                        lexical_skips = 0;
                     }
                     Action::Reduce(production_id) => {
                         let (lhs, rhs_len) = Self::production_data(production_id);
                         let rhs = parse_stack.pop_n(rhs_len);
                         let next_state = Self::goto_state(&lhs, parse_stack.current_state());
-                        let attribute = self
-                            .do_semantic_action(production_id, rhs, |s, l| tokens.inject(&s, &l));
+                        let attribute =
+                            self.do_semantic_action_la(production_id, rhs, &token, |s, l| {
+                                match Self::lexical_mode(&l) {
+                                    Some(mode) if s.is_empty() => tokens.set_mode(mode),
+                                    _ => tokens.inject(&s, &l),
+                                }
+                            });
                         parse_stack.push_non_terminal(lhs, attribute, next_state);
                     }
                     Action::SyntaxError => {
                         let expected_tokens = Self::look_ahead_set(parse_stack.current_state());
                         let error = Error::SyntaxError(token.clone(), expected_tokens);
                         self.report_error(&error);
-                        result = Err(error.clone());
-                        if !Self::recover_from_error(error, &mut parse_stack, &mut tokens) {
-                            return result;
+                        errors.push(error.clone());
+                        if !Self::recover_from_error(error.clone(), &mut parse_stack, &mut tokens) {
+                            break ParseOutcome::Failed(error);
                         }
                     }
                 },
             };
-        }
+        };
+        statistics.max_stack_depth = parse_stack.max_depth();
+        self.after_parse(label, &outcome);
+        (outcome, statistics)
     }
 }
 
@@ -334,6 +572,8 @@ mod tests {
     pub struct Calc {
         errors: u32,
         variables: HashMap<String, f64>,
+        // the look ahead tokens' tags at each reduction
+        look_aheads: Vec<AATerminal>,
     }
 
     impl lalr1::ReportError<AATerminal> for Calc {}
@@ -343,6 +583,7 @@ mod tests {
             Self {
                 errors: 0,
                 variables: HashMap::new(),
+                look_aheads: vec![],
             }
         }
 
@@ -756,6 +997,17 @@ mod tests {
             };
         }
 
+        fn do_semantic_action_la<F: FnMut(String, String)>(
+            &mut self,
+            aa_production_id: u32,
+            aa_rhs: Vec<AttributeData>,
+            aa_look_ahead: &lexan::Token<AATerminal>,
+            aa_inject: F,
+        ) -> AttributeData {
+            self.look_aheads.push(*aa_look_ahead.tag());
+            self.do_semantic_action(aa_production_id, aa_rhs, aa_inject)
+        }
+
         fn do_semantic_action<F: FnMut(String, String)>(
             &mut self,
             aa_production_id: u32,
@@ -854,4 +1106,67 @@ mod tests {
         assert!(calc.parse_text("b = a * 5\n", "raw").is_ok());
         assert_eq!(calc.variables.get("b"), Some(&35.0));
     }
+
+    #[test]
+    fn parse_outcomes_distinguished() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        let (outcome, _) = calc.parse_text_outcome("c = 3\n", "raw");
+        assert!(outcome.is_clean());
+        assert!(outcome.into_result().is_ok());
+        let (outcome, _) = calc.parse_text_outcome("d = + 3\ne = 4\n", "raw");
+        match outcome {
+            ParseOutcome::Recovered(ref errors) => assert_eq!(errors.len(), 1),
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert!(!outcome.is_failed());
+        assert!(outcome.into_result().is_err());
+        assert_eq!(calc.variable("e"), Some(4.0));
+        // nothing can be recovered at the end of the input
+        let (outcome, _) = calc.parse_text_outcome("f = (4", "raw");
+        assert!(outcome.is_failed());
+        assert!(outcome.into_result().is_err());
+    }
+
+    #[test]
+    fn stack_depth_measured() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let (_, flat) = calc.parse_text_outcome("f = 1\n", "raw");
+        let (_, nested) = calc.parse_text_outcome("g = (((1)))\n", "raw");
+        assert_eq!(flat.max_stack_depth, 5);
+        // the parenthesized expressions are nested on the stack
+        assert_eq!(nested.max_stack_depth, flat.max_stack_depth + 4);
+        let parse_stack = crate::ParseStack::<AATerminal, AANonTerminal, AttributeData>::new();
+        assert_eq!(parse_stack.max_depth(), 1);
+    }
+
+    #[test]
+    fn shifts_counted() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let (result, shift_count) = calc.parse_text_counting_shifts("c = 1 + 2\n", "raw");
+        assert!(result.is_ok());
+        assert_eq!(shift_count, 6);
+        let (result, shift_count) = calc.parse_text_counting_shifts("c = (1 + 2)\n", "raw");
+        assert!(result.is_ok());
+        assert_eq!(shift_count, 8);
+    }
+
+    #[test]
+    fn recovery_from_bottom_state() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("+ 1\na = 2\n", "raw").is_err());
+        assert_eq!(calc.variable("a"), Some(2.0));
+    }
+
+    #[test]
+    fn look_ahead_given_to_actions() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = 1 + 2\n", "raw").is_ok());
+        use AATerminal::*;
+        assert_eq!(calc.look_aheads, vec![ID, PLUS, EOL, EOL, EOL, AAEnd]);
+    }
 }
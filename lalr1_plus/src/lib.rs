@@ -1,860 +1,4344 @@
+//! Without the (default) `std` feature the crate is `no_std` (but needs `alloc`): errors
+//! are then only reported by implementations of `ReportError` that say so and
+//! `Parser::diagnose_panics()` is ignored.
+#![cfg_attr(not(feature = "std"), no_std)]
+
 #[cfg(test)]
 #[macro_use]
 extern crate lazy_static;
+extern crate alloc;
 extern crate lexan;
 
-pub use std::{
-    collections::BTreeSet,
+pub use alloc::collections::BTreeSet;
+pub use core::{
     convert::From,
     default::Default,
     fmt::{self, Debug, Display},
-    io::Write,
 };
+#[cfg(feature = "std")]
+pub use std::io::Write;
+
+/// The `alloc` items that code generated with `--no-std` uses (as it can't rely on
+/// `std`'s prelude).
+pub mod alloc_prelude {
+    pub use alloc::{
+        boxed::Box,
+        collections::BTreeSet,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
+
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::{format, vec, vec::Vec};
 
 use lexan::TokenStream;
 
+/// A parse error with (the found tokens') locations of type `L` (see `Parser::location()`).
 #[derive(Debug, Clone)]
-pub enum Error<T: Ord + Copy + Debug + Display + Eq> {
+pub enum Error<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone = lexan::Location> {
     LexicalError(lexan::Error<T>, BTreeSet<T>),
-    SyntaxError(lexan::Token<T>, BTreeSet<T>),
+    SyntaxError(lexan::Token<T, L>, BTreeSet<T>),
+    /// A predicate rejected a reduction (see `Action::SemanticError`) with this message
+    /// when the look ahead token was the one given.
+    SemanticError(lexan::Token<T, L>, String, BTreeSet<T>),
+    /// The parse stack would have grown deeper than `Parser::max_stack_depth()`.
+    StackOverflow(L, BTreeSet<T>),
+    /// The tables have no entries for this state (so the expected set is empty).
+    InternalTableError(u32, BTreeSet<T>),
+}
+
+/// How a parser recovers from syntax errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecoveryStrategy {
+    /// Discard tokens (and pop the stack) until an `%error` production can continue.
+    #[default]
+    DeleteUntilViable,
+    /// First try inserting a missing terminal that would let the offending token be
+    /// shifted (e.g. a missing ")") and only discard tokens if there's no such terminal.
+    InsertThenDelete,
+}
+
+/// Measurements of a parse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStatistics {
+    /// The number of tokens shifted
+    pub shift_count: usize,
+    /// The greatest depth reached by the parse stack
+    pub max_stack_depth: usize,
+}
+
+/// The outcome of a parse.
+///
+/// Neither it nor the results of the parse methods should be ignored so that (with
+/// `unused_must_use` denied)
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// # #[derive(Default)]
+/// # struct Attr;
+/// # impl From<lalr1_plus::Error<u8>> for Attr {
+/// #     fn from(_: lalr1_plus::Error<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// # impl From<lexan::Token<u8>> for Attr {
+/// #     fn from(_: lexan::Token<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// fn parse<P: lalr1_plus::Parser<u8, u8, Attr>>(parser: &mut P) {
+///     parser.parse_text("1", "raw");
+/// }
+/// ```
+///
+/// doesn't compile whereas explicitly discarding the result
+///
+/// ```
+/// #![deny(unused_must_use)]
+/// # #[derive(Default)]
+/// # struct Attr;
+/// # impl From<lalr1_plus::Error<u8>> for Attr {
+/// #     fn from(_: lalr1_plus::Error<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// # impl From<lexan::Token<u8>> for Attr {
+/// #     fn from(_: lexan::Token<u8>) -> Self {
+/// #         Attr
+/// #     }
+/// # }
+/// fn parse<P: lalr1_plus::Parser<u8, u8, Attr>>(parser: &mut P) {
+///     let _ = parser.parse_text("1", "raw");
+/// }
+/// ```
+///
+/// does.
+#[must_use]
+#[derive(Debug, Clone)]
+pub enum ParseOutcome<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone = lexan::Location> {
+    /// No errors occurred.
+    Clean,
+    /// The parse completed after recovering from these errors.
+    Recovered(Vec<Error<T, L>>),
+    /// The parse was abandoned because the last of these errors could not be recovered from.
+    Failed(Vec<Error<T, L>>),
+}
+
+impl<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone> ParseOutcome<T, L> {
+    pub fn is_clean(&self) -> bool {
+        matches!(self, ParseOutcome::Clean)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, ParseOutcome::Failed(_))
+    }
+
+    /// The equivalent `parse_text()` result, i.e. the last error (if any).
+    pub fn into_result(self) -> Result<(), Error<T, L>> {
+        match self {
+            ParseOutcome::Clean => Ok(()),
+            ParseOutcome::Recovered(mut errors) | ParseOutcome::Failed(mut errors) => {
+                Err(errors.pop().expect("at least one"))
+            }
+        }
+    }
 }
 
-fn format_set<T: Ord + Display>(set: &BTreeSet<T>) -> String {
+fn format_set<T: Ord, D: Fn(&T) -> String>(set: &BTreeSet<T>, display: &D) -> String {
     let mut string = String::new();
     let last = set.len() - 1;
     for (index, item) in set.iter().enumerate() {
         if index == 0 {
-            string += &item.to_string();
+            string += &display(item);
         } else {
             if index == last {
                 string += " or ";
             } else {
                 string += ", ";
             };
-            string += &item.to_string()
+            string += &display(item)
         }
     }
     string
 }
 
-impl<T: Ord + Copy + Debug + Display + Eq> Display for Error<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone> Error<T, L> {
+    /// The terminals that would have been acceptable where the error occurred.
+    pub fn expected(&self) -> &BTreeSet<T> {
+        match self {
+            Error::LexicalError(_, expected)
+            | Error::SyntaxError(_, expected)
+            | Error::SemanticError(_, _, expected)
+            | Error::StackOverflow(_, expected)
+            | Error::InternalTableError(_, expected) => expected,
+        }
+    }
+
+    /// The token that was found (if the error is a syntax or semantic error).
+    pub fn found(&self) -> Option<&lexan::Token<T, L>> {
+        match self {
+            Error::SyntaxError(found, _) | Error::SemanticError(found, _, _) => Some(found),
+            _ => None,
+        }
+    }
+
+    /// The error's message with terminals rendered by `display_terminal` (see
+    /// `Parser::display_terminal()`).
+    pub fn message<D: Fn(&T) -> String>(&self, display_terminal: D) -> String {
         match self {
-            Error::LexicalError(lex_err, expected) => write!(
-                f,
+            Error::LexicalError(lex_err, expected) => format!(
                 "Lexical Error: {}: expected: {}.",
                 lex_err,
-                format_set(expected)
+                format_set(expected, &display_terminal)
             ),
-            Error::SyntaxError(found, expected) => write!(
-                f,
+            Error::SyntaxError(found, expected) => format!(
                 "Syntax Error: expected: {} found: {} at: {}.",
-                format_set(expected),
-                found.tag(),
+                format_set(expected, &display_terminal),
+                display_terminal(found.tag()),
                 found.location()
             ),
+            Error::SemanticError(found, message, _) => {
+                format!("Semantic Error: {message} at: {}.", found.location())
+            }
+            Error::StackOverflow(location, _) => {
+                format!("Stack Overflow: the parse stack is too deep at: {location}.")
+            }
+            Error::InternalTableError(state, _) => {
+                format!("Internal Error: the parse tables have no entries for state {state}.")
+            }
         }
     }
 }
 
-pub trait ReportError<T: Ord + Copy + Debug + Display + Eq> {
-    fn report_error(&mut self, error: &Error<T>) {
-        let message = error.to_string();
-        if let Error::LexicalError(lexan::Error::AmbiguousMatches(_, _, _), _) = error {
-            panic!("Fatal Error: {message}!!");
-        };
-        std::io::stderr()
-            .write_all(message.as_bytes())
-            .expect("Nowhere to go here!!!");
+impl<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone> Display for Error<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message(|terminal| terminal.to_string()))
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Symbol<T, N> {
-    Terminal(T),
-    NonTerminal(N),
-    Start,
-    Error,
-}
-
-#[derive(Debug)]
-pub struct ParseStack<T, N, A>
+impl<T, L> core::error::Error for Error<T, L>
 where
-    T: Copy + Ord + Debug + Display,
-    A: From<lexan::Token<T>> + From<Error<T>>,
+    T: Ord + Copy + Debug + Display + Eq + 'static,
+    L: Debug + Display + Clone,
 {
-    states: Vec<(Symbol<T, N>, u32)>,
-    attributes: Vec<A>,
-    last_error_state: Option<u32>,
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::LexicalError(lex_err, _) => Some(lex_err),
+            _ => None,
+        }
+    }
 }
 
-impl<T, N, A> ParseStack<T, N, A>
-where
-    T: Copy + Ord + Debug + Display,
-    A: From<lexan::Token<T>> + From<Error<T>>,
-{
-    fn new() -> Self {
-        Self {
-            states: vec![(Symbol::Start, 0)],
-            attributes: vec![],
-            last_error_state: None,
+/// A set of (small) unsigned integers, e.g. production ids.
+#[cfg(feature = "coverage")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BitSet(Vec<u64>);
+
+#[cfg(feature = "coverage")]
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, item: u32) -> bool {
+        let (index, mask) = (item as usize / 64, 1_u64 << (item % 64));
+        if index >= self.0.len() {
+            self.0.resize(index + 1, 0);
         }
+        let inserted = self.0[index] & mask == 0;
+        self.0[index] |= mask;
+        inserted
     }
 
-    fn current_state(&self) -> u32 {
-        self.states.last().unwrap().1
+    pub fn contains(&self, item: u32) -> bool {
+        let (index, mask) = (item as usize / 64, 1_u64 << (item % 64));
+        index < self.0.len() && self.0[index] & mask != 0
     }
 
-    pub fn at_len_minus_n(&self, n: usize) -> &A {
-        let len = self.attributes.len();
-        &self.attributes[len - n]
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
     }
 
-    fn pop_n(&mut self, n: usize) -> Vec<A> {
-        let len = self.states.len();
-        self.states.truncate(len - n);
-        let len = self.attributes.len();
-        self.attributes.split_off(len - n)
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
     }
 
-    fn push_error(&mut self, state: u32, error: Error<T>) {
-        self.states.push((Symbol::Error, state));
-        self.attributes.push(A::from(error))
+    pub fn clear(&mut self) {
+        self.0.clear()
     }
 
-    fn push_terminal(&mut self, token: lexan::Token<T>, new_state: u32) {
-        self.states
-            .push((Symbol::Terminal(*token.tag()), new_state));
-        self.attributes.push(A::from(token));
+    /// The items in `0..limit` that are not in the set.
+    pub fn missing(&self, limit: u32) -> Vec<u32> {
+        (0..limit).filter(|item| !self.contains(*item)).collect()
+    }
+}
+
+/// A handle for an interned string: equal handles (from the same `Interner`) denote
+/// equal strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interned(u32);
+
+impl Interned {
+    pub fn index(&self) -> u32 {
+        self.0
     }
+}
 
-    fn push_non_terminal(&mut self, non_terminal: N, attribute: A, new_state: u32) {
-        self.attributes.push(attribute);
-        self.states
-            .push((Symbol::NonTerminal(non_terminal), new_state));
+#[cfg(feature = "std")]
+type InternerIndices = std::collections::HashMap<String, Interned>;
+#[cfg(not(feature = "std"))]
+type InternerIndices = alloc::collections::BTreeMap<String, Interned>;
+
+/// Storage for strings (e.g. identifiers' lexemes) that occur many times so that each
+/// is allocated only once.
+#[derive(Debug, Default)]
+pub struct Interner {
+    indices: InternerIndices,
+    strings: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    fn is_last_error_state(&self, state: u32) -> bool {
-        if let Some(last_error_state) = self.last_error_state {
-            state == last_error_state
+    pub fn intern(&mut self, text: &str) -> Interned {
+        if let Some(interned) = self.indices.get(text) {
+            *interned
         } else {
-            false
+            let interned = Interned(self.strings.len() as u32);
+            self.strings.push(text.to_string());
+            self.indices.insert(text.to_string(), interned);
+            interned
         }
     }
 
-    fn distance_to_viable_state<F: Fn(&T) -> BTreeSet<u32>>(
-        &mut self,
-        tokens: &mut TokenStream<T>,
-        viable_error_recovery_states: F,
-    ) -> Option<usize> {
-        while !tokens.is_empty() {
-            if let Ok(token) = tokens.front() {
-                let viable_states = viable_error_recovery_states(token.tag());
-                for sub in 1..self.states.len() {
-                    let candidate = self.states[self.states.len() - sub].1;
-                    if !self.is_last_error_state(candidate) && viable_states.contains(&candidate) {
-                        self.last_error_state = Some(candidate);
-                        return Some(sub - 1);
-                    }
-                }
-            };
-            tokens.advance();
-        }
-        None
+    pub fn get(&self, text: &str) -> Option<Interned> {
+        self.indices.get(text).copied()
+    }
+
+    pub fn resolve(&self, interned: Interned) -> &str {
+        &self.strings[interned.0 as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum Action {
-    Shift(u32),
-    Reduce(u32),
-    Accept,
-    SyntaxError,
+/// Whether a parse should attempt to recover from an error that has been reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorOutcome {
+    Continue,
+    Abort,
 }
 
-pub trait Parser<T: Ord + Copy + Debug, N, A>
-where
-    T: Ord + Copy + Debug + Display,
-    N: Ord + Display + Debug,
-    A: Default + From<lexan::Token<T>> + From<Error<T>>,
-    Self: ReportError<T>,
-{
-    fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<T>;
-    fn next_action(
-        &self,
-        state: u32,
-        attributes: &ParseStack<T, N, A>,
-        o_token: &lexan::Token<T>,
-    ) -> Action;
-    fn production_data(production_id: u32) -> (N, usize);
-    fn goto_state(lhs: &N, current_state: u32) -> u32;
-    fn do_semantic_action<F: FnMut(String, String)>(
-        &mut self,
-        _production_id: u32,
-        _attributes: Vec<A>,
-        mut inject: F,
-    ) -> A {
-        // NB: required in order to cop with issue #35203
-        inject(String::new(), String::new());
-        // confirm multiple injects OK.
-        inject(String::new(), String::new());
-        A::default()
+pub trait ReportError<T: Ord + Copy + Debug + Display + Eq, L: Display + Clone = lexan::Location> {
+    /// Report `error`, whose `message` has been rendered by the parser (with its
+    /// terminals shown by `Parser::display_terminal()`), and decide whether the parse
+    /// should attempt to recover from it.  By default, the message is written to stderr
+    /// (if there is one, i.e. with the `std` feature) and only ambiguous matches (which
+    /// are faults in the lexicon) abort the parse.
+    fn report_error(&mut self, error: &Error<T, L>, message: &str) -> ErrorOutcome {
+        if let Error::LexicalError(lexan::Error::AmbiguousMatches(_, _, _), _) = error {
+            #[cfg(feature = "std")]
+            eprintln!("Fatal Error: {message}!!");
+            return ErrorOutcome::Abort;
+        };
+        #[cfg(feature = "std")]
+        std::io::stderr()
+            .write_all(message.as_bytes())
+            .expect("Nowhere to go here!!!");
+        #[cfg(not(feature = "std"))]
+        let _ = message;
+        ErrorOutcome::Continue
     }
+}
 
-    fn viable_error_recovery_states(tag: &T) -> BTreeSet<u32>;
+/// Receives the events of a parse along with the spans of the text involved (in the
+/// parser's locations, see `Parser::location()`), e.g. to supply a language server with
+/// document symbols and diagnostics.
+pub trait ParseObserver<
+    T: Ord + Copy + Debug + Display + Eq,
+    N,
+    L: Display + Clone = lexan::Location,
+>
+{
+    /// `token` (spanning `span`) has been shifted.
+    fn on_shift(&mut self, _token: &lexan::Token<T, L>, _span: &lexan::Span<L>) {}
 
-    fn error_goto_state(state: u32) -> u32 {
-        panic!("No error go to state for {state}")
-    }
+    /// The symbols spanning `span` have been reduced to `lhs` by production `production_id`.
+    fn on_reduce(&mut self, _lhs: &N, _production_id: u32, _span: &lexan::Span<L>) {}
 
-    fn look_ahead_set(state: u32) -> BTreeSet<T>;
+    /// `error` has occurred and the parse has (or hasn't) `recovered` from it.
+    fn on_error(&mut self, _error: &Error<T, L>, _recovered: bool) {}
 
-    fn recover_from_error(
-        error: Error<T>,
-        parse_stack: &mut ParseStack<T, N, A>,
-        tokens: &mut TokenStream<T>,
-    ) -> bool {
-        if let Some(distance) =
-            parse_stack.distance_to_viable_state(tokens, |t| Self::viable_error_recovery_states(t))
-        {
-            parse_stack.pop_n(distance);
-            let next_state = Self::error_goto_state(parse_stack.current_state());
-            parse_stack.push_error(next_state, error);
-            true
-        } else {
-            false
-        }
+    /// The input has been accepted (whether or not there were errors on the way).
+    fn on_accept(&mut self) {}
+}
+
+impl<T: Ord + Copy + Debug + Display + Eq, N, L: Display + Clone> ParseObserver<T, N, L> for () {}
+
+/// Where a parse gets its tokens from: a `lexan::TokenStream` or (with the `async`
+/// feature) the tokens that have arrived from an `AsyncTokenSource` so far.
+pub trait TokenSource<T: Ord + Copy + Debug + Display + Eq> {
+    /// The next token (or piece of unexpected text) to be parsed.
+    fn front(&self) -> Result<lexan::Token<T>, lexan::Error<T>>;
+
+    fn advance(&mut self);
+
+    /// Whether the end of the input has been reached (i.e. only the end marker remains).
+    fn is_empty(&self) -> bool;
+
+    /// The number of tokens (including pieces of unexpected text) advanced past
+    fn consumed(&self) -> usize;
+
+    /// Whether the next token has yet to arrive (in which case `front()` mustn't be
+    /// called).  Token streams are never pending.
+    fn is_pending(&self) -> bool {
+        false
     }
 
-    fn parse_text(&mut self, text: &str, label: &str) -> Result<(), Error<T>> {
-        let mut tokens = self.lexical_analyzer().token_stream(text, label);
-        let mut parse_stack = ParseStack::<T, N, A>::new();
-        let mut result: Result<(), Error<T>> = Ok(());
+    fn set_skipping(&mut self, _skipping: bool) {}
 
-        loop {
-            match tokens.front() {
-                Err(err) => {
-                    let expected_tokens = Self::look_ahead_set(parse_stack.current_state());
-                    let error = Error::LexicalError(err, expected_tokens);
-                    self.report_error(&error);
-                    result = Err(error.clone());
-                    if !Self::recover_from_error(error, &mut parse_stack, &mut tokens) {
-                        return result;
-                    }
-                }
-                Ok(token) => {
-                    match self.next_action(parse_stack.current_state(), &parse_stack, &token) {
-                        Action::Accept => return result,
-                        Action::Shift(next_state) => {
-                            parse_stack.push_terminal(token, next_state);
-                            tokens.advance();
-                        }
-                        Action::Reduce(production_id) => {
-                            let (lhs, rhs_len) = Self::production_data(production_id);
-                            let rhs = parse_stack.pop_n(rhs_len);
-                            let next_state = Self::goto_state(&lhs, parse_stack.current_state());
-                            let attribute = self.do_semantic_action(production_id, rhs, |s, l| {
-                                tokens.inject(&s, &l)
-                            });
-                            parse_stack.push_non_terminal(lhs, attribute, next_state);
-                        }
-                        Action::SyntaxError => {
-                            let expected_tokens = Self::look_ahead_set(parse_stack.current_state());
-                            let error = Error::SyntaxError(token.clone(), expected_tokens);
-                            self.report_error(&error);
-                            result = Err(error.clone());
-                            if !Self::recover_from_error(error, &mut parse_stack, &mut tokens) {
-                                return result;
-                            }
-                        }
-                    }
-                }
-            };
-        }
+    /// Switch to lexical mode `mode` (see `%mode`) for the text that follows.
+    fn set_mode(&mut self, mode: usize) {
+        panic!("{mode}: token source has no lexical modes");
+    }
+
+    /// Insert `text` (labelled `label`) in front of the rest of the text.  Only empty
+    /// text can be injected into sources without text.
+    fn inject(&mut self, text: &str, label: &str) {
+        assert!(
+            text.is_empty(),
+            "{label}: token source can't have text injected"
+        );
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::ReportError;
-    use std::collections::{BTreeSet, HashMap};
-    use std::convert::From;
-    use std::fmt;
-    use std::str::FromStr;
+impl<T: Ord + Copy + Debug + Display + Eq> TokenSource<T> for TokenStream<T> {
+    fn front(&self) -> Result<lexan::Token<T>, lexan::Error<T>> {
+        TokenStream::front(self)
+    }
 
-    macro_rules! btree_set {
-        ( $( $x:expr ),* ) => {
-            {
-                let mut set = BTreeSet::new();
-                $( set.insert($x); )*
-                set
-            }
-        };
-        ( $( $x:expr ),+ , ) => {
-            btree_set![ $( $x ), * ]
-        };
+    fn advance(&mut self) {
+        TokenStream::advance(self)
     }
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-    enum Terminal {
-        Plus,
-        Minus,
-        Times,
-        Divide,
-        Assign,
-        LPR,
-        RPR,
-        EOL,
-        Number,
-        Id,
-        EndMarker,
+
+    fn is_empty(&self) -> bool {
+        TokenStream::is_empty(self)
     }
 
-    impl fmt::Display for Terminal {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            match self {
-                Terminal::Plus => write!(f, "+"),
-                Terminal::Minus => write!(f, "-"),
-                Terminal::Times => write!(f, "*"),
-                Terminal::Divide => write!(f, "/"),
-                Terminal::Assign => write!(f, "="),
-                Terminal::LPR => write!(f, "("),
-                Terminal::RPR => write!(f, ")"),
-                Terminal::EOL => write!(f, "EOL"),
-                Terminal::Number => write!(f, "Number"),
-                Terminal::Id => write!(f, "Id"),
-                Terminal::EndMarker => write!(f, "EndMarker"),
-            }
-        }
+    fn consumed(&self) -> usize {
+        TokenStream::consumed(self)
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-    enum NonTerminal {
-        Line,
-        SetUp,
-        Expr,
+    fn set_skipping(&mut self, skipping: bool) {
+        TokenStream::set_skipping(self, skipping)
     }
 
-    impl fmt::Display for NonTerminal {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            match self {
-                NonTerminal::Line => write!(f, "Line"),
-                NonTerminal::SetUp => write!(f, "SetUp"),
-                NonTerminal::Expr => write!(f, "Expr"),
-            }
+    fn set_mode(&mut self, mode: usize) {
+        TokenStream::set_mode(self, mode)
+    }
+
+    fn inject(&mut self, text: &str, label: &str) {
+        TokenStream::inject(self, text, label)
+    }
+}
+
+/// The tokens of some bytes (e.g. a binary format or protocol message) as found by
+/// `lexer`: given the bytes that remain, it returns the tag and length (at least 1) of
+/// the token at their start or `None` if the first byte is unexpected.  The offsets of
+/// the tokens' locations count bytes (from 1, with the line number always 1) and their
+/// lexemes have a `char` for each byte (see `lexeme_bytes()`), so their `span()`s
+/// (which measure lexemes as text) should be ignored.
+pub struct ByteTokens<'a, T: Ord + Copy + Debug + Display + Eq, F> {
+    bytes: &'a [u8],
+    label: String,
+    lexer: F,
+    end_marker: T,
+    index: usize,
+    front: Result<lexan::Token<T>, lexan::Error<T>>,
+    consumed: usize,
+}
+
+impl<'a, T, F> ByteTokens<'a, T, F>
+where
+    T: Ord + Copy + Debug + Display + Eq,
+    F: FnMut(&[u8]) -> Option<(T, usize)>,
+{
+    pub fn new(bytes: &'a [u8], label: &str, end_marker: T, lexer: F) -> Self {
+        let mut byte_tokens = Self {
+            bytes,
+            label: label.to_string(),
+            lexer,
+            end_marker,
+            index: 0,
+            front: Err(lexan::Error::AdvancedWhenEmpty(lexan::Location::at(
+                label, 1, 1,
+            ))),
+            consumed: 0,
+        };
+        byte_tokens.scan();
+        byte_tokens
+    }
+
+    // Make the token (or unexpected byte) at `index` the front
+    fn scan(&mut self) {
+        let location = lexan::Location::at(&self.label, 1, self.index + 1);
+        let rest = &self.bytes[self.index..];
+        self.front = if rest.is_empty() {
+            Ok(lexan::Token::new(self.end_marker, String::new(), location))
+        } else if let Some((tag, length)) = (self.lexer)(rest) {
+            assert!(
+                0 < length && length <= rest.len(),
+                "{length}: bad length for a token of {} bytes",
+                rest.len()
+            );
+            let lexeme = rest[..length]
+                .iter()
+                .map(|byte| char::from(*byte))
+                .collect();
+            self.index += length;
+            Ok(lexan::Token::new(tag, lexeme, location))
+        } else {
+            self.index += 1;
+            let lexeme = char::from(rest[0]).to_string();
+            Err(lexan::Error::UnexpectedText(lexeme, location))
+        };
+    }
+}
+
+impl<'a, T, F> TokenSource<T> for ByteTokens<'a, T, F>
+where
+    T: Ord + Copy + Debug + Display + Eq,
+    F: FnMut(&[u8]) -> Option<(T, usize)>,
+{
+    fn front(&self) -> Result<lexan::Token<T>, lexan::Error<T>> {
+        self.front.clone()
+    }
+
+    fn advance(&mut self) {
+        // the end marker stays at the front (as for token streams)
+        if !self.is_empty() {
+            self.consumed += 1;
+            self.scan();
         }
     }
 
-    #[derive(Debug, Default, Clone)]
-    struct AttributeData {
-        id: String,
-        value: f64,
+    fn is_empty(&self) -> bool {
+        matches!(&self.front, Ok(token) if *token.tag() == self.end_marker)
     }
 
-    impl From<lexan::Token<Terminal>> for AttributeData {
-        fn from(input: lexan::Token<Terminal>) -> Self {
-            let mut attr = AttributeData::default();
-            match input.tag() {
-                Terminal::Number => {
-                    attr.value = f64::from_str(input.lexeme()).unwrap();
-                }
-                Terminal::Id => {
-                    attr.id = input.lexeme().to_string();
-                }
-                _ => (),
-            };
-            attr
+    fn consumed(&self) -> usize {
+        self.consumed
+    }
+}
+
+/// The bytes of the lexeme of `token` (one of `ByteTokens`' tokens)
+pub fn lexeme_bytes<T: Copy + Display + Eq>(token: &lexan::Token<T>) -> Vec<u8> {
+    token.lexeme().chars().map(|c| c as u8).collect()
+}
+
+/// Supplies tokens (e.g. as they are lexed from the text arriving on a socket) for
+/// `Parser::parse_stream()`, which suspends the parse while awaiting them.
+#[cfg(feature = "async")]
+pub trait AsyncTokenSource<T: Ord + Copy + Debug + Display + Eq> {
+    /// The next token: `None` at the end of the input (the parser supplies the end marker).
+    fn next_token(&mut self) -> impl core::future::Future<Output = Option<lexan::Token<T>>>;
+}
+
+// The tokens that have arrived from an `AsyncTokenSource` (pending while the next hasn't)
+#[cfg(feature = "async")]
+struct Arrivals<T: Ord + Copy + Debug + Display + Eq> {
+    front: Option<lexan::Token<T>>,
+    end_marker: T,
+    ended: bool,
+    consumed: usize,
+    // where the last token to arrive ended (and so where the end marker goes)
+    location: lexan::Location,
+}
+
+#[cfg(feature = "async")]
+impl<T: Ord + Copy + Debug + Display + Eq> Arrivals<T> {
+    fn new(end_marker: T, label: &str) -> Self {
+        Self {
+            front: None,
+            end_marker,
+            ended: false,
+            consumed: 0,
+            location: lexan::Location::at(label, 1, 1),
         }
     }
 
-    impl From<crate::Error<Terminal>> for AttributeData {
-        fn from(_error: crate::Error<Terminal>) -> Self {
-            AttributeData::default()
+    fn arrive(&mut self, token: Option<lexan::Token<T>>) {
+        let token = match token {
+            Some(token) => {
+                self.location = token.span().end;
+                token
+            }
+            None => {
+                self.ended = true;
+                lexan::Token::new(self.end_marker, String::new(), self.location.clone())
+            }
+        };
+        self.front = Some(token);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: Ord + Copy + Debug + Display + Eq> TokenSource<T> for Arrivals<T> {
+    fn front(&self) -> Result<lexan::Token<T>, lexan::Error<T>> {
+        Ok(self.front.clone().expect("the front has yet to arrive"))
+    }
+
+    fn advance(&mut self) {
+        // the end marker stays at the front (as for token streams)
+        if !self.ended {
+            self.front = None;
+            self.consumed += 1;
         }
     }
 
-    const UNDEFINED_VARIABLE: u32 = 1 << 0;
-    const DIVIDE_BY_ZERO: u32 = 1 << 1;
-    const SYNTAX_ERROR: u32 = 1 << 2;
-    const LEXICAL_ERROR: u32 = 1 << 3;
+    fn is_empty(&self) -> bool {
+        self.ended
+    }
 
-    struct Calc {
-        errors: u32,
-        variables: HashMap<String, f64>,
+    fn consumed(&self) -> usize {
+        self.consumed
     }
 
-    impl ReportError<Terminal> for Calc {}
+    fn is_pending(&self) -> bool {
+        self.front.is_none()
+    }
+}
 
-    lazy_static! {
-        static ref AALEXAN: lexan::LexicalAnalyzer<Terminal> = {
-            use Terminal::*;
-            lexan::LexicalAnalyzer::new(
-                &[
-                    (Plus, "+"),
-                    (Minus, "-"),
-                    (Times, "*"),
-                    (Divide, "/"),
-                    (Assign, "="),
-                    (LPR, "("),
-                    (RPR, ")"),
-                ],
-                &[
-                    (EOL, r"(\n)"),
-                    (Number, r"([0-9]+(\.[0-9]+){0,1})"),
-                    (Id, r"([a-zA-Z]+)"),
-                ],
-                &[r"([\t\r ]+)"],
-                EndMarker,
-            )
-        };
+/// An attribute `value` along with the `span` of the text that it was derived from
+/// (see `%attr ... %spanned`).  It dereferences to its `value`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Spanned<V> {
+    pub value: V,
+    pub span: lexan::Span,
+}
+
+impl<V> Spanned<V> {
+    pub fn new(value: V, span: lexan::Span) -> Self {
+        Self { value, span }
     }
 
-    impl Calc {
-        pub fn new() -> Self {
-            Self {
-                errors: 0,
-                variables: HashMap::new(),
+    /// The span covering all of `spanned` (or an empty span at `location` if there are none)
+    pub fn span_of(spanned: &[Self], location: &lexan::Location) -> lexan::Span {
+        match (spanned.first(), spanned.last()) {
+            (Some(first), Some(last)) => {
+                lexan::Span::new(first.span.start.clone(), last.span.end.clone())
             }
+            _ => lexan::Span::empty_at(location),
         }
+    }
+}
 
-        fn report_errors(&self) {
-            if self.errors == 0 {
-                println!("no errrs")
-            } else {
-                if self.errors & UNDEFINED_VARIABLE == UNDEFINED_VARIABLE {
-                    println!("undefined variable errors")
-                }
-                if self.errors & DIVIDE_BY_ZERO == DIVIDE_BY_ZERO {
-                    println!("divide by zero errors")
-                }
-                if self.errors & SYNTAX_ERROR == SYNTAX_ERROR {
-                    println!("syntax errors")
+impl<V> core::ops::Deref for Spanned<V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.value
+    }
+}
+
+impl<V> core::ops::DerefMut for Spanned<V> {
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self.value
+    }
+}
+
+impl<T: Copy + Display + Eq, V: From<lexan::Token<T>>> From<lexan::Token<T>> for Spanned<V> {
+    fn from(token: lexan::Token<T>) -> Self {
+        let span = token.span();
+        Self::new(V::from(token), span)
+    }
+}
+
+impl<T: Ord + Copy + Debug + Display + Eq, V: From<Error<T>>> From<Error<T>> for Spanned<V> {
+    fn from(error: Error<T>) -> Self {
+        let span = match &error {
+            Error::LexicalError(error, _) => lexan::Span::empty_at(error.location()),
+            Error::SyntaxError(token, _) | Error::SemanticError(token, _, _) => token.span(),
+            Error::StackOverflow(location, _) => lexan::Span::empty_at(location),
+            Error::InternalTableError(..) => lexan::Span::default(),
+        };
+        Self::new(V::from(error), span)
+    }
+}
+
+/// Attributes that can collect the attributes of the elements of separated lists
+/// (see `%` and `%%` in productions).
+pub trait ListAttribute: Sized {
+    fn empty_list() -> Self;
+    fn push_element(&mut self, element: Self);
+}
+
+impl<V: ListAttribute> ListAttribute for Spanned<V> {
+    fn empty_list() -> Self {
+        Self::new(V::empty_list(), lexan::Span::default())
+    }
+
+    fn push_element(&mut self, element: Self) {
+        self.value.push_element(element.value);
+    }
+}
+
+/// List attributes that can also collect elements at their fronts, as the elements of
+/// right recursive repetitions (see `%repeat right`) are collected last first.
+pub trait FrontListAttribute: ListAttribute {
+    fn push_front_element(&mut self, element: Self);
+}
+
+impl<V: FrontListAttribute> FrontListAttribute for Spanned<V> {
+    fn push_front_element(&mut self, element: Self) {
+        self.value.push_front_element(element.value);
+    }
+}
+
+/// Attributes that can record the presence (and attribute) or absence of optional
+/// symbols (see `?` in productions).
+pub trait OptionalAttribute: Sized {
+    fn absent() -> Self;
+    fn present(element: Self) -> Self;
+}
+
+impl<V: OptionalAttribute> OptionalAttribute for Spanned<V> {
+    fn absent() -> Self {
+        Self::new(V::absent(), lexan::Span::default())
+    }
+
+    fn present(element: Self) -> Self {
+        Self::new(V::present(element.value), element.span)
+    }
+}
+
+/// Attributes that can tuple up the attributes of the members of groups (see `(` and `)`
+/// in productions).
+pub trait GroupAttribute: Sized {
+    fn group(members: Vec<Self>) -> Self;
+}
+
+impl<V: GroupAttribute> GroupAttribute for Spanned<V> {
+    fn group(members: Vec<Self>) -> Self {
+        let values = members.into_iter().map(|member| member.value).collect();
+        Self::new(V::group(values), lexan::Span::default())
+    }
+}
+
+/// Attributes that can hold the values of the types given to non terminals by `%type`
+/// (which their `$n` and `$$` then denote in actions and predicates).  The generated
+/// code only converts attributes of such non terminals so the conversions may panic
+/// if `self` holds something else.  For an enum with a variant holding each type, see
+/// `typed_attribute!`.
+pub trait TypedAttribute<V>: Sized {
+    fn from_typed(value: V) -> Self;
+    fn into_typed(self) -> V;
+    fn typed(&self) -> &V;
+}
+
+impl<X, V: TypedAttribute<X>> TypedAttribute<X> for Spanned<V> {
+    fn from_typed(value: X) -> Self {
+        Self::new(V::from_typed(value), lexan::Span::default())
+    }
+
+    fn into_typed(self) -> X {
+        self.value.into_typed()
+    }
+
+    fn typed(&self) -> &X {
+        self.value.typed()
+    }
+}
+
+/// Implement `TypedAttribute<$value>` for `$attr` using its `$variant($value)`, e.g.
+/// `lalr1_plus::typed_attribute!(AttributeData, AttributeData::Value, f64);`.
+#[macro_export]
+macro_rules! typed_attribute {
+    ( $attr:ty, $variant:path, $value:ty ) => {
+        impl $crate::TypedAttribute<$value> for $attr {
+            fn from_typed(value: $value) -> Self {
+                $variant(value)
+            }
+
+            fn into_typed(self) -> $value {
+                match self {
+                    $variant(value) => value,
+                    _ => panic!("attribute doesn't hold a {}", stringify!($value)),
                 }
-                if self.errors & LEXICAL_ERROR == LEXICAL_ERROR {
-                    println!("lexical errors")
+            }
+
+            fn typed(&self) -> &$value {
+                match self {
+                    $variant(value) => value,
+                    _ => panic!("attribute doesn't hold a {}", stringify!($value)),
                 }
             }
-            println!("#errors = {}", self.errors)
         }
-    }
+    };
+}
 
-    impl crate::Parser<Terminal, NonTerminal, AttributeData> for Calc {
-        fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<Terminal> {
-            &AALEXAN
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Symbol<T, N> {
+    Terminal(T),
+    NonTerminal(N),
+    Start,
+    Error,
+}
+
+impl<T: Display, N: Display> Display for Symbol<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Symbol::Terminal(terminal) => write!(f, "{terminal}"),
+            Symbol::NonTerminal(non_terminal) => write!(f, "{non_terminal}"),
+            Symbol::Start => write!(f, "^"),
+            Symbol::Error => write!(f, "%error"),
         }
+    }
+}
 
-        fn viable_error_recovery_states(tag: &Terminal) -> BTreeSet<u32> {
-            use Terminal::*;
-            match tag {
-                EOL => btree_set![0_u32, 4], //BTreeSet::from_iter([0_u32, 4].into_iter().cloned()),
-                _ => BTreeSet::new(),
+// The number of recently shifted tokens kept for diagnosing panics
+const TOKEN_HISTORY_LENGTH: usize = 8;
+
+// The number and (greatest) length of the continuations offered by `explain_failure()`
+const EXPLANATION_CONTINUATIONS: usize = 4;
+const EXPLANATION_CONTINUATION_LENGTH: usize = 3;
+
+// Tell `event` to the `observer` given for the parse or, failing that, the parser's own
+fn notify<T: Ord + Copy + Debug + Display + Eq, N, L: Display + Clone>(
+    observer: &mut Option<&mut dyn ParseObserver<T, N, L>>,
+    own: Option<&mut dyn ParseObserver<T, N, L>>,
+    event: impl FnOnce(&mut dyn ParseObserver<T, N, L>),
+) {
+    match observer {
+        Some(observer) => event(*observer),
+        None => {
+            if let Some(own) = own {
+                event(own)
             }
         }
+    }
+}
 
-        fn error_goto_state(state: u32) -> u32 {
-            match state {
-                0 | 4 => 3,
-                _ => panic!("No error go to state for {state}"),
-            }
+// Call `f` and, should it panic, print `describe()` before continuing to unwind
+#[cfg(feature = "std")]
+fn diagnosed<R, F: FnOnce() -> R, D: FnOnce() -> String>(diagnose: bool, describe: D, f: F) -> R {
+    if !diagnose {
+        return f();
+    }
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            eprintln!("{}", describe());
+            std::panic::resume_unwind(payload)
         }
+    }
+}
 
-        fn look_ahead_set(state: u32) -> BTreeSet<Terminal> {
-            use Terminal::*;
-            return match state {
-                0 => btree_set![Minus, LPR, Number, Id],
-                1 => btree_set![EndMarker, EOL],
-                2 => btree_set![Minus, LPR, Number, Id],
-                3 => btree_set![EndMarker, EOL],
-                4 => btree_set![EndMarker, EOL, Minus, Number, Id, LPR],
-                5 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide],
-                6 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, Assign],
-                7 | 8 => btree_set![Minus, Number, Id, LPR],
-                9 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                10 => btree_set![EndMarker, EOL],
-                11 | 12 | 13 | 14 | 15 => btree_set![Minus, Number, Id, LPR],
-                16 => btree_set![Plus, Minus, Times, Divide, RPR],
-                17 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                18 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                19 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                20 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                21 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                22 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                23 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                24 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
-                _ => panic!("illegal state: {state}"),
-            };
+// Without `std` there's no catching panics (nor anywhere to print to)
+#[cfg(not(feature = "std"))]
+fn diagnosed<R, F: FnOnce() -> R, D: FnOnce() -> String>(_diagnose: bool, _describe: D, f: F) -> R {
+    f()
+}
+
+// What the checkpoints of a parse (see `Parser::parse_text_checkpointed()`) share: the
+// label of the text, the number of tokens between them and the tokens (etc.) consumed
+#[derive(Debug)]
+struct Checkpointed<T: Copy + Ord + Debug + Display> {
+    label: String,
+    interval: usize,
+    consumed: Vec<Result<lexan::Token<T>, lexan::Error<T>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseStack<T, N, A>
+where
+    T: Copy + Ord + Debug + Display,
+{
+    states: Vec<(Symbol<T, N>, u32)>,
+    attributes: Vec<A>,
+    // the spans of the attributes' text (kept only if the parse is observed)
+    spans: Option<Vec<lexan::Span>>,
+    // the number of tokens consumed when each state was pushed (so a state reached by
+    // a reduction depends on one more: the look ahead token)
+    positions: Vec<usize>,
+    consumed: usize,
+    checkpointed: Option<Arc<Checkpointed<T>>>,
+    last_error_state: Option<u32>,
+    max_depth: usize,
+}
+
+impl<T, N, A> ParseStack<T, N, A>
+where
+    T: Copy + Ord + Debug + Display,
+{
+    /// A stack in the parser's initial configuration
+    pub fn new() -> Self {
+        Self {
+            states: vec![(Symbol::Start, 0)],
+            attributes: vec![],
+            spans: None,
+            positions: vec![0],
+            consumed: 0,
+            checkpointed: None,
+            last_error_state: None,
+            max_depth: 1,
         }
+    }
 
-        fn next_action(
-            &self,
-            state: u32,
-            attributes: &crate::ParseStack<Terminal, NonTerminal, AttributeData>,
-            token: &lexan::Token<Terminal>,
-        ) -> crate::Action {
-            use crate::Action;
-            use Terminal::*;
-            let tag = *token.tag();
-            return match state {
-                0 => match tag {
-                    Minus | LPR | Number | Id => Action::Reduce(8),
-                    _ => Action::SyntaxError,
-                },
-                1 => match tag {
-                    EndMarker => Action::Accept,
-                    EOL => Action::Shift(4),
-                    _ => Action::SyntaxError,
-                },
-                2 => match tag {
-                    Minus => Action::Shift(8),
-                    LPR => Action::Shift(7),
-                    Number => Action::Shift(9),
-                    Id => Action::Shift(6),
-                    _ => Action::SyntaxError,
-                },
-                3 => match tag {
-                    EndMarker | EOL => Action::Reduce(7),
-                    _ => Action::SyntaxError,
-                },
-                4 => match tag {
-                    EndMarker | EOL => Action::Reduce(6),
-                    Minus | Number | Id | LPR => Action::Reduce(8),
-                    _ => Action::SyntaxError,
-                },
-                5 => match tag {
-                    Plus => Action::Shift(11),
-                    Minus => Action::Shift(12),
-                    Times => Action::Shift(13),
-                    Divide => Action::Shift(14),
-                    EndMarker | EOL => {
-                        if self.errors > 0 {
-                            Action::Reduce(1)
-                        } else {
-                            Action::Reduce(2)
-                        }
-                    }
-                    _ => Action::SyntaxError,
-                },
-                6 => match tag {
-                    Assign => Action::Shift(15),
-                    EndMarker | EOL | Plus | Minus | Times | Divide => {
-                        if self
-                            .variables
-                            .contains_key(&attributes.at_len_minus_n(2 - 1).id)
-                        {
-                            Action::Reduce(26)
-                        } else {
-                            Action::Reduce(27)
-                        }
-                    }
-                    _ => Action::SyntaxError,
-                },
-                7 | 8 => match tag {
-                    Minus => Action::Shift(8),
-                    LPR => Action::Shift(7),
-                    Number => Action::Shift(9),
-                    Id => Action::Shift(17),
-                    _ => Action::SyntaxError,
-                },
-                9 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(25),
-                    _ => Action::SyntaxError,
-                },
-                10 => match tag {
-                    EndMarker | EOL => Action::Reduce(5),
-                    _ => Action::SyntaxError,
-                },
-                11 | 12 | 13 | 14 | 15 => match tag {
-                    Minus => Action::Shift(8),
-                    LPR => Action::Shift(7),
-                    Number => Action::Shift(9),
-                    Id => Action::Shift(17),
-                    _ => Action::SyntaxError,
-                },
-                16 => match tag {
-                    Plus => Action::Shift(11),
-                    Minus => Action::Shift(12),
-                    Times => Action::Shift(13),
-                    Divide => Action::Shift(14),
-                    RPR => Action::Shift(24),
-                    _ => Action::SyntaxError,
-                },
-                17 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
-                        if self
-                            .variables
-                            .contains_key(&attributes.at_len_minus_n(2 - 1).id)
-                        {
-                            Action::Reduce(26)
-                        } else {
-                            Action::Reduce(27)
-                        }
-                    }
-                    _ => Action::SyntaxError,
-                },
-                18 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(24),
-                    _ => Action::SyntaxError,
-                },
-                19 => match tag {
-                    Times => Action::Shift(13),
-                    Divide => Action::Shift(14),
-                    EndMarker | EOL | Plus | Minus | RPR => {
-                        if attributes.at_len_minus_n(4 - 1).value == 0.0 {
-                            Action::Reduce(9)
-                        } else if attributes.at_len_minus_n(4 - 3).value == 0.0 {
-                            Action::Reduce(10)
-                        } else {
-                            Action::Reduce(11)
-                        }
+    fn with_spans() -> Self {
+        Self {
+            spans: Some(vec![]),
+            ..Self::new()
+        }
+    }
+
+    // In the initial configuration for the start symbol whose start state is `state`
+    fn starting_in(mut self, state: u32) -> Self {
+        self.states[0].1 = state;
+        self
+    }
+
+    /// The greatest number of states that this stack has held
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    fn note_depth(&mut self) {
+        self.max_depth = self.max_depth.max(self.states.len());
+    }
+
+    fn current_state(&self) -> u32 {
+        self.states.last().unwrap().1
+    }
+
+    /// The symbols and states on the stack (from the bottom up) followed by `history`
+    /// (the most recently shifted tokens), e.g. for diagnosing a malformed table.
+    pub fn description(&self, history: &VecDeque<String>) -> String
+    where
+        N: Display,
+    {
+        let mut text = "Parse stack:".to_string();
+        for (symbol, state) in self.states.iter() {
+            text += &format!(" {symbol}@{state}");
+        }
+        text += "\nRecent tokens:";
+        for token in history.iter() {
+            text += &format!(" {token}");
+        }
+        text
+    }
+
+    /// The (symbol, state) frames on the stack from the bottom up or, if `limit` is given,
+    /// just the top `limit` of them, e.g. to see why the parser expected what it did.
+    pub fn state_path(&self, limit: Option<usize>) -> Vec<(Symbol<T, N>, u32)>
+    where
+        N: Clone,
+    {
+        let skip = limit.map_or(0, |limit| self.states.len().saturating_sub(limit));
+        self.states[skip..].to_vec()
+    }
+
+    // The top `limit` frames in the same form as `description()`
+    fn state_path_text(&self, limit: usize) -> String
+    where
+        N: Display,
+    {
+        let skip = self.states.len().saturating_sub(limit);
+        let mut text = "State path:".to_string();
+        if skip > 0 {
+            text += " ...";
+        }
+        for (symbol, state) in self.states[skip..].iter() {
+            text += &format!(" {symbol}@{state}");
+        }
+        text
+    }
+
+    pub fn at_len_minus_n(&self, n: usize) -> &A {
+        let len = self.attributes.len();
+        &self.attributes[len - n]
+    }
+
+    /// As for `at_len_minus_n()` but `None` (rather than a panic) if there aren't `n`
+    /// attributes on the stack.
+    pub fn try_at_len_minus_n(&self, n: usize) -> Option<&A> {
+        let len = self.attributes.len();
+        len.checked_sub(n)
+            .and_then(|index| self.attributes.get(index))
+    }
+
+    /// The number of tokens (including any pieces of unexpected text) consumed from the
+    /// start of the text when the top state was pushed.
+    pub fn token_count(&self) -> usize {
+        *self.positions.last().unwrap()
+    }
+
+    // Pop the states that depend on more than the first `tokens` tokens of the text
+    fn truncate_to_trusted(&mut self, tokens: usize) {
+        let trusted = self
+            .states
+            .iter()
+            .zip(self.positions.iter())
+            .take_while(|((symbol, _), position)| match symbol {
+                Symbol::Start => true,
+                Symbol::Terminal(_) => **position <= tokens,
+                Symbol::NonTerminal(_) | Symbol::Error => **position < tokens,
+            })
+            .count();
+        self.pop_n(self.states.len() - trusted);
+    }
+
+    fn pop_n(&mut self, n: usize) -> Vec<A> {
+        let len = self.states.len();
+        self.states.truncate(len - n);
+        self.positions.truncate(len - n);
+        let len = self.attributes.len();
+        if let Some(spans) = &mut self.spans {
+            spans.truncate(len - n);
+        }
+        self.attributes.split_off(len - n)
+    }
+
+    // The span of the top `n` attributes' text (or an empty span at `location`
+    // if `n` is zero) if spans are being kept
+    fn span_of_top_n(&self, n: usize, location: &lexan::Location) -> Option<lexan::Span> {
+        let spans = self.spans.as_ref()?;
+        if n == 0 {
+            Some(lexan::Span::empty_at(location))
+        } else {
+            let start = spans[spans.len() - n].start.clone();
+            let end = spans[spans.len() - 1].end.clone();
+            Some(lexan::Span::new(start, end))
+        }
+    }
+
+    fn push_span(&mut self, span: lexan::Span) {
+        if let Some(spans) = &mut self.spans {
+            spans.push(span);
+        }
+    }
+
+    // The span of an error symbol that replaces the top `n` symbols because of an error
+    // at `span` (if spans are being kept)
+    fn error_span(&self, n: usize, span: lexan::Span) -> Option<lexan::Span> {
+        let start = self.span_of_top_n(n, &span.start)?.start;
+        Some(lexan::Span::new(start, span.end))
+    }
+
+    fn push_error(&mut self, state: u32, attribute: A) {
+        self.positions.push(self.consumed);
+        self.states.push((Symbol::Error, state));
+        self.attributes.push(attribute);
+        self.note_depth();
+    }
+
+    fn push_terminal(&mut self, tag: T, attribute: A, new_state: u32) {
+        self.positions.push(self.consumed);
+        self.states.push((Symbol::Terminal(tag), new_state));
+        self.attributes.push(attribute);
+        self.note_depth();
+    }
+
+    fn push_non_terminal(&mut self, non_terminal: N, attribute: A, new_state: u32) {
+        self.attributes.push(attribute);
+        self.positions.push(self.consumed);
+        self.states
+            .push((Symbol::NonTerminal(non_terminal), new_state));
+        self.note_depth();
+    }
+
+    /// Forget the state that the last error recovery resumed in so that it may be
+    /// chosen again (which it can't otherwise be, so that recovery can't loop).
+    pub fn clear_last_error_state(&mut self) {
+        self.last_error_state = None;
+    }
+
+    fn is_last_error_state(&self, state: u32) -> bool {
+        if let Some(last_error_state) = self.last_error_state {
+            state == last_error_state
+        } else {
+            false
+        }
+    }
+
+    // `None` if the tokens to decide with have yet to arrive
+    fn distance_to_viable_state<F: Fn(&T) -> BTreeSet<u32>>(
+        &mut self,
+        tokens: &mut dyn TokenSource<T>,
+        viable_error_recovery_states: F,
+    ) -> Option<Option<usize>> {
+        while !tokens.is_empty() {
+            if tokens.is_pending() {
+                return None;
+            }
+            if let Ok(token) = tokens.front() {
+                let viable_states = viable_error_recovery_states(token.tag());
+                for sub in 1..=self.states.len() {
+                    let candidate = self.states[self.states.len() - sub].1;
+                    if !self.is_last_error_state(candidate) && viable_states.contains(&candidate) {
+                        self.last_error_state = Some(candidate);
+                        return Some(Some(sub - 1));
                     }
-                    _ => Action::SyntaxError,
-                },
-                20 => match tag {
-                    Times => Action::Shift(13),
-                    Divide => Action::Shift(14),
-                    EndMarker | EOL | Plus | Minus | RPR => {
-                        if attributes.at_len_minus_n(4 - 1).value == 0.0 {
-                            Action::Reduce(12)
-                        } else if attributes.at_len_minus_n(4 - 3).value == 0.0 {
-                            Action::Reduce(13)
-                        } else {
-                            Action::Reduce(14)
-                        }
+                }
+            };
+            tokens.advance();
+        }
+        Some(None)
+    }
+}
+
+impl<T, N, A> Default for ParseStack<T, N, A>
+where
+    T: Copy + Ord + Debug + Display,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Copies of the parse stack taken during a parse for `Parser::reparse()`
+pub type Checkpoints<T, N, A> = Vec<ParseStack<T, N, A>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Shift(u32),
+    Reduce(u32),
+    Accept,
+    SyntaxError,
+    /// A predicate found the reduction that would otherwise be made to be semantically
+    /// invalid (see `%reject`): treated as a syntax error but with this message.
+    SemanticError(&'static str),
+    /// The (generated) tables have no entries for the state.
+    IllegalState,
+}
+
+// The state stacks that result from shifting `tag` onto `states` (following any
+// reductions) and whether `tag` can be accepted instead.
+fn shift_outcomes<T, N, P, D, G>(
+    states: Vec<u32>,
+    tag: &T,
+    possible_actions: P,
+    production_data: D,
+    goto_state: G,
+) -> (Vec<Vec<u32>>, bool)
+where
+    T: Ord,
+    P: Fn(u32, &T) -> Vec<Action>,
+    D: Fn(u32) -> (N, usize),
+    G: Fn(&N, u32) -> u32,
+{
+    let mut shifted = vec![];
+    let mut accepted = false;
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![states];
+    while let Some(states) = pending.pop() {
+        if !seen.insert(states.clone()) {
+            continue;
+        }
+        let current = *states.last().expect("stack is never empty");
+        for action in possible_actions(current, tag) {
+            match action {
+                Action::Shift(next_state) => {
+                    let mut states = states.clone();
+                    states.push(next_state);
+                    shifted.push(states);
+                }
+                Action::Reduce(production_id) => {
+                    let (lhs, rhs_len) = production_data(production_id);
+                    if rhs_len < states.len() {
+                        let mut states = states.clone();
+                        states.truncate(states.len() - rhs_len);
+                        let next_state = goto_state(&lhs, *states.last().unwrap());
+                        states.push(next_state);
+                        pending.push(states);
                     }
-                    _ => Action::SyntaxError,
-                },
-                21 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
-                        if attributes.at_len_minus_n(4 - 1).value == 0.0
-                            || attributes.at_len_minus_n(4 - 3).value == 0.0
-                        {
-                            Action::Reduce(15)
-                        } else if attributes.at_len_minus_n(4 - 1).value == 1.0 {
-                            Action::Reduce(16)
-                        } else if attributes.at_len_minus_n(4 - 3).value == 1.0 {
-                            Action::Reduce(17)
-                        } else {
-                            Action::Reduce(18)
-                        }
+                }
+                Action::Accept => accepted = true,
+                Action::SyntaxError | Action::SemanticError(_) | Action::IllegalState => (),
+            }
+        }
+    }
+    (shifted, accepted)
+}
+
+/// The tokens shifted and the productions reduced by a parse as a tree (e.g. as built by
+/// a `ParseTreeBuilder`): each node being a non terminal along with the production that
+/// it was reduced by and the trees of that production's symbols.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseTree<T: Copy + Display + Eq, N, L = lexan::Location> {
+    Leaf(lexan::Token<T, L>),
+    Node(N, u32, Vec<ParseTree<T, N, L>>),
+}
+
+/// Builds the `ParseTree` of a parse from its events (as its `ParseObserver`).
+pub struct ParseTreeBuilder<T: Copy + Display + Eq, N, L = lexan::Location> {
+    production_data: fn(u32) -> (N, usize),
+    trees: Vec<ParseTree<T, N, L>>,
+    failed: bool,
+}
+
+impl<T: Copy + Display + Eq, N, L> ParseTreeBuilder<T, N, L> {
+    /// A builder for the trees of a parser whose `Parser::production_data()` is
+    /// `production_data`.
+    pub fn new(production_data: fn(u32) -> (N, usize)) -> Self {
+        Self {
+            production_data,
+            trees: vec![],
+            failed: false,
+        }
+    }
+
+    /// The tree of the last parse observed if it was accepted without errors (and the
+    /// builder is then ready for another parse).
+    pub fn take_tree(&mut self) -> Option<ParseTree<T, N, L>> {
+        let tree = (!self.failed && self.trees.len() == 1).then(|| self.trees.pop());
+        self.trees.clear();
+        self.failed = false;
+        tree.flatten()
+    }
+}
+
+impl<T: Ord + Copy + Debug + Display + Eq, N, L: Display + Clone> ParseObserver<T, N, L>
+    for ParseTreeBuilder<T, N, L>
+{
+    fn on_shift(&mut self, token: &lexan::Token<T, L>, _span: &lexan::Span<L>) {
+        self.trees.push(ParseTree::Leaf(token.clone()));
+    }
+
+    fn on_reduce(&mut self, _lhs: &N, production_id: u32, _span: &lexan::Span<L>) {
+        let (lhs, rhs_len) = (self.production_data)(production_id);
+        let children = self
+            .trees
+            .split_off(self.trees.len().saturating_sub(rhs_len));
+        self.trees
+            .push(ParseTree::Node(lhs, production_id, children));
+    }
+
+    fn on_error(&mut self, _error: &Error<T, L>, _recovered: bool) {
+        self.failed = true;
+    }
+}
+
+/// The associativity of an operator (see `ProductionShape`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    NonAssoc,
+    Left,
+    Right,
+}
+
+/// How `unparse()` treats the nodes of a production (see `alap_gen_ng --unparser`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductionShape {
+    /// Its symbols in order (e.g. `Expr: NUMBER`)
+    Plain,
+    /// Its non terminal (the same as its left hand side) between a pair of parentheses
+    /// (e.g. `Expr: "(" Expr ")"`) that are kept only where needed
+    Parenthesized,
+    /// An operator of the given associativity and precedence between two operands of the
+    /// same non terminal as its left hand side (e.g. `Expr: Expr "+" Expr`)
+    Infix(Associativity, u32),
+    /// An operator with the given precedence before such an operand (e.g.
+    /// `Expr: "-" Expr %prec UMINUS`)
+    Prefix(u32),
+}
+
+/// Render `tree` back into text (the lexemes of its tokens separated by spaces) without
+/// any of the parentheses that the precedences and associativities of its operators make
+/// redundant.  The shape of each production is given by `shape` (e.g. the generated
+/// parser's `aa_production_shape()`).
+pub fn unparse<T: Copy + Display + Eq, N, L>(
+    tree: &ParseTree<T, N, L>,
+    shape: &dyn Fn(u32) -> ProductionShape,
+) -> String {
+    let mut text = String::new();
+    unparse_into(&mut text, tree, shape, None);
+    text
+}
+
+// The side of its operator that an operand is on
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+// Append the text of `tree` to `text` as (one of) the operand(s) of an operator with the
+// given associativity and precedence on its `side` (if it's an operand)
+fn unparse_into<T: Copy + Display + Eq, N, L>(
+    text: &mut String,
+    tree: &ParseTree<T, N, L>,
+    shape: &dyn Fn(u32) -> ProductionShape,
+    operand_of: Option<(Associativity, u32, Side)>,
+) {
+    let (production_id, children) = match tree {
+        ParseTree::Leaf(token) => {
+            if !token.lexeme().is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(token.lexeme());
+            }
+            return;
+        }
+        ParseTree::Node(_, production_id, children) => (*production_id, children),
+    };
+    match (shape(production_id), children.as_slice()) {
+        (ProductionShape::Parenthesized, [open, operand, close]) => {
+            if needs_parentheses(operand, shape, operand_of) {
+                unparse_into(text, open, shape, None);
+                unparse_into(text, operand, shape, None);
+                unparse_into(text, close, shape, None);
+            } else {
+                unparse_into(text, operand, shape, operand_of);
+            }
+        }
+        (ProductionShape::Infix(associativity, precedence), [left, operator, right]) => {
+            unparse_into(
+                text,
+                left,
+                shape,
+                Some((associativity, precedence, Side::Left)),
+            );
+            unparse_into(text, operator, shape, None);
+            unparse_into(
+                text,
+                right,
+                shape,
+                Some((associativity, precedence, Side::Right)),
+            );
+        }
+        (ProductionShape::Prefix(precedence), [operator, operand]) => {
+            unparse_into(text, operator, shape, None);
+            let operand_of = Some((Associativity::Right, precedence, Side::Right));
+            unparse_into(text, operand, shape, operand_of);
+        }
+        _ => {
+            for child in children.iter() {
+                unparse_into(text, child, shape, None);
+            }
+        }
+    }
+}
+
+// Whether `tree` needs parentheses as (one of) the operand(s) of an operator (if any)
+fn needs_parentheses<T: Copy + Display + Eq, N, L>(
+    tree: &ParseTree<T, N, L>,
+    shape: &dyn Fn(u32) -> ProductionShape,
+    operand_of: Option<(Associativity, u32, Side)>,
+) -> bool {
+    let (associativity, precedence) = match tree {
+        ParseTree::Node(_, production_id, _) => match shape(*production_id) {
+            ProductionShape::Infix(associativity, precedence) => (associativity, precedence),
+            ProductionShape::Prefix(precedence) => (Associativity::Right, precedence),
+            // any parentheses of its own are kept if needed
+            _ => return false,
+        },
+        ParseTree::Leaf(_) => return false,
+    };
+    match operand_of {
+        Some((outer_associativity, outer_precedence, side)) => {
+            precedence < outer_precedence
+                || (precedence == outer_precedence
+                    && !matches!(
+                        (outer_associativity, associativity, side),
+                        (Associativity::Left, Associativity::Left, Side::Left)
+                            | (Associativity::Right, Associativity::Right, Side::Right)
+                    ))
+        }
+        None => false,
+    }
+}
+
+/// A parser whose errors' locations are of type `L` (see `location()`).
+pub trait Parser<T: Ord + Copy + Debug, N, A, L = lexan::Location>
+where
+    T: Ord + Copy + Debug + Display,
+    N: Ord + Display + Debug,
+    A: Default + From<lexan::Token<T>> + From<Error<T, L>>,
+    L: Display + Clone + From<lexan::Location>,
+    Self: ReportError<T, L>,
+{
+    fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<T>;
+    /// The attribute for a token that is being shifted.  Parsers whose tokens' attributes
+    /// need more than the token (e.g. an `Interner`) override this.
+    fn token_attribute(&mut self, token: lexan::Token<T>) -> A {
+        A::from(token)
+    }
+
+    fn next_action(
+        &self,
+        state: u32,
+        attributes: &ParseStack<T, N, A>,
+        o_token: &lexan::Token<T>,
+    ) -> Action;
+
+    /// As for `next_action()` but failing with the state if the tables have no entries
+    /// for it, e.g. so that servers survive a corrupt table.
+    fn next_action_checked(
+        &self,
+        state: u32,
+        attributes: &ParseStack<T, N, A>,
+        token: &lexan::Token<T>,
+    ) -> Result<Action, u32> {
+        match self.next_action(state, attributes, token) {
+            Action::IllegalState => Err(state),
+            action => Ok(action),
+        }
+    }
+
+    fn production_data(production_id: u32) -> (N, usize);
+    fn goto_state(lhs: &N, current_state: u32) -> u32;
+    fn do_semantic_action<F: FnMut(String, String)>(
+        &mut self,
+        _production_id: u32,
+        _attributes: Vec<A>,
+        mut inject: F,
+    ) -> A {
+        // NB: required in order to cop with issue #35203
+        inject(String::new(), String::new());
+        // confirm multiple injects OK.
+        inject(String::new(), String::new());
+        A::default()
+    }
+
+    /// Like `do_semantic_action` but also given the look ahead token that triggered the
+    /// reduction.  The default ignores the token and calls `do_semantic_action`.
+    fn do_semantic_action_la<F: FnMut(String, String)>(
+        &mut self,
+        production_id: u32,
+        attributes: Vec<A>,
+        _look_ahead: &lexan::Token<T>,
+        inject: F,
+    ) -> A {
+        self.do_semantic_action(production_id, attributes, inject)
+    }
+
+    /// Map an action's (empty text) injection label to the lexical mode it requests
+    /// a switch to, if any.  The default (for analysers without modes) is `None`.
+    fn lexical_mode(_label: &str) -> Option<usize> {
+        None
+    }
+
+    /// Whether the lexical analyzer's skip rules (`%skip`) are to be suspended for the
+    /// next token, e.g. from a flag set by semantic actions.  This is consulted (along
+    /// with `skips_suppressed_in()`) whenever a token is shifted, before the token stream
+    /// advances, and so affects the token after the new look ahead (which has already
+    /// been read).  The default never suppresses them.
+    fn suppress_skips(&self) -> bool {
+        false
+    }
+
+    /// Whether the skip rules are suspended for the token read on shifting into `state`
+    /// (see `%noskip`).  The default (for parsers generated without it) is `false`.
+    fn skips_suppressed_in(_state: u32) -> bool {
+        false
+    }
+
+    /// Advance `tokens` past a token that has been shifted into `state` with the skip
+    /// rules applied (or not) as `suppress_skips()` and `skips_suppressed_in()` decree.
+    fn advance_into(&self, state: u32, tokens: &mut dyn TokenSource<T>) {
+        tokens.set_skipping(!(self.suppress_skips() || Self::skips_suppressed_in(state)));
+        tokens.advance();
+    }
+
+    /// The interner used by `intern()` (see `%intern`), if any.
+    fn interner(&mut self) -> Option<&mut Interner> {
+        None
+    }
+
+    fn intern(&mut self, text: &str) -> Interned {
+        self.interner()
+            .expect("parser has no interner (see %intern)")
+            .intern(text)
+    }
+
+    /// Where the productions reduced by this parser are recorded (see `%coverage`), if anywhere.
+    #[cfg(feature = "coverage")]
+    fn coverage(&self) -> Option<&BitSet> {
+        None
+    }
+
+    #[cfg(feature = "coverage")]
+    fn coverage_mut(&mut self) -> Option<&mut BitSet> {
+        None
+    }
+
+    /// The ids of the productions that have been reduced (by all parses).
+    #[cfg(feature = "coverage")]
+    fn covered_productions(&self) -> &BitSet {
+        self.coverage()
+            .expect("parser has no coverage set (see %coverage)")
+    }
+
+    /// Where the productions reduced by the last parse are recorded (see `%derivation`), if anywhere.
+    #[cfg(feature = "derivation")]
+    fn derivation_mut(&mut self) -> Option<&mut Vec<u32>> {
+        None
+    }
+
+    /// The ids of the productions reduced by the last parse in the order that they were
+    /// reduced, i.e. the rightmost derivation of its text read in reverse.
+    #[cfg(feature = "derivation")]
+    fn derivation(&self) -> &[u32] {
+        &[]
+    }
+
+    fn viable_error_recovery_states(tag: &T) -> BTreeSet<u32>;
+
+    fn error_goto_state(state: u32) -> u32 {
+        panic!("No error go to state for {state}")
+    }
+
+    fn look_ahead_set(state: u32) -> BTreeSet<T>;
+
+    /// All of the actions that might be taken in `state` for `tag` if predicates are
+    /// ignored.  The default (for parsers generated without it) knows of none.
+    fn possible_actions(_state: u32, _tag: &T) -> Vec<Action> {
+        vec![]
+    }
+
+    /// The terminal sequences, of at most `max_len` terminals, that could validly follow
+    /// the configuration in `stack`: those after which the input could end and those of
+    /// length `max_len` that are still viable.  Shortest sequences come first and
+    /// predicates are ignored.
+    fn completions(&self, stack: &ParseStack<T, N, A>, max_len: usize) -> Vec<Vec<T>> {
+        let mut completions = BTreeSet::new();
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        let states: Vec<u32> = stack.states.iter().map(|(_, state)| *state).collect();
+        queue.push_back((states, vec![]));
+        while let Some((states, sequence)) = queue.pop_front() {
+            if sequence.len() == max_len {
+                completions.insert(sequence);
+                continue;
+            }
+            let current = *states.last().expect("stack is never empty");
+            for tag in Self::look_ahead_set(current) {
+                let (shifted, accepted) = shift_outcomes(
+                    states.clone(),
+                    &tag,
+                    Self::possible_actions,
+                    Self::production_data,
+                    Self::goto_state,
+                );
+                if accepted {
+                    completions.insert(sequence.clone());
+                }
+                for states in shifted {
+                    let mut sequence = sequence.clone();
+                    sequence.push(tag);
+                    if seen.insert((states.clone(), sequence.clone())) {
+                        queue.push_back((states, sequence));
                     }
-                    _ => Action::SyntaxError,
-                },
-                22 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
-                        if attributes.at_len_minus_n(4 - 1).value == 0.0
-                            || attributes.at_len_minus_n(4 - 3).value == 0.0
-                        {
-                            Action::Reduce(19)
-                        } else if attributes.at_len_minus_n(4 - 1).value == 1.0 {
-                            Action::Reduce(20)
-                        } else if attributes.at_len_minus_n(4 - 3).value == 1.0 {
-                            Action::Reduce(21)
-                        } else {
-                            Action::Reduce(22)
+                }
+            }
+        }
+        let mut completions: Vec<Vec<T>> = completions.into_iter().collect();
+        completions.sort_by_key(|sequence| sequence.len());
+        completions
+    }
+
+    /// The terminal (if any) whose insertion before a token tagged `tag` would let that
+    /// token be shifted (or accepted) following the configuration in `stack`.
+    /// Predicates are ignored.
+    fn viable_insertion(&self, stack: &ParseStack<T, N, A>, tag: &T) -> Option<T> {
+        let end_marker = self.lexical_analyzer().end_marker();
+        let states: Vec<u32> = stack.states.iter().map(|(_, state)| *state).collect();
+        Self::look_ahead_set(stack.current_state())
+            .into_iter()
+            .filter(|candidate| *candidate != end_marker)
+            .find(|candidate| {
+                let (shifted, _) = shift_outcomes(
+                    states.clone(),
+                    candidate,
+                    Self::possible_actions,
+                    Self::production_data,
+                    Self::goto_state,
+                );
+                shifted.into_iter().any(|states| {
+                    let (shifted, accepted) = shift_outcomes(
+                        states,
+                        tag,
+                        Self::possible_actions,
+                        Self::production_data,
+                        Self::goto_state,
+                    );
+                    accepted || !shifted.is_empty()
+                })
+            })
+    }
+
+    /// Recover from `error` (which occurred at `span`) if possible: `None` if the
+    /// tokens to decide with have yet to arrive.
+    fn recover_from_error(
+        error: Error<T, L>,
+        span: lexan::Span,
+        parse_stack: &mut ParseStack<T, N, A>,
+        tokens: &mut dyn TokenSource<T>,
+    ) -> Option<bool> {
+        if let Some(distance) = parse_stack
+            .distance_to_viable_state(tokens, |t| Self::viable_error_recovery_states(t))?
+        {
+            let span = parse_stack.error_span(distance, span);
+            parse_stack.pop_n(distance);
+            parse_stack.consumed = tokens.consumed();
+            let next_state = Self::error_goto_state(parse_stack.current_state());
+            parse_stack.push_error(next_state, A::from(error));
+            if let Some(span) = span {
+                parse_stack.push_span(span);
+            }
+            Some(true)
+        } else {
+            Some(false)
+        }
+    }
+
+    /// How `terminal` is shown in error messages, e.g. the end marker as "end of input".
+    fn display_terminal(&self, terminal: &T) -> String {
+        terminal.to_string()
+    }
+
+    /// The location reported in errors for `location` (one of the lexical analyzer's),
+    /// e.g. a position within the virtual document that the text was taken from.
+    fn location(&self, location: &lexan::Location) -> L {
+        L::from(location.clone())
+    }
+
+    /// The message for `error` (with its terminals shown by `display_terminal()`).
+    fn error_message(&self, error: &Error<T, L>) -> String {
+        error.message(|terminal| self.display_terminal(terminal))
+    }
+
+    /// The number of frames from the top of the parse stack (see `ParseStack::state_path()`)
+    /// shown, on a line of their own, after the messages of the errors reported during
+    /// parses.  The default (0) shows none.
+    fn reported_state_path_len(&self) -> usize {
+        0
+    }
+
+    // The message for `error` as reported (i.e. with any state path)
+    fn reported_message(&self, error: &Error<T, L>, parse_stack: &ParseStack<T, N, A>) -> String {
+        let message = self.error_message(error);
+        match self.reported_state_path_len() {
+            0 => message,
+            limit => format!("{message}\n{}", parse_stack.state_path_text(limit)),
+        }
+    }
+
+    /// The number of consecutive pieces of unexpected text that are skipped (without
+    /// disturbing the parse stack) before resorting to error recovery.
+    fn lexical_skip_limit(&self) -> usize {
+        3
+    }
+
+    /// How syntax errors are recovered from: inserting tokens relies on the tables'
+    /// `possible_actions()` and the attributes of inserted tokens are derived from the
+    /// error.
+    fn recovery_strategy(&self) -> RecoveryStrategy {
+        RecoveryStrategy::DeleteUntilViable
+    }
+
+    /// The greatest depth that the parse stack may reach (if limited): a parse that
+    /// would go deeper gets an `Error::StackOverflow` (and attempts error recovery).
+    fn max_stack_depth(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the parse stack and recently shifted tokens are printed should a table
+    /// lookup panic (see `--diagnose-panics`).
+    fn diagnose_panics(&self) -> bool {
+        false
+    }
+
+    /// The observer (if any) to be notified of the events of parses.
+    fn observer(&mut self) -> Option<&mut dyn ParseObserver<T, N, L>> {
+        None
+    }
+
+    /// Whether `token` marks the end of the input: by default, whether it is the
+    /// lexical analyzer's end marker.  Tokens for which this is true are treated
+    /// as if they were the end marker.
+    fn is_end_of_input(&self, token: &lexan::Token<T>) -> bool {
+        *token.tag() == self.lexical_analyzer().end_marker()
+    }
+
+    /// Called before the parse of the text labelled `label` begins.
+    fn before_parse(&mut self, _label: &str) {}
+
+    /// Called with the outcome of the parse of the text labelled `label`.
+    fn after_parse(&mut self, _label: &str, _outcome: &ParseOutcome<T, L>) {}
+
+    /// Apply `actions` (without semantic actions or error recovery) to `tokens` for testing
+    /// the parse tables.  Fails with the index of the first action that differs from that
+    /// chosen by the tables and the table's choice.  Stops after an accept or syntax error.
+    fn drive(
+        &self,
+        actions: &[Action],
+        tokens: &mut lexan::TokenStream<T>,
+    ) -> Result<ParseStack<T, N, A>, (usize, Action)> {
+        let mut parse_stack = ParseStack::<T, N, A>::new();
+        for (index, action) in actions.iter().enumerate() {
+            let front = tokens.front().map(|token| {
+                if self.is_end_of_input(&token) {
+                    token.with_tag(self.lexical_analyzer().end_marker())
+                } else {
+                    token
+                }
+            });
+            let table_action = match front {
+                Ok(ref token) => self.next_action(parse_stack.current_state(), &parse_stack, token),
+                Err(_) => Action::SyntaxError,
+            };
+            if table_action != *action {
+                return Err((index, table_action));
+            }
+            match table_action {
+                Action::Shift(next_state) => {
+                    let tag = *front.expect("not an error").tag();
+                    parse_stack.push_terminal(tag, A::default(), next_state);
+                    self.advance_into(next_state, tokens);
+                }
+                Action::Reduce(production_id) => {
+                    let (lhs, rhs_len) = Self::production_data(production_id);
+                    parse_stack.pop_n(rhs_len);
+                    let next_state = Self::goto_state(&lhs, parse_stack.current_state());
+                    parse_stack.push_non_terminal(lhs, A::default(), next_state);
+                }
+                Action::Accept
+                | Action::SyntaxError
+                | Action::SemanticError(_)
+                | Action::IllegalState => break,
+            }
+        }
+        Ok(parse_stack)
+    }
+
+    /// An explanation, for the users of the parser, of why `text` fails to parse: what had
+    /// been parsed (the symbols on the stack), what was found and where, what was expected
+    /// and some ways in which the text could have continued.  The text is parsed as for
+    /// `parse_text()` but without error recovery so it is the first error that is explained.
+    fn explain_failure(&mut self, text: &str, label: &str) -> String {
+        let mut tokens = self.lexical_analyzer().token_stream(text, label);
+        let mut parse_stack = ParseStack::<T, N, A>::new();
+        let (error, found) = loop {
+            let front = tokens.front().map(|token| {
+                if self.is_end_of_input(&token) {
+                    token.with_tag(self.lexical_analyzer().end_marker())
+                } else {
+                    token
+                }
+            });
+            let expected = Self::look_ahead_set(parse_stack.current_state());
+            match front {
+                Err(err) => {
+                    let found = err.to_string();
+                    break (Error::LexicalError(err, expected), found);
+                }
+                Ok(token) => {
+                    match self.next_action(parse_stack.current_state(), &parse_stack, &token) {
+                        Action::Accept => return format!("{label}: parses without errors."),
+                        Action::Shift(next_state) => {
+                            let tag = *token.tag();
+                            let attribute = self.token_attribute(token);
+                            parse_stack.push_terminal(tag, attribute, next_state);
+                            self.advance_into(next_state, &mut tokens);
+                        }
+                        Action::Reduce(production_id) => {
+                            let (lhs, rhs_len) = Self::production_data(production_id);
+                            let rhs = parse_stack.pop_n(rhs_len);
+                            let next_state = Self::goto_state(&lhs, parse_stack.current_state());
+                            let attribute =
+                                self.do_semantic_action_la(production_id, rhs, &token, |s, l| {
+                                    match Self::lexical_mode(&l) {
+                                        Some(mode) if s.is_empty() => tokens.set_mode(mode),
+                                        _ => tokens.inject(&s, &l),
+                                    }
+                                });
+                            parse_stack.push_non_terminal(lhs, attribute, next_state);
+                        }
+                        Action::SemanticError(message) => {
+                            let found = format!(
+                                "{} \"{}\" at: {}",
+                                self.display_terminal(token.tag()),
+                                token.lexeme(),
+                                token.location()
+                            );
+                            let location = self.location(token.location());
+                            let token = token.with_location(location);
+                            break (
+                                Error::SemanticError(token, message.to_string(), expected),
+                                found,
+                            );
+                        }
+                        Action::SyntaxError | Action::IllegalState => {
+                            let span = token.span();
+                            let found = format!(
+                                "{} \"{}\" at: {} to {}",
+                                self.display_terminal(token.tag()),
+                                token.lexeme(),
+                                span.start,
+                                span.end
+                            );
+                            let location = self.location(token.location());
+                            let token = token.with_location(location);
+                            break (Error::SyntaxError(token, expected), found);
                         }
                     }
-                    _ => Action::SyntaxError,
-                },
-                23 => match tag {
-                    Plus => Action::Shift(11),
-                    Minus => Action::Shift(12),
-                    Times => Action::Shift(13),
-                    Divide => Action::Shift(14),
-                    EndMarker | EOL => {
-                        if self.errors == 0 {
-                            Action::Reduce(3)
-                        } else {
-                            Action::Reduce(4)
+                }
+            }
+        };
+        let parsed: Vec<String> = parse_stack.states[1..]
+            .iter()
+            .map(|(symbol, _)| match symbol {
+                Symbol::Terminal(tag) => self.display_terminal(tag),
+                _ => symbol.to_string(),
+            })
+            .collect();
+        let mut text = format!("{label}: failed to parse.\n");
+        if parsed.is_empty() {
+            text += "Parsed: nothing\n";
+        } else {
+            text += &format!("Parsed: {}\n", parsed.join(" "));
+        }
+        text += &format!("Found: {found}\n");
+        let display = |terminal: &T| self.display_terminal(terminal);
+        text += &format!("Expected: {}", format_set(error.expected(), &display));
+        let continuations: Vec<String> = self
+            .completions(&parse_stack, EXPLANATION_CONTINUATION_LENGTH)
+            .iter()
+            .filter(|sequence| !sequence.is_empty())
+            .take(EXPLANATION_CONTINUATIONS)
+            .map(|sequence| {
+                let terminals: Vec<String> = sequence.iter().map(display).collect();
+                terminals.join(" ")
+            })
+            .collect();
+        if !continuations.is_empty() {
+            text += &format!("\nCould continue with: {}", continuations.join("; "));
+        }
+        text
+    }
+
+    /// Parse `text` (as for `explain_failure()`, i.e. without error recovery) writing a line
+    /// to `out` for each action, e.g. "[^ Expr + NUMBER] reduce Expr: NUMBER", that shows
+    /// the symbols on the stack before it.  Errors writing to `out` are ignored.
+    #[cfg(feature = "std")]
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_trace(
+        &mut self,
+        text: &str,
+        label: &str,
+        out: &mut impl Write,
+    ) -> Result<(), Error<T, L>> {
+        let mut tokens = self.lexical_analyzer().token_stream(text, label);
+        let mut parse_stack = ParseStack::<T, N, A>::new();
+        let display = |parser: &Self, symbol: &Symbol<T, N>| match symbol {
+            Symbol::Terminal(tag) => parser.display_terminal(tag),
+            _ => symbol.to_string(),
+        };
+        loop {
+            let stack: Vec<String> = parse_stack
+                .states
+                .iter()
+                .map(|(symbol, _)| display(self, symbol))
+                .collect();
+            let stack = stack.join(" ");
+            let front = tokens.front().map(|token| {
+                if self.is_end_of_input(&token) {
+                    token.with_tag(self.lexical_analyzer().end_marker())
+                } else {
+                    token
+                }
+            });
+            let expected = Self::look_ahead_set(parse_stack.current_state());
+            let error = match front {
+                Err(err) => Error::LexicalError(err, expected),
+                Ok(token) => {
+                    match self.next_action(parse_stack.current_state(), &parse_stack, &token) {
+                        Action::Accept => {
+                            let _ = writeln!(out, "[{stack}] accept");
+                            return Ok(());
+                        }
+                        Action::Shift(next_state) => {
+                            let tag = *token.tag();
+                            let _ = writeln!(
+                                out,
+                                "[{stack}] shift {} \"{}\"",
+                                self.display_terminal(&tag),
+                                token.lexeme()
+                            );
+                            let attribute = self.token_attribute(token);
+                            parse_stack.push_terminal(tag, attribute, next_state);
+                            self.advance_into(next_state, &mut tokens);
+                            continue;
+                        }
+                        Action::Reduce(production_id) => {
+                            let (lhs, rhs_len) = Self::production_data(production_id);
+                            let mut reduction = format!("{lhs}:");
+                            let len = parse_stack.states.len();
+                            for (symbol, _) in parse_stack.states[len - rhs_len..].iter() {
+                                reduction += &format!(" {}", display(self, symbol));
+                            }
+                            let _ = writeln!(out, "[{stack}] reduce {reduction}");
+                            let rhs = parse_stack.pop_n(rhs_len);
+                            let next_state = Self::goto_state(&lhs, parse_stack.current_state());
+                            let attribute =
+                                self.do_semantic_action_la(production_id, rhs, &token, |s, l| {
+                                    match Self::lexical_mode(&l) {
+                                        Some(mode) if s.is_empty() => tokens.set_mode(mode),
+                                        _ => tokens.inject(&s, &l),
+                                    }
+                                });
+                            parse_stack.push_non_terminal(lhs, attribute, next_state);
+                            continue;
+                        }
+                        Action::SemanticError(message) => {
+                            let location = self.location(token.location());
+                            let found = token.with_location(location);
+                            Error::SemanticError(found, message.to_string(), expected)
+                        }
+                        Action::SyntaxError | Action::IllegalState => {
+                            let location = self.location(token.location());
+                            Error::SyntaxError(token.with_location(location), expected)
                         }
                     }
+                }
+            };
+            let _ = writeln!(out, "[{stack}] error: {}", self.error_message(&error));
+            return Err(error);
+        }
+    }
+
+    /// Parse `text` (labelled `label`), which is borrowed for the duration of the call
+    /// and copied for the lexical analyzer.  As tokens own their lexemes, the attributes
+    /// built from them never borrow from `text` and so outlive it (see `parse_string()`).
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text(&mut self, text: &str, label: &str) -> Result<(), Error<T, L>> {
+        self.parse_text_collect(text, label)
+            .map_err(|mut errors| errors.pop().expect("at least one"))
+    }
+
+    /// As for `parse_text()` but with the events of the parse told to `observer` rather
+    /// than to the parser's own `observer()` (if any).
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_observed(
+        &mut self,
+        text: &str,
+        label: &str,
+        observer: &mut dyn ParseObserver<T, N, L>,
+    ) -> Result<(), Error<T, L>> {
+        let tokens = self.lexical_analyzer().token_stream(text, label);
+        let (outcome, _, _) = self.parse_token_stream_observed(tokens, Some(observer));
+        outcome.into_result()
+    }
+
+    /// As for `parse_text()` but failing with all of the errors encountered (in order)
+    /// rather than just the last of them.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_collect(&mut self, text: &str, label: &str) -> Result<(), Vec<Error<T, L>>> {
+        match self.parse_text_outcome(text, label).0 {
+            ParseOutcome::Clean => Ok(()),
+            ParseOutcome::Recovered(errors) | ParseOutcome::Failed(errors) => Err(errors),
+        }
+    }
+
+    /// As for `parse_text()` but taking ownership of `text` so that the lexical analyzer
+    /// needn't copy it, e.g. for input that has been read into a `String` for the parse.
+    /// The attributes outlive `text` (which is dropped when the parse ends) just the same.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_string(&mut self, text: String, label: &str) -> Result<(), Error<T, L>> {
+        let tokens = self.lexical_analyzer().token_stream_owned(text, label);
+        self.parse_token_stream(tokens)
+    }
+
+    /// As for `parse_text()` but also returning the number of tokens shifted.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_counting_shifts(
+        &mut self,
+        text: &str,
+        label: &str,
+    ) -> (Result<(), Error<T, L>>, usize) {
+        let (outcome, statistics) = self.parse_text_outcome(text, label);
+        (outcome.into_result(), statistics.shift_count)
+    }
+
+    /// Parse `text` distinguishing parses that recovered from errors from those that
+    /// failed and also returning some measurements of the parse.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_outcome(
+        &mut self,
+        text: &str,
+        label: &str,
+    ) -> (ParseOutcome<T, L>, ParseStatistics) {
+        let (outcome, statistics, _) = self.parse_to_outcome(text, label);
+        (outcome, statistics)
+    }
+
+    /// As for `parse_text()` but starting in `start_state` (which must be the start state
+    /// of one of the grammar's start symbols) to parse `text` as that symbol.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_text_from(
+        &mut self,
+        start_state: u32,
+        text: &str,
+        label: &str,
+    ) -> Result<(), Error<T, L>> {
+        let tokens = self.lexical_analyzer().token_stream(text, label);
+        let (outcome, _, _) = self.parse_token_stream_observed_from(start_state, tokens, None);
+        outcome.into_result()
+    }
+
+    /// Parse `text` returning the attribute of the start symbol: e.g. for parsers
+    /// whose semantic actions build a value rather than update the target.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_to(&mut self, text: &str, label: &str) -> Result<A, Error<T, L>> {
+        let (outcome, _, attribute) = self.parse_to_outcome(text, label);
+        outcome.into_result().map(|_| attribute)
+    }
+
+    /// As for `parse_to()` but starting in `start_state` (see `parse_text_from()`).
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_to_from(
+        &mut self,
+        start_state: u32,
+        text: &str,
+        label: &str,
+    ) -> Result<A, Error<T, L>> {
+        let tokens = self.lexical_analyzer().token_stream(text, label);
+        let (outcome, _, attribute) =
+            self.parse_token_stream_observed_from(start_state, tokens, None);
+        outcome.into_result().map(|_| attribute)
+    }
+
+    /// As for `parse_text_outcome()` but also returning the attribute of the start
+    /// symbol.  For parses that recovered from errors this is the (partial) attribute
+    /// built by the semantic actions and for those that failed it is the default.
+    #[must_use = "the text may not have parsed successfully"]
+    fn parse_to_outcome(
+        &mut self,
+        text: &str,
+        label: &str,
+    ) -> (ParseOutcome<T, L>, ParseStatistics, A) {
+        let tokens = self.lexical_analyzer().token_stream(text, label);
+        self.parse_token_stream_to_outcome(tokens)
+    }
+
+    /// As for `parse_text_outcome()` but also returning checkpoints for `reparse()`:
+    /// copies of the parse stack at the start and after every `interval` (at least 1)
+    /// tokens up to the first error (if any).
+    fn parse_text_checkpointed(
+        &mut self,
+        text: &str,
+        label: &str,
+        interval: usize,
+    ) -> (ParseOutcome<T, L>, Checkpoints<T, N, A>)
+    where
+        N: Clone,
+        A: Clone,
+    {
+        let mut tokens = self.lexical_analyzer().token_stream(text, label);
+        tokens.record(vec![]);
+        let parse_stack = if self.observer().is_some() {
+            ParseStack::<T, N, A>::with_spans()
+        } else {
+            ParseStack::<T, N, A>::new()
+        };
+        self.parse_checkpointed(parse_stack, tokens, label, interval)
+    }
+
+    /// Parse text that has been parsed (at least as far as `old_stack`, one of the
+    /// checkpoints of that parse) but has since been edited so that `new_tail` follows
+    /// its first `unchanged_prefix_tokens` tokens (which mustn't be more than that parse
+    /// consumed).  The parse resumes after the last of `old_stack`'s states that depends
+    /// on those tokens alone, replaying any of them that came after it, so the semantic
+    /// actions before then aren't redone.  It suits parsers whose semantic actions
+    /// don't inject text or switch lexical modes and whose targets don't care about any
+    /// actions done for the old tail.
+    fn reparse(
+        &mut self,
+        mut old_stack: ParseStack<T, N, A>,
+        unchanged_prefix_tokens: usize,
+        new_tail: &str,
+    ) -> (ParseOutcome<T, L>, Checkpoints<T, N, A>)
+    where
+        N: Clone,
+        A: Clone,
+    {
+        let checkpointed = old_stack
+            .checkpointed
+            .take()
+            .expect("the old stack should be a checkpoint");
+        let prefix = &checkpointed.consumed[..unchanged_prefix_tokens];
+        old_stack.truncate_to_trusted(unchanged_prefix_tokens);
+        old_stack.clear_last_error_state();
+        let resumption = old_stack.token_count();
+        let location = match prefix.last() {
+            Some(Ok(token)) => token.span().end,
+            Some(Err(error)) => error.span().end,
+            None => lexan::Location::at(&checkpointed.label, 1, 1),
+        };
+        let mut tokens = self.lexical_analyzer().token_stream_at(new_tail, &location);
+        for item in prefix[resumption..].iter().rev() {
+            tokens.push_front(item.clone());
+        }
+        tokens.record(prefix[..resumption].to_vec());
+        let label = &checkpointed.label;
+        self.parse_checkpointed(old_stack, tokens, label, checkpointed.interval)
+    }
+
+    // Parse the (recorded) `tokens` from `parse_stack` keeping checkpoints for `reparse()`
+    fn parse_checkpointed(
+        &mut self,
+        parse_stack: ParseStack<T, N, A>,
+        mut tokens: TokenStream<T>,
+        label: &str,
+        interval: usize,
+    ) -> (ParseOutcome<T, L>, Checkpoints<T, N, A>)
+    where
+        N: Clone,
+        A: Clone,
+    {
+        assert!(interval > 0, "checkpoints need an interval of at least 1");
+        let mut checkpoints = vec![parse_stack.clone()];
+        let (outcome, _, _) =
+            self.parse_token_stream_resumed(parse_stack, &mut tokens, None, &mut |stack| {
+                if stack.token_count() % interval == 0 {
+                    checkpoints.push(stack.clone());
+                }
+            });
+        let checkpointed = Arc::new(Checkpointed {
+            label: label.to_string(),
+            interval,
+            consumed: tokens.take_recorded(),
+        });
+        for checkpoint in checkpoints.iter_mut() {
+            checkpoint.checkpointed = Some(Arc::clone(&checkpointed));
+        }
+        (outcome, checkpoints)
+    }
+
+    /// Parse the tokens from the already started `tokens` (into which semantic actions
+    /// still inject text) rather than starting a new stream for some text.
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_stream(&mut self, tokens: TokenStream<T>) -> Result<(), Error<T, L>> {
+        self.parse_token_stream_to_outcome(tokens).0.into_result()
+    }
+
+    /// As for `parse_to_outcome()` but for the tokens from `tokens` (see
+    /// `parse_token_stream()`).  The label is that of the first token's location.
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_stream_to_outcome(
+        &mut self,
+        tokens: TokenStream<T>,
+    ) -> (ParseOutcome<T, L>, ParseStatistics, A) {
+        self.parse_token_stream_observed(tokens, None)
+    }
+
+    /// As for `parse_token_stream_to_outcome()` but with the events of the parse told
+    /// to `observer` (if given) rather than to the parser's own `observer()`.
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_stream_observed(
+        &mut self,
+        tokens: TokenStream<T>,
+        observer: Option<&mut dyn ParseObserver<T, N, L>>,
+    ) -> (ParseOutcome<T, L>, ParseStatistics, A) {
+        self.parse_token_stream_observed_from(0, tokens, observer)
+    }
+
+    /// As for `parse_token_stream_observed()` but starting in `start_state` (see
+    /// `parse_text_from()`).
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_stream_observed_from(
+        &mut self,
+        start_state: u32,
+        mut tokens: TokenStream<T>,
+        observer: Option<&mut dyn ParseObserver<T, N, L>>,
+    ) -> (ParseOutcome<T, L>, ParseStatistics, A) {
+        let parse_stack = if observer.is_some() || self.observer().is_some() {
+            ParseStack::<T, N, A>::with_spans()
+        } else {
+            ParseStack::<T, N, A>::new()
+        }
+        .starting_in(start_state);
+        self.parse_token_stream_resumed(parse_stack, &mut tokens, observer, &mut |_| ())
+    }
+
+    /// As for `parse_token_stream_observed()` but continuing from `parse_stack` (see
+    /// `reparse()`) and calling `checkpoint` with the stack after each token is shifted
+    /// until an error occurs.
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_stream_resumed(
+        &mut self,
+        parse_stack: ParseStack<T, N, A>,
+        tokens: &mut dyn TokenSource<T>,
+        mut observer: Option<&mut dyn ParseObserver<T, N, L>>,
+        checkpoint: &mut dyn FnMut(&ParseStack<T, N, A>),
+    ) -> (ParseOutcome<T, L>, ParseStatistics, A) {
+        let mut progress = begin_parse(self, parse_stack, tokens);
+        let outcome = parse_some(self, &mut progress, tokens, &mut observer, checkpoint)
+            .expect("only asynchronous token sources are ever pending");
+        finish_parse(self, progress, outcome)
+    }
+
+    /// As for `parse_token_stream()` but for the tokens from any `TokenSource`.
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_token_source(&mut self, tokens: &mut dyn TokenSource<T>) -> Result<(), Error<T, L>> {
+        let parse_stack = if self.observer().is_some() {
+            ParseStack::<T, N, A>::with_spans()
+        } else {
+            ParseStack::<T, N, A>::new()
+        };
+        self.parse_token_stream_resumed(parse_stack, tokens, None, &mut |_| ())
+            .0
+            .into_result()
+    }
+
+    /// Parse `bytes` (labelled `label`) rather than text, with the tokens found in them
+    /// by `lexer` (see `ByteTokens`).  Errors are located by byte offset.
+    #[must_use = "the bytes may not have parsed successfully"]
+    fn parse_bytes<F>(&mut self, bytes: &[u8], label: &str, lexer: F) -> Result<(), Error<T, L>>
+    where
+        Self: Sized,
+        F: FnMut(&[u8]) -> Option<(T, usize)>,
+    {
+        let end_marker = self.lexical_analyzer().end_marker();
+        let mut tokens = ByteTokens::new(bytes, label, end_marker, lexer);
+        self.parse_token_source(&mut tokens)
+    }
+
+    /// Parse the tokens supplied by `tokens` as they arrive, awaiting each in turn, e.g.
+    /// to parse a request while it's being received.  The input is labelled `label` if
+    /// it's empty (otherwise the first token's location supplies the label).  Semantic
+    /// actions may neither inject text nor switch lexical modes as there's no text.
+    #[cfg(feature = "async")]
+    #[must_use = "the tokens may not have parsed successfully"]
+    fn parse_stream<S: AsyncTokenSource<T>>(
+        &mut self,
+        mut tokens: S,
+        label: &str,
+    ) -> impl core::future::Future<Output = Result<(), Error<T, L>>>
+    where
+        Self: Sized,
+    {
+        let mut arrivals = Arrivals::new(self.lexical_analyzer().end_marker(), label);
+        async move {
+            arrivals.arrive(tokens.next_token().await);
+            let parse_stack = if self.observer().is_some() {
+                ParseStack::<T, N, A>::with_spans()
+            } else {
+                ParseStack::<T, N, A>::new()
+            };
+            let mut progress = begin_parse(self, parse_stack, &arrivals);
+            let outcome = loop {
+                if let Some(outcome) =
+                    parse_some(self, &mut progress, &mut arrivals, &mut None, &mut |_| ())
+                {
+                    break outcome;
+                }
+                arrivals.arrive(tokens.next_token().await);
+            };
+            finish_parse(self, progress, outcome).0.into_result()
+        }
+    }
+}
+
+// The state of a parse between the arrivals of its tokens (see `Parser::parse_stream()`)
+struct Progress<T: Ord + Copy + Debug + Display + Eq, N, A, L: Display + Clone> {
+    label: String,
+    parse_stack: ParseStack<T, N, A>,
+    errors: Vec<Error<T, L>>,
+    statistics: ParseStatistics,
+    lexical_skips: usize,
+    // a token inserted by error recovery (and its attribute) waiting to be shifted
+    inserted: Option<(lexan::Token<T>, A)>,
+    insertions: usize,
+    history: VecDeque<String>,
+    // an error (and its span) whose recovery is waiting for tokens to arrive
+    recovering: Option<(Error<T, L>, lexan::Span)>,
+}
+
+// Start a parse of the tokens from `tokens` from `parse_stack`
+fn begin_parse<P, T, N, A, L>(
+    parser: &mut P,
+    parse_stack: ParseStack<T, N, A>,
+    tokens: &dyn TokenSource<T>,
+) -> Progress<T, N, A, L>
+where
+    P: Parser<T, N, A, L> + ?Sized,
+    T: Ord + Copy + Debug + Display,
+    N: Ord + Display + Debug,
+    A: Default + From<lexan::Token<T>> + From<Error<T, L>>,
+    L: Display + Clone + From<lexan::Location>,
+{
+    let label = match tokens.front() {
+        Ok(token) => token.location().label().clone(),
+        Err(error) => error.location().label().clone(),
+    };
+    #[cfg(feature = "derivation")]
+    if let Some(derivation) = parser.derivation_mut() {
+        derivation.clear();
+    }
+    parser.before_parse(&label);
+    Progress {
+        label,
+        parse_stack,
+        errors: vec![],
+        statistics: ParseStatistics::default(),
+        lexical_skips: 0,
+        inserted: None,
+        insertions: 0,
+        history: VecDeque::new(),
+        recovering: None,
+    }
+}
+
+// Continue the parse until it's over (with its outcome) or `tokens` is pending (`None`)
+fn parse_some<P, T, N, A, L>(
+    parser: &mut P,
+    progress: &mut Progress<T, N, A, L>,
+    tokens: &mut dyn TokenSource<T>,
+    observer: &mut Option<&mut dyn ParseObserver<T, N, L>>,
+    checkpoint: &mut dyn FnMut(&ParseStack<T, N, A>),
+) -> Option<ParseOutcome<T, L>>
+where
+    P: Parser<T, N, A, L> + ?Sized,
+    T: Ord + Copy + Debug + Display,
+    N: Ord + Display + Debug,
+    A: Default + From<lexan::Token<T>> + From<Error<T, L>>,
+    L: Display + Clone + From<lexan::Location>,
+{
+    let Progress {
+        parse_stack,
+        errors,
+        statistics,
+        lexical_skips,
+        inserted,
+        insertions,
+        history,
+        recovering,
+        ..
+    } = progress;
+    let diagnose = parser.diagnose_panics();
+    let max_stack_depth = parser.max_stack_depth();
+    let recovery_strategy = parser.recovery_strategy();
+    let outcome = loop {
+        if let Some((error, span)) = recovering.take() {
+            let Some(recovered) =
+                P::recover_from_error(error.clone(), span.clone(), parse_stack, tokens)
+            else {
+                // the rest of the recovery has to wait for more tokens
+                *recovering = Some((error, span));
+                return None;
+            };
+            notify(observer, parser.observer(), |o| {
+                o.on_error(&error, recovered)
+            });
+            if !recovered {
+                break ParseOutcome::Failed(core::mem::take(errors));
+            }
+        }
+        if inserted.is_none() && tokens.is_pending() {
+            return None;
+        }
+        let front = match &*inserted {
+            Some((token, _)) => Ok(token.clone()),
+            None => tokens.front(),
+        };
+        let front = front.map(|token| {
+            if parser.is_end_of_input(&token) {
+                token.with_tag(parser.lexical_analyzer().end_marker())
+            } else {
+                token
+            }
+        });
+        match front {
+            Err(err) => {
+                let expected_tokens = P::look_ahead_set(parse_stack.current_state());
+                let skippable =
+                    err.is_unexpected_text() && *lexical_skips < parser.lexical_skip_limit();
+                let span = lexan::Span::empty_at(err.location());
+                let error = Error::LexicalError(err, expected_tokens);
+                let message = parser.reported_message(&error, parse_stack);
+                let outcome = parser.report_error(&error, &message);
+                errors.push(error.clone());
+                let recovered = if outcome == ErrorOutcome::Abort {
+                    false
+                } else if skippable {
+                    *lexical_skips += 1;
+                    tokens.advance();
+                    true
+                } else {
+                    *recovering = Some((error, span));
+                    continue;
+                };
+                notify(observer, parser.observer(), |o| {
+                    o.on_error(&error, recovered)
+                });
+                if !recovered {
+                    break ParseOutcome::Failed(core::mem::take(errors));
+                }
+            }
+            Ok(token) => {
+                let action = diagnosed(
+                    diagnose,
+                    || parse_stack.description(history),
+                    || {
+                        let state = parse_stack.current_state();
+                        parser.next_action_checked(state, parse_stack, &token)
+                    },
+                );
+                let (action, illegal_state) = match action {
+                    Ok(action) => (action, None),
+                    Err(state) => (Action::IllegalState, Some(state)),
+                };
+                let overflows = max_stack_depth.is_some_and(|max| {
+                    parse_stack.states.len() >= max
+                        && match action {
+                            Action::Shift(_) => true,
+                            Action::Reduce(production_id) => {
+                                P::production_data(production_id).1 == 0
+                            }
+                            _ => false,
+                        }
+                });
+                match action {
+                    Action::Accept => {
+                        notify(observer, parser.observer(), |o| o.on_accept());
+                        break if errors.is_empty() {
+                            ParseOutcome::Clean
+                        } else {
+                            ParseOutcome::Recovered(core::mem::take(errors))
+                        };
+                    }
+                    Action::Shift(next_state) if !overflows => {
+                        let tag = *token.tag();
+                        if diagnose {
+                            if history.len() == TOKEN_HISTORY_LENGTH {
+                                history.pop_front();
+                            }
+                            history.push_back(format!("{}@{}", tag, token.location()));
+                        }
+                        let span = parse_stack.spans.is_some().then(|| token.span());
+                        if let Some(span) = &span {
+                            let located = token
+                                .clone()
+                                .with_location(parser.location(token.location()));
+                            let span = lexan::Span::new(
+                                parser.location(&span.start),
+                                parser.location(&span.end),
+                            );
+                            notify(observer, parser.observer(), |o| o.on_shift(&located, &span));
+                        }
+                        let attribute = match inserted.take() {
+                            Some((_, attribute)) => attribute,
+                            None => {
+                                parser.advance_into(next_state, tokens);
+                                *lexical_skips = 0;
+                                *insertions = 0;
+                                // progress has been made since any recovery
+                                parse_stack.clear_last_error_state();
+                                parser.token_attribute(token)
+                            }
+                        };
+                        parse_stack.consumed = tokens.consumed();
+                        parse_stack.push_terminal(tag, attribute, next_state);
+                        if let Some(span) = span {
+                            parse_stack.push_span(span);
+                        }
+                        statistics.shift_count += 1;
+                        if errors.is_empty() {
+                            checkpoint(parse_stack);
+                        }
+                    }
+                    Action::Reduce(production_id) if !overflows => {
+                        #[cfg(feature = "coverage")]
+                        if let Some(coverage) = parser.coverage_mut() {
+                            coverage.insert(production_id);
+                        }
+                        #[cfg(feature = "derivation")]
+                        if let Some(derivation) = parser.derivation_mut() {
+                            derivation.push(production_id);
+                        }
+                        let (lhs, rhs_len) = diagnosed(
+                            diagnose,
+                            || parse_stack.description(history),
+                            || P::production_data(production_id),
+                        );
+                        let span = parse_stack.span_of_top_n(rhs_len, token.location());
+                        let rhs = parse_stack.pop_n(rhs_len);
+                        let next_state = diagnosed(
+                            diagnose,
+                            || parse_stack.description(history),
+                            || P::goto_state(&lhs, parse_stack.current_state()),
+                        );
+                        let attribute =
+                            parser.do_semantic_action_la(production_id, rhs, &token, |s, l| {
+                                match P::lexical_mode(&l) {
+                                    Some(mode) if s.is_empty() => tokens.set_mode(mode),
+                                    _ => tokens.inject(&s, &l),
+                                }
+                            });
+                        if let Some(span) = &span {
+                            let span = lexan::Span::new(
+                                parser.location(&span.start),
+                                parser.location(&span.end),
+                            );
+                            notify(observer, parser.observer(), |o| {
+                                o.on_reduce(&lhs, production_id, &span)
+                            });
+                        }
+                        parse_stack.consumed = tokens.consumed();
+                        parse_stack.push_non_terminal(lhs, attribute, next_state);
+                        if let Some(span) = span {
+                            parse_stack.push_span(span);
+                        }
+                    }
+                    _ => {
+                        let location = parser.location(token.location());
+                        let (error, span) = if let Some(state) = illegal_state {
+                            (
+                                Error::InternalTableError(state, BTreeSet::new()),
+                                token.span(),
+                            )
+                        } else {
+                            let expected_tokens = P::look_ahead_set(parse_stack.current_state());
+                            if overflows {
+                                let span = lexan::Span::empty_at(token.location());
+                                (Error::StackOverflow(location, expected_tokens), span)
+                            } else if let Action::SemanticError(message) = action {
+                                let found = token.clone().with_location(location);
+                                let message = message.to_string();
+                                let error = Error::SemanticError(found, message, expected_tokens);
+                                (error, token.span())
+                            } else {
+                                let found = token.clone().with_location(location);
+                                (Error::SyntaxError(found, expected_tokens), token.span())
+                            }
+                        };
+                        let message = parser.reported_message(&error, parse_stack);
+                        let outcome = parser.report_error(&error, &message);
+                        errors.push(error.clone());
+                        // only one insertion per real token (an inserted token that
+                        // doesn't fit because of predicates is abandoned)
+                        *inserted = None;
+                        let insertion = match error {
+                            Error::SyntaxError(..)
+                                if *insertions == 0
+                                    && recovery_strategy == RecoveryStrategy::InsertThenDelete =>
+                            {
+                                parser.viable_insertion(parse_stack, token.tag())
+                            }
+                            _ => None,
+                        };
+                        let recovered = match insertion {
+                            _ if outcome == ErrorOutcome::Abort => false,
+                            Some(tag) => {
+                                let token = token.clone().with_tag(tag).with_lexeme(String::new());
+                                *inserted = Some((token, A::from(error.clone())));
+                                *insertions += 1;
+                                true
+                            }
+                            None => {
+                                *recovering = Some((error, span));
+                                continue;
+                            }
+                        };
+                        notify(observer, parser.observer(), |o| {
+                            o.on_error(&error, recovered)
+                        });
+                        if !recovered {
+                            break ParseOutcome::Failed(core::mem::take(errors));
+                        }
+                    }
+                }
+            }
+        };
+    };
+    Some(outcome)
+}
+
+// The outcome and statistics of the finished parse and the attribute of its start symbol
+fn finish_parse<P, T, N, A, L>(
+    parser: &mut P,
+    progress: Progress<T, N, A, L>,
+    outcome: ParseOutcome<T, L>,
+) -> (ParseOutcome<T, L>, ParseStatistics, A)
+where
+    P: Parser<T, N, A, L> + ?Sized,
+    T: Ord + Copy + Debug + Display,
+    N: Ord + Display + Debug,
+    A: Default + From<lexan::Token<T>> + From<Error<T, L>>,
+    L: Display + Clone + From<lexan::Location>,
+{
+    let Progress {
+        label,
+        mut parse_stack,
+        mut statistics,
+        ..
+    } = progress;
+    statistics.max_stack_depth = parse_stack.max_depth();
+    parser.after_parse(&label, &outcome);
+    let attribute = if outcome.is_failed() {
+        A::default()
+    } else {
+        parse_stack.pop_n(1).pop().unwrap_or_default()
+    };
+    (outcome, statistics, attribute)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReportError;
+    use std::collections::{BTreeSet, HashMap};
+    use std::convert::From;
+    use std::fmt;
+    use std::str::FromStr;
+
+    macro_rules! btree_set {
+        ( $( $x:expr ),* ) => {
+            {
+                let mut set = BTreeSet::new();
+                $( set.insert($x); )*
+                set
+            }
+        };
+        ( $( $x:expr ),+ , ) => {
+            btree_set![ $( $x ), * ]
+        };
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        Plus,
+        Minus,
+        Times,
+        Divide,
+        Assign,
+        LPR,
+        RPR,
+        EOL,
+        Number,
+        Id,
+        EndMarker,
+    }
+
+    impl fmt::Display for Terminal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Terminal::Plus => write!(f, "+"),
+                Terminal::Minus => write!(f, "-"),
+                Terminal::Times => write!(f, "*"),
+                Terminal::Divide => write!(f, "/"),
+                Terminal::Assign => write!(f, "="),
+                Terminal::LPR => write!(f, "("),
+                Terminal::RPR => write!(f, ")"),
+                Terminal::EOL => write!(f, "EOL"),
+                Terminal::Number => write!(f, "Number"),
+                Terminal::Id => write!(f, "Id"),
+                Terminal::EndMarker => write!(f, "EndMarker"),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum NonTerminal {
+        Line,
+        SetUp,
+        Expr,
+    }
+
+    impl fmt::Display for NonTerminal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                NonTerminal::Line => write!(f, "Line"),
+                NonTerminal::SetUp => write!(f, "SetUp"),
+                NonTerminal::Expr => write!(f, "Expr"),
+            }
+        }
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct AttributeData {
+        id: String,
+        value: f64,
+    }
+
+    impl From<lexan::Token<Terminal>> for AttributeData {
+        fn from(input: lexan::Token<Terminal>) -> Self {
+            let mut attr = AttributeData::default();
+            match input.tag() {
+                Terminal::Number => {
+                    attr.value = f64::from_str(input.lexeme()).unwrap();
+                }
+                Terminal::Id => {
+                    attr.id = input.lexeme().to_string();
+                }
+                _ => (),
+            };
+            attr
+        }
+    }
+
+    impl From<crate::Error<Terminal>> for AttributeData {
+        fn from(_error: crate::Error<Terminal>) -> Self {
+            AttributeData::default()
+        }
+    }
+
+    const UNDEFINED_VARIABLE: u32 = 1 << 0;
+    const DIVIDE_BY_ZERO: u32 = 1 << 1;
+    const SYNTAX_ERROR: u32 = 1 << 2;
+    const LEXICAL_ERROR: u32 = 1 << 3;
+
+    struct Calc {
+        errors: u32,
+        variables: HashMap<String, f64>,
+        parses: Vec<(String, Option<bool>)>,
+        end_at_eol: bool,
+        max_stack_depth: Option<usize>,
+        recovery_strategy: crate::RecoveryStrategy,
+        abort_on_error: bool,
+        // the messages passed to report_error()
+        reported: Vec<String>,
+        // whether undefined variables are semantic errors (rather than zero)
+        reject_undefined: bool,
+        state_path_len: usize,
+        // the look ahead tokens' tags at each reduction
+        look_aheads: Vec<Terminal>,
+        #[cfg(feature = "coverage")]
+        coverage: crate::BitSet,
+        #[cfg(feature = "derivation")]
+        derivation: Vec<u32>,
+    }
+
+    impl ReportError<Terminal> for Calc {
+        fn report_error(
+            &mut self,
+            _error: &crate::Error<Terminal>,
+            message: &str,
+        ) -> crate::ErrorOutcome {
+            eprint!("{message}");
+            self.reported.push(message.to_string());
+            if self.abort_on_error {
+                crate::ErrorOutcome::Abort
+            } else {
+                crate::ErrorOutcome::Continue
+            }
+        }
+    }
+
+    lazy_static! {
+        static ref AALEXAN: lexan::LexicalAnalyzer<Terminal> = {
+            use Terminal::*;
+            lexan::LexicalAnalyzer::new(
+                &[
+                    (Plus, "+"),
+                    (Minus, "-"),
+                    (Times, "*"),
+                    (Divide, "/"),
+                    (Assign, "="),
+                    (LPR, "("),
+                    (RPR, ")"),
+                ],
+                &[
+                    (EOL, r"(\n)"),
+                    (Number, r"([0-9]+(\.[0-9]+){0,1})"),
+                    (Id, r"([a-zA-Z]+)"),
+                ],
+                &[r"([\t\r ]+)"],
+                EndMarker,
+            )
+        };
+    }
+
+    impl Calc {
+        pub fn new() -> Self {
+            Self {
+                errors: 0,
+                variables: HashMap::new(),
+                parses: vec![],
+                end_at_eol: false,
+                max_stack_depth: None,
+                recovery_strategy: crate::RecoveryStrategy::DeleteUntilViable,
+                abort_on_error: false,
+                reported: vec![],
+                reject_undefined: false,
+                state_path_len: 0,
+                look_aheads: vec![],
+                #[cfg(feature = "coverage")]
+                coverage: crate::BitSet::new(),
+                #[cfg(feature = "derivation")]
+                derivation: vec![],
+            }
+        }
+
+        fn report_errors(&self) {
+            if self.errors == 0 {
+                println!("no errrs")
+            } else {
+                if self.errors & UNDEFINED_VARIABLE == UNDEFINED_VARIABLE {
+                    println!("undefined variable errors")
+                }
+                if self.errors & DIVIDE_BY_ZERO == DIVIDE_BY_ZERO {
+                    println!("divide by zero errors")
+                }
+                if self.errors & SYNTAX_ERROR == SYNTAX_ERROR {
+                    println!("syntax errors")
+                }
+                if self.errors & LEXICAL_ERROR == LEXICAL_ERROR {
+                    println!("lexical errors")
+                }
+            }
+            println!("#errors = {}", self.errors)
+        }
+    }
+
+    impl crate::Parser<Terminal, NonTerminal, AttributeData> for Calc {
+        fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<Terminal> {
+            &AALEXAN
+        }
+
+        #[cfg(feature = "coverage")]
+        fn coverage(&self) -> Option<&crate::BitSet> {
+            Some(&self.coverage)
+        }
+
+        #[cfg(feature = "coverage")]
+        fn coverage_mut(&mut self) -> Option<&mut crate::BitSet> {
+            Some(&mut self.coverage)
+        }
+
+        #[cfg(feature = "derivation")]
+        fn derivation_mut(&mut self) -> Option<&mut Vec<u32>> {
+            Some(&mut self.derivation)
+        }
+
+        #[cfg(feature = "derivation")]
+        fn derivation(&self) -> &[u32] {
+            &self.derivation
+        }
+
+        fn display_terminal(&self, terminal: &Terminal) -> String {
+            match terminal {
+                Terminal::EndMarker => "end of input".to_string(),
+                _ => terminal.to_string(),
+            }
+        }
+
+        fn max_stack_depth(&self) -> Option<usize> {
+            self.max_stack_depth
+        }
+
+        fn recovery_strategy(&self) -> crate::RecoveryStrategy {
+            self.recovery_strategy
+        }
+
+        fn reported_state_path_len(&self) -> usize {
+            self.state_path_len
+        }
+
+        // Approximated by the action for default attributes (rather than all actions)
+        fn possible_actions(state: u32, tag: &Terminal) -> Vec<crate::Action> {
+            let calc = Calc::new();
+            let mut stack = crate::ParseStack::new();
+            for _ in 0..3 {
+                stack.push_terminal(Terminal::Id, AttributeData::default(), 0);
+            }
+            let token = calc
+                .lexical_analyzer()
+                .token_stream("x", "")
+                .front()
+                .unwrap();
+            match calc.next_action(state, &stack, &token.with_tag(*tag)) {
+                crate::Action::SyntaxError
+                | crate::Action::SemanticError(_)
+                | crate::Action::IllegalState => vec![],
+                action => vec![action],
+            }
+        }
+
+        fn is_end_of_input(&self, token: &lexan::Token<Terminal>) -> bool {
+            match token.tag() {
+                Terminal::EOL => self.end_at_eol,
+                tag => *tag == Terminal::EndMarker,
+            }
+        }
+
+        fn before_parse(&mut self, label: &str) {
+            self.parses.push((label.to_string(), None));
+        }
+
+        fn after_parse(&mut self, label: &str, outcome: &crate::ParseOutcome<Terminal>) {
+            let last = self.parses.last_mut().expect("before_parse() called");
+            assert_eq!(last.0, label);
+            last.1 = Some(outcome.is_clean());
+        }
+
+        fn viable_error_recovery_states(tag: &Terminal) -> BTreeSet<u32> {
+            use Terminal::*;
+            match tag {
+                EOL => btree_set![0_u32, 4], //BTreeSet::from_iter([0_u32, 4].into_iter().cloned()),
+                _ => BTreeSet::new(),
+            }
+        }
+
+        fn error_goto_state(state: u32) -> u32 {
+            match state {
+                0 | 4 => 3,
+                _ => panic!("No error go to state for {state}"),
+            }
+        }
+
+        fn look_ahead_set(state: u32) -> BTreeSet<Terminal> {
+            use Terminal::*;
+            return match state {
+                0 => btree_set![Minus, LPR, Number, Id],
+                1 => btree_set![EndMarker, EOL],
+                2 => btree_set![Minus, LPR, Number, Id],
+                3 => btree_set![EndMarker, EOL],
+                4 => btree_set![EndMarker, EOL, Minus, Number, Id, LPR],
+                5 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide],
+                6 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, Assign],
+                7 | 8 => btree_set![Minus, Number, Id, LPR],
+                9 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                10 => btree_set![EndMarker, EOL],
+                11 | 12 | 13 | 14 | 15 => btree_set![Minus, Number, Id, LPR],
+                16 => btree_set![Plus, Minus, Times, Divide, RPR],
+                17 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                18 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                19 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                20 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                21 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                22 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                23 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                24 => btree_set![EndMarker, EOL, Plus, Minus, Times, Divide, RPR],
+                _ => panic!("illegal state: {state}"),
+            };
+        }
+
+        fn next_action(
+            &self,
+            state: u32,
+            attributes: &crate::ParseStack<Terminal, NonTerminal, AttributeData>,
+            token: &lexan::Token<Terminal>,
+        ) -> crate::Action {
+            use crate::Action;
+            use Terminal::*;
+            let tag = *token.tag();
+            return match state {
+                0 => match tag {
+                    Minus | LPR | Number | Id => Action::Reduce(8),
+                    _ => Action::SyntaxError,
+                },
+                1 => match tag {
+                    EndMarker => Action::Accept,
+                    EOL => Action::Shift(4),
+                    _ => Action::SyntaxError,
+                },
+                2 => match tag {
+                    Minus => Action::Shift(8),
+                    LPR => Action::Shift(7),
+                    Number => Action::Shift(9),
+                    Id => Action::Shift(6),
+                    _ => Action::SyntaxError,
+                },
+                3 => match tag {
+                    EndMarker | EOL => Action::Reduce(7),
+                    _ => Action::SyntaxError,
+                },
+                4 => match tag {
+                    EndMarker | EOL => Action::Reduce(6),
+                    Minus | Number | Id | LPR => Action::Reduce(8),
                     _ => Action::SyntaxError,
                 },
-                24 => match tag {
-                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(23),
-                    _ => Action::SyntaxError,
-                },
-                _ => panic!("illegal state: {state}"),
+                5 => match tag {
+                    Plus => Action::Shift(11),
+                    Minus => Action::Shift(12),
+                    Times => Action::Shift(13),
+                    Divide => Action::Shift(14),
+                    EndMarker | EOL => {
+                        if self.errors > 0 {
+                            Action::Reduce(1)
+                        } else {
+                            Action::Reduce(2)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                6 => match tag {
+                    Assign => Action::Shift(15),
+                    EndMarker | EOL | Plus | Minus | Times | Divide => {
+                        if self
+                            .variables
+                            .contains_key(&attributes.at_len_minus_n(2 - 1).id)
+                        {
+                            Action::Reduce(26)
+                        } else if self.reject_undefined {
+                            Action::SemanticError("undefined variable")
+                        } else {
+                            Action::Reduce(27)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                7 | 8 => match tag {
+                    Minus => Action::Shift(8),
+                    LPR => Action::Shift(7),
+                    Number => Action::Shift(9),
+                    Id => Action::Shift(17),
+                    _ => Action::SyntaxError,
+                },
+                9 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(25),
+                    _ => Action::SyntaxError,
+                },
+                10 => match tag {
+                    EndMarker | EOL => Action::Reduce(5),
+                    _ => Action::SyntaxError,
+                },
+                11 | 12 | 13 | 14 | 15 => match tag {
+                    Minus => Action::Shift(8),
+                    LPR => Action::Shift(7),
+                    Number => Action::Shift(9),
+                    Id => Action::Shift(17),
+                    _ => Action::SyntaxError,
+                },
+                16 => match tag {
+                    Plus => Action::Shift(11),
+                    Minus => Action::Shift(12),
+                    Times => Action::Shift(13),
+                    Divide => Action::Shift(14),
+                    RPR => Action::Shift(24),
+                    _ => Action::SyntaxError,
+                },
+                17 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
+                        if self
+                            .variables
+                            .contains_key(&attributes.at_len_minus_n(2 - 1).id)
+                        {
+                            Action::Reduce(26)
+                        } else if self.reject_undefined {
+                            Action::SemanticError("undefined variable")
+                        } else {
+                            Action::Reduce(27)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                18 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(24),
+                    _ => Action::SyntaxError,
+                },
+                19 => match tag {
+                    Times => Action::Shift(13),
+                    Divide => Action::Shift(14),
+                    EndMarker | EOL | Plus | Minus | RPR => {
+                        if attributes.at_len_minus_n(4 - 1).value == 0.0 {
+                            Action::Reduce(9)
+                        } else if attributes.at_len_minus_n(4 - 3).value == 0.0 {
+                            Action::Reduce(10)
+                        } else {
+                            Action::Reduce(11)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                20 => match tag {
+                    Times => Action::Shift(13),
+                    Divide => Action::Shift(14),
+                    EndMarker | EOL | Plus | Minus | RPR => {
+                        if attributes.at_len_minus_n(4 - 1).value == 0.0 {
+                            Action::Reduce(12)
+                        } else if attributes.at_len_minus_n(4 - 3).value == 0.0 {
+                            Action::Reduce(13)
+                        } else {
+                            Action::Reduce(14)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                21 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
+                        if attributes.at_len_minus_n(4 - 1).value == 0.0
+                            || attributes.at_len_minus_n(4 - 3).value == 0.0
+                        {
+                            Action::Reduce(15)
+                        } else if attributes.at_len_minus_n(4 - 1).value == 1.0 {
+                            Action::Reduce(16)
+                        } else if attributes.at_len_minus_n(4 - 3).value == 1.0 {
+                            Action::Reduce(17)
+                        } else {
+                            Action::Reduce(18)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                22 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => {
+                        if attributes.at_len_minus_n(4 - 1).value == 0.0
+                            || attributes.at_len_minus_n(4 - 3).value == 0.0
+                        {
+                            Action::Reduce(19)
+                        } else if attributes.at_len_minus_n(4 - 1).value == 1.0 {
+                            Action::Reduce(20)
+                        } else if attributes.at_len_minus_n(4 - 3).value == 1.0 {
+                            Action::Reduce(21)
+                        } else {
+                            Action::Reduce(22)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                23 => match tag {
+                    Plus => Action::Shift(11),
+                    Minus => Action::Shift(12),
+                    Times => Action::Shift(13),
+                    Divide => Action::Shift(14),
+                    EndMarker | EOL => {
+                        if self.errors == 0 {
+                            Action::Reduce(3)
+                        } else {
+                            Action::Reduce(4)
+                        }
+                    }
+                    _ => Action::SyntaxError,
+                },
+                24 => match tag {
+                    EndMarker | EOL | Plus | Minus | Times | Divide | RPR => Action::Reduce(23),
+                    _ => Action::SyntaxError,
+                },
+                _ => Action::IllegalState,
+            };
+        }
+
+        fn production_data(production_id: u32) -> (NonTerminal, usize) {
+            match production_id {
+                1 => (NonTerminal::Line, 2),
+                2 => (NonTerminal::Line, 2),
+                3 => (NonTerminal::Line, 4),
+                4 => (NonTerminal::Line, 4),
+                5 => (NonTerminal::Line, 3),
+                6 => (NonTerminal::Line, 2),
+                7 => (NonTerminal::Line, 1),
+                8 => (NonTerminal::SetUp, 0),
+                9 => (NonTerminal::Expr, 3),
+                10 => (NonTerminal::Expr, 3),
+                11 => (NonTerminal::Expr, 3),
+                12 => (NonTerminal::Expr, 3),
+                13 => (NonTerminal::Expr, 3),
+                14 => (NonTerminal::Expr, 3),
+                15 => (NonTerminal::Expr, 3),
+                16 => (NonTerminal::Expr, 3),
+                17 => (NonTerminal::Expr, 3),
+                18 => (NonTerminal::Expr, 3),
+                19 => (NonTerminal::Expr, 3),
+                20 => (NonTerminal::Expr, 3),
+                21 => (NonTerminal::Expr, 3),
+                22 => (NonTerminal::Expr, 3),
+                23 => (NonTerminal::Expr, 3),
+                24 => (NonTerminal::Expr, 2),
+                25 => (NonTerminal::Expr, 1),
+                26 => (NonTerminal::Expr, 1),
+                27 => (NonTerminal::Expr, 1),
+                _ => panic!("malformed production data table"),
+            }
+        }
+
+        fn goto_state(lhs: &NonTerminal, current_state: u32) -> u32 {
+            match current_state {
+                0 => match lhs {
+                    NonTerminal::Line => 1,
+                    NonTerminal::SetUp => 2,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                2 => match lhs {
+                    NonTerminal::Expr => 5,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                4 => match lhs {
+                    NonTerminal::Line => 10,
+                    NonTerminal::SetUp => 2,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                7 => match lhs {
+                    NonTerminal::Expr => 16,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                8 => match lhs {
+                    NonTerminal::Expr => 18,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                11 => match lhs {
+                    NonTerminal::Expr => 19,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                12 => match lhs {
+                    NonTerminal::Expr => 20,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                13 => match lhs {
+                    NonTerminal::Expr => 21,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                14 => match lhs {
+                    NonTerminal::Expr => 22,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                15 => match lhs {
+                    NonTerminal::Expr => 23,
+                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+                },
+                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
+            }
+        }
+
+        fn do_semantic_action_la<F: FnMut(String, String)>(
+            &mut self,
+            production_id: u32,
+            rhs: Vec<AttributeData>,
+            look_ahead: &lexan::Token<Terminal>,
+            inject: F,
+        ) -> AttributeData {
+            self.look_aheads.push(*look_ahead.tag());
+            self.do_semantic_action(production_id, rhs, inject)
+        }
+
+        fn do_semantic_action<F: FnMut(String, String)>(
+            &mut self,
+            production_id: u32,
+            rhs: Vec<AttributeData>,
+            mut inject: F,
+        ) -> AttributeData {
+            let mut lhs = AttributeData::default();
+            // test that multiple injects are OK
+            inject(String::new(), String::new());
+            inject(String::new(), String::new());
+            match production_id {
+                1 | 4 => {
+                    self.report_errors();
+                }
+                2 => {
+                    println!("{}", rhs[2 - 1].value);
+                    lhs.value = rhs[2 - 1].value;
+                }
+                3 => {
+                    self.variables
+                        .insert(rhs[2 - 1].id.clone(), rhs[4 - 1].value);
+                }
+                7 => {
+                    self.errors |= SYNTAX_ERROR;
+                }
+                8 => {
+                    self.errors = 0;
+                }
+                9 => {
+                    lhs.value = rhs[3 - 1].value;
+                }
+                10 => {
+                    lhs.value = rhs[1 - 1].value;
+                }
+                11 => {
+                    lhs.value = rhs[1 - 1].value + rhs[3 - 1].value;
+                }
+                12 => {
+                    lhs.value = -rhs[3 - 1].value;
+                }
+                13 => {
+                    lhs.value = rhs[1 - 1].value;
+                }
+                14 => {
+                    lhs.value = rhs[1 - 1].value - rhs[3 - 1].value;
+                }
+                15 => {
+                    lhs.value = -rhs[3 - 1].value;
+                }
+                16 => {
+                    lhs.value = rhs[3 - 1].value;
+                }
+                17 => {
+                    lhs.value = rhs[1 - 1].value;
+                }
+                18 => {
+                    lhs.value = rhs[1 - 1].value * rhs[3 - 1].value;
+                }
+                19 => {
+                    lhs.value = rhs[1 - 1].value;
+                }
+                20 => {
+                    self.errors |= DIVIDE_BY_ZERO;
+                }
+                21 => {
+                    lhs.value = 0.0;
+                }
+                22 => {
+                    lhs.value = rhs[1 - 1].value / rhs[3 - 1].value;
+                }
+                23 => {
+                    lhs.value = rhs[2 - 1].value;
+                }
+                24 => {
+                    lhs.value = -rhs[2 - 1].value;
+                }
+                25 => {
+                    lhs.value = rhs[1 - 1].value;
+                }
+                26 => {
+                    lhs.value = *self.variables.get(&rhs[1 - 1].id).unwrap();
+                }
+                27 => {
+                    self.errors |= UNDEFINED_VARIABLE;
+                    lhs.value = 0.0;
+                }
+                _ => (),
+            }
+            lhs
+        }
+    }
+
+    #[test]
+    fn calc_works() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = (3 + 4)\n", "raw").is_ok());
+        assert_eq!(calc.variables.get("a"), Some(&7.0));
+        assert!(calc.parse_text("b = a * 5\n", "raw").is_ok());
+        assert_eq!(calc.variables.get("b"), Some(&35.0));
+    }
+
+    #[test]
+    fn end_of_input_overridden() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        calc.end_at_eol = true;
+        assert!(calc.parse_text("h = 1\ni = 2\n", "raw").is_ok());
+        assert_eq!(calc.variables.get("h"), Some(&1.0));
+        assert_eq!(calc.variables.get("i"), None);
+    }
+
+    #[test]
+    fn owned_text_parsed_like_borrowed_text() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let text = String::from("a = 2 * 3\n");
+        assert!(calc.parse_text(&text, "raw").is_ok());
+        assert!(calc.parse_string(text.replace('a', "b"), "raw").is_ok());
+        assert_eq!(calc.variables.get("a"), calc.variables.get("b"));
+        let borrowed = calc.parse_text("c = +\n", "raw").unwrap_err();
+        let owned = calc.parse_string("c = +\n".to_string(), "raw").unwrap_err();
+        assert_eq!(borrowed.to_string(), owned.to_string());
+    }
+
+    #[test]
+    fn parse_hooks_called() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = 1\n", "clean").is_ok());
+        assert!(calc.parse_text("b = + 1\nc = 2\n", "recovered").is_err());
+        assert_eq!(
+            calc.parses,
+            vec![
+                ("clean".to_string(), Some(true)),
+                ("recovered".to_string(), Some(false))
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_outcomes_distinguished() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        let (outcome, _) = calc.parse_text_outcome("c = 3\n", "raw");
+        assert!(outcome.is_clean());
+        assert!(outcome.into_result().is_ok());
+        let (outcome, _) = calc.parse_text_outcome("d = + 3\ne = 4\n", "raw");
+        match outcome {
+            ParseOutcome::Recovered(ref errors) => assert_eq!(errors.len(), 1),
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert!(!outcome.is_failed());
+        assert!(outcome.into_result().is_err());
+        assert_eq!(calc.variables.get("e"), Some(&4.0));
+        let (outcome, _) = calc.parse_text_outcome("f = (4", "raw");
+        // nothing can be recovered at the end of the input
+        assert!(outcome.is_failed());
+        assert!(outcome.into_result().is_err());
+    }
+
+    #[test]
+    fn stack_depth_measured() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let (_, flat) = calc.parse_text_outcome("f = 1\n", "raw");
+        let (_, nested) = calc.parse_text_outcome("g = (((1)))\n", "raw");
+        assert_eq!(flat.max_stack_depth, 5);
+        // the parenthesized expressions are nested on the stack
+        assert_eq!(nested.max_stack_depth, flat.max_stack_depth + 4);
+        let parse_stack = crate::ParseStack::<Terminal, NonTerminal, AttributeData>::new();
+        assert_eq!(parse_stack.max_depth(), 1);
+    }
+
+    #[test]
+    fn shifts_counted() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let (result, shift_count) = calc.parse_text_counting_shifts("c = 1 + 2\n", "raw");
+        assert!(result.is_ok());
+        assert_eq!(shift_count, 6);
+        let (result, shift_count) = calc.parse_text_counting_shifts("c = (1 + 2)\n", "raw");
+        assert!(result.is_ok());
+        assert_eq!(shift_count, 8);
+    }
+
+    #[test]
+    fn look_ahead_given_to_actions() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = 1 + 2\n", "raw").is_ok());
+        use Terminal::*;
+        assert_eq!(calc.look_aheads, vec![Id, Plus, EOL, EOL, EOL, EndMarker]);
+    }
+
+    #[test]
+    fn bytes_parsed() {
+        use crate::{lexeme_bytes, ByteTokens, Parser, TokenSource};
+        // single byte tokens apart from runs of digits
+        let lexer = |bytes: &[u8]| {
+            let tag = match bytes[0] {
+                b'0'..=b'9' => {
+                    let length = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+                    return Some((Terminal::Number, length));
+                }
+                b'a'..=b'z' => Terminal::Id,
+                b'+' => Terminal::Plus,
+                b'*' => Terminal::Times,
+                b'=' => Terminal::Assign,
+                b'\n' => Terminal::EOL,
+                _ => return None,
             };
+            Some((tag, 1))
+        };
+        let mut calc = Calc::new();
+        assert!(calc.parse_bytes(b"a=3+4\nb=a*10\n", "bytes", lexer).is_ok());
+        assert_eq!(calc.variables.get("b"), Some(&70.0));
+        // unexpected bytes are located by offset
+        let error = calc
+            .parse_bytes(b"c=1\nd=\xff2\n", "bytes", lexer)
+            .unwrap_err();
+        assert!(error.to_string().contains(" at: bytes:1:7."));
+        assert_eq!(calc.variables.get("d"), Some(&2.0));
+        let mut tokens = ByteTokens::new(b"\xfe", "bytes", Terminal::EndMarker, |_| {
+            Some((Terminal::Id, 1))
+        });
+        assert_eq!(lexeme_bytes(&tokens.front().unwrap()), vec![0xfe]);
+        tokens.advance();
+        assert!(tokens.is_empty());
+        assert_eq!(tokens.front().unwrap().location().offset(), 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn streams_parsed_as_tokens_arrive() {
+        use crate::{AsyncTokenSource, Parser};
+        use core::future::Future;
+        use core::task::{Context, Poll, Waker};
+        // the tokens of some text each of which is pending (once) before it arrives
+        struct Trickle {
+            tokens: std::vec::IntoIter<lexan::Token<Terminal>>,
+            pending: bool,
+        }
+        impl AsyncTokenSource<Terminal> for Trickle {
+            fn next_token(&mut self) -> impl Future<Output = Option<lexan::Token<Terminal>>> {
+                core::future::poll_fn(move |context| {
+                    self.pending = !self.pending;
+                    if self.pending {
+                        context.waker().wake_by_ref();
+                        Poll::Pending
+                    } else {
+                        Poll::Ready(self.tokens.next())
+                    }
+                })
+            }
+        }
+        fn trickle(calc: &Calc, text: &str) -> Trickle {
+            let mut stream = calc.lexical_analyzer().token_stream(text, "stream");
+            let mut tokens = vec![];
+            while let Ok(token) = stream.front() {
+                if *token.tag() == Terminal::EndMarker {
+                    break;
+                }
+                tokens.push(token);
+                stream.advance();
+            }
+            Trickle {
+                tokens: tokens.into_iter(),
+                pending: false,
+            }
+        }
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut future = core::pin::pin!(future);
+            let mut context = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                    return output;
+                }
+            }
+        }
+        let mut calc = Calc::new();
+        let tokens = trickle(&calc, "a = (3 + 4)\nb = a * 5\n");
+        assert!(block_on(calc.parse_stream(tokens, "stream")).is_ok());
+        assert_eq!(calc.variables.get("b"), Some(&35.0));
+        // error recovery awaits the tokens that it skips
+        let tokens = trickle(&calc, "d = + 3\ne = 4\n");
+        assert!(block_on(calc.parse_stream(tokens, "stream")).is_err());
+        assert_eq!(calc.variables.get("e"), Some(&4.0));
+        let tokens = trickle(&calc, "");
+        let error = block_on(calc.parse_stream(tokens, "stream")).unwrap_err();
+        assert!(error.to_string().contains(" at: stream:1:1."));
+    }
+
+    #[test]
+    fn parse_trees_unparsed() {
+        use crate::{unparse, Associativity, ParseTree, Parser, ProductionShape};
+        let shape = |production_id: u32| match production_id {
+            9..=15 => ProductionShape::Infix(Associativity::Left, 1),
+            16..=22 => ProductionShape::Infix(Associativity::Left, 2),
+            23 => ProductionShape::Parenthesized,
+            24 => ProductionShape::Prefix(3),
+            _ => ProductionShape::Plain,
+        };
+        let calc = Calc::new();
+        let text = "(1 - 2) - (3 - 4) * -(5)";
+        let mut tokens = calc.lexical_analyzer().token_stream(text, "raw");
+        let mut leaves = vec![];
+        while let Ok(token) = tokens.front() {
+            if *token.tag() == Terminal::EndMarker {
+                break;
+            }
+            leaves.push(ParseTree::Leaf(token));
+            tokens.advance();
+        }
+        let mut leaves = leaves.into_iter();
+        let mut leaf = || leaves.next().unwrap();
+        let node =
+            |production_id, children| ParseTree::Node(NonTerminal::Expr, production_id, children);
+        let number = |leaf| node(25, vec![leaf]);
+        let first = node(
+            23,
+            vec![
+                leaf(),
+                node(10, vec![number(leaf()), leaf(), number(leaf())]),
+                leaf(),
+            ],
+        );
+        let minus = leaf();
+        let second = node(
+            23,
+            vec![
+                leaf(),
+                node(10, vec![number(leaf()), leaf(), number(leaf())]),
+                leaf(),
+            ],
+        );
+        let times = leaf();
+        let negated = node(
+            24,
+            vec![leaf(), node(23, vec![leaf(), number(leaf()), leaf()])],
+        );
+        let tree = node(
+            10,
+            vec![first, minus, node(16, vec![second, times, negated])],
+        );
+        assert_eq!(unparse(&tree, &shape), "1 - 2 - ( 3 - 4 ) * - 5");
+    }
+
+    #[test]
+    fn empty_text_is_at_its_end() {
+        use crate::{Error, Parser};
+        let mut calc = Calc::new();
+        let error = calc.parse_text("", "raw").unwrap_err();
+        assert!(matches!(error, Error::SyntaxError(..)));
+        assert_eq!(*error.found().unwrap().tag(), Terminal::EndMarker);
+        assert!(error.to_string().contains(" at: raw:1:1."));
+    }
+
+    #[test]
+    fn parse_to_returns_attribute() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        assert_eq!(calc.parse_to("3 + 4", "raw").unwrap().value, 7.0);
+        // recovered parses' attributes are still available
+        let (outcome, _, attribute) = calc.parse_to_outcome("3 $ + 4", "raw");
+        assert!(matches!(outcome, ParseOutcome::Recovered(_)));
+        assert_eq!(attribute.value, 7.0);
+        assert!(calc.parse_to("3 $ + 4", "raw").is_err());
+        let (outcome, _, attribute) = calc.parse_to_outcome("3 +", "raw");
+        assert!(outcome.is_failed());
+        assert_eq!(attribute.value, 0.0);
+    }
+
+    #[test]
+    fn all_errors_collected() {
+        use crate::{Error, Parser};
+        let mut calc = Calc::new();
+        assert!(calc.parse_text_collect("a = 1 + 2\n", "raw").is_ok());
+        let errors = calc
+            .parse_text_collect("a = 1 $ + @@ 2\n", "raw")
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(e, Error::LexicalError(..))));
+        // including those recovered from before the parse failed
+        let errors = calc.parse_text_collect("3 $ +", "raw").unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[1], Error::SyntaxError(..)));
+        let error = calc.parse_text("3 $ +", "raw").unwrap_err();
+        assert_eq!(error.to_string(), errors[1].to_string());
+    }
+
+    #[test]
+    fn errors_are_std_errors() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let errors = calc.parse_text_collect("$ 1", "raw").unwrap_err();
+        let error: Box<dyn std::error::Error> = Box::new(errors[0].clone());
+        let source = error.source().expect("lexical errors have a source");
+        assert_eq!(source.to_string(), "Unexpected text \"$\" at: raw:1:1.");
+        let error: Box<dyn std::error::Error> =
+            Box::new(calc.parse_text("1 +", "raw").unwrap_err());
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn error_accessors() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let errors = calc.parse_text_collect("$ 1 +", "raw").unwrap_err();
+        let expected: BTreeSet<Terminal> = [
+            Terminal::Minus,
+            Terminal::LPR,
+            Terminal::Number,
+            Terminal::Id,
+        ]
+        .into();
+        assert_eq!(errors[0].expected(), &expected);
+        assert!(errors[0].found().is_none());
+        assert_eq!(errors[1].expected(), &expected);
+        let found = errors[1].found().expect("syntax errors have a found token");
+        assert_eq!(*found.tag(), Terminal::EndMarker);
+        assert_eq!(errors[1].to_string(), errors[1].message(|t| t.to_string()));
+    }
+
+    #[test]
+    fn expected_in_terminal_order() {
+        use crate::Parser;
+        let calc = Calc::new();
+        let token = calc
+            .lexical_analyzer()
+            .token_stream("=", "raw")
+            .front()
+            .unwrap();
+        let messages: Vec<String> = [
+            vec![
+                Terminal::Id,
+                Terminal::Minus,
+                Terminal::EndMarker,
+                Terminal::LPR,
+            ],
+            vec![
+                Terminal::EndMarker,
+                Terminal::LPR,
+                Terminal::Id,
+                Terminal::Minus,
+            ],
+        ]
+        .into_iter()
+        .map(|expected| {
+            let error = crate::Error::SyntaxError(token.clone(), expected.into_iter().collect());
+            calc.error_message(&error)
+        })
+        .collect();
+        assert_eq!(messages[0], messages[1]);
+        assert_eq!(
+            messages[0],
+            "Syntax Error: expected: -, (, Id or end of input found: = at: raw:1:1."
+        );
+    }
+
+    #[test]
+    fn stack_depth_limited() {
+        use crate::{Error, Parser};
+        let mut calc = Calc::new();
+        calc.max_stack_depth = Some(12);
+        assert!(calc.parse_text("a = ((1))\n", "raw").is_ok());
+        let errors = calc
+            .parse_text_collect("b = ((((((((((1))))))))))\nc = 2\n", "raw")
+            .unwrap_err();
+        assert!(matches!(errors[0], Error::StackOverflow(..)));
+        assert!(errors[0].to_string().starts_with("Stack Overflow: "));
+        assert_eq!(calc.variables.get("b"), None);
+        assert_eq!(calc.variables.get("c"), Some(&2.0));
+        calc.max_stack_depth = None;
+        assert!(calc
+            .parse_text("b = ((((((((((1))))))))))\n", "raw")
+            .is_ok());
+    }
+
+    #[test]
+    fn token_streams_parsed() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let mut tokens = calc.lexical_analyzer().token_stream("2\n", "raw");
+        tokens.inject("a = 1 + ", "prefix");
+        assert!(calc.parse_token_stream(tokens).is_ok());
+        assert_eq!(calc.variables.get("a"), Some(&3.0));
+        assert_eq!(
+            calc.parses.last(),
+            Some(&("prefix".to_string(), Some(true)))
+        );
+        let tokens = calc.lexical_analyzer().token_stream("b = 1 +\n", "raw");
+        let error = calc.parse_token_stream(tokens).unwrap_err();
+        assert!(error.to_string().ends_with("found: EOL at: raw:1:8."));
+    }
+
+    #[test]
+    fn parse_events_observed() {
+        use crate::{Error, ParseObserver, Parser};
+        #[derive(Default)]
+        struct Trace(Vec<String>);
+        impl ParseObserver<Terminal, NonTerminal> for Trace {
+            fn on_shift(&mut self, token: &lexan::Token<Terminal>, _span: &lexan::Span) {
+                self.0.push(format!("shift {}", token.tag()));
+            }
+            fn on_reduce(&mut self, lhs: &NonTerminal, production_id: u32, _span: &lexan::Span) {
+                self.0.push(format!("reduce {} by {}", lhs, production_id));
+            }
+            fn on_error(&mut self, _error: &Error<Terminal>, recovered: bool) {
+                self.0.push(format!("error recovered: {}", recovered));
+            }
+            fn on_accept(&mut self) {
+                self.0.push("accept".to_string());
+            }
+        }
+        let mut calc = Calc::new();
+        let mut trace = Trace::default();
+        assert!(calc.parse_text_observed("7\n", "raw", &mut trace).is_ok());
+        assert_eq!(trace.0.first(), Some(&"reduce SetUp by 8".to_string()));
+        assert_eq!(trace.0.iter().filter(|e| e.starts_with("shift")).count(), 2);
+        assert!(trace.0.iter().any(|e| e.starts_with("reduce Line by")));
+        assert_eq!(trace.0.last(), Some(&"accept".to_string()));
+        let mut trace = Trace::default();
+        assert!(calc
+            .parse_text_observed("7 +\n", "raw", &mut trace)
+            .is_err());
+        assert!(trace.0.contains(&"error recovered: true".to_string()));
+        assert_eq!(trace.0.last(), Some(&"accept".to_string()));
+    }
+
+    #[test]
+    fn attributes_checked() {
+        use crate::ParseStack;
+        let mut parse_stack = ParseStack::<Terminal, NonTerminal, AttributeData>::new();
+        let attribute = |value: f64| AttributeData {
+            value,
+            ..AttributeData::default()
+        };
+        parse_stack.push_terminal(Terminal::Number, attribute(1.0), 1);
+        parse_stack.push_terminal(Terminal::Plus, attribute(2.0), 2);
+        assert_eq!(parse_stack.at_len_minus_n(2).value, 1.0);
+        assert_eq!(
+            parse_stack.try_at_len_minus_n(1).map(|a| a.value),
+            Some(2.0)
+        );
+        assert!(parse_stack.try_at_len_minus_n(0).is_none());
+        assert!(parse_stack.try_at_len_minus_n(3).is_none());
+    }
+
+    #[test]
+    fn missing_tokens_inserted() {
+        use crate::{Parser, RecoveryStrategy};
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = (1 + 2\n", "raw").is_err());
+        assert_eq!(calc.variables.get("a"), None);
+        calc.recovery_strategy = RecoveryStrategy::InsertThenDelete;
+        let errors = calc
+            .parse_text_collect("a = (1 + 2\nb = 3 4\n", "raw")
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("found: EOL at: raw:1:11."));
+        assert_eq!(calc.variables.get("a"), Some(&3.0));
+        // the first terminal that fits is the one inserted
+        assert_eq!(calc.variables.get("b"), Some(&7.0));
+    }
+
+    #[test]
+    fn recovery_resyncs_on_each_line() {
+        use crate::{ParseObserver, Parser};
+        // the spans of the lines reduced
+        #[derive(Default)]
+        struct Lines(Vec<lexan::Span>);
+        impl ParseObserver<Terminal, NonTerminal> for Lines {
+            fn on_reduce(&mut self, lhs: &NonTerminal, _production_id: u32, span: &lexan::Span) {
+                if *lhs == NonTerminal::Line {
+                    self.0.push(span.clone());
+                }
+            }
+        }
+        let mut calc = Calc::new();
+        let mut lines = Lines::default();
+        let text = "a = = 1\nb = * 2\nc = 3\nd = ) 4\ne = 5\n";
+        assert!(calc.parse_text_observed(text, "raw", &mut lines).is_err());
+        for (variable, value) in [("c", 3.0), ("e", 5.0)] {
+            assert_eq!(calc.variables.get(variable), Some(&value));
+        }
+        // recovery from the third error resumed after the previous lines (rather than
+        // swallowing them) so the fourth line was reduced on its own
+        let starts: Vec<String> = lines.0.iter().map(|span| span.start.to_string()).collect();
+        for line in 1..=5 {
+            assert!(starts.contains(&format!("raw:{line}:1")));
+        }
+    }
+
+    #[test]
+    fn reported_messages_display_terminals() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("a = 1\n)\n", "raw").is_err());
+        assert_eq!(calc.reported.len(), 1);
+        assert!(calc.reported[0].contains("end of input"));
+        assert!(!calc.reported[0].contains("EndMarker"));
+    }
+
+    #[test]
+    fn reported_errors_may_abort() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        let text = "a = = 1\nb = 2\n";
+        assert!(matches!(
+            calc.parse_text_outcome(text, "raw").0,
+            ParseOutcome::Recovered(_)
+        ));
+        assert_eq!(calc.variables.remove("b"), Some(2.0));
+        assert_eq!(calc.reported.len(), 1);
+        calc.reported.clear();
+        calc.abort_on_error = true;
+        match calc.parse_text_outcome(text, "raw").0 {
+            ParseOutcome::Failed(errors) => assert_eq!(errors.len(), 1),
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(calc.variables.get("b"), None);
+        // the abort was decided by the overriding report_error()
+        assert_eq!(calc.reported.len(), 1);
+        assert!(calc.reported[0].starts_with("Syntax Error: "));
+    }
+
+    #[test]
+    fn recovery_from_bottom_state() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let (outcome, _) = calc.parse_text_outcome("+ 1\na = 2\n", "raw");
+        match outcome {
+            crate::ParseOutcome::Recovered(errors) => assert_eq!(errors.len(), 1),
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(calc.variables.get("a"), Some(&2.0));
+    }
+
+    #[test]
+    fn strings_interned() {
+        let mut interner = crate::Interner::new();
+        assert!(interner.is_empty());
+        let abc = interner.intern("abc");
+        let xyz = interner.intern("xyz");
+        assert_ne!(abc, xyz);
+        assert_eq!(interner.intern("abc"), abc);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("xyz"), Some(xyz));
+        assert_eq!(interner.get("pqr"), None);
+        assert_eq!(interner.resolve(abc), "abc");
+    }
+
+    #[test]
+    fn completions_listed() {
+        use crate::{ParseStack, Parser};
+        use Terminal::*;
+        let calc = Calc::new();
+        let parse_stack = ParseStack::new();
+        assert_eq!(
+            calc.completions(&parse_stack, 1),
+            vec![vec![Minus], vec![LPR], vec![Number], vec![Id]]
+        );
+        // those that could be the whole input come first
+        let completions = calc.completions(&parse_stack, 2);
+        assert_eq!(completions[..2], [vec![Number], vec![Id]]);
+        assert!(completions[2..].iter().all(|sequence| sequence.len() == 2));
+        assert!(completions.contains(&vec![Id, Assign]));
+        assert!(!completions.contains(&vec![Number, Assign]));
+    }
+
+    #[test]
+    fn illegal_states_checked() {
+        use crate::{Action, Error, ParseStack, Parser};
+        let calc = Calc::new();
+        let parse_stack = ParseStack::new();
+        let token = calc
+            .lexical_analyzer()
+            .token_stream("7", "raw")
+            .front()
+            .unwrap();
+        assert_eq!(
+            calc.next_action_checked(0, &parse_stack, &token),
+            Ok(Action::Reduce(8))
+        );
+        assert_eq!(calc.next_action_checked(99, &parse_stack, &token), Err(99));
+        let error = Error::<Terminal>::InternalTableError(99, BTreeSet::new());
+        assert!(error.expected().is_empty());
+        assert_eq!(
+            error.to_string(),
+            "Internal Error: the parse tables have no entries for state 99."
+        );
+    }
+
+    #[test]
+    fn unexpected_text_is_skipped() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        let (outcome, _) = calc.parse_text_outcome("a = 1 $ + @@ 2\n", "raw");
+        match outcome {
+            ParseOutcome::Recovered(errors) => assert_eq!(errors.len(), 2),
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(calc.variables.get("a"), Some(&3.0));
+        // too much garbage in a row and the parse stack's error recovery takes over
+        let (outcome, _) = calc.parse_text_outcome("b = 1 $ @ $ @ + 2\nc = 4\n", "raw");
+        match outcome {
+            ParseOutcome::Recovered(errors) => assert_eq!(errors.len(), 4),
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(calc.variables.get("b"), None);
+        assert_eq!(calc.variables.get("c"), Some(&4.0));
+    }
+
+    #[test]
+    fn semantic_errors_are_recovered_from() {
+        use crate::{Error, ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        calc.reject_undefined = true;
+        let (outcome, _) = calc.parse_text_outcome("a = b + 1\nc = 4\n", "raw");
+        match outcome {
+            ParseOutcome::Recovered(errors) => {
+                assert_eq!(errors.len(), 1);
+                assert!(matches!(errors[0], Error::SemanticError(..)));
+                assert_eq!(
+                    calc.error_message(&errors[0]),
+                    "Semantic Error: undefined variable at: raw:1:7."
+                );
+            }
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(calc.errors & UNDEFINED_VARIABLE, 0);
+        assert_eq!(calc.variables.get("a"), None);
+        assert_eq!(calc.variables.get("c"), Some(&4.0));
+    }
+
+    #[cfg(feature = "coverage")]
+    #[test]
+    fn bit_sets() {
+        let mut set = crate::BitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(70));
+        assert!(!set.insert(3));
+        assert!(set.contains(70) && !set.contains(71));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.missing(5), vec![0, 1, 2, 4]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[cfg(feature = "coverage")]
+    #[test]
+    fn productions_covered() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("7", "raw").is_ok());
+        // the same reductions as `drive_follows_tables()`
+        assert_eq!(
+            calc.covered_productions().missing(9),
+            vec![0, 1, 3, 4, 5, 6, 7]
+        );
+        // and the coverage accumulates over parses
+        assert!(calc.parse_text("a = 7", "raw").is_ok());
+        assert_eq!(
+            calc.covered_productions().missing(9),
+            vec![0, 1, 4, 5, 6, 7]
+        );
+        assert!(calc.covered_productions().contains(25));
+    }
+
+    #[cfg(feature = "derivation")]
+    #[test]
+    fn derivation_recorded() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        assert!(calc.parse_text("7", "raw").is_ok());
+        // the same reductions as `drive_follows_tables()`
+        assert_eq!(calc.derivation(), &[8, 25, 2]);
+        assert!(calc.parse_text("a = 7", "raw").is_ok());
+        assert_eq!(calc.derivation().len(), 3);
+    }
+
+    #[test]
+    fn terminals_displayed_by_parser() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let error = calc.parse_text("a = 1 )", "raw").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with(", EOL or EndMarker found: ) at: raw:1:7."));
+        assert!(calc
+            .error_message(&error)
+            .ends_with(", EOL or end of input found: ) at: raw:1:7."));
+    }
+
+    #[test]
+    fn failure_explained() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let explanation = calc.explain_failure("a = 1 )", "raw");
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines[0], "raw: failed to parse.");
+        assert_eq!(lines[1], "Parsed: SetUp Id = Expr");
+        assert_eq!(lines[2], "Found: ) \")\" at: raw:1:7 to raw:1:8");
+        assert!(lines[3].ends_with(", EOL or end of input"));
+        let explanation = calc.explain_failure("$", "raw");
+        assert!(explanation.contains("Parsed: nothing\nFound: Unexpected text \"$\" at: raw:1:1"));
+        assert_eq!(
+            calc.explain_failure("a = 1", "raw"),
+            "raw: parses without errors."
+        );
+        assert_eq!(calc.variables.get("a"), Some(&1.0));
+    }
+
+    #[test]
+    fn parse_traced() {
+        use crate::Parser;
+        let mut calc = Calc::new();
+        let mut trace = vec![];
+        assert!(calc
+            .parse_text_trace("a = 1 + 2", "raw", &mut trace)
+            .is_ok());
+        let trace = String::from_utf8(trace).unwrap();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "[^] reduce SetUp:");
+        assert_eq!(lines[1], "[^ SetUp] shift Id \"a\"");
+        assert_eq!(
+            lines[8],
+            "[^ SetUp Id = Expr + Expr] reduce Expr: Expr + Expr"
+        );
+        assert_eq!(lines[10], "[^ Line] accept");
+        // the semantic actions are done as usual
+        assert_eq!(calc.variables.get("a"), Some(&3.0));
+        let mut trace = vec![];
+        assert!(calc.parse_text_trace("a = 1 )", "raw", &mut trace).is_err());
+        let trace = String::from_utf8(trace).unwrap();
+        let last = trace.lines().last().unwrap();
+        assert!(last.starts_with("[^ SetUp Id = Expr] error: Syntax Error: "));
+    }
+
+    #[test]
+    fn panics_are_diagnosed() {
+        use crate::{Action, Parser};
+        let calc = Calc::new();
+        let mut tokens = calc.lexical_analyzer().token_stream("7", "raw");
+        let trace = [Action::Reduce(8), Action::Shift(9)];
+        let parse_stack = calc.drive(&trace, &mut tokens).unwrap();
+        let history = std::collections::VecDeque::from(["Number@raw:1:1".to_string()]);
+        assert_eq!(
+            parse_stack.description(&history),
+            "Parse stack: ^@0 SetUp@2 Number@9\nRecent tokens: Number@raw:1:1"
+        );
+        let described = std::cell::Cell::new(false);
+        let describe = || {
+            described.set(true);
+            String::new()
+        };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            crate::diagnosed(true, describe, || panic!("illegal state: 99"))
+        }));
+        assert!(result.is_err());
+        assert!(described.get());
+    }
+
+    // Locations within a document of which the parsed text starts at line `first_line`
+    #[derive(Debug, Clone)]
+    struct DocumentLocation(usize, usize);
+
+    impl fmt::Display for DocumentLocation {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "line {} column {}", self.0, self.1)
+        }
+    }
+
+    impl From<lexan::Location> for DocumentLocation {
+        fn from(location: lexan::Location) -> Self {
+            Self(location.line_number(), location.offset())
+        }
+    }
+
+    impl From<crate::Error<Terminal, DocumentLocation>> for AttributeData {
+        fn from(_error: crate::Error<Terminal, DocumentLocation>) -> Self {
+            AttributeData::default()
+        }
+    }
+
+    struct DocumentCalc {
+        calc: Calc,
+        first_line: usize,
+    }
+
+    impl ReportError<Terminal, DocumentLocation> for DocumentCalc {}
+
+    impl crate::Parser<Terminal, NonTerminal, AttributeData, DocumentLocation> for DocumentCalc {
+        fn lexical_analyzer(&self) -> &lexan::LexicalAnalyzer<Terminal> {
+            self.calc.lexical_analyzer()
+        }
+
+        fn location(&self, location: &lexan::Location) -> DocumentLocation {
+            DocumentLocation(
+                location.line_number() + self.first_line - 1,
+                location.offset(),
+            )
+        }
+
+        fn viable_error_recovery_states(tag: &Terminal) -> BTreeSet<u32> {
+            Calc::viable_error_recovery_states(tag)
+        }
+
+        fn error_goto_state(state: u32) -> u32 {
+            Calc::error_goto_state(state)
+        }
+
+        fn look_ahead_set(state: u32) -> BTreeSet<Terminal> {
+            Calc::look_ahead_set(state)
+        }
+
+        fn next_action(
+            &self,
+            state: u32,
+            attributes: &crate::ParseStack<Terminal, NonTerminal, AttributeData>,
+            token: &lexan::Token<Terminal>,
+        ) -> crate::Action {
+            self.calc.next_action(state, attributes, token)
         }
 
         fn production_data(production_id: u32) -> (NonTerminal, usize) {
-            match production_id {
-                1 => (NonTerminal::Line, 2),
-                2 => (NonTerminal::Line, 2),
-                3 => (NonTerminal::Line, 4),
-                4 => (NonTerminal::Line, 4),
-                5 => (NonTerminal::Line, 3),
-                6 => (NonTerminal::Line, 2),
-                7 => (NonTerminal::Line, 1),
-                8 => (NonTerminal::SetUp, 0),
-                9 => (NonTerminal::Expr, 3),
-                10 => (NonTerminal::Expr, 3),
-                11 => (NonTerminal::Expr, 3),
-                12 => (NonTerminal::Expr, 3),
-                13 => (NonTerminal::Expr, 3),
-                14 => (NonTerminal::Expr, 3),
-                15 => (NonTerminal::Expr, 3),
-                16 => (NonTerminal::Expr, 3),
-                17 => (NonTerminal::Expr, 3),
-                18 => (NonTerminal::Expr, 3),
-                19 => (NonTerminal::Expr, 3),
-                20 => (NonTerminal::Expr, 3),
-                21 => (NonTerminal::Expr, 3),
-                22 => (NonTerminal::Expr, 3),
-                23 => (NonTerminal::Expr, 3),
-                24 => (NonTerminal::Expr, 2),
-                25 => (NonTerminal::Expr, 1),
-                26 => (NonTerminal::Expr, 1),
-                27 => (NonTerminal::Expr, 1),
-                _ => panic!("malformed production data table"),
-            }
+            Calc::production_data(production_id)
         }
 
         fn goto_state(lhs: &NonTerminal, current_state: u32) -> u32 {
-            match current_state {
-                0 => match lhs {
-                    NonTerminal::Line => 1,
-                    NonTerminal::SetUp => 2,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                2 => match lhs {
-                    NonTerminal::Expr => 5,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                4 => match lhs {
-                    NonTerminal::Line => 10,
-                    NonTerminal::SetUp => 2,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                7 => match lhs {
-                    NonTerminal::Expr => 16,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                8 => match lhs {
-                    NonTerminal::Expr => 18,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                11 => match lhs {
-                    NonTerminal::Expr => 19,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                12 => match lhs {
-                    NonTerminal::Expr => 20,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                13 => match lhs {
-                    NonTerminal::Expr => 21,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                14 => match lhs {
-                    NonTerminal::Expr => 22,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                15 => match lhs {
-                    NonTerminal::Expr => 23,
-                    _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-                },
-                _ => panic!("Malformed goto table: ({lhs}, {current_state})"),
-            }
+            Calc::goto_state(lhs, current_state)
         }
 
         fn do_semantic_action<F: FnMut(String, String)>(
             &mut self,
             production_id: u32,
             rhs: Vec<AttributeData>,
-            mut inject: F,
+            inject: F,
         ) -> AttributeData {
-            let mut lhs = AttributeData::default();
-            // test that multiple injects are OK
-            inject(String::new(), String::new());
-            inject(String::new(), String::new());
-            match production_id {
-                1 | 4 => {
-                    self.report_errors();
-                }
-                2 => {
-                    println!("{}", rhs[2 - 1].value);
-                }
-                3 => {
-                    self.variables
-                        .insert(rhs[2 - 1].id.clone(), rhs[4 - 1].value);
-                }
-                7 => {
-                    self.errors |= SYNTAX_ERROR;
-                }
-                8 => {
-                    self.errors = 0;
-                }
-                9 => {
-                    lhs.value = rhs[3 - 1].value;
-                }
-                10 => {
-                    lhs.value = rhs[1 - 1].value;
-                }
-                11 => {
-                    lhs.value = rhs[1 - 1].value + rhs[3 - 1].value;
-                }
-                12 => {
-                    lhs.value = -rhs[3 - 1].value;
-                }
-                13 => {
-                    lhs.value = rhs[1 - 1].value;
-                }
-                14 => {
-                    lhs.value = rhs[1 - 1].value - rhs[3 - 1].value;
-                }
-                15 => {
-                    lhs.value = -rhs[3 - 1].value;
-                }
-                16 => {
-                    lhs.value = rhs[3 - 1].value;
-                }
-                17 => {
-                    lhs.value = rhs[1 - 1].value;
-                }
-                18 => {
-                    lhs.value = rhs[1 - 1].value * rhs[3 - 1].value;
-                }
-                19 => {
-                    lhs.value = rhs[1 - 1].value;
-                }
-                20 => {
-                    self.errors |= DIVIDE_BY_ZERO;
-                }
-                21 => {
-                    lhs.value = 0.0;
-                }
-                22 => {
-                    lhs.value = rhs[1 - 1].value / rhs[3 - 1].value;
-                }
-                23 => {
-                    lhs.value = rhs[2 - 1].value;
-                }
-                24 => {
-                    lhs.value = -rhs[2 - 1].value;
-                }
-                25 => {
-                    lhs.value = rhs[1 - 1].value;
-                }
-                26 => {
-                    lhs.value = *self.variables.get(&rhs[1 - 1].id).unwrap();
-                }
-                27 => {
-                    self.errors |= UNDEFINED_VARIABLE;
-                    lhs.value = 0.0;
+            self.calc.do_semantic_action(production_id, rhs, inject)
+        }
+    }
+
+    #[test]
+    fn errors_have_parser_locations() {
+        use crate::Parser;
+        let mut calc = DocumentCalc {
+            calc: Calc::new(),
+            first_line: 10,
+        };
+        assert!(calc.parse_text("a = 1\n", "raw").is_ok());
+        assert_eq!(calc.calc.variables.get("a"), Some(&1.0));
+        let error = calc.parse_text("b = 2 +\n", "raw").unwrap_err();
+        assert_eq!(error.found().unwrap().location().0, 10);
+        assert!(error
+            .to_string()
+            .ends_with("found: EOL at: line 10 column 8."));
+        let errors = calc.parse_text_collect("c = 1\n\nd = )\ne = 3\n", "raw");
+        assert!(errors.unwrap_err()[0]
+            .to_string()
+            .ends_with("found: ) at: line 12 column 5."));
+        assert_eq!(calc.calc.variables.get("e"), Some(&3.0));
+    }
+
+    #[test]
+    fn observers_have_parser_locations() {
+        use crate::{ParseObserver, ParseTree, ParseTreeBuilder, Parser};
+        // the spans of the lines reduced
+        #[derive(Default)]
+        struct Lines(Vec<lexan::Span<DocumentLocation>>);
+        impl ParseObserver<Terminal, NonTerminal, DocumentLocation> for Lines {
+            fn on_reduce(
+                &mut self,
+                lhs: &NonTerminal,
+                _production_id: u32,
+                span: &lexan::Span<DocumentLocation>,
+            ) {
+                if *lhs == NonTerminal::Line {
+                    self.0.push(span.clone());
                 }
-                _ => (),
             }
-            lhs
         }
+        let mut calc = DocumentCalc {
+            calc: Calc::new(),
+            first_line: 10,
+        };
+        let mut lines = Lines::default();
+        assert!(calc
+            .parse_text_observed("a = 1\nb = 2\n", "raw", &mut lines)
+            .is_ok());
+        let starts: Vec<usize> = lines.0.iter().map(|span| span.start.0).collect();
+        assert!(starts.contains(&10) && starts.contains(&11));
+
+        let mut builder = ParseTreeBuilder::new(Calc::production_data);
+        assert!(calc
+            .parse_text_observed("c = 3\n", "raw", &mut builder)
+            .is_ok());
+        // the leaves (i.e. the tokens) are relocated too
+        fn first_leaf<T: Copy + fmt::Display + Eq, N, L>(
+            tree: &ParseTree<T, N, L>,
+        ) -> Option<&lexan::Token<T, L>> {
+            match tree {
+                ParseTree::Leaf(token) => Some(token),
+                ParseTree::Node(_, _, children) => children.iter().find_map(first_leaf),
+            }
+        }
+        let tree = builder.take_tree().unwrap();
+        assert_eq!(first_leaf(&tree).unwrap().location().0, 10);
+        assert!(calc
+            .parse_text_observed("d = 4 +\n", "raw", &mut builder)
+            .is_err());
+        assert!(builder.take_tree().is_none());
     }
 
     #[test]
-    fn calc_works() {
-        use crate::Parser;
+    fn drive_follows_tables() {
+        use crate::{Action, Parser};
+        let calc = Calc::new();
+        let trace = [
+            Action::Reduce(8),
+            Action::Shift(9),
+            Action::Reduce(25),
+            Action::Reduce(2),
+            Action::Accept,
+        ];
+        let mut tokens = calc.lexical_analyzer().token_stream("7", "raw");
+        assert!(calc.drive(&trace, &mut tokens).is_ok());
+        let mut tokens = calc.lexical_analyzer().token_stream("7", "raw");
+        assert_eq!(calc.drive(&trace[..1], &mut tokens).unwrap().max_depth(), 2);
+        let mut tokens = calc.lexical_analyzer().token_stream("(7", "raw");
+        assert_eq!(
+            calc.drive(&trace, &mut tokens).err(),
+            Some((1, Action::Shift(7)))
+        );
+    }
+
+    #[test]
+    fn parse_trees_built() {
+        use crate::{unparse, Associativity, ParseTree, ParseTreeBuilder, Parser, ProductionShape};
+        let shape = |production_id: u32| match production_id {
+            9..=15 => ProductionShape::Infix(Associativity::Left, 1),
+            16..=22 => ProductionShape::Infix(Associativity::Left, 2),
+            23 => ProductionShape::Parenthesized,
+            24 => ProductionShape::Prefix(3),
+            _ => ProductionShape::Plain,
+        };
         let mut calc = Calc::new();
-        assert!(calc.parse_text("a = (3 + 4)\n", "raw").is_ok());
-        assert_eq!(calc.variables.get("a"), Some(&7.0));
-        assert!(calc.parse_text("b = a * 5\n", "raw").is_ok());
-        assert_eq!(calc.variables.get("b"), Some(&35.0));
+        let mut builder = ParseTreeBuilder::new(Calc::production_data);
+        let text = "a = ((1 * 2) + 3) - (4 - (5 + 6)) * -(7)\n";
+        assert!(calc.parse_text_observed(text, "raw", &mut builder).is_ok());
+        let tree = builder.take_tree().unwrap();
+        assert!(matches!(tree, ParseTree::Node(NonTerminal::Line, _, _)));
+        assert_eq!(
+            unparse(&tree, &shape),
+            "a = 1 * 2 + 3 - ( 4 - ( 5 + 6 ) ) * - 7 \n"
+        );
+        assert!(builder.take_tree().is_none());
+        assert!(calc
+            .parse_text_observed("b = 1 +\n", "raw", &mut builder)
+            .is_err());
+        assert!(builder.take_tree().is_none());
+    }
+
+    #[test]
+    fn state_path_reported() {
+        use crate::{Action, Error, Parser, Symbol};
+        let mut calc = Calc::new();
+        let mut tokens = calc.lexical_analyzer().token_stream("7 7", "raw");
+        let parse_stack = calc
+            .drive(&[Action::Reduce(8), Action::Shift(9)], &mut tokens)
+            .unwrap();
+        assert_eq!(parse_stack.state_path(None).len(), 3);
+        assert_eq!(parse_stack.state_path(None)[0], (Symbol::Start, 0));
+        assert_eq!(
+            parse_stack.state_path(Some(1)),
+            vec![(Symbol::Terminal(Terminal::Number), 9)]
+        );
+        assert_eq!(parse_stack.state_path(Some(5)).len(), 3);
+        let token = tokens.front().unwrap();
+        let error = Error::SyntaxError(token, Calc::look_ahead_set(9));
+        assert_eq!(
+            calc.reported_message(&error, &parse_stack),
+            calc.error_message(&error)
+        );
+        calc.state_path_len = 1;
+        assert!(calc
+            .reported_message(&error, &parse_stack)
+            .ends_with(".\nState path: ... Number@9"));
+    }
+
+    #[test]
+    fn reparse_resumes_from_checkpoint() {
+        use crate::{ParseOutcome, Parser};
+        let mut calc = Calc::new();
+        let (outcome, checkpoints) = calc.parse_text_checkpointed("a = 1\nb = a + 2\n", "raw", 2);
+        assert!(matches!(outcome, ParseOutcome::Clean));
+        assert_eq!(calc.variables.get("b"), Some(&3.0));
+        let token_counts: Vec<usize> = checkpoints.iter().map(|c| c.token_count()).collect();
+        assert_eq!(token_counts, vec![0, 2, 4, 6, 8, 10]);
+        // had "a = 1" been redone "a" would be 1 again
+        calc.variables.insert("a".to_string(), 10.0);
+        // "b = a + 2\n" edited to "b = a * 5\n" (i.e. after 7 tokens)
+        let (outcome, _) = calc.reparse(checkpoints[3].clone(), 7, " * 5\n");
+        assert!(matches!(outcome, ParseOutcome::Clean));
+        assert_eq!(calc.variables.get("a"), Some(&10.0));
+        assert_eq!(calc.variables.get("b"), Some(&50.0));
+        // states that depend on edited tokens are discarded: here all but the start (as
+        // both lines have been reduced to one) so "a = 1" is replayed
+        let (outcome, checkpoints) = calc.reparse(checkpoints[5].clone(), 7, " * 5\n");
+        assert!(matches!(outcome, ParseOutcome::Clean));
+        assert_eq!(calc.variables.get("a"), Some(&1.0));
+        assert_eq!(calc.variables.get("b"), Some(&5.0));
+        // and those from a reparse can be used in turn
+        let (outcome, _) = calc.reparse(checkpoints[0].clone(), 4, "c = $\n");
+        match outcome {
+            ParseOutcome::Recovered(errors) | ParseOutcome::Failed(errors) => {
+                assert!(errors[0].to_string().contains(" at: raw:2:5."))
+            }
+            _ => panic!("unexpected outcome: {outcome:?}"),
+        }
     }
 }
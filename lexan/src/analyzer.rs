@@ -1,7 +1,8 @@
-pub use std::{
-    fmt::{self, Debug, Display},
-    sync::Arc,
-};
+pub use alloc::{collections::BTreeMap, sync::Arc};
+pub use core::fmt::{self, Debug, Display};
+
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
 
 use crate::lexicon::Lexicon;
 
@@ -25,6 +26,15 @@ impl Location {
         }
     }
 
+    /// A location at a known position, e.g. for errors found by pre-processing text
+    pub fn at(label: &str, line_number: usize, offset: usize) -> Self {
+        Self {
+            line_number,
+            offset,
+            label: label.to_string(),
+        }
+    }
+
     pub fn line_number(&self) -> usize {
         self.line_number
     }
@@ -36,6 +46,26 @@ impl Location {
     pub fn label(&self) -> &String {
         &self.label
     }
+
+    // Move this location to just after `text`
+    fn advance(&mut self, text: &str) {
+        let length = text.len();
+        let mut i = 0;
+        while i < length {
+            if let Some(eol_i) = text[i..].find("\r\n") {
+                self.line_number += 1;
+                self.offset = 1;
+                i += eol_i + 2;
+            } else if let Some(eol_i) = text[i..].find('\n') {
+                self.line_number += 1;
+                self.offset = 1;
+                i += eol_i + 1;
+            } else {
+                self.offset += length - i;
+                i = length;
+            };
+        }
+    }
 }
 
 impl Display for Location {
@@ -56,6 +86,25 @@ impl Display for Location {
     }
 }
 
+/// The extent of some text: from its start to the location just after its end (or,
+/// like a relocated `Token`, at some other type of location)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Span<L = Location> {
+    pub start: L,
+    pub end: L,
+}
+
+impl<L: Clone> Span<L> {
+    pub fn new(start: L, end: L) -> Self {
+        Self { start, end }
+    }
+
+    /// An empty span at `location`
+    pub fn empty_at(location: &L) -> Self {
+        Self::new(location.clone(), location.clone())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Error<T: Display + Copy> {
     UnexpectedText(String, Location),
@@ -75,6 +124,26 @@ impl<T: Display + Copy> Error<T> {
     pub fn is_advance_when_empty(&self) -> bool {
         matches!(self, Error::AdvancedWhenEmpty(_))
     }
+
+    pub fn location(&self) -> &Location {
+        match self {
+            Error::UnexpectedText(_, location)
+            | Error::AmbiguousMatches(_, _, location)
+            | Error::AdvancedWhenEmpty(location) => location,
+        }
+    }
+
+    /// The extent of the offending text (empty for advances past the end)
+    pub fn span(&self) -> Span {
+        let mut end = self.location().clone();
+        match self {
+            Error::UnexpectedText(text, _) | Error::AmbiguousMatches(_, text, _) => {
+                end.advance(text)
+            }
+            Error::AdvancedWhenEmpty(_) => (),
+        }
+        Span::new(self.location().clone(), end)
+    }
 }
 
 impl<T: Debug + Display + Copy> Display for Error<T> {
@@ -94,16 +163,27 @@ impl<T: Debug + Display + Copy> Display for Error<T> {
     }
 }
 
-impl<T: Debug + Display + Copy> std::error::Error for Error<T> {}
+impl<T: Debug + Display + Copy> core::error::Error for Error<T> {}
 
+/// A token found at a `Location` (or, once relocated by `with_location()`, at some
+/// other type of location, e.g. an offset within a virtual document).
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Token<T: Display + Copy + Eq> {
+pub struct Token<T: Display + Copy + Eq, L = Location> {
     tag: T,
     lexeme: String,
-    location: Location,
+    location: L,
 }
 
-impl<T: Display + Copy + Eq> Token<T> {
+impl<T: Display + Copy + Eq, L> Token<T, L> {
+    /// A token made elsewhere than by a token stream, e.g. by an asynchronous lexer
+    pub fn new(tag: T, lexeme: String, location: L) -> Self {
+        Self {
+            tag,
+            lexeme,
+            location,
+        }
+    }
+
     pub fn tag(&self) -> &T {
         &self.tag
     }
@@ -112,9 +192,38 @@ impl<T: Display + Copy + Eq> Token<T> {
         &self.lexeme
     }
 
-    pub fn location(&self) -> &Location {
+    pub fn location(&self) -> &L {
         &self.location
     }
+
+    /// This token with its tag replaced
+    pub fn with_tag(self, tag: T) -> Self {
+        Self { tag, ..self }
+    }
+
+    /// This token with its lexeme replaced (e.g. by a normalised version)
+    pub fn with_lexeme(self, lexeme: String) -> Self {
+        Self { lexeme, ..self }
+    }
+
+    /// This token with its location replaced (possibly by one of a different type)
+    pub fn with_location<M>(self, location: M) -> Token<T, M> {
+        Token {
+            tag: self.tag,
+            lexeme: self.lexeme,
+            location,
+        }
+    }
+}
+
+impl<T: Display + Copy + Eq> Token<T> {
+    /// The extent of this token's lexeme (which may differ from the text matched if the
+    /// lexeme has been replaced)
+    pub fn span(&self) -> Span {
+        let mut end = self.location.clone();
+        end.advance(&self.lexeme);
+        Span::new(self.location.clone(), end)
+    }
 }
 
 struct BasicTokenStream<T>
@@ -126,20 +235,31 @@ where
     index: usize,
     location: Location,
     front: Option<Result<Token<T>, Error<T>>>,
+    // where scanning for the current front started (i.e. before skipping)
+    front_start: (usize, Location),
+    // whether the lexicon's skip rules are applied before each token
+    skipping: bool,
 }
 
 impl<T> BasicTokenStream<T>
 where
     T: Debug + Display + Copy + Eq + Ord,
 {
-    pub fn new(lexicon: &Arc<Lexicon<T>>, text: &str, label: &str) -> Self {
-        let location = Location::new(label);
+    pub fn new(
+        lexicon: &Arc<Lexicon<T>>,
+        text: String,
+        location: &Location,
+        skipping: bool,
+    ) -> Self {
+        let location = location.clone();
         let mut bts = Self {
             lexicon: Arc::clone(lexicon),
-            text: text.to_string(),
+            text,
+            front_start: (0, location.clone()),
             location,
             index: 0,
             front: None,
+            skipping,
         };
         bts.advance();
         bts
@@ -154,36 +274,32 @@ where
     }
 
     fn advance(&mut self) {
+        self.front_start = (self.index, self.location.clone());
         self.front = self.next();
     }
 
+    // Rescan the current front (and what follows it) using a different lexicon
+    fn set_lexicon(&mut self, lexicon: &Arc<Lexicon<T>>) {
+        self.lexicon = Arc::clone(lexicon);
+        self.index = self.front_start.0;
+        self.location = self.front_start.1.clone();
+        self.advance();
+    }
+
     fn location(&self) -> Location {
         self.location.clone()
     }
 
     fn incr_index_and_location(&mut self, length: usize) {
         let next_index = self.index + length;
-        let slice = &self.text[self.index..next_index];
-        let mut i = 0;
-        while i < length {
-            if let Some(eol_i) = slice[i..].find("\r\n") {
-                self.location.line_number += 1;
-                self.location.offset = 1;
-                i += eol_i + 2;
-            } else if let Some(eol_i) = slice[i..].find('\n') {
-                self.location.line_number += 1;
-                self.location.offset = 1;
-                i += eol_i + 1;
-            } else {
-                self.location.offset += length - i;
-                i = length;
-            };
-        }
+        self.location.advance(&self.text[self.index..next_index]);
         self.index = next_index;
     }
 
     fn next(&mut self) -> Option<Result<Token<T>, Error<T>>> {
-        self.incr_index_and_location(self.lexicon.skippable_count(&self.text[self.index..]));
+        if self.skipping {
+            self.incr_index_and_location(self.lexicon.skippable_count(&self.text[self.index..]));
+        }
         if self.index >= self.text.len() {
             return None;
         }
@@ -247,9 +363,17 @@ pub struct TokenStream<T>
 where
     T: Debug + Display + Copy + Eq + Ord,
 {
-    lexicon: Arc<Lexicon<T>>,
+    lexicons: Vec<Arc<Lexicon<T>>>,
+    mode_switches: Arc<BTreeMap<T, usize>>,
+    mode: usize,
     token_stream_stack: Vec<BasicTokenStream<T>>,
     front: Result<Token<T>, Error<T>>,
+    skipping: bool,
+    // tokens (etc.) pushed in front of the current front, the next to come last
+    pushed: Vec<Result<Token<T>, Error<T>>>,
+    // the number of tokens (etc.) advanced past and, if recording, the tokens themselves
+    consumed: usize,
+    recorded: Option<Vec<Result<Token<T>, Error<T>>>>,
 }
 
 impl<T> TokenStream<T>
@@ -257,49 +381,152 @@ where
     T: Debug + Display + Copy + Eq + Ord,
 {
     pub fn new(lexicon: &Arc<Lexicon<T>>, text: &str, label: &str) -> Self {
+        Self::with_modes(
+            &[Arc::clone(lexicon)],
+            &Arc::default(),
+            text.to_string(),
+            label,
+        )
+    }
+
+    pub(crate) fn with_modes(
+        lexicons: &[Arc<Lexicon<T>>],
+        mode_switches: &Arc<BTreeMap<T, usize>>,
+        text: String,
+        label: &str,
+    ) -> Self {
         let mut stream = Self {
-            lexicon: Arc::clone(lexicon),
+            lexicons: lexicons.to_vec(),
+            mode_switches: Arc::clone(mode_switches),
+            mode: 0,
             token_stream_stack: vec![],
             front: Err(Error::AdvancedWhenEmpty(Location::default())),
+            skipping: true,
+            pushed: vec![],
+            consumed: 0,
+            recorded: None,
         };
-        stream.inject(text, label);
+        stream.inject_owned_at(text, &Location::new(label));
         stream
     }
 
     pub fn is_empty(&self) -> bool {
-        self.token_stream_stack.len() == 0
+        self.token_stream_stack.is_empty() && self.pushed.is_empty()
     }
 
     pub fn front(&self) -> Result<Token<T>, Error<T>> {
         self.front.clone()
     }
 
+    /// The index of the lexical mode currently in use (0 being the initial mode)
+    pub fn mode(&self) -> usize {
+        self.mode
+    }
+
+    /// Switch to the given lexical mode.  The current front token (and all text
+    /// following it) is rescanned using the new mode's lexicon.
+    pub fn set_mode(&mut self, mode: usize) {
+        assert!(mode < self.lexicons.len(), "{mode}: unknown lexical mode");
+        if mode == self.mode {
+            return;
+        }
+        self.mode = mode;
+        let pushed_front = (!self.pushed.is_empty()).then(|| self.front.clone());
+        let lexicon = Arc::clone(&self.lexicons[mode]);
+        for token_stream in self.token_stream_stack.iter_mut() {
+            token_stream.set_lexicon(&lexicon);
+        }
+        if let Some(token_stream) = self.token_stream_stack.last() {
+            if let Some(front) = token_stream.front() {
+                self.front = front;
+            } else {
+                self.pop_empty_streams();
+            }
+        }
+        // the rescanned front belongs behind any pushed in front of it
+        if let Some(front) = pushed_front {
+            self.pushed[0] = core::mem::replace(&mut self.front, front);
+        }
+    }
+
+    /// Whether the skip rules are applied (as they are initially) before the tokens
+    /// after the current front, i.e. from the next `advance()` on.  With them off
+    /// text such as white space must be matched by tokens (or it's unexpected text).
+    pub fn set_skipping(&mut self, skipping: bool) {
+        self.skipping = skipping;
+        for token_stream in self.token_stream_stack.iter_mut() {
+            token_stream.skipping = skipping;
+        }
+    }
+
     pub fn inject(&mut self, text: &str, label: &str) {
-        let token_stream = BasicTokenStream::new(&self.lexicon, text, label);
+        self.inject_at(text, &Location::new(label));
+    }
+
+    /// As for `inject()` but with `text` starting at `location` (rather than at the
+    /// start of the first line of some labelled text).
+    pub fn inject_at(&mut self, text: &str, location: &Location) {
+        self.inject_owned_at(text.to_string(), location);
+    }
+
+    // As for `inject_at()` but taking ownership of `text` (rather than copying it)
+    fn inject_owned_at(&mut self, text: String, location: &Location) {
+        let token_stream =
+            BasicTokenStream::new(&self.lexicons[self.mode], text, location, self.skipping);
         if !token_stream.is_empty() {
             self.front = token_stream.front().unwrap();
             self.token_stream_stack.push(token_stream);
+        } else if self.token_stream_stack.is_empty() && self.pushed.is_empty() {
+            // there's nothing (but skippable text) to analyse so the input ends here
+            self.end_at(&token_stream.location());
         }
     }
 
+    /// Make `item` the front (with the current front following it) e.g. to replay
+    /// tokens consumed earlier.  Pushed items aren't rescanned by mode switches.
+    pub fn push_front(&mut self, item: Result<Token<T>, Error<T>>) {
+        let front = core::mem::replace(&mut self.front, item);
+        self.pushed.push(front);
+    }
+
+    /// The number of tokens (including pieces of unexpected text) advanced past
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Keep a copy of the tokens (etc.) advanced past from now on following `consumed`
+    /// (those consumed earlier, if any, e.g. by a previous stream for the same text).
+    pub fn record(&mut self, consumed: Vec<Result<Token<T>, Error<T>>>) {
+        self.consumed = consumed.len();
+        self.recorded = Some(consumed);
+    }
+
+    /// The tokens (etc.) recorded (see `record()`) so far
+    pub fn take_recorded(&mut self) -> Vec<Result<Token<T>, Error<T>>> {
+        self.recorded.take().unwrap_or_default()
+    }
+
     pub fn advance(&mut self) {
-        let mut i = self.token_stream_stack.len();
+        if !self.pushed.is_empty() || !self.token_stream_stack.is_empty() {
+            self.consumed += 1;
+            if let Some(recorded) = &mut self.recorded {
+                recorded.push(self.front.clone());
+            }
+        }
+        if let Some(front) = self.pushed.pop() {
+            self.front = front;
+            return;
+        }
+        let i = self.token_stream_stack.len();
         if i > 0 {
             self.token_stream_stack[i - 1].advance();
-            let mut popped = None;
-            while i > 0 && self.token_stream_stack[i - 1].is_empty() {
-                popped = self.token_stream_stack.pop();
-                i -= 1;
-            }
-            self.front = if i > 0 {
-                self.token_stream_stack[i - 1].front().unwrap()
-            } else {
-                let end_location = popped.unwrap().location();
-                Ok(Token {
-                    tag: self.lexicon.end_marker(),
-                    lexeme: String::new(),
-                    location: end_location,
-                })
+            let o_mode = match &self.front {
+                Ok(token) => self.mode_switches.get(token.tag()).copied(),
+                Err(_) => None,
+            };
+            self.pop_empty_streams();
+            if let Some(mode) = o_mode {
+                self.set_mode(mode);
             }
         } else {
             let location = match &self.front {
@@ -314,6 +541,34 @@ where
         }
     }
 
+    // Make the front the end marker at `location`
+    fn end_at(&mut self, location: &Location) {
+        self.front = Ok(Token {
+            tag: self.lexicons[self.mode].end_marker(),
+            lexeme: String::new(),
+            location: location.clone(),
+        });
+    }
+
+    fn pop_empty_streams(&mut self) {
+        let mut i = self.token_stream_stack.len();
+        let mut popped = None;
+        while i > 0 && self.token_stream_stack[i - 1].is_empty() {
+            popped = self.token_stream_stack.pop();
+            i -= 1;
+        }
+        self.front = if i > 0 {
+            self.token_stream_stack[i - 1].front().unwrap()
+        } else {
+            let end_location = popped.unwrap().location();
+            Ok(Token {
+                tag: self.lexicons[self.mode].end_marker(),
+                lexeme: String::new(),
+                location: end_location,
+            })
+        }
+    }
+
     pub fn front_advance(&mut self) -> Result<Token<T>, Error<T>> {
         let front = self.front.clone();
         self.advance();
@@ -356,6 +611,8 @@ mod tests {
             location: Location::new("whatever"),
             index: 0,
             front: None,
+            front_start: (0, Location::new("whatever")),
+            skipping: true,
         };
         token_stream.incr_index_and_location(11);
         println!("{:?}", token_stream.location);
@@ -364,6 +621,20 @@ mod tests {
         assert_eq!(token_stream.location.offset, 5);
     }
 
+    #[test]
+    fn token_span() {
+        let token = Token {
+            tag: 0_u32,
+            lexeme: "two\nlines".to_string(),
+            location: Location::at("span", 3, 7),
+        };
+        let span = token.span();
+        assert_eq!(span.start, Location::at("span", 3, 7));
+        assert_eq!(span.end, Location::at("span", 4, 6));
+        let token = token.with_lexeme("one".to_string());
+        assert_eq!(token.span().end, Location::at("span", 3, 10));
+    }
+
     #[test]
     fn token_stream_basics() {
         #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
@@ -397,7 +668,11 @@ mod tests {
         let label = "label";
         let mut token_stream = TokenStream::new(&lexicon, text, label);
         assert!(token_stream.is_empty());
-        assert!(token_stream.front().is_err());
+        // nothing but skippable text is the end of the input
+        let end = token_stream.front().unwrap();
+        assert_eq!(*end.tag(), End);
+        assert_eq!(end.location().to_string(), "label:1:7");
+        assert!(TokenStream::new(&lexicon, "", label).front().is_ok());
         let text = " if nothing happens 9 ";
         let label = "another";
         token_stream.inject(text, label);
@@ -1,6 +1,6 @@
-use std::convert::From;
-use std::fmt::Formatter;
-use std::{error, fmt};
+use core::convert::From;
+use core::fmt::Formatter;
+use core::{error, fmt};
 
 #[derive(Debug, PartialEq)]
 pub enum LexanError<'a, T> {
@@ -1,4 +1,6 @@
-pub use std::fmt::{Debug, Display};
+pub use core::fmt::{Debug, Display};
+
+use alloc::{vec, vec::Vec};
 
 use crate::error::LexanError;
 use crate::matcher::{LiteralMatcher, RegexMatcher, SkipMatcher};
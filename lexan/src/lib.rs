@@ -1,21 +1,29 @@
+//! Without the (default) `std` feature the crate is `no_std` (but needs `alloc`).
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate regex;
 
-pub use std::fmt::{Debug, Display};
-use std::sync::Arc;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::{vec, vec::Vec};
+pub use core::fmt::{Debug, Display};
 
 mod analyzer;
 mod error;
 mod lexicon;
 mod matcher;
 
-pub use analyzer::{Error, Location, Token, TokenStream};
+pub use analyzer::{Error, Location, Span, Token, TokenStream};
 use lexicon::Lexicon;
 
 pub struct LexicalAnalyzer<T>
 where
     T: Ord + Copy + PartialEq + Debug + Display,
 {
-    lexicon: Arc<Lexicon<T>>,
+    lexicons: Vec<Arc<Lexicon<T>>>,
+    mode_switches: Arc<BTreeMap<T, usize>>,
 }
 
 impl<T> LexicalAnalyzer<T>
@@ -33,11 +41,58 @@ where
                 Ok(lexicon) => Arc::new(lexicon),
                 Err(err) => panic!("Fatal Error: {err:?}"),
             };
-        Self { lexicon }
+        Self {
+            lexicons: vec![lexicon],
+            mode_switches: Arc::default(),
+        }
+    }
+
+    /// Add an extra lexical mode (whose index is the number of modes already defined)
+    /// that token streams may be switched into using `TokenStream::set_mode()`.
+    pub fn with_mode<'a>(
+        mut self,
+        literal_lexemes: &[(T, &'a str)],
+        regex_lexemes: &[(T, &'a str)],
+        skip_regex_strs: &[&'a str],
+    ) -> Self {
+        let end_marker = self.lexicons[0].end_marker();
+        match Lexicon::new(literal_lexemes, regex_lexemes, skip_regex_strs, end_marker) {
+            Ok(lexicon) => self.lexicons.push(Arc::new(lexicon)),
+            Err(err) => panic!("Fatal Error: {err:?}"),
+        };
+        self
+    }
+
+    /// Make token streams switch to `mode` whenever a token tagged `tag` is consumed
+    /// (i.e. the token following it is scanned using that mode's lexicon).
+    pub fn with_mode_switch(mut self, tag: T, mode: usize) -> Self {
+        assert!(mode < self.lexicons.len(), "{mode}: unknown lexical mode");
+        Arc::make_mut(&mut self.mode_switches).insert(tag, mode);
+        self
     }
 
+    /// The tag of the token that marks the end of the text.
+    pub fn end_marker(&self) -> T {
+        self.lexicons[0].end_marker()
+    }
+
+    /// A token stream for (a copy of) `text` labelled `label`
     pub fn token_stream(&self, text: &str, label: &str) -> TokenStream<T> {
-        TokenStream::new(&self.lexicon, text, label)
+        self.token_stream_owned(text.to_string(), label)
+    }
+
+    /// As for `token_stream()` but taking ownership of `text` rather than copying it.
+    pub fn token_stream_owned(&self, text: String, label: &str) -> TokenStream<T> {
+        TokenStream::with_modes(&self.lexicons, &self.mode_switches, text, label)
+    }
+
+    /// As for `token_stream()` but with `text` starting at `location`, e.g. for the
+    /// rest of some text whose start has already been analysed.
+    pub fn token_stream_at(&self, text: &str, location: &Location) -> TokenStream<T> {
+        let mut token_stream =
+            TokenStream::with_modes(&self.lexicons, &self.mode_switches, String::new(), "");
+        token_stream.inject_at(text, location);
+        token_stream
     }
 }
 
@@ -355,4 +410,182 @@ mod tests {
         };
         assert!(second_token_stream.advance_front().is_err());
     }
+
+    #[test]
+    fn lexical_modes() {
+        use Handle::*;
+
+        let lexan = super::LexicalAnalyzer::new(
+            &[(If, "if"), (Literal, "\"")],
+            &[(Ident, "[a-zA-Z]+")],
+            &[r"(\s+)"],
+            End,
+        )
+        .with_mode(&[(Pred, "\"")], &[(Code, "[^\"]+")], &[]);
+
+        let mut token_stream = lexan.token_stream("if \"if it\" if", "modes");
+        assert_eq!(token_stream.mode(), 0);
+        match token_stream.front_advance() {
+            Ok(token) => assert_eq!(*token.tag(), If),
+            _ => assert!(false),
+        };
+        match token_stream.front_advance() {
+            Ok(token) => assert_eq!(*token.tag(), Literal),
+            _ => assert!(false),
+        };
+        match token_stream.front() {
+            Ok(token) => assert_eq!(*token.tag(), If),
+            _ => assert!(false),
+        };
+        token_stream.set_mode(1);
+        assert_eq!(token_stream.mode(), 1);
+        match token_stream.front_advance() {
+            Ok(token) => {
+                assert_eq!(*token.tag(), Code);
+                assert_eq!(token.lexeme(), "if it");
+                assert_eq!(format!("{}", token.location()), "modes:1:5");
+            }
+            _ => assert!(false),
+        };
+        match token_stream.front() {
+            Ok(token) => assert_eq!(*token.tag(), Pred),
+            _ => assert!(false),
+        };
+        token_stream.set_mode(0);
+        match token_stream.front_advance() {
+            Ok(token) => assert_eq!(*token.tag(), Literal),
+            _ => assert!(false),
+        };
+        match token_stream.front_advance() {
+            Ok(token) => {
+                assert_eq!(*token.tag(), If);
+                assert_eq!(format!("{}", token.location()), "modes:1:12");
+            }
+            _ => assert!(false),
+        };
+        match token_stream.front() {
+            Ok(token) => assert_eq!(*token.tag(), End),
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn lexical_mode_switches() {
+        use Handle::*;
+
+        let lexan = super::LexicalAnalyzer::new(
+            &[(If, "if"), (Literal, "`")],
+            &[(Ident, "[a-zA-Z]+")],
+            &[r"(\s+)"],
+            End,
+        )
+        .with_mode(&[(Pred, "'")], &[(Code, "[^']+")], &[])
+        .with_mode_switch(Literal, 1)
+        .with_mode_switch(Pred, 0);
+
+        let mut token_stream = lexan.token_stream("if ` if it' if", "switches");
+        let mut tags = vec![];
+        while let Ok(token) = token_stream.front_advance() {
+            tags.push((*token.tag(), token.lexeme().to_string()));
+            if *token.tag() == End {
+                break;
+            }
+        }
+        assert_eq!(
+            tags,
+            vec![
+                (If, "if".to_string()),
+                (Literal, "`".to_string()),
+                (Code, " if it".to_string()),
+                (Pred, "'".to_string()),
+                (If, "if".to_string()),
+                (End, "".to_string()),
+            ]
+        );
+        assert_eq!(token_stream.mode(), 0);
+    }
+
+    #[test]
+    fn skipping_suspended() {
+        use Handle::*;
+
+        let lexan = super::LexicalAnalyzer::new(
+            &[(If, "if")],
+            &[(Ident, "[a-zA-Z]+"), (Code, "[ \t]+")],
+            &[r"(\s+)"],
+            End,
+        );
+        let mut token_stream = lexan.token_stream("if  x if\ty", "skipping");
+        let mut tags = vec![];
+        for skipping in [false, false, true, false, true] {
+            token_stream.set_skipping(skipping);
+            if let Ok(token) = token_stream.front_advance() {
+                tags.push((*token.tag(), token.lexeme().to_string()));
+            }
+        }
+        assert_eq!(
+            tags,
+            vec![
+                (If, "if".to_string()),
+                (Code, "  ".to_string()),
+                (Ident, "x".to_string()),
+                (If, "if".to_string()),
+                (Code, "\t".to_string()),
+            ]
+        );
+        assert_eq!(*token_stream.front().unwrap().tag(), Ident);
+    }
+
+    #[test]
+    fn tokens_replayed() {
+        use Handle::*;
+
+        let lexan =
+            super::LexicalAnalyzer::new(&[(If, "if")], &[(Ident, "[a-zA-Z]+")], &[r"(\s+)"], End);
+        let mut token_stream = lexan.token_stream("if x\nz", "replay");
+        token_stream.record(vec![]);
+        token_stream.advance();
+        token_stream.advance();
+        assert_eq!(token_stream.consumed(), 2);
+        let recorded = token_stream.take_recorded();
+        assert_eq!(recorded.len(), 2);
+        let end = recorded[1].as_ref().unwrap().span().end;
+        let mut token_stream = lexan.token_stream_at(" y", &end);
+        for item in recorded.into_iter().rev() {
+            token_stream.push_front(item);
+        }
+        let mut tokens = vec![];
+        while let Ok(token) = token_stream.front_advance() {
+            tokens.push((*token.tag(), token.location().to_string()));
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                (If, "replay:1:1".to_string()),
+                (Ident, "replay:1:4".to_string()),
+                (Ident, "replay:1:6".to_string()),
+                (End, "replay:1:7".to_string()),
+            ]
+        );
+        assert_eq!(token_stream.consumed(), 3);
+        let token_stream = lexan.token_stream_at("", &end);
+        assert_eq!(*token_stream.front().unwrap().tag(), End);
+    }
+
+    #[test]
+    fn end_marker_and_retagging() {
+        use Handle::*;
+
+        let lexan = super::LexicalAnalyzer::new(&[(If, "if")], &[], &[r"(\s+)"], End).with_mode(
+            &[(When, "when")],
+            &[],
+            &[],
+        );
+        assert_eq!(lexan.end_marker(), End);
+        let mut token_stream = lexan.token_stream("if", "retag");
+        let token = token_stream.front_advance().unwrap().with_tag(End);
+        assert_eq!(*token.tag(), End);
+        assert_eq!(token.lexeme(), "if");
+        assert_eq!(token.location().to_string(), "retag:1:1");
+    }
 }
@@ -1,5 +1,8 @@
-use std::cmp::Ordering;
-use std::{cmp::Eq, collections::HashMap, fmt::Debug};
+use alloc::collections::{btree_map::Entry, BTreeMap};
+use alloc::string::ToString;
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use core::{cmp::Eq, fmt::Debug};
 
 use regex::Regex;
 
@@ -9,13 +12,13 @@ use crate::error::LexanError;
 struct LiteralMatcherNode<T: PartialEq + Debug + Copy> {
     tag: Option<T>,
     length: usize,
-    tails: HashMap<u8, LiteralMatcherNode<T>>,
+    tails: BTreeMap<u8, LiteralMatcherNode<T>>,
 }
 
 impl<T: PartialEq + Debug + Copy> LiteralMatcherNode<T> {
     fn new(tag: T, string: &str, s_index: usize) -> LiteralMatcherNode<T> {
         debug_assert!(!string.is_empty());
-        let mut t = HashMap::<u8, LiteralMatcherNode<T>>::new();
+        let mut t = BTreeMap::<u8, LiteralMatcherNode<T>>::new();
         if string.len() == s_index {
             LiteralMatcherNode {
                 tag: Some(tag),
@@ -48,7 +51,7 @@ impl<T: PartialEq + Debug + Copy> LiteralMatcherNode<T> {
             self.length = string.len();
         } else {
             let key = string.as_bytes()[s_index];
-            if let std::collections::hash_map::Entry::Vacant(e) = self.tails.entry(key) {
+            if let Entry::Vacant(e) = self.tails.entry(key) {
                 e.insert(LiteralMatcherNode::<T>::new(tag, string, s_index + 1));
             } else {
                 self.tails
@@ -63,12 +66,12 @@ impl<T: PartialEq + Debug + Copy> LiteralMatcherNode<T> {
 
 #[derive(Debug, Default)]
 pub(crate) struct LiteralMatcher<T: PartialEq + Debug + Copy> {
-    lexemes: HashMap<u8, LiteralMatcherNode<T>>,
+    lexemes: BTreeMap<u8, LiteralMatcherNode<T>>,
 }
 
 impl<T: Eq + Debug + Copy + Ord> LiteralMatcher<T> {
     pub fn new<'a>(lexemes: &[(T, &'a str)]) -> Result<LiteralMatcher<T>, LexanError<'a, T>> {
-        let mut lexes = HashMap::<u8, LiteralMatcherNode<T>>::new();
+        let mut lexes = BTreeMap::<u8, LiteralMatcherNode<T>>::new();
         for &(tag, pattern) in lexemes.iter() {
             // make sure that tags are unique and strings are not empty
             if pattern.is_empty() {
@@ -76,7 +79,7 @@ impl<T: Eq + Debug + Copy + Ord> LiteralMatcher<T> {
             }
 
             let key = pattern.as_bytes()[0];
-            if let std::collections::hash_map::Entry::Vacant(e) = lexes.entry(key) {
+            if let Entry::Vacant(e) = lexes.entry(key) {
                 e.insert(LiteralMatcherNode::<T>::new(tag, pattern, 1));
             } else {
                 lexes.get_mut(&key).unwrap().add(tag, pattern, 1)?;